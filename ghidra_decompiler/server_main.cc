@@ -14,6 +14,7 @@
 #include <vector>
 #include <sstream>
 #include <set>
+#include <map>
 #include <fstream>
 
 #include <grpcpp/grpcpp.h>
@@ -111,6 +112,65 @@ public:
     }
 };
 
+// Line map - instruction addresses behind each line of the printed C code.
+// The pretty printer's low-level emitter is private, so the function is
+// printed a second time with markup: every <break> starts a new line and
+// every opref names the p-code op (by sequence time) a token came from.
+static void collectLineMap(Architecture* arch, Funcdata* fd, DecompileResponse* reply) {
+    std::map<uintm, uint64_t> op_addrs;
+    for (PcodeOpTree::const_iterator it = fd->beginOpAll(); it != fd->endOpAll(); ++it) {
+        const PcodeOp* op = (*it).second;
+        op_addrs[op->getTime()] = op->getAddr().getOffset();
+    }
+
+    // Print with unpacked markup into a local stream, then put the printer
+    // back as the regular C output expects it, even if printing throws.
+    // Restoring the stream also resets a markup emitter's encoder to its
+    // packed default, which the emitter offers no getter for.
+    struct PrinterState {
+        PrintLanguage* print;
+        ostream* stream;
+        bool markup;
+        ~PrinterState() {
+            print->setMarkup(markup);
+            print->setOutputStream(stream);
+        }
+    } saved{arch->print, arch->print->getOutputStream(), arch->print->emitsMarkup()};
+
+    std::ostringstream markup;
+    arch->print->setMarkup(true);
+    arch->print->setOutputStream(&markup);
+    arch->print->setPackedOutput(false);
+    arch->print->docFunction(fd);
+
+    const std::string xml = markup.str();
+    const std::string opref = "opref=\"0x";
+    std::vector<std::set<uint64_t>> lines(1);
+    size_t pos = 0;
+    while (true) {
+        size_t brk = xml.find("<break", pos);
+        size_t ref = xml.find(opref, pos);
+        if (brk == std::string::npos && ref == std::string::npos) break;
+        if (ref == std::string::npos || (brk != std::string::npos && brk < ref)) {
+            lines.emplace_back();
+            pos = brk + 1;
+            continue;
+        }
+        size_t start = ref + opref.size();
+        uintm time = std::stoul(xml.substr(start, xml.find('"', start) - start), nullptr, 16);
+        auto found = op_addrs.find(time);
+        if (found != op_addrs.end()) lines.back().insert(found->second);
+        pos = start;
+    }
+
+    for (size_t i = 0; i < lines.size(); ++i) {
+        if (lines[i].empty()) continue;
+        ghidra_service::LineMapping* pb_line = reply->add_line_map();
+        pb_line->set_line(i);
+        for (uint64_t addr : lines[i]) pb_line->add_addresses(addr);
+    }
+}
+
 // Assembly Emitter - captures disassembly output
 class ServerAssemblyEmit : public AssemblyEmit {
 public:
@@ -123,6 +183,31 @@ public:
     }
 };
 
+// P-code Emitter - captures raw p-code ops as text
+class ServerPcodeEmit : public PcodeEmit {
+    static void printVarnode(ostream &s, const VarnodeData *vn) {
+        s << '(' << vn->space->getName() << ',';
+        vn->space->printOffset(s, vn->offset);
+        s << ',' << std::dec << vn->size << ')';
+    }
+public:
+    std::ostringstream out;
+    
+    virtual void dump(const Address &addr, OpCode opc, VarnodeData *outvar,
+                      VarnodeData *vars, int4 isize) override {
+        if (outvar != nullptr) {
+            printVarnode(out, outvar);
+            out << " = ";
+        }
+        out << get_opname(opc);
+        for (int4 i = 0; i < isize; ++i) {
+            out << ' ';
+            printVarnode(out, vars + i);
+        }
+        out << '\n';
+    }
+};
+
 class DecompilerServiceImpl final : public DecompilerService::Service {
     std::mutex mu_;
     std::unique_ptr<MemoryLoadImage> loader;
@@ -296,6 +381,26 @@ public:
             reply->set_c_code(c_stream.str());
             reply->set_signature(fd->getName() + "()");
             reply->set_success(true);
+
+            // ===== C Line -> Address Map =====
+            collectLineMap(arch.get(), fd, reply);
+
+            // ===== Stack Variables =====
+            AddrSpace* stack_space = arch->getStackSpace();
+            ScopeLocal* local_scope = fd->getScopeLocal();
+            if (stack_space != nullptr && local_scope != nullptr) {
+                MapIterator iter = local_scope->begin();
+                MapIterator enditer = local_scope->end();
+                for (; iter != enditer; ++iter) {
+                    const SymbolEntry* entry = *iter;
+                    if (entry->isDynamic() || entry->getAddr().getSpace() != stack_space) continue;
+                    ghidra_service::StackVariable* pb_var = reply->add_stack_vars();
+                    int4 sign_bit = 8 * stack_space->getAddrSize() - 1;
+                    pb_var->set_offset(sign_extend((intb)entry->getAddr().getOffset(), sign_bit));
+                    pb_var->set_size(entry->getSize());
+                    pb_var->set_name(entry->getSymbol()->getName());
+                }
+            }
             
             // ===== Generate Disassembly Blocks =====
             ghidra_service::BasicBlock* pb_block = reply->add_blocks();
@@ -319,6 +424,12 @@ public:
                 pb_instr->set_mnemonic(emit.mnem);
                 pb_instr->set_operands(emit.body);
                 
+                if (request->include_pcode()) {
+                    ServerPcodeEmit pcode_emit;
+                    arch->translate->oneInstruction(pcode_emit, cur);
+                    pb_instr->set_pcode_text(pcode_emit.out.str());
+                }
+                
                 // Stop at RET
                 if (emit.mnem.find("RET") != string::npos) {
                     break;
@@ -360,8 +471,8 @@ public:
     }
 };
 
-void RunServer() {
-    std::string server_address("0.0.0.0:50051");
+void RunServer(const std::string& port) {
+    std::string server_address("0.0.0.0:" + port);
     DecompilerServiceImpl service;
 
     ServerBuilder builder;
@@ -378,8 +489,16 @@ int main(int argc, char** argv) {
         return 0;
     }
 
+    // Optional: --port <n> (default 50051)
+    std::string port = "50051";
+    for (int i = 1; i + 1 < argc; ++i) {
+        if (string(argv[i]) == "--port") {
+            port = argv[i + 1];
+        }
+    }
+
     try {
-        RunServer();
+        RunServer(port);
     } catch (const std::exception& e) {
         std::cerr << "CRITICAL ERROR: " << e.what() << std::endl;
     } catch (...) {
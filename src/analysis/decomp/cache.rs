@@ -0,0 +1,209 @@
+//! On-disk cache for decompilation results
+//!
+//! Entries are keyed by the SHA-256 of the binary contents, the architecture
+//! and load address it was decompiled with, and the function address, stored
+//! as `<root>/<hash>/<address>.c` and `.pcode` files, with the stack variables
+//! and line map in a `.json` file beside them. Beyond the entry cap the least
+//! recently used entries are evicted, going by the `.c` file's mtime.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::analysis::stackvars::StackVariable;
+
+/// Default maximum number of cached functions across all binaries
+pub const DEFAULT_MAX_ENTRIES: usize = 2000;
+
+/// Hex-encoded SHA-256 of binary contents, used as the cache key
+pub fn content_hash(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// One cached decompile
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CacheEntry {
+    pub c_code: String,
+    pub pcode: String,
+    /// Variables Ghidra placed on the stack
+    pub stack_vars: Vec<StackVariable>,
+    /// Instruction addresses behind each 0-based line of `c_code`
+    pub line_map: HashMap<usize, Vec<u64>>,
+}
+
+/// The parts of an entry kept in its `.json` file
+#[derive(Default, Serialize, Deserialize)]
+struct EntryMeta {
+    stack_vars: Vec<StackVariable>,
+    line_map: HashMap<usize, Vec<u64>>,
+}
+
+/// Cache directory name for a binary: its content hash, architecture and
+/// base, since the same bytes decompile differently as another architecture
+/// and cached code holds absolute addresses
+pub fn binary_key(hash: &str, arch: &str, image_base: u64) -> String {
+    let arch: String = arch.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{}-{}-{:x}", hash, arch, image_base)
+}
+
+/// Disk-backed decompile cache with an entry cap
+pub struct DiskCache {
+    root: PathBuf,
+    max_entries: usize,
+}
+
+impl DiskCache {
+    pub fn new(root: impl Into<PathBuf>, max_entries: usize) -> Self {
+        Self {
+            root: root.into(),
+            max_entries,
+        }
+    }
+
+    /// Cache under the user config directory
+    pub fn open_default() -> Option<Self> {
+        crate::app::config::config_dir()
+            .map(|dir| Self::new(dir.join("decomp_cache"), DEFAULT_MAX_ENTRIES))
+    }
+
+    fn entry_path(&self, hash: &str, address: u64, ext: &str) -> PathBuf {
+        self.root.join(hash).join(format!("{:016x}.{}", address, ext))
+    }
+
+    /// Look up the cached result for a function. Entries written before the
+    /// `.json` file existed come back without stack variables or line map.
+    pub fn get(&self, hash: &str, address: u64) -> Option<CacheEntry> {
+        let c_path = self.entry_path(hash, address, "c");
+        let c_code = fs::read_to_string(&c_path).ok()?;
+        // A hit counts as a use, so eviction drops the least recently used
+        let _ = touch(&c_path, SystemTime::now());
+        let pcode = fs::read_to_string(self.entry_path(hash, address, "pcode")).unwrap_or_default();
+        let meta: EntryMeta = fs::read_to_string(self.entry_path(hash, address, "json")).ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Some(CacheEntry { c_code, pcode, stack_vars: meta.stack_vars, line_map: meta.line_map })
+    }
+
+    /// Store a result, evicting the least recently used entries beyond the cap
+    pub fn put(&self, hash: &str, address: u64, entry: &CacheEntry) -> io::Result<()> {
+        fs::create_dir_all(self.root.join(hash))?;
+        fs::write(self.entry_path(hash, address, "pcode"), &entry.pcode)?;
+        // Optional, like for entries written before it existed
+        let meta = EntryMeta { stack_vars: entry.stack_vars.clone(), line_map: entry.line_map.clone() };
+        if let Ok(json) = serde_json::to_string(&meta) {
+            fs::write(self.entry_path(hash, address, "json"), json)?;
+        }
+        // Write C code last: its presence marks the entry as complete
+        fs::write(self.entry_path(hash, address, "c"), &entry.c_code)?;
+        self.evict()
+    }
+
+    /// Remove all cached entries, returning how many were deleted
+    pub fn clear(&self) -> io::Result<usize> {
+        let count = self.entries().len();
+        if self.root.exists() {
+            fs::remove_dir_all(&self.root)?;
+        }
+        Ok(count)
+    }
+
+    /// All `.c` entry files with their modification time
+    fn entries(&self) -> Vec<(PathBuf, SystemTime)> {
+        let mut entries = Vec::new();
+        let Ok(dirs) = fs::read_dir(&self.root) else { return entries; };
+        for dir in dirs.flatten() {
+            let Ok(files) = fs::read_dir(dir.path()) else { continue; };
+            for file in files.flatten() {
+                let path = file.path();
+                if path.extension().is_some_and(|e| e == "c") {
+                    let mtime = file.metadata()
+                        .and_then(|m| m.modified())
+                        .unwrap_or(SystemTime::UNIX_EPOCH);
+                    entries.push((path, mtime));
+                }
+            }
+        }
+        entries
+    }
+
+    fn evict(&self) -> io::Result<()> {
+        let mut entries = self.entries();
+        if entries.len() <= self.max_entries {
+            return Ok(());
+        }
+
+        entries.sort_by_key(|(_, mtime)| *mtime);
+        let excess = entries.len() - self.max_entries;
+        for (path, _) in entries.into_iter().take(excess) {
+            let _ = fs::remove_file(path.with_extension("pcode"));
+            let _ = fs::remove_file(path.with_extension("json"));
+            fs::remove_file(&path)?;
+            remove_if_empty(path.parent());
+        }
+        Ok(())
+    }
+}
+
+fn touch(path: &Path, time: SystemTime) -> io::Result<()> {
+    fs::File::options().append(true).open(path)?.set_modified(time)
+}
+
+fn remove_if_empty(dir: Option<&Path>) {
+    if let Some(dir) = dir {
+        if fs::read_dir(dir).map(|mut d| d.next().is_none()).unwrap_or(false) {
+            let _ = fs::remove_dir(dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_key() {
+        let hash = content_hash(b"\x90\x90\xc3");
+        let x86 = binary_key(&hash, "x86:LE:64:default", 0x1000);
+        assert_ne!(x86, binary_key(&hash, "ARM:LE:32:v8", 0x1000));
+        assert_ne!(x86, binary_key(&hash, "x86:LE:64:default", 0x400000));
+        assert_eq!(x86, binary_key(&hash, "x86:LE:64:default", 0x1000));
+        assert!(!x86.contains(':'));
+    }
+
+    #[test]
+    fn test_put_get_evict() {
+        let root = std::env::temp_dir().join(format!("fission_cache_test_{}", std::process::id()));
+        let cache = DiskCache::new(&root, 2);
+
+        let entry = CacheEntry { c_code: "int f(void);".into(), pcode: "COPY".into(), ..Default::default() };
+        cache.put("abc", 0x1000, &entry).unwrap();
+        assert_eq!(cache.get("abc", 0x1000), Some(entry));
+        assert_eq!(cache.get("abc", 0x2000), None);
+
+        let code = |c_code: &str| CacheEntry { c_code: c_code.into(), ..Default::default() };
+        cache.put("abc", 0x2000, &code("b")).unwrap();
+
+        // Reading the older entry keeps it over the one that was not read
+        let age = |address, secs| {
+            let at = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+            touch(&cache.entry_path("abc", address, "c"), at).unwrap();
+        };
+        age(0x1000, 1);
+        age(0x2000, 2);
+        assert!(cache.get("abc", 0x1000).is_some());
+        cache.put("def", 0x3000, &code("c")).unwrap();
+        assert_eq!(cache.entries().len(), 2);
+        assert!(cache.get("abc", 0x1000).is_some());
+        assert_eq!(cache.get("abc", 0x2000), None);
+
+        assert_eq!(cache.clear().unwrap(), 2);
+        assert!(!root.exists());
+    }
+}
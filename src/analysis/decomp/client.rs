@@ -105,15 +105,28 @@ pub struct ClientConfig {
     pub initial_retry_delay_ms: u64,
     /// Maximum timeout for decompilation (ms)
     pub decompile_timeout_ms: u32,
+    /// Explicit server executable, tried before `SERVER_PATHS`
+    pub server_path: Option<String>,
+}
+
+impl ClientConfig {
+    /// Default port, overridable with the `FISSION_GHIDRA_PORT` env var
+    pub const DEFAULT_PORT: u16 = 50051;
+    pub const PORT_ENV: &'static str = "FISSION_GHIDRA_PORT";
 }
 
 impl Default for ClientConfig {
     fn default() -> Self {
+        let port = std::env::var(Self::PORT_ENV)
+            .ok()
+            .and_then(|p| p.trim().parse().ok())
+            .unwrap_or(Self::DEFAULT_PORT);
         Self {
-            port: 50051,
+            port,
             max_retries: 5,
             initial_retry_delay_ms: 500,
             decompile_timeout_ms: 30000,
+            server_path: None,
         }
     }
 }
@@ -170,7 +183,7 @@ impl GhidraClient {
 
         // Start server if not running
         log::info!("Starting Ghidra server...");
-        let child = Self::spawn_server()?;
+        let child = Self::spawn_server(&config)?;
         
         // Wait for server to become ready
         let client = Self::wait_for_server(&uri, &config).await?;
@@ -185,10 +198,16 @@ impl GhidraClient {
         })
     }
 
-    /// Spawn the server process
-    fn spawn_server() -> Result<Child> {
-        for path in Self::SERVER_PATHS {
+    /// Spawn the server process, preferring `config.server_path`
+    fn spawn_server(config: &ClientConfig) -> Result<Child> {
+        let candidates = config.server_path.as_deref()
+            .into_iter()
+            .chain(Self::SERVER_PATHS.iter().copied());
+
+        for path in candidates {
             match Command::new(path)
+                .arg("--port")
+                .arg(config.port.to_string())
                 .stdout(Stdio::inherit())
                 .stderr(Stdio::inherit())
                 .spawn() 
@@ -201,9 +220,9 @@ impl GhidraClient {
             }
         }
         
-        Err(GhidraError::ServerNotFound(
-            Self::SERVER_PATHS.join(", ")
-        ))
+        let mut searched: Vec<&str> = config.server_path.as_deref().into_iter().collect();
+        searched.extend(Self::SERVER_PATHS);
+        Err(GhidraError::ServerNotFound(searched.join(", ")))
     }
 
     /// Wait for server to become ready with exponential backoff
@@ -363,23 +382,50 @@ pub async fn quick_decompile(
     arch: &str
 ) -> Result<String> {
     let mut client = GhidraClient::connect().await?;
-    let id = BinaryId::new(None, binary.len() as u64, arch.to_string(), None);
+    let id = BinaryId::new(None, binary.len() as u64, arch.to_string(), base_addr, None);
     client.load_binary(binary, base_addr, arch, id).await?;
     let result = client.decompile_function(func_addr).await?;
     Ok(result.c_code)
 }
 
-/// Identifier for a loaded binary to decide when to reload server-side
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// Flatten per-instruction p-code from the response into a listing
+pub fn format_pcode(response: &ghidra_service::DecompileResponse) -> String {
+    let mut out = String::new();
+    for insn in response.blocks.iter().flat_map(|b| &b.instructions) {
+        if insn.pcode_text.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("0x{:x}: {} {}\n", insn.address, insn.mnemonic, insn.operands));
+        for op in insn.pcode_text.lines() {
+            out.push_str(&format!("    {}\n", op));
+        }
+    }
+    out
+}
+
+/// Identifier for a loaded binary to decide when to reload server-side, and
+/// to scope the GUI's decompile cache
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct BinaryId {
     pub path: Option<String>,
     pub size: u64,
     pub arch: String,
+    /// Load address; the same raw blob can be opened at different bases
+    pub image_base: u64,
     pub mtime: Option<u64>,
 }
 
 impl BinaryId {
-    pub fn new(path: Option<String>, size: u64, arch: String, mtime: Option<u64>) -> Self {
-        Self { path, size, arch, mtime }
+    pub fn new(path: Option<String>, size: u64, arch: String, image_base: u64, mtime: Option<u64>) -> Self {
+        Self { path, size, arch, image_base, mtime }
+    }
+
+    /// Identify a loaded binary by path, size, architecture, base and file mtime
+    pub fn for_binary(binary: &crate::analysis::loader::LoadedBinary) -> Self {
+        let mtime = std::fs::metadata(&binary.path).ok()
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+        Self::new(Some(binary.path.clone()), binary.data.len() as u64, binary.arch_spec.clone(), binary.image_base, mtime)
     }
 }
@@ -3,6 +3,7 @@
 //! Provides decompilation and advanced disassembly via gRPC communication
 //! with the native Ghidra C++ server.
 
+pub mod cache;
 pub mod client;
 
 #[cfg(test)]
@@ -10,3 +11,4 @@ mod tests;
 
 // Re-export the gRPC client for external use
 pub use client::GhidraClient;
+pub use cache::{CacheEntry, DiskCache};
@@ -0,0 +1,186 @@
+//! Function-level binary diffing
+//!
+//! Matches the functions of two versions of a binary by name, then pairs
+//! the remaining unnamed functions by an instruction hash. The hash covers
+//! mnemonics only, so code that merely moved (shifted call targets and
+//! displacements) still compares equal.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use super::disasm::{DisasmEngine, DisasmSyntax};
+use super::loader::LoadedBinary;
+
+/// Body length assumed for a function without a size, when no later
+/// function bounds it
+const MAX_UNSIZED_BODY: u64 = 0x1000;
+
+/// A function's code, copied out of a binary so it can be fingerprinted
+/// off the UI thread
+#[derive(Debug, Clone)]
+pub struct FunctionBody {
+    /// Symbol name (empty for unnamed functions)
+    pub name: String,
+    pub address: u64,
+    pub bytes: Vec<u8>,
+}
+
+/// Name and instruction hash of one function
+#[derive(Debug, Clone)]
+pub struct FunctionFingerprint {
+    pub name: String,
+    pub address: u64,
+    pub hash: u64,
+}
+
+/// How a function differs between the primary and secondary binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    /// Only in the secondary binary
+    Added,
+    /// Only in the primary binary
+    Removed,
+    /// In both, with different instructions
+    Changed,
+    /// In both, with the same instructions
+    Unchanged,
+}
+
+impl DiffKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Changed => "changed",
+            Self::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// One row of a function diff
+#[derive(Debug, Clone)]
+pub struct FunctionDiff {
+    pub kind: DiffKind,
+    /// Symbol name (empty when matched by hash or unmatched and unnamed)
+    pub name: String,
+    /// Address in the primary binary
+    pub primary: Option<u64>,
+    /// Address in the secondary binary
+    pub secondary: Option<u64>,
+}
+
+/// Copy out the code of every non-import function.
+///
+/// Functions without a size run up to the next function, at most
+/// [`MAX_UNSIZED_BODY`] bytes.
+pub fn function_bodies(binary: &LoadedBinary) -> Vec<FunctionBody> {
+    let mut starts: Vec<u64> = binary.functions.iter()
+        .filter(|f| !f.is_import)
+        .map(|f| f.address)
+        .collect();
+    starts.sort_unstable();
+    starts.dedup();
+
+    binary.functions.iter()
+        .filter(|f| !f.is_import)
+        .filter_map(|f| {
+            let size = if f.size > 0 {
+                f.size
+            } else {
+                let next = starts.partition_point(|&a| a <= f.address);
+                starts.get(next).map_or(MAX_UNSIZED_BODY, |&n| n - f.address).min(MAX_UNSIZED_BODY)
+            };
+            let bytes = binary.get_bytes(f.address, size as usize)?;
+            Some(FunctionBody { name: f.name.clone(), address: f.address, bytes })
+        })
+        .collect()
+}
+
+/// Hash the mnemonic sequence of a function, or its raw bytes when it does
+/// not disassemble
+pub fn fingerprint(engine: Option<&DisasmEngine>, body: &FunctionBody) -> FunctionFingerprint {
+    let mut hasher = DefaultHasher::new();
+    match engine.and_then(|engine| engine.disassemble(&body.bytes, body.address).ok()) {
+        Some(insns) => insns.iter().for_each(|insn| insn.mnemonic.hash(&mut hasher)),
+        None => body.bytes.hash(&mut hasher),
+    }
+    FunctionFingerprint {
+        name: body.name.clone(),
+        address: body.address,
+        hash: hasher.finish(),
+    }
+}
+
+/// Fingerprint `bodies` with an engine for `arch_spec`
+pub fn fingerprint_all(arch_spec: &str, bodies: &[FunctionBody]) -> Vec<FunctionFingerprint> {
+    let engine = DisasmEngine::for_arch(arch_spec, DisasmSyntax::default()).ok();
+    bodies.iter().map(|body| fingerprint(engine.as_ref(), body)).collect()
+}
+
+/// Match `primary` against `secondary` functions.
+///
+/// Named functions pair up by name; unnamed ones pair up only when their
+/// hashes are equal. Rows follow the primary order, then the added
+/// functions in secondary order.
+pub fn diff_functions(primary: &[FunctionFingerprint], secondary: &[FunctionFingerprint]) -> Vec<FunctionDiff> {
+    let mut by_name: HashMap<&str, usize> = HashMap::new();
+    let mut by_hash: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (i, f) in secondary.iter().enumerate().rev() {
+        if f.name.is_empty() {
+            by_hash.entry(f.hash).or_default().push(i);
+        } else {
+            by_name.insert(&f.name, i);
+        }
+    }
+
+    let mut matched = vec![false; secondary.len()];
+    let mut rows = Vec::new();
+    for f in primary {
+        let partner = if f.name.is_empty() {
+            by_hash.get_mut(&f.hash).and_then(|candidates| candidates.pop())
+        } else {
+            by_name.remove(f.name.as_str())
+        };
+        let row = match partner {
+            Some(i) => {
+                matched[i] = true;
+                let kind = if secondary[i].hash == f.hash { DiffKind::Unchanged } else { DiffKind::Changed };
+                FunctionDiff { kind, name: f.name.clone(), primary: Some(f.address), secondary: Some(secondary[i].address) }
+            }
+            None => FunctionDiff { kind: DiffKind::Removed, name: f.name.clone(), primary: Some(f.address), secondary: None },
+        };
+        rows.push(row);
+    }
+
+    rows.extend(secondary.iter().zip(&matched)
+        .filter(|(_, &matched)| !matched)
+        .map(|(f, _)| FunctionDiff { kind: DiffKind::Added, name: f.name.clone(), primary: None, secondary: Some(f.address) }));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fp(name: &str, address: u64, hash: u64) -> FunctionFingerprint {
+        FunctionFingerprint { name: name.to_string(), address, hash }
+    }
+
+    #[test]
+    fn test_diff_functions() {
+        let primary = [fp("main", 0x1000, 1), fp("parse", 0x1100, 2), fp("old", 0x1200, 3), fp("", 0x1300, 4)];
+        let secondary = [fp("parse", 0x2100, 9), fp("main", 0x2000, 1), fp("", 0x2300, 4), fp("new", 0x2400, 5)];
+
+        let kinds: Vec<_> = diff_functions(&primary, &secondary).iter()
+            .map(|d| (d.kind, d.primary, d.secondary))
+            .collect();
+        assert_eq!(kinds, vec![
+            (DiffKind::Unchanged, Some(0x1000), Some(0x2000)),
+            (DiffKind::Changed, Some(0x1100), Some(0x2100)),
+            (DiffKind::Removed, Some(0x1200), None),
+            (DiffKind::Unchanged, Some(0x1300), Some(0x2300)),
+            (DiffKind::Added, None, Some(0x2400)),
+        ]);
+    }
+}
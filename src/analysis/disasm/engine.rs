@@ -1,7 +1,9 @@
 //! Disassembly Engine using Capstone
 //!
 //! Provides local disassembly capabilities for immediate feedback.
+//! Supports x86/x64, ARM and AArch64.
 
+use std::collections::HashSet;
 use thiserror::Error;
 use capstone::prelude::*;
 
@@ -19,6 +21,38 @@ impl From<capstone::Error> for DisasmError {
     }
 }
 
+/// Longest x86 instruction; bounds the start offsets tried when decoding backwards
+pub(crate) const MAX_INSN_LEN: usize = 15;
+/// Upper bound on capstone register ids probed for register names (all archs)
+const MAX_REG_ID: u16 = 512;
+/// Mnemonic of the pseudo-instruction emitted per undecodable byte
+pub const DATA_MNEMONIC: &str = "db";
+/// x86 operand size keywords (`qword ptr [...]`)
+const SIZE_KEYWORDS: &[&str] = &[
+    "byte", "word", "dword", "fword", "qword", "tbyte", "oword",
+    "xmmword", "ymmword", "zmmword", "ptr",
+];
+
+/// Classification of an operand token for highlighting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperandKind {
+    Register,
+    Immediate,
+    /// Size keywords and displacements of a memory operand (`qword ptr [rbp - 0x8]`)
+    Memory,
+    /// Branch target of a call/jump
+    Address,
+    /// Separators, operators and any other text
+    Punctuation,
+}
+
+/// A piece of an instruction's operand text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperandToken {
+    pub text: String,
+    pub kind: OperandKind,
+}
+
 /// A single disassembled instruction structure optimized for UI rendering
 #[derive(Debug, Clone)]
 pub struct DisassembledInstruction {
@@ -26,9 +60,14 @@ pub struct DisassembledInstruction {
     pub bytes: Vec<u8>,
     pub mnemonic: String,
     pub operands: String,
+    /// `operands` split into classified tokens; their texts concatenate back to `operands`
+    pub tokens: Vec<OperandToken>,
     pub length: usize,
     /// Is this a jump/call/ret instruction?
     pub is_flow_control: bool,
+    /// Resolved absolute target of a direct jmp/jcc/call/loop; None for
+    /// indirect branches and returns
+    pub target: Option<u64>,
 }
 
 impl DisassembledInstruction {
@@ -44,14 +83,265 @@ impl DisassembledInstruction {
             self.address, bytes_str, self.mnemonic, self.operands
         )
     }
+
+    /// Whether this is a `db` pseudo-instruction for a byte that could not be decoded
+    pub fn is_skipped_data(&self) -> bool {
+        self.mnemonic == DATA_MNEMONIC
+    }
+
+    /// Absolute target of a direct jmp/jcc/call/loop, or None for indirect
+    /// branches (`jmp rax`, `call [rip + 0x10]`) and non-branches.
+    ///
+    /// Resolved from the decoded relative displacement, so it is the same
+    /// in every syntax; callers should use this rather than parse `operands`.
+    pub fn branch_target(&self) -> Option<u64> {
+        self.target
+    }
+
+    /// Address of data referenced by a memory or immediate operand:
+    /// `[rip + 0x2f1a]` / `0x2f1a(%rip)` (relative to the next instruction),
+    /// `[0x403000]`, or a trailing immediate such as `push 0x403000`.
+    ///
+    /// Immediates of flow-control instructions are left to `branch_target`.
+    pub fn data_reference(&self) -> Option<u64> {
+        let ops = self.operands.as_str();
+        let next = self.address.wrapping_add(self.length as u64);
+
+        // AT&T: disp(%rip)
+        if let Some(end) = ops.find("(%rip)") {
+            let disp = ops[..end].rsplit([' ', ',']).next()?;
+            return match disp.strip_prefix('-') {
+                Some(disp) => parse_hex_operand(disp).map(|d| next.wrapping_sub(d)),
+                None => parse_hex_operand(disp).map(|d| next.wrapping_add(d)),
+            };
+        }
+
+        // Intel/MASM: [rip +/- disp] or [absolute]
+        if let Some(start) = ops.find('[') {
+            let inner = ops[start + 1..].split(']').next()?.trim();
+            if let Some(rel) = inner.strip_prefix("rip") {
+                let rel = rel.trim_start();
+                if let Some(disp) = rel.strip_prefix('+') {
+                    return parse_hex_operand(disp.trim()).map(|d| next.wrapping_add(d));
+                }
+                return parse_hex_operand(rel.strip_prefix('-')?.trim()).map(|d| next.wrapping_sub(d));
+            }
+            return parse_hex_operand(inner);
+        }
+
+        if self.is_flow_control {
+            return None;
+        }
+        let imm = ops.rsplit(',').next()?.trim();
+        parse_hex_operand(imm.trim_start_matches(['$', '#']))
+    }
+}
+
+/// Target of a decoded jump or call whose operand is a single immediate.
+///
+/// Capstone has already added the relative displacement to the address of
+/// the next instruction. ARM `cbz`/`tbz` carry the label as their last
+/// operand; any register or memory operand in its place means indirect.
+fn immediate_target(detail: &capstone::InsnDetail) -> Option<u64> {
+    use capstone::arch::{arm::ArmOperandType, arm64::Arm64OperandType, x86::X86OperandType, ArchOperand};
+
+    let operands = detail.arch_detail().operands();
+    match operands.last()? {
+        ArchOperand::X86Operand(op) if operands.len() == 1 => match op.op_type {
+            X86OperandType::Imm(imm) => Some(imm as u64),
+            _ => None,
+        },
+        ArchOperand::Arm64Operand(op) => match op.op_type {
+            Arm64OperandType::Imm(imm) => Some(imm as u64),
+            _ => None,
+        },
+        ArchOperand::ArmOperand(op) => match op.op_type {
+            ArmOperandType::Imm(imm) => Some(imm as u32 as u64),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Branch target from operand text, for instructions without decode detail
+/// (`call 0x401000`, `call 401000h` in MASM syntax, `bl #0x401000` on ARM)
+fn parse_target_operand(operands: &str) -> Option<u64> {
+    let op = operands.trim();
+    parse_hex_operand(op.strip_prefix('#').unwrap_or(op))
+}
+
+/// Parse a hex operand in capstone's formats: `0x401000`, or `401000h` (MASM)
+fn parse_hex_operand(op: &str) -> Option<u64> {
+    let hex = op.strip_prefix("0x").or_else(|| op.strip_prefix("0X"))
+        .or_else(|| op.strip_suffix('h').filter(|h| h.starts_with(|c: char| c.is_ascii_digit())))?;
+    u64::from_str_radix(hex, 16).ok()
+}
+
+/// Split capstone operand text into tokens.
+///
+/// `registers` holds the register names of the engine's architecture, so
+/// registers are recognised exactly rather than by pattern. Numbers inside
+/// `[...]`/`(...)`, or directly before `(` in AT&T syntax, are memory
+/// displacements; other numbers of flow-control instructions are addresses.
+fn tokenize_operands(ops: &str, is_flow_control: bool, registers: &HashSet<String>) -> Vec<OperandToken> {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut tokens: Vec<OperandToken> = Vec::new();
+    let mut depth = 0usize;
+    let mut rest = ops;
+
+    while let Some(c) = rest.chars().next() {
+        let word_len = if matches!(c, '%' | '$' | '#') || is_word(c) {
+            c.len_utf8() + rest[c.len_utf8()..].find(|c: char| !is_word(c)).unwrap_or(rest.len() - c.len_utf8())
+        } else {
+            0
+        };
+
+        if word_len == 0 {
+            match c {
+                '[' | '(' => depth += 1,
+                ']' | ')' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            match tokens.last_mut() {
+                Some(last) if last.kind == OperandKind::Punctuation => last.text.push(c),
+                _ => tokens.push(OperandToken { text: c.to_string(), kind: OperandKind::Punctuation }),
+            }
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        let (word, tail) = rest.split_at(word_len);
+        let bare = word.trim_start_matches(['%', '$', '#']);
+        let lower = bare.to_ascii_lowercase();
+        let is_number = !bare.is_empty()
+            && (bare.bytes().all(|b| b.is_ascii_digit()) || parse_hex_operand(bare).is_some());
+
+        let kind = if !word.starts_with(['$', '#']) && registers.contains(&lower) {
+            OperandKind::Register
+        } else if is_number && (depth > 0 || tail.starts_with('(')) {
+            OperandKind::Memory
+        } else if is_number && is_flow_control {
+            OperandKind::Address
+        } else if is_number {
+            OperandKind::Immediate
+        } else if SIZE_KEYWORDS.contains(&lower.as_str()) {
+            OperandKind::Memory
+        } else {
+            OperandKind::Punctuation
+        };
+
+        match tokens.last_mut() {
+            Some(last) if kind == OperandKind::Punctuation && last.kind == kind => last.text.push_str(word),
+            _ => tokens.push(OperandToken { text: word.to_string(), kind }),
+        }
+        rest = tail;
+    }
+    tokens
+}
+
+/// Assembly output syntax for x86/x64
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisasmSyntax {
+    #[default]
+    Intel,
+    Att,
+    Masm,
+    /// Capstone has no dedicated NASM printer; its Intel output is NASM-compatible
+    Nasm,
+}
+
+impl DisasmSyntax {
+    pub const ALL: [DisasmSyntax; 4] = [Self::Intel, Self::Att, Self::Masm, Self::Nasm];
+
+    /// Short name used by settings commands (`intel`, `att`, `masm`, `nasm`)
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Intel => "intel",
+            Self::Att => "att",
+            Self::Masm => "masm",
+            Self::Nasm => "nasm",
+        }
+    }
+
+    /// Parse a syntax name (case-insensitive)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "intel" => Some(Self::Intel),
+            "att" | "at&t" | "gas" => Some(Self::Att),
+            "masm" => Some(Self::Masm),
+            "nasm" => Some(Self::Nasm),
+            _ => None,
+        }
+    }
+
+    fn capstone_syntax(self) -> capstone::arch::x86::ArchSyntax {
+        use capstone::arch::x86::ArchSyntax;
+        match self {
+            Self::Intel | Self::Nasm => ArchSyntax::Intel,
+            Self::Att => ArchSyntax::Att,
+            Self::Masm => ArchSyntax::Masm,
+        }
+    }
+}
+
+impl std::fmt::Display for DisasmSyntax {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Intel => "Intel",
+            Self::Att => "AT&T",
+            Self::Masm => "MASM",
+            Self::Nasm => "NASM",
+        })
+    }
 }
 
 pub struct DisasmEngine {
     cs: Capstone,
+    /// Register names of the architecture, for operand tokenizing
+    registers: HashSet<String>,
 }
 
 impl DisasmEngine {
     pub fn new(is_64bit: bool) -> Result<Self, DisasmError> {
+        Self::with_syntax(is_64bit, DisasmSyntax::default())
+    }
+
+    /// Create an engine for a Ghidra-style architecture spec (e.g. `AARCH64:LE:64:v8A`).
+    ///
+    /// `syntax` only applies to x86; unknown processors fall back to x86,
+    /// except WebAssembly, which capstone cannot decode here.
+    pub fn for_arch(arch_spec: &str, syntax: DisasmSyntax) -> Result<Self, DisasmError> {
+        let mut parts = arch_spec.split(':');
+        let processor = parts.next().unwrap_or("");
+        let endian = match parts.next() {
+            Some("BE") => capstone::Endian::Big,
+            _ => capstone::Endian::Little,
+        };
+        let is_64bit = parts.next() == Some("64");
+
+        let mut cs = match processor {
+            "AARCH64" => Capstone::new()
+                .arm64()
+                .mode(capstone::arch::arm64::ArchMode::Arm)
+                .endian(endian)
+                .detail(true)
+                .build()?,
+            "ARM" => Capstone::new()
+                .arm()
+                .mode(capstone::arch::arm::ArchMode::Arm)
+                .endian(endian)
+                .detail(true)
+                .build()?,
+            "wasm" => return Err(DisasmError::UnsupportedArch),
+            _ => return Self::with_syntax(is_64bit, syntax),
+        };
+        cs.set_skipdata(true)?;
+
+        Ok(Self::from_capstone(cs))
+    }
+
+    /// Create an x86/x64 engine that formats instructions in the given syntax
+    pub fn with_syntax(is_64bit: bool, syntax: DisasmSyntax) -> Result<Self, DisasmError> {
         let mode = if is_64bit {
             capstone::arch::x86::ArchMode::Mode64
         } else {
@@ -61,44 +351,290 @@ impl DisasmEngine {
         let mut cs = Capstone::new()
             .x86()
             .mode(mode)
+            .syntax(syntax.capstone_syntax())
             .detail(true)
             .build()?;
             
         // Enable SKIPDATA to handle invalid bytes gracefully
         cs.set_skipdata(true)?;
 
-        Ok(Self { cs })
+        Ok(Self::from_capstone(cs))
+    }
+
+    fn from_capstone(cs: Capstone) -> Self {
+        let registers = (1..MAX_REG_ID)
+            .filter_map(|id| cs.reg_name(RegId(id)))
+            .map(|name| name.to_ascii_lowercase())
+            .collect();
+        Self { cs, registers }
+    }
+
+    /// Find where to start decoding so that instructions end exactly at `target`.
+    ///
+    /// `bytes` are the bytes immediately before `target`. Each start offset
+    /// within one maximum instruction length is tried; among those whose
+    /// decode lands on `target`, the one with the fewest undecodable bytes
+    /// wins, earliest first. The choice depends only on the bytes, so
+    /// repeated calls for the same target agree.
+    pub fn backward_start(&self, bytes: &[u8], target: u64) -> Option<u64> {
+        let base = target.checked_sub(bytes.len() as u64)?;
+        (0..MAX_INSN_LEN.min(bytes.len()))
+            .filter_map(|skip| {
+                let insns = self.disassemble(&bytes[skip..], base + skip as u64).ok()?;
+                let last = insns.last()?;
+                if last.is_skipped_data() || last.address + last.length as u64 != target {
+                    return None;
+                }
+                let skipped = insns.iter().filter(|i| i.is_skipped_data()).count();
+                Some((skipped, skip))
+            })
+            .min()
+            .map(|(_, skip)| base + skip as u64)
     }
 
-    /// Disassemble a byte slice starting at address
+    /// Disassemble a byte slice starting at address.
+    ///
+    /// Every byte is covered: bytes capstone skips, and any it leaves at the
+    /// end, become one `db 0x??` pseudo-instruction each.
     pub fn disassemble(&self, bytes: &[u8], address: u64) -> Result<Vec<DisassembledInstruction>, DisasmError> {
         let insns = self.cs.disasm_all(bytes, address)?;
         
-        let result = insns.iter().map(|insn| {
-            let is_flow_control = if let Ok(detail) = self.cs.insn_detail(&insn) {
-                let groups = detail.groups();
-                groups.iter().any(|g| {
-                    let g_u8: u8 = g.0;
-                    g_u8 == capstone::InsnGroupType::CS_GRP_JUMP as u8 || 
-                    g_u8 == capstone::InsnGroupType::CS_GRP_CALL as u8 || 
-                    g_u8 == capstone::InsnGroupType::CS_GRP_RET as u8
-                })
+        let mut result: Vec<DisassembledInstruction> = Vec::with_capacity(insns.len());
+        for insn in insns.iter() {
+            if insn.mnemonic() == Some(".byte") {
+                result.extend(insn.bytes().iter().enumerate()
+                    .map(|(i, &b)| self.data_byte(insn.address() + i as u64, b)));
+                continue;
+            }
+
+            let operands = insn.op_str().unwrap_or("");
+            let (is_flow_control, target) = if let Ok(detail) = self.cs.insn_detail(insn) {
+                let in_group = |group: capstone::InsnGroupType::Type| {
+                    detail.groups().iter().any(|g| g.0 == group as u8)
+                };
+                // x86 `loop`/`jecxz` are only tagged as relative branches
+                let is_branch = in_group(capstone::InsnGroupType::CS_GRP_JUMP)
+                    || in_group(capstone::InsnGroupType::CS_GRP_CALL)
+                    || in_group(capstone::InsnGroupType::CS_GRP_BRANCH_RELATIVE);
+                let is_ret = in_group(capstone::InsnGroupType::CS_GRP_RET);
+                (is_branch || is_ret, if is_branch { immediate_target(&detail) } else { None })
             } else {
                 // Fallback heuristic if detail fails
                 let m = insn.mnemonic().unwrap_or("");
-                m.starts_with('j') || m.starts_with("call") || m.starts_with("ret")
+                let is_flow_control = m.starts_with('j') || m.starts_with("call") || m.starts_with("ret");
+                (is_flow_control, if is_flow_control { parse_target_operand(operands) } else { None })
             };
 
-            DisassembledInstruction {
+            result.push(DisassembledInstruction {
                 address: insn.address(),
                 bytes: insn.bytes().to_vec(),
                 mnemonic: insn.mnemonic().unwrap_or("???").to_string(),
-                operands: insn.op_str().unwrap_or("").to_string(),
+                operands: operands.to_string(),
+                tokens: tokenize_operands(operands, is_flow_control, &self.registers),
                 length: insn.len(),
                 is_flow_control,
-            }
-        }).collect();
+                target,
+            });
+        }
 
+        let decoded = result.last().map_or(0, |last| (last.address + last.length as u64 - address) as usize);
+        result.extend(bytes.iter().enumerate().skip(decoded)
+            .map(|(i, &b)| self.data_byte(address + i as u64, b)));
         Ok(result)
     }
+
+    fn data_byte(&self, address: u64, byte: u8) -> DisassembledInstruction {
+        let operands = format!("0x{:02x}", byte);
+        DisassembledInstruction {
+            address,
+            bytes: vec![byte],
+            mnemonic: DATA_MNEMONIC.to_string(),
+            tokens: tokenize_operands(&operands, false, &self.registers),
+            operands,
+            length: 1,
+            is_flow_control: false,
+            target: None,
+        }
+    }
+
+    /// Wrap an instruction decoded elsewhere (e.g. Ghidra's listing) with the
+    /// same flow-control classification and operand tokens as local output.
+    ///
+    /// The text is kept as given; `bytes` are only decoded to classify it
+    /// and resolve its branch target.
+    pub fn external_instruction(&self, address: u64, bytes: Vec<u8>, mnemonic: String, operands: String) -> DisassembledInstruction {
+        let (is_flow_control, target) = self.disassemble(&bytes, address).ok()
+            .and_then(|insns| insns.first().map(|insn| (insn.is_flow_control, insn.target)))
+            .unwrap_or_else(|| {
+                let m = mnemonic.to_ascii_lowercase();
+                let is_flow_control = m.starts_with('j') || m.starts_with("call") || m.starts_with("ret");
+                (is_flow_control, if is_flow_control { parse_target_operand(&operands) } else { None })
+            });
+        DisassembledInstruction {
+            address,
+            length: bytes.len(),
+            bytes,
+            mnemonic,
+            tokens: tokenize_operands(&operands, is_flow_control, &self.registers),
+            operands,
+            is_flow_control,
+            target,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syntax_selection() {
+        // mov rbp, rsp
+        let bytes = [0x48, 0x89, 0xe5];
+
+        let intel = DisasmEngine::with_syntax(true, DisasmSyntax::Intel).unwrap();
+        let insn = &intel.disassemble(&bytes, 0x1000).unwrap()[0];
+        assert_eq!((insn.mnemonic.as_str(), insn.operands.as_str()), ("mov", "rbp, rsp"));
+
+        let att = DisasmEngine::with_syntax(true, DisasmSyntax::Att).unwrap();
+        let insn = &att.disassemble(&bytes, 0x1000).unwrap()[0];
+        assert_eq!((insn.mnemonic.as_str(), insn.operands.as_str()), ("movq", "%rsp, %rbp"));
+
+        assert_eq!(DisasmSyntax::parse("AT&T"), Some(DisasmSyntax::Att));
+        assert_eq!(DisasmSyntax::parse("arm"), None);
+    }
+
+    #[test]
+    fn test_undecodable_bytes() {
+        // invalid in 64-bit mode ; nop ; truncated mov
+        let bytes = [0x06, 0x90, 0x48, 0x89];
+        let engine = DisasmEngine::new(true).unwrap();
+        let insns = engine.disassemble(&bytes, 0x1000).unwrap();
+
+        let listing: Vec<_> = insns.iter()
+            .map(|i| (i.address, i.mnemonic.as_str(), i.operands.as_str()))
+            .collect();
+        assert_eq!(listing, vec![
+            (0x1000, "db", "0x06"),
+            (0x1001, "nop", ""),
+            (0x1002, "db", "0x48"),
+            (0x1003, "db", "0x89"),
+        ]);
+        assert!(insns[0].is_skipped_data() && !insns[1].is_skipped_data());
+    }
+
+    #[test]
+    fn test_backward_start() {
+        // push rbp ; mov rbp, rsp ; sub rsp, 0x10
+        let bytes = [0x55, 0x48, 0x89, 0xe5, 0x48, 0x83, 0xec, 0x10];
+        let engine = DisasmEngine::new(true).unwrap();
+
+        assert_eq!(engine.backward_start(&bytes, 0x1008), Some(0x1000));
+        // Dropping the first byte still lands on the target
+        assert_eq!(engine.backward_start(&bytes[1..], 0x1008), Some(0x1001));
+        assert_eq!(engine.backward_start(&[], 0x1008), None);
+    }
+
+    #[test]
+    fn test_data_reference() {
+        // lea rax, [rip + 0x10] ; push 0x403000 ; call qword ptr [rip - 0x20]
+        let bytes = [
+            0x48, 0x8d, 0x05, 0x10, 0x00, 0x00, 0x00,
+            0x68, 0x00, 0x30, 0x40, 0x00,
+            0xff, 0x15, 0xe0, 0xff, 0xff, 0xff,
+        ];
+        let intel = DisasmEngine::new(true).unwrap();
+        let insns = intel.disassemble(&bytes, 0x1000).unwrap();
+        assert_eq!(insns[0].data_reference(), Some(0x1017));
+        assert_eq!(insns[1].data_reference(), Some(0x403000));
+        assert_eq!(insns[2].data_reference(), Some(0x1012 - 0x20));
+
+        let att = DisasmEngine::with_syntax(true, DisasmSyntax::Att).unwrap();
+        let insns = att.disassemble(&bytes, 0x1000).unwrap();
+        assert_eq!(insns[0].data_reference(), Some(0x1017));
+        assert_eq!(insns[1].data_reference(), Some(0x403000));
+
+        // mov rbp, rsp
+        let insns = intel.disassemble(&[0x48, 0x89, 0xe5], 0x1000).unwrap();
+        assert_eq!(insns[0].data_reference(), None);
+    }
+
+    #[test]
+    fn test_operand_tokens() {
+        use OperandKind::*;
+        let kinds = |insn: &DisassembledInstruction| {
+            assert_eq!(insn.tokens.iter().map(|t| t.text.as_str()).collect::<String>(), insn.operands);
+            insn.tokens.iter()
+                .filter(|t| t.kind != Punctuation)
+                .map(|t| (t.text.clone(), t.kind))
+                .collect::<Vec<_>>()
+        };
+        let tok = |text: &str, kind| (text.to_string(), kind);
+
+        // mov qword ptr [rbp - 8], 0x10 ; call 0x1000
+        let bytes = [0x48, 0xc7, 0x45, 0xf8, 0x10, 0x00, 0x00, 0x00, 0xe8, 0xf3, 0xff, 0xff, 0xff];
+        let intel = DisasmEngine::new(true).unwrap();
+        let insns = intel.disassemble(&bytes, 0x1000).unwrap();
+        assert_eq!(kinds(&insns[0]), vec![
+            tok("qword", Memory), tok("ptr", Memory), tok("rbp", Register), tok("8", Memory), tok("0x10", Immediate),
+        ]);
+        assert_eq!(kinds(&insns[1]), vec![tok("0x1000", Address)]);
+
+        let att = DisasmEngine::with_syntax(true, DisasmSyntax::Att).unwrap();
+        let insns = att.disassemble(&bytes, 0x1000).unwrap();
+        assert_eq!(kinds(&insns[0]), vec![
+            tok("$0x10", Immediate), tok("8", Memory), tok("%rbp", Register),
+        ]);
+    }
+
+    #[test]
+    fn test_aarch64_branch() {
+        // bl #0x1010 ; ret
+        let bytes = [0x04, 0x00, 0x00, 0x94, 0xc0, 0x03, 0x5f, 0xd6];
+        let engine = DisasmEngine::for_arch("AARCH64:LE:64:v8A", DisasmSyntax::Intel).unwrap();
+        let insns = engine.disassemble(&bytes, 0x1000).unwrap();
+
+        assert_eq!(insns.len(), 2);
+        assert_eq!(insns[0].mnemonic, "bl");
+        assert_eq!(insns[0].branch_target(), Some(0x1010));
+        assert_eq!(insns[1].mnemonic, "ret");
+        assert!(insns[1].is_flow_control);
+    }
+
+    #[test]
+    fn test_branch_targets() {
+        // loop 0x1000 ; je 0x1000 ; jmp rax ; call qword ptr [rip + 0x10] ; ret 8 ; jmp 0x1000
+        let bytes = [
+            0xe2, 0xfe,
+            0x74, 0xfc,
+            0xff, 0xe0,
+            0xff, 0x15, 0x10, 0x00, 0x00, 0x00,
+            0xc2, 0x08, 0x00,
+            0xe9, 0xec, 0xff, 0xff, 0xff,
+        ];
+        for syntax in DisasmSyntax::ALL {
+            let engine = DisasmEngine::with_syntax(true, syntax).unwrap();
+            let targets: Vec<_> = engine.disassemble(&bytes, 0x1000).unwrap()
+                .iter()
+                .map(|insn| insn.branch_target())
+                .collect();
+            assert_eq!(targets, vec![Some(0x1000), Some(0x1000), None, None, None, Some(0x1000)], "{:?}", syntax);
+        }
+    }
+
+    #[test]
+    fn test_external_instruction() {
+        let engine = DisasmEngine::new(true).unwrap();
+
+        // Ghidra-style text for `call 0x1005`
+        let insn = engine.external_instruction(0x1000, vec![0xe8, 0, 0, 0, 0], "CALL".into(), "0x1005".into());
+        assert!(insn.is_flow_control);
+        assert_eq!(insn.mnemonic, "CALL");
+        assert_eq!(insn.branch_target(), Some(0x1005));
+
+        let insn = engine.external_instruction(0x1005, vec![0x48, 0x89, 0xc8], "MOV".into(), "RAX,RCX".into());
+        assert!(!insn.is_flow_control);
+        assert_eq!(insn.tokens[0].kind, OperandKind::Register);
+        assert_eq!(insn.length, 3);
+    }
 }
@@ -1,2 +1,3 @@
 mod engine;
+pub mod search;
 pub use engine::*;
@@ -0,0 +1,123 @@
+//! Instruction search
+//!
+//! Linear-sweep decoding of code regions, collecting instructions whose
+//! mnemonic contains a query or whose `mnemonic operands` text matches a regex.
+
+use regex::Regex;
+
+use super::engine::{DisasmEngine, DisasmError, DisassembledInstruction, MAX_INSN_LEN};
+
+/// Bytes decoded per capstone call, so large sections are not held as one
+/// instruction list
+const CHUNK_LEN: usize = 0x10000;
+
+/// What to look for
+pub enum InsnQuery {
+    /// Case-insensitive substring of the mnemonic
+    Mnemonic(String),
+    /// Regex over `mnemonic operands`
+    Pattern(Regex),
+}
+
+impl InsnQuery {
+    /// Build a query; `regex` selects matching on the full instruction text
+    pub fn new(query: &str, regex: bool) -> Result<Self, regex::Error> {
+        if regex {
+            Regex::new(query).map(Self::Pattern)
+        } else {
+            Ok(Self::Mnemonic(query.trim().to_ascii_lowercase()))
+        }
+    }
+
+    pub fn matches(&self, insn: &DisassembledInstruction) -> bool {
+        match self {
+            Self::Mnemonic(mnemonic) => insn.mnemonic.to_ascii_lowercase().contains(mnemonic.as_str()),
+            Self::Pattern(re) => re.is_match(&instruction_text(insn)),
+        }
+    }
+}
+
+/// A matching instruction
+#[derive(Debug, Clone)]
+pub struct InsnMatch {
+    pub address: u64,
+    /// `mnemonic operands`
+    pub text: String,
+}
+
+impl From<&DisassembledInstruction> for InsnMatch {
+    fn from(insn: &DisassembledInstruction) -> Self {
+        Self { address: insn.address, text: instruction_text(insn) }
+    }
+}
+
+fn instruction_text(insn: &DisassembledInstruction) -> String {
+    if insn.operands.is_empty() {
+        insn.mnemonic.clone()
+    } else {
+        format!("{} {}", insn.mnemonic, insn.operands)
+    }
+}
+
+/// Decode each `(address, bytes)` region and collect matches in address order,
+/// stopping after `limit` matches
+pub fn search<'a>(
+    engine: &DisasmEngine,
+    regions: impl IntoIterator<Item = (u64, &'a [u8])>,
+    query: &InsnQuery,
+    limit: usize,
+) -> Result<Vec<InsnMatch>, DisasmError> {
+    let mut matches = Vec::new();
+    for (base, bytes) in regions {
+        let mut pos = 0;
+        while pos < bytes.len() && matches.len() < limit {
+            // Decode a little past the chunk so its last instruction is complete;
+            // the next chunk starts where the last accepted instruction ends
+            let chunk_end = (pos + CHUNK_LEN).min(bytes.len());
+            let decode_end = (chunk_end + MAX_INSN_LEN).min(bytes.len());
+            let chunk_base = base + pos as u64;
+            let insns = engine.disassemble(&bytes[pos..decode_end], chunk_base)?;
+
+            let mut next = chunk_end;
+            for insn in insns.iter().take_while(|i| i.address < base + chunk_end as u64) {
+                next = (insn.address - base) as usize + insn.length;
+                if !insn.is_skipped_data() && query.matches(insn) {
+                    matches.push(InsnMatch::from(insn));
+                    if matches.len() >= limit {
+                        break;
+                    }
+                }
+            }
+            pos = next.max(pos + 1);
+        }
+    }
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_instructions() {
+        // mov eax, 1 ; syscall ; int 0x80 ; ret
+        let bytes = [0xb8, 0x01, 0x00, 0x00, 0x00, 0x0f, 0x05, 0xcd, 0x80, 0xc3];
+        let engine = DisasmEngine::new(true).unwrap();
+        let find = |query: &str, regex: bool| {
+            let query = InsnQuery::new(query, regex).unwrap();
+            search(&engine, [(0x1000, &bytes[..])], &query, 100).unwrap()
+                .into_iter()
+                .map(|m| (m.address, m.text))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(find("SYSCALL", false), vec![(0x1005, "syscall".to_string())]);
+        assert_eq!(find(r"^int 0x80$", true), vec![(0x1007, "int 0x80".to_string())]);
+        assert_eq!(find("jmp", false), vec![]);
+        assert!(InsnQuery::new("(", true).is_err());
+
+        // The limit stops the sweep early
+        let any = InsnQuery::new("", false).unwrap();
+        assert_eq!(search(&engine, [(0x1000, &bytes[..])], &any, 2).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,121 @@
+//! DWARF debug info (`dwarf` feature)
+//!
+//! Reads subprogram ranges and the line program from an ELF image's
+//! `.debug_*` sections.
+
+use std::collections::HashMap;
+
+use gimli::{AttributeValue, EndianSlice, RunTimeEndian, SectionId};
+
+use super::source::{LineRow, LineTable};
+use super::DebugFunction;
+
+/// Debug info extracted from DWARF
+pub struct DebugInfo {
+    pub functions: Vec<DebugFunction>,
+    pub lines: LineTable,
+}
+
+/// Parse DWARF from an ELF image; `None` when it has no (readable) debug info
+pub fn parse_elf(elf: &goblin::elf::Elf, data: &[u8]) -> Option<DebugInfo> {
+    if section_bytes(elf, data, ".debug_info").is_empty() {
+        return None;
+    }
+
+    let endian = if elf.little_endian { RunTimeEndian::Little } else { RunTimeEndian::Big };
+    match load(elf, data, endian) {
+        Ok(info) => Some(info),
+        Err(e) => {
+            log::warn!("Failed to parse DWARF: {}", e);
+            None
+        }
+    }
+}
+
+/// File contents of a named ELF section (empty if missing)
+fn section_bytes<'a>(elf: &goblin::elf::Elf, data: &'a [u8], name: &str) -> &'a [u8] {
+    elf.section_headers.iter()
+        .find(|sh| {
+            sh.sh_type != goblin::elf::section_header::SHT_NOBITS
+                && elf.shdr_strtab.get_at(sh.sh_name) == Some(name)
+        })
+        .and_then(|sh| data.get(sh.sh_offset as usize..(sh.sh_offset + sh.sh_size) as usize))
+        .unwrap_or(&[])
+}
+
+fn load(elf: &goblin::elf::Elf, data: &[u8], endian: RunTimeEndian) -> gimli::Result<DebugInfo> {
+    let dwarf = gimli::Dwarf::load(|id: SectionId| -> gimli::Result<_> {
+        Ok(EndianSlice::new(section_bytes(elf, data, id.name()), endian))
+    })?;
+
+    let mut functions = Vec::new();
+    let mut files: Vec<String> = Vec::new();
+    let mut file_ids: HashMap<String, u32> = HashMap::new();
+    let mut rows = Vec::new();
+
+    let mut units = dwarf.units();
+    while let Some(header) = units.next()? {
+        let unit = dwarf.unit(header)?;
+
+        // Functions
+        let mut entries = unit.entries();
+        while let Some((_, entry)) = entries.next_dfs()? {
+            if entry.tag() != gimli::DW_TAG_subprogram {
+                continue;
+            }
+            let Some(low_pc) = entry.attr_value(gimli::DW_AT_low_pc)? else { continue };
+            let Some(address) = dwarf.attr_address(&unit, low_pc)? else { continue };
+            if address == 0 {
+                continue;
+            }
+            // DWARF 4+ encodes high_pc as a length
+            let size = match entry.attr_value(gimli::DW_AT_high_pc)? {
+                Some(AttributeValue::Udata(len)) => len,
+                Some(high_pc) => dwarf.attr_address(&unit, high_pc)?
+                    .map_or(0, |end| end.saturating_sub(address)),
+                None => 0,
+            };
+            let name = match entry.attr_value(gimli::DW_AT_name)? {
+                Some(value) => dwarf.attr_string(&unit, value)?.to_string_lossy().into_owned(),
+                None => String::new(),
+            };
+            functions.push(DebugFunction { name, address, size });
+        }
+
+        // Line program
+        let Some(program) = unit.line_program.clone() else { continue };
+        let mut unit_files: HashMap<u64, u32> = HashMap::new();
+        let mut program_rows = program.rows();
+        while let Some((header, row)) = program_rows.next_row()? {
+            if row.end_sequence() {
+                rows.push(LineRow { address: row.address(), file: None, line: 0 });
+                continue;
+            }
+
+            let file = match unit_files.get(&row.file_index()) {
+                Some(&id) => id,
+                None => {
+                    let Some(entry) = row.file(header) else { continue };
+                    let name = dwarf.attr_string(&unit, entry.path_name())?.to_string_lossy().into_owned();
+                    let path = match entry.directory(header) {
+                        Some(dir) if !name.starts_with('/') => {
+                            let dir = dwarf.attr_string(&unit, dir)?.to_string_lossy().into_owned();
+                            format!("{}/{}", dir, name)
+                        }
+                        _ => name,
+                    };
+                    let id = *file_ids.entry(path).or_insert_with_key(|path| {
+                        files.push(path.clone());
+                        (files.len() - 1) as u32
+                    });
+                    unit_files.insert(row.file_index(), id);
+                    id
+                }
+            };
+            let line = row.line().map_or(0, |l| l.get() as u32);
+            rows.push(LineRow { address: row.address(), file: Some(file), line });
+        }
+    }
+
+    Ok(DebugInfo { functions, lines: LineTable::new(files, rows) })
+}
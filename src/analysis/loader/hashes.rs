@@ -0,0 +1,88 @@
+//! File hashes for triage
+//!
+//! MD5, SHA-1 and SHA-256 of the file contents, plus the PE import hash
+//! (imphash) used by VirusTotal and other sandboxes to cluster samples.
+
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use serde::Serialize;
+
+use super::LoadedBinary;
+
+/// Hex-encoded hashes of a loaded file
+#[derive(Debug, Clone, Serialize)]
+pub struct Hashes {
+    pub md5: String,
+    pub sha1: String,
+    pub sha256: String,
+    /// MD5 of the normalized import list (PE with imports only)
+    pub imphash: Option<String>,
+}
+
+impl LoadedBinary {
+    /// Hash the file as loaded (patches are not included)
+    pub fn hashes(&self) -> Hashes {
+        let imphash = if self.format == "PE" {
+            goblin::pe::PE::parse(&self.data).ok()
+                .map(|pe| imphash_input(pe.imports.iter().map(|i| (i.dll, i.name.as_ref()))))
+                .filter(|input| !input.is_empty())
+                .map(|input| hex::encode(Md5::digest(input)))
+        } else {
+            None
+        };
+
+        Hashes {
+            md5: hex::encode(Md5::digest(&self.data)),
+            sha1: hex::encode(Sha1::digest(&self.data)),
+            sha256: hex::encode(Sha256::digest(&self.data)),
+            imphash,
+        }
+    }
+}
+
+/// The string hashed for the imphash, following pefile's normalization:
+/// `dll.function` pairs in import order, lowercased, with `.dll`/`.ocx`/`.sys`
+/// dropped from library names, joined by commas.
+///
+/// Ordinal imports become `ordN`; pefile additionally resolves a few
+/// well-known ordinals (ws2_32, oleaut32) to names, which is not done here.
+fn imphash_input<'a>(imports: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+    imports
+        .map(|(dll, name)| {
+            let dll = dll.to_ascii_lowercase();
+            let lib = match dll.rsplit_once('.') {
+                Some((stem, "dll" | "ocx" | "sys")) => stem,
+                _ => dll.as_str(),
+            };
+            // goblin names ordinal imports "ORDINAL <n>"
+            let func = match name.strip_prefix("ORDINAL ") {
+                Some(ordinal) => format!("ord{}", ordinal),
+                None => name.to_ascii_lowercase(),
+            };
+            format!("{}.{}", lib, func)
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imphash_input() {
+        let imports = [
+            ("KERNEL32.dll", "CreateFileA"),
+            ("WS2_32.dll", "ORDINAL 115"),
+            ("msvbvm60.OCX", "Foo"),
+            ("custom.bin", "Bar"),
+        ];
+        assert_eq!(
+            imphash_input(imports.into_iter()),
+            "kernel32.createfilea,ws2_32.ord115,msvbvm60.foo,custom.bin.bar"
+        );
+        assert_eq!(imphash_input(std::iter::empty()), "");
+    }
+}
@@ -5,13 +5,38 @@
 //! - Exported/imported functions
 //! - Sections with code
 //! - Symbol information
+//! - Source line mapping (DWARF, with the `dwarf` feature)
+//! - Private PE symbols from a `.pdb` (with the `pdb` feature)
+//! - WebAssembly modules (with the `wasm` feature)
 
 use anyhow::{anyhow, Result};
+use serde::Serialize;
+use crate::analysis::disasm::{DisasmEngine, DisasmSyntax};
+use std::collections::HashSet;
 use std::fs;
+use std::ops::Range;
 use std::path::Path;
 
+mod hashes;
+#[cfg(feature = "dwarf")]
+mod dwarf;
+#[cfg(feature = "pdb")]
+mod pdb_symbols;
+#[cfg(feature = "wasm")]
+mod wasm;
+pub mod source;
+
+pub use hashes::Hashes;
+pub use source::LineTable;
+
+/// Symbol names taken as the program's `main`, in order of preference
+const MAIN_NAMES: &[&str] = &["main", "_main", "wmain", "WinMain", "wWinMain"];
+
+/// Bytes of the entry point scanned for the call into the C runtime
+const CRT_START_SCAN: usize = 0x80;
+
 /// Information about a function found in the binary
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FunctionInfo {
     /// Function name (may be empty for unnamed functions)
     pub name: String,
@@ -23,10 +48,26 @@ pub struct FunctionInfo {
     pub is_export: bool,
     /// Whether this is an imported function (stub)
     pub is_import: bool,
+    /// Export ordinal (PE only)
+    pub ordinal: Option<u16>,
+    /// Forwarder target such as `NTDLL.RtlAllocateHeap` (PE only)
+    pub forwarded_to: Option<String>,
+    /// Whether this is a TLS callback (PE only, runs before the entry point)
+    pub is_tls_callback: bool,
 }
 
-/// Information about a section in the binary
+/// An import's slot in the PE import address table
 #[derive(Debug, Clone)]
+pub struct IatSlot {
+    /// Virtual address of the slot (the import's `FunctionInfo::address`)
+    pub address: u64,
+    /// DLL the import is bound from, e.g. `KERNEL32.dll`
+    pub module: String,
+    pub name: String,
+}
+
+/// Information about a section in the binary
+#[derive(Debug, Clone, Serialize)]
 pub struct SectionInfo {
     /// Section name
     pub name: String,
@@ -44,14 +85,72 @@ pub struct SectionInfo {
     pub is_readable: bool,
     /// Is this section writable?
     pub is_writable: bool,
+    /// Shannon entropy of the section's file bytes (0.0 - 8.0 bits per byte)
+    pub entropy: f64,
+}
+
+impl SectionInfo {
+    /// Size of the section in memory
+    ///
+    /// Some formats leave `virtual_size` at 0; the raw size is used then.
+    pub fn mapped_size(&self) -> u64 {
+        if self.virtual_size > 0 { self.virtual_size } else { self.file_size }
+    }
+}
+
+/// Architectures offered when opening a headerless blob
+pub const RAW_ARCH_SPECS: &[&str] = &[
+    "x86:LE:64:default",
+    "x86:LE:32:default",
+    "AARCH64:LE:64:v8A",
+    "ARM:LE:32:v7",
+];
+
+/// Upper bound on `LoadedBinary::estimate_function_size`
+pub const MAX_ESTIMATED_FUNCTION_SIZE: u64 = 0x10000;
+
+/// Entropy above which a code section is likely packed or encrypted
+pub const PACKED_ENTROPY_THRESHOLD: f64 = 7.0;
+
+/// Shannon entropy of a byte slice in bits per byte
+pub fn shannon_entropy(data: &[u8]) -> f64 {
+    if data.is_empty() {
+        return 0.0;
+    }
+
+    let mut counts = [0usize; 256];
+    for &b in data {
+        counts[b as usize] += 1;
+    }
+
+    let len = data.len() as f64;
+    counts.iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// File range past the last section's raw data, if the file extends beyond it.
+///
+/// With no raw section data the headers are taken as the end of the image.
+fn pe_overlay(sections: &[SectionInfo], size_of_headers: u64, file_len: u64) -> Option<Range<u64>> {
+    let end = sections.iter()
+        .filter(|s| s.file_size > 0)
+        .map(|s| s.file_offset + s.file_size)
+        .fold(size_of_headers, u64::max);
+    (file_len > end).then_some(end..file_len)
 }
 
 /// Parsed binary information
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct LoadedBinary {
     /// Original file path
     pub path: String,
     /// Raw bytes of the file
+    #[serde(skip)]
     pub data: Vec<u8>,
     /// Detected architecture (e.g., "x86:LE:64:default")
     pub arch_spec: String,
@@ -67,6 +166,308 @@ pub struct LoadedBinary {
     pub is_64bit: bool,
     /// Binary format (PE, ELF, Mach-O)
     pub format: String,
+    /// Whether the binary has a TLS directory (PE only)
+    pub has_tls: bool,
+    /// Address-to-source mapping from debug info (empty if none)
+    #[serde(skip)]
+    pub line_table: LineTable,
+    /// In-memory byte patches, in the order they were applied.
+    ///
+    /// `data` keeps the file as loaded; `get_bytes` reads through this overlay.
+    pub patches: Vec<Patch>,
+    /// File bytes past the end of the last section (appended data, PE only)
+    pub overlay: Option<Range<u64>>,
+}
+
+/// Bytes written over the loaded image by `LoadedBinary::patch`
+#[derive(Debug, Clone, Serialize)]
+pub struct Patch {
+    /// Virtual address of the first patched byte
+    pub address: u64,
+    /// Bytes that were visible at `address` before the patch
+    pub original: Vec<u8>,
+    /// Replacement bytes
+    pub bytes: Vec<u8>,
+}
+
+impl Patch {
+    /// Copy the part of this patch that overlaps `buf` (which starts at `address`)
+    fn apply(&self, address: u64, buf: &mut [u8]) {
+        let start = self.address.max(address);
+        let end = (self.address + self.bytes.len() as u64).min(address + buf.len() as u64);
+        if start >= end {
+            return;
+        }
+        let src = (start - self.address) as usize;
+        let dst = (start - address) as usize;
+        let len = (end - start) as usize;
+        buf[dst..dst + len].copy_from_slice(&self.bytes[src..src + len]);
+    }
+}
+
+/// Collect PE exports, including ordinal-only exports and forwarders
+fn pe_exports(pe: &goblin::pe::PE, data: &[u8], image_base: u64) -> Vec<FunctionInfo> {
+    use goblin::pe::export::{ExportAddressTableEntry, Reexport};
+
+    let Some(export_data) = &pe.export_data else {
+        return Vec::new();
+    };
+    let ordinal_base = export_data.export_directory_table.ordinal_base;
+    let ordinal_of = |slot: usize| u16::try_from(ordinal_base as usize + slot).ok();
+
+    // goblin yields named exports in name-table order; the ordinal table maps
+    // each name to its address-table slot. Only trust the pairing when no
+    // entry was dropped during parsing.
+    let name_slots = &export_data.export_ordinal_table;
+    let paired = pe.exports.len() == name_slots.len();
+    let named: HashSet<u16> = name_slots.iter().copied().collect();
+
+    let mut functions = Vec::new();
+    for (i, export) in pe.exports.iter().enumerate() {
+        let Some(name) = export.name else { continue };
+        let forwarded_to = export.reexport.as_ref().map(|r| match r {
+            Reexport::DLLName { export, lib } => format!("{}.{}", lib, export),
+            Reexport::DLLOrdinal { ordinal, lib } => format!("{}.#{}", lib, ordinal),
+        });
+        functions.push(FunctionInfo {
+            name: name.to_string(),
+            address: image_base + export.rva as u64,
+            size: 0,
+            is_export: true,
+            is_import: false,
+            ordinal: if paired { ordinal_of(name_slots[i] as usize) } else { None },
+            forwarded_to,
+            is_tls_callback: false,
+        });
+    }
+
+    // Address-table slots that no name refers to are ordinal-only exports
+    for (slot, entry) in export_data.export_address_table.iter().enumerate() {
+        if named.contains(&(slot as u16)) {
+            continue;
+        }
+        let (rva, forwarded_to) = match *entry {
+            ExportAddressTableEntry::ExportRVA(0) => continue,
+            ExportAddressTableEntry::ExportRVA(rva) => (rva, None),
+            ExportAddressTableEntry::ForwarderRVA(rva) => (rva, pe_string_at(pe, data, rva)),
+        };
+        let Some(ordinal) = ordinal_of(slot) else { continue };
+        functions.push(FunctionInfo {
+            name: format!("ordinal_{}", ordinal),
+            address: image_base + rva as u64,
+            size: 0,
+            is_export: true,
+            is_import: false,
+            ordinal: Some(ordinal),
+            forwarded_to,
+            is_tls_callback: false,
+        });
+    }
+
+    functions
+}
+
+/// Function recovered from debug info (DWARF or PDB)
+struct DebugFunction {
+    name: String,
+    address: u64,
+    /// Size in bytes (0 if unknown)
+    size: u64,
+}
+
+impl From<DebugFunction> for FunctionInfo {
+    fn from(df: DebugFunction) -> Self {
+        FunctionInfo {
+            name: df.name,
+            address: df.address,
+            size: df.size,
+            is_export: false,
+            is_import: false,
+            ordinal: None,
+            forwarded_to: None,
+            is_tls_callback: false,
+        }
+    }
+}
+
+/// Merge debug-info functions into the symbol-derived function list.
+///
+/// Existing entries take the debug size and, if unnamed, the debug name.
+/// Returns how many functions were added.
+fn merge_debug_functions(functions: &mut Vec<FunctionInfo>, debug: Vec<FunctionInfo>) -> usize {
+    let mut index: std::collections::HashMap<u64, usize> = functions.iter()
+        .enumerate()
+        .map(|(i, f)| (f.address, i))
+        .collect();
+    let before = functions.len();
+
+    for df in debug {
+        match index.get(&df.address) {
+            Some(&i) => {
+                let func = &mut functions[i];
+                if df.size > 0 {
+                    func.size = df.size;
+                }
+                if func.name.is_empty() {
+                    func.name = df.name;
+                }
+            }
+            None => {
+                index.insert(df.address, functions.len());
+                functions.push(df);
+            }
+        }
+    }
+
+    functions.len() - before
+}
+
+/// Functions found by one step of the loader, in discovery order
+#[derive(Debug, Clone)]
+pub enum FunctionBatch {
+    /// New entries, appended as they are
+    Found(Vec<FunctionInfo>),
+    /// Functions (and line numbers) from debug info. Known addresses take
+    /// the debug size and, if unnamed, the debug name; the rest are appended.
+    Debug {
+        functions: Vec<FunctionInfo>,
+        lines: Option<LineTable>,
+    },
+}
+
+impl FunctionBatch {
+    /// Add the batch to `binary`
+    pub fn apply(self, binary: &mut LoadedBinary) {
+        self.apply_to(&mut binary.functions, &mut binary.line_table);
+    }
+
+    fn apply_to(self, functions: &mut Vec<FunctionInfo>, line_table: &mut LineTable) {
+        match self {
+            Self::Found(found) => functions.extend(found),
+            Self::Debug { functions: debug, lines } => {
+                merge_debug_functions(functions, debug);
+                if let Some(lines) = lines {
+                    *line_table = lines;
+                }
+            }
+        }
+    }
+}
+
+/// Functions read so far by `LoadedBinary::discover_functions`
+struct Discovery<'a> {
+    functions: Vec<FunctionInfo>,
+    line_table: LineTable,
+    found: &'a mut dyn FnMut(FunctionBatch),
+}
+
+impl Discovery<'_> {
+    /// Report a batch, then keep it
+    fn push(&mut self, batch: FunctionBatch) {
+        if matches!(&batch, FunctionBatch::Found(found) if found.is_empty()) {
+            return;
+        }
+        (self.found)(batch.clone());
+        batch.apply_to(&mut self.functions, &mut self.line_table);
+    }
+
+    fn has(&self, address: u64) -> bool {
+        self.functions.iter().any(|f| f.address == address)
+    }
+}
+
+/// PDB file name and GUID from the CodeView debug record
+#[cfg(feature = "pdb")]
+fn pe_codeview(pe: &goblin::pe::PE) -> Option<(String, [u8; 16])> {
+    let info = pe.debug_data.as_ref()?.codeview_pdb70_debug_info.as_ref()?;
+    let len = info.filename.iter().position(|&b| b == 0).unwrap_or(info.filename.len());
+    Some((String::from_utf8_lossy(&info.filename[..len]).into_owned(), info.signature))
+}
+
+/// Upper bound on TLS callbacks read from a (possibly malformed) callback array
+const MAX_TLS_CALLBACKS: usize = 64;
+
+/// Read the TLS callback addresses of a PE image.
+///
+/// Returns `None` when there is no TLS directory, and an empty list when the
+/// directory exists but registers no callbacks.
+fn pe_tls_callbacks(pe: &goblin::pe::PE, data: &[u8]) -> Option<Vec<u64>> {
+    let optional_header = pe.header.optional_header.as_ref()?;
+    let dir = optional_header.data_directories.get_tls_table()?;
+    if dir.virtual_address == 0 {
+        return None;
+    }
+
+    // IMAGE_TLS_DIRECTORY: AddressOfCallBacks is the fourth pointer-sized field
+    let ptr_size = if pe.is_64 { 8 } else { 4 };
+    let read_ptr = |offset: usize| -> Option<u64> {
+        let bytes = data.get(offset..offset + ptr_size)?;
+        Some(if pe.is_64 {
+            u64::from_le_bytes(bytes.try_into().ok()?)
+        } else {
+            u32::from_le_bytes(bytes.try_into().ok()?) as u64
+        })
+    };
+
+    let dir_offset = pe_rva_to_offset(pe, dir.virtual_address)?;
+    let mut callbacks = Vec::new();
+    let callbacks_va = read_ptr(dir_offset + 3 * ptr_size).unwrap_or(0);
+    let image_base = pe.image_base as u64;
+    if callbacks_va > image_base {
+        // The callback array holds absolute VAs, terminated by zero
+        if let Some(mut offset) = u32::try_from(callbacks_va - image_base).ok()
+            .and_then(|rva| pe_rva_to_offset(pe, rva))
+        {
+            while callbacks.len() < MAX_TLS_CALLBACKS {
+                match read_ptr(offset) {
+                    Some(va) if va != 0 => callbacks.push(va),
+                    _ => break,
+                }
+                offset += ptr_size;
+            }
+        }
+    }
+    Some(callbacks)
+}
+
+/// File offset of an RVA inside a PE image
+fn pe_rva_to_offset(pe: &goblin::pe::PE, rva: u32) -> Option<usize> {
+    // Header fields are untrusted: sums that overflow match nothing
+    let section = pe.sections.iter().find(|s| {
+        let size = s.virtual_size.max(s.size_of_raw_data);
+        rva >= s.virtual_address && s.virtual_address.checked_add(size).is_some_and(|end| rva < end)
+    })?;
+    let offset = (rva - section.virtual_address).checked_add(section.pointer_to_raw_data)?;
+    Some(offset as usize)
+}
+
+/// Read a NUL-terminated string at an RVA inside a PE image
+fn pe_string_at(pe: &goblin::pe::PE, data: &[u8], rva: u32) -> Option<String> {
+    let offset = pe_rva_to_offset(pe, rva)?;
+    let bytes = data.get(offset..)?;
+    let end = bytes.iter().position(|&b| b == 0)?;
+    Some(String::from_utf8_lossy(&bytes[..end]).into_owned())
+}
+
+/// Synthetic `seg0`, `seg1`, ... sections for the `PT_LOAD` segments of an
+/// ELF whose section headers are missing or zeroed
+fn elf_segments(program_headers: &[goblin::elf::ProgramHeader]) -> Vec<SectionInfo> {
+    use goblin::elf::program_header::{PF_R, PF_W, PF_X, PT_LOAD};
+    program_headers.iter()
+        .filter(|ph| ph.p_type == PT_LOAD)
+        .enumerate()
+        .map(|(i, ph)| SectionInfo {
+            name: format!("seg{}", i),
+            virtual_address: ph.p_vaddr,
+            virtual_size: ph.p_memsz,
+            file_offset: ph.p_offset,
+            file_size: ph.p_filesz,
+            is_executable: ph.p_flags & PF_X != 0,
+            is_readable: ph.p_flags & PF_R != 0,
+            is_writable: ph.p_flags & PF_W != 0,
+            entropy: 0.0,
+        })
+        .collect()
 }
 
 impl LoadedBinary {
@@ -79,6 +480,126 @@ impl LoadedBinary {
 
     /// Parse binary from bytes
     pub fn from_bytes(data: Vec<u8>, path: String) -> Result<Self> {
+        let mut binary = Self::parse(data, path)?;
+        binary.compute_section_entropy();
+        binary.discover_functions(&mut |_| {});
+        Ok(binary)
+    }
+
+    /// Parse a binary for a caller that shows it while it loads.
+    ///
+    /// `loaded` gets the binary as soon as its headers are parsed, without
+    /// functions; they follow through `found` batch by batch as they are
+    /// read. Applying the batches in order gives what `from_bytes` returns.
+    pub fn from_bytes_progressive(
+        data: Vec<u8>,
+        path: String,
+        loaded: impl FnOnce(Self),
+        mut found: impl FnMut(FunctionBatch),
+    ) -> Result<()> {
+        let mut binary = Self::parse(data, path)?;
+        binary.compute_section_entropy();
+
+        // Formats read in one pass (WASM) already carry their functions
+        let parsed = std::mem::take(&mut binary.functions);
+        loaded(binary.clone());
+        if !parsed.is_empty() {
+            found(FunctionBatch::Found(parsed.clone()));
+        }
+        binary.functions = parsed;
+        binary.discover_functions(&mut found);
+        Ok(())
+    }
+
+    /// Read the functions of a freshly parsed binary, reporting each batch to
+    /// `found`: symbol tables, exports, imports and TLS callbacks first, then
+    /// debug info, then the entry point and, for stripped ELFs, CRT `main`.
+    fn discover_functions(&mut self, found: &mut dyn FnMut(FunctionBatch)) {
+        let mut discovery = Discovery {
+            functions: std::mem::take(&mut self.functions),
+            line_table: std::mem::take(&mut self.line_table),
+            found,
+        };
+        match self.format.as_str() {
+            "PE" => self.discover_pe(&mut discovery),
+            "PE (Fallback)" => self.discover_pe_fallback(&mut discovery),
+            "ELF" => self.discover_elf(&mut discovery),
+            "Mach-O" => self.discover_macho(&mut discovery),
+            _ => {}
+        }
+        let Discovery { functions, line_table, found } = discovery;
+        self.functions = functions;
+        self.line_table = line_table;
+
+        // Stripped CRT binaries: recover main from what _start hands the C runtime
+        if self.format == "ELF" && self.main_function().is_none() {
+            if let Some(address) = self.crt_main() {
+                let main = FunctionInfo {
+                    name: "main".to_string(),
+                    address,
+                    size: 0,
+                    is_export: false,
+                    is_import: false,
+                    ordinal: None,
+                    forwarded_to: None,
+                    is_tls_callback: false,
+                };
+                found(FunctionBatch::Found(vec![main.clone()]));
+                self.functions.push(main);
+            }
+        }
+    }
+
+    /// Wrap headerless bytes (e.g. shellcode) mapped at `base_addr`.
+    ///
+    /// The whole blob becomes one executable section, with a single function
+    /// spanning it at the entry point (the base).
+    pub fn from_raw(data: Vec<u8>, base_addr: u64, arch_spec: &str) -> Result<Self> {
+        if data.is_empty() {
+            return Err(anyhow!("File too small"));
+        }
+
+        let size = data.len() as u64;
+        let mut binary = Self {
+            path: "<raw>".to_string(),
+            data,
+            arch_spec: arch_spec.to_string(),
+            entry_point: base_addr,
+            image_base: base_addr,
+            functions: vec![FunctionInfo {
+                name: "entry".to_string(),
+                address: base_addr,
+                size,
+                is_export: false,
+                is_import: false,
+                ordinal: None,
+                forwarded_to: None,
+                is_tls_callback: false,
+            }],
+            sections: vec![SectionInfo {
+                name: ".raw".to_string(),
+                virtual_address: base_addr,
+                virtual_size: size,
+                file_offset: 0,
+                file_size: size,
+                is_executable: true,
+                is_readable: true,
+                is_writable: true,
+                entropy: 0.0,
+            }],
+            is_64bit: arch_spec.split(':').nth(2) == Some("64"),
+            format: "Raw".to_string(),
+            has_tls: false,
+            line_table: LineTable::default(),
+            patches: Vec::new(),
+            overlay: None,
+        };
+        binary.compute_section_entropy();
+        Ok(binary)
+    }
+
+    /// Dispatch to the format-specific parser
+    fn parse(data: Vec<u8>, path: String) -> Result<Self> {
         // Check magic bytes to determine format
         if data.len() < 4 {
             return Err(anyhow!("File too small"));
@@ -94,6 +615,14 @@ impl LoadedBinary {
             return Self::parse_elf(data, path);
         }
         
+        // Check for WebAssembly (\0asm)
+        if data.starts_with(b"\0asm") {
+            #[cfg(feature = "wasm")]
+            return Self::parse_wasm(data, path);
+            #[cfg(not(feature = "wasm"))]
+            return Err(anyhow!("WebAssembly support not enabled (build with --features wasm)"));
+        }
+        
         // Check for Mach-O
         if data.len() > 4 {
             let magic = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
@@ -139,45 +668,17 @@ impl LoadedBinary {
                         is_executable: (characteristics & 0x20000000) != 0,
                         is_readable: (characteristics & 0x40000000) != 0,
                         is_writable: (characteristics & 0x80000000) != 0,
+                        entropy: 0.0,
                     });
                 }
 
-                // Collect functions from exports
-                let mut functions = Vec::new();
-                for export in &pe.exports {
-                    if let Some(name) = &export.name {
-                        functions.push(FunctionInfo {
-                            name: name.to_string(),
-                            address: image_base + export.rva as u64,
-                            size: 0,
-                            is_export: true,
-                            is_import: false,
-                        });
-                    }
-                }
-
-                // Add imports
-                for import in &pe.imports {
-                    functions.push(FunctionInfo {
-                        name: import.name.to_string(),
-                        address: image_base + import.rva as u64,
-                        size: 0,
-                        is_export: false,
-                        is_import: true,
-                    });
-                }
+                // TLS callbacks themselves are read with the other functions
+                let has_tls = pe_tls_callbacks(&pe, &data).is_some();
 
-                // Add entry point
-                let has_entry = functions.iter().any(|f| f.address == entry_point);
-                if !has_entry {
-                    functions.push(FunctionInfo {
-                        name: "_start".to_string(),
-                        address: entry_point,
-                        size: 0,
-                        is_export: false,
-                        is_import: false,
-                    });
-                }
+                let size_of_headers = pe.header.optional_header
+                    .map(|o| o.windows_fields.size_of_headers as u64)
+                    .unwrap_or(0);
+                let overlay = pe_overlay(&sections, size_of_headers, data.len() as u64);
 
                 Ok(Self {
                     path,
@@ -185,44 +686,192 @@ impl LoadedBinary {
                     arch_spec: arch_spec.to_string(),
                     entry_point,
                     image_base,
-                    functions,
+                    functions: Vec::new(),
                     sections,
                     is_64bit,
                     format: "PE".to_string(),
+                    has_tls,
+                    line_table: LineTable::default(),
+                    patches: Vec::new(),
+                    overlay,
                 })
             }
             Err(e) => {
-                // Fallback to 'object' crate if goblin fails
-                // Note: We need to import object features here or at top level
-                use object::{Object, File};
-                
-                let file = File::parse(&*data).map_err(|e| anyhow!("Failed fallback parsing: {}", e))?;
-                
-                let is_64bit = file.is_64();
-                let entry_point = file.entry();
-                let image_base = file.relative_address_base();
-                let sections: Vec<SectionInfo> = Vec::new(); // Basic info only for fallback
-                
-                // object crate gives limited section info easily, this is a minimal fallback
-                
-                // Just return minimal info to allow loading
-                let arch_spec = if is_64bit { "x86:LE:64:default" } else { "x86:LE:32:default" };
-                
-                Ok(Self {
-                    path,
-                    data,
-                    arch_spec: arch_spec.to_string(),
-                    entry_point,
-                    image_base,
-                    functions: Vec::new(), // Minimal info
-                    sections,
-                    is_64bit,
-                    format: "PE (Fallback)".to_string(),
-                })
+                log::warn!("goblin could not parse {} ({}), falling back to object", path, e);
+                Self::parse_pe_fallback(data, path)
             }
         }
     }
 
+    /// Exports, imports, TLS callbacks, then PDB symbols and the entry point
+    fn discover_pe(&self, discovery: &mut Discovery) {
+        let Ok(pe) = goblin::pe::PE::parse(&self.data) else {
+            return;
+        };
+        let image_base = self.image_base;
+        discovery.push(FunctionBatch::Found(pe_exports(&pe, &self.data, image_base)));
+
+        let imports = pe.imports.iter()
+            .map(|import| FunctionInfo {
+                name: import.name.to_string(),
+                address: image_base + import.rva as u64,
+                size: 0,
+                is_export: false,
+                is_import: true,
+                ordinal: None,
+                forwarded_to: None,
+                is_tls_callback: false,
+            })
+            .collect();
+        discovery.push(FunctionBatch::Found(imports));
+
+        // TLS callbacks run before the entry point
+        let tls_callbacks = pe_tls_callbacks(&pe, &self.data).unwrap_or_default().into_iter()
+            .enumerate()
+            .map(|(i, address)| FunctionInfo {
+                name: format!("tls_callback_{}", i),
+                address,
+                size: 0,
+                is_export: false,
+                is_import: false,
+                ordinal: None,
+                forwarded_to: None,
+                is_tls_callback: true,
+            })
+            .collect();
+        discovery.push(FunctionBatch::Found(tls_callbacks));
+
+        // Private symbols from a matching PDB next to the binary
+        #[cfg(feature = "pdb")]
+        {
+            let codeview = pe_codeview(&pe);
+            let pdb_name = codeview.as_ref().map(|(name, _)| name.as_str());
+            if let Some(pdb_path) = pdb_symbols::find_sibling(Path::new(&self.path), pdb_name) {
+                match pdb_symbols::load(&pdb_path, codeview.map(|(_, guid)| guid), image_base) {
+                    Ok(debug) => discovery.push(FunctionBatch::Debug {
+                        functions: debug.into_iter().map(FunctionInfo::from).collect(),
+                        lines: None,
+                    }),
+                    Err(e) => log::debug!("Skipping {}: {}", pdb_path.display(), e),
+                }
+            }
+        }
+
+        // Add entry point
+        if !discovery.has(self.entry_point) {
+            discovery.push(FunctionBatch::Found(vec![FunctionInfo {
+                name: "_start".to_string(),
+                address: self.entry_point,
+                size: 0,
+                is_export: false,
+                is_import: false,
+                ordinal: None,
+                forwarded_to: None,
+                is_tls_callback: false,
+            }]));
+        }
+    }
+
+    /// Parse a PE that goblin rejects with the `object` crate.
+    ///
+    /// Sections come from the section table; functions from symbols and
+    /// exports, plus the entry point (see `discover_pe_fallback`). Imports,
+    /// TLS callbacks and PDB symbols are not recovered.
+    fn parse_pe_fallback(data: Vec<u8>, path: String) -> Result<Self> {
+        use object::{Object, ObjectSection, SectionFlags};
+
+        let file = object::File::parse(&*data).map_err(|e| anyhow!("Failed fallback parsing: {}", e))?;
+
+        let is_64bit = file.is_64();
+        let entry_point = file.entry();
+        let image_base = file.relative_address_base();
+        let arch_spec = if is_64bit { "x86:LE:64:default" } else { "x86:LE:32:default" };
+
+        let sections: Vec<SectionInfo> = file.sections()
+            .map(|section| {
+                let characteristics = match section.flags() {
+                    SectionFlags::Coff { characteristics } => characteristics,
+                    _ => 0,
+                };
+                let (file_offset, file_size) = section.file_range().unwrap_or((0, 0));
+                SectionInfo {
+                    name: section.name().unwrap_or("").to_string(),
+                    virtual_address: section.address(),
+                    virtual_size: section.size(),
+                    file_offset,
+                    file_size,
+                    is_executable: (characteristics & 0x20000000) != 0,
+                    is_readable: (characteristics & 0x40000000) != 0,
+                    is_writable: (characteristics & 0x80000000) != 0,
+                    entropy: 0.0,
+                }
+            })
+            .collect();
+
+        // Without the optional header's header size, only trust raw section data
+        let overlay = if sections.iter().any(|s| s.file_size > 0) {
+            pe_overlay(&sections, 0, data.len() as u64)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            path,
+            data,
+            arch_spec: arch_spec.to_string(),
+            entry_point,
+            image_base,
+            functions: Vec::new(),
+            sections,
+            is_64bit,
+            format: "PE (Fallback)".to_string(),
+            has_tls: false,
+            line_table: LineTable::default(),
+            patches: Vec::new(),
+            overlay,
+        })
+    }
+
+    /// Symbols, exports and the entry point of a PE parsed by `parse_pe_fallback`
+    fn discover_pe_fallback(&self, discovery: &mut Discovery) {
+        use object::{Object, ObjectSymbol, SymbolKind};
+
+        let Ok(file) = object::File::parse(&*self.data) else {
+            return;
+        };
+
+        let function = |name: String, address: u64, size: u64, is_export: bool| FunctionInfo {
+            name,
+            address,
+            size,
+            is_export,
+            is_import: false,
+            ordinal: None,
+            forwarded_to: None,
+            is_tls_callback: false,
+        };
+
+        let symbols: Vec<FunctionInfo> = file.symbols()
+            .filter(|sym| sym.kind() == SymbolKind::Text && sym.is_definition() && sym.address() != 0)
+            .filter_map(|sym| Some(function(sym.name().ok()?.to_string(), sym.address(), sym.size(), false)))
+            .collect();
+        let mut seen: HashSet<u64> = symbols.iter().map(|f| f.address).collect();
+        discovery.push(FunctionBatch::Found(symbols));
+
+        let mut exports = Vec::new();
+        for export in file.exports().unwrap_or_default() {
+            if seen.insert(export.address()) {
+                let name = String::from_utf8_lossy(export.name()).to_string();
+                exports.push(function(name, export.address(), 0, true));
+            }
+        }
+        discovery.push(FunctionBatch::Found(exports));
+
+        if !seen.contains(&self.entry_point) {
+            discovery.push(FunctionBatch::Found(vec![function("_start".to_string(), self.entry_point, 0, false)]));
+        }
+    }
+
     /// Parse ELF (Linux executable)
     fn parse_elf(data: Vec<u8>, path: String) -> Result<Self> {
         let elf = goblin::elf::Elf::parse(&data)?;
@@ -256,67 +905,109 @@ impl LoadedBinary {
                 virtual_address: section.sh_addr,
                 virtual_size: section.sh_size,
                 file_offset: section.sh_offset,
-                file_size: section.sh_size,
+                // SHT_NOBITS sections (.bss) occupy no file bytes
+                file_size: if section.sh_type == goblin::elf::section_header::SHT_NOBITS {
+                    0
+                } else {
+                    section.sh_size
+                },
                 is_executable: (flags & goblin::elf::section_header::SHF_EXECINSTR as u64) != 0,
                 is_readable: (flags & goblin::elf::section_header::SHF_ALLOC as u64) != 0,
                 is_writable: (flags & goblin::elf::section_header::SHF_WRITE as u64) != 0,
+                entropy: 0.0,
             });
         }
+        // Stripped or obfuscated files may carry no usable section headers
+        // (only the null section); map the loadable segments instead
+        if !sections.iter().any(|s| s.virtual_address != 0) {
+            sections = elf_segments(&elf.program_headers);
+        }
+
+        Ok(Self {
+            path,
+            data,
+            arch_spec: arch_spec.to_string(),
+            entry_point,
+            image_base,
+            functions: Vec::new(),
+            sections,
+            is_64bit,
+            format: "ELF".to_string(),
+            has_tls: false,
+            line_table: LineTable::default(),
+            patches: Vec::new(),
+            overlay: None,
+        })
+    }
+
+    /// Symbol tables, then DWARF, then the entry point of an ELF
+    fn discover_elf(&self, discovery: &mut Discovery) {
+        let Ok(elf) = goblin::elf::Elf::parse(&self.data) else {
+            return;
+        };
 
         // Collect functions from symbols
-        let mut functions = Vec::new();
+        let mut symbols = Vec::new();
         for sym in &elf.syms {
             if sym.st_type() == goblin::elf::sym::STT_FUNC && sym.st_value != 0 {
                 let name = elf.strtab.get_at(sym.st_name).unwrap_or("").to_string();
-                functions.push(FunctionInfo {
+                symbols.push(FunctionInfo {
                     name,
                     address: sym.st_value,
                     size: sym.st_size,
                     is_export: sym.st_bind() == goblin::elf::sym::STB_GLOBAL,
                     is_import: sym.st_shndx == goblin::elf::section_header::SHN_UNDEF as usize,
+                    ordinal: None,
+                    forwarded_to: None,
+                    is_tls_callback: false,
                 });
             }
         }
+        discovery.push(FunctionBatch::Found(symbols));
 
         // Dynamic symbols
+        let mut dynamic: Vec<FunctionInfo> = Vec::new();
         for sym in &elf.dynsyms {
             if sym.st_type() == goblin::elf::sym::STT_FUNC && sym.st_value != 0 {
                 let name = elf.dynstrtab.get_at(sym.st_name).unwrap_or("").to_string();
-                if !functions.iter().any(|f| f.address == sym.st_value) {
-                    functions.push(FunctionInfo {
+                if !discovery.has(sym.st_value) && !dynamic.iter().any(|f| f.address == sym.st_value) {
+                    dynamic.push(FunctionInfo {
                         name,
                         address: sym.st_value,
                         size: sym.st_size,
                         is_export: sym.st_bind() == goblin::elf::sym::STB_GLOBAL,
                         is_import: sym.st_shndx == goblin::elf::section_header::SHN_UNDEF as usize,
+                        ordinal: None,
+                        forwarded_to: None,
+                        is_tls_callback: false,
                     });
                 }
             }
         }
+        discovery.push(FunctionBatch::Found(dynamic));
+
+        // Debug info: exact sizes, unexported functions, line numbers
+        #[cfg(feature = "dwarf")]
+        if let Some(info) = dwarf::parse_elf(&elf, &self.data) {
+            discovery.push(FunctionBatch::Debug {
+                functions: info.functions.into_iter().map(FunctionInfo::from).collect(),
+                lines: Some(info.lines),
+            });
+        }
 
         // Add entry point
-        let has_entry = functions.iter().any(|f| f.address == entry_point);
-        if !has_entry && entry_point != 0 {
-            functions.push(FunctionInfo {
+        if !discovery.has(self.entry_point) && self.entry_point != 0 {
+            discovery.push(FunctionBatch::Found(vec![FunctionInfo {
                 name: "_start".to_string(),
-                address: entry_point,
+                address: self.entry_point,
                 size: 0,
                 is_export: false,
                 is_import: false,
-            });
+                ordinal: None,
+                forwarded_to: None,
+                is_tls_callback: false,
+            }]));
         }
-
-        Ok(Self {
-            path,
-            data,
-            arch_spec: arch_spec.to_string(),
-            entry_point,
-            image_base,
-            functions,
-            sections,
-            is_64bit,
-            format: "ELF".to_string(),
-        })
     }
 
     /// Parse Mach-O (macOS executable)
@@ -346,29 +1037,7 @@ impl LoadedBinary {
                         is_executable: (segment.initprot & 0x4) != 0,
                         is_readable: (segment.initprot & 0x1) != 0,
                         is_writable: (segment.initprot & 0x2) != 0,
-                    });
-                }
-
-                let mut functions = Vec::new();
-                if let Ok(exports) = macho.exports() {
-                    for export in exports {
-                        functions.push(FunctionInfo {
-                            name: export.name.to_string(),
-                            address: export.offset,
-                            size: 0,
-                            is_export: true,
-                            is_import: false,
-                        });
-                    }
-                }
-
-                if entry_point != 0 {
-                    functions.push(FunctionInfo {
-                        name: "_main".to_string(),
-                        address: entry_point,
-                        size: 0,
-                        is_export: false,
-                        is_import: false,
+                        entropy: 0.0,
                     });
                 }
 
@@ -378,33 +1047,351 @@ impl LoadedBinary {
                     arch_spec: arch_spec.to_string(),
                     entry_point,
                     image_base: 0,
-                    functions,
+                    functions: Vec::new(),
                     sections,
                     is_64bit,
                     format: "Mach-O".to_string(),
+                    has_tls: false,
+                    line_table: LineTable::default(),
+                    patches: Vec::new(),
+                    overlay: None,
                 })
             }
             goblin::mach::Mach::Fat(_) => Err(anyhow!("Fat Mach-O binaries not yet supported")),
         }
     }
 
+    /// Exports and the entry point of a thin Mach-O
+    fn discover_macho(&self, discovery: &mut Discovery) {
+        let Ok(goblin::mach::Mach::Binary(macho)) = goblin::mach::Mach::parse(&self.data) else {
+            return;
+        };
+
+        let exports = macho.exports().unwrap_or_default().into_iter()
+            .map(|export| FunctionInfo {
+                name: export.name.to_string(),
+                address: export.offset,
+                size: 0,
+                is_export: true,
+                is_import: false,
+                ordinal: None,
+                forwarded_to: None,
+                is_tls_callback: false,
+            })
+            .collect();
+        discovery.push(FunctionBatch::Found(exports));
+
+        if self.entry_point != 0 {
+            discovery.push(FunctionBatch::Found(vec![FunctionInfo {
+                name: "_main".to_string(),
+                address: self.entry_point,
+                size: 0,
+                is_export: false,
+                is_import: false,
+                ordinal: None,
+                forwarded_to: None,
+                is_tls_callback: false,
+            }]));
+        }
+    }
+
     /// Get bytes at a given address
+    ///
+    /// The read stops at the end of the containing section. Bytes in the
+    /// zero-filled tail past the section's raw data (e.g. `.bss`) read as 0.
     pub fn get_bytes(&self, address: u64, size: usize) -> Option<Vec<u8>> {
-        for section in &self.sections {
-            if address >= section.virtual_address 
-                && address < section.virtual_address + section.virtual_size 
-            {
-                let offset_in_section = address - section.virtual_address;
-                let file_offset = section.file_offset + offset_in_section;
-                let end = (file_offset as usize + size).min(self.data.len());
-                let start = file_offset as usize;
-                
-                if start < self.data.len() {
-                    return Some(self.data[start..end].to_vec());
+        let (len, file_part) = match self.section_at_va(address) {
+            Some(section) => {
+                let delta = address - section.virtual_address;
+                let len = (size as u64).min(section.mapped_size() - delta);
+                let file_part = self.va_to_file_extent(address)
+                    .map(|(offset, available)| (offset, available.min(len)));
+                (len, file_part)
+            }
+            None => {
+                let (offset, available) = self.va_to_file_extent(address)?;
+                let len = (size as u64).min(available);
+                (len, Some((offset, len)))
+            }
+        };
+
+        let mut bytes = vec![0u8; len as usize];
+        if let Some((offset, count)) = file_part {
+            let start = (offset as usize).min(self.data.len());
+            let end = start.saturating_add(count as usize).min(self.data.len());
+            bytes[..end - start].copy_from_slice(&self.data[start..end]);
+        }
+        for patch in &self.patches {
+            patch.apply(address, &mut bytes);
+        }
+        Some(bytes)
+    }
+
+    /// Overwrite `bytes.len()` bytes at `address` in the patch overlay.
+    ///
+    /// The whole range must be mapped by a single section (or the headers).
+    pub fn patch(&mut self, address: u64, bytes: &[u8]) -> Result<()> {
+        if bytes.is_empty() {
+            return Err(anyhow!("Nothing to patch"));
+        }
+        let original = self.get_bytes(address, bytes.len())
+            .filter(|b| b.len() == bytes.len())
+            .ok_or_else(|| anyhow!("0x{:x}..0x{:x} is not mapped", address, address + bytes.len() as u64))?;
+        self.patches.push(Patch { address, original, bytes: bytes.to_vec() });
+        Ok(())
+    }
+
+    /// File contents with every file-backed patch applied
+    pub fn patched_data(&self) -> Vec<u8> {
+        self.apply_patches().0
+    }
+
+    /// Write the file with the patch overlay applied to `path`.
+    ///
+    /// Only the file bytes behind patched addresses change; headers and all
+    /// other bytes are copied as loaded. Patched bytes without file backing
+    /// (e.g. the zero-filled tail of `.bss`) cannot be saved and are reported
+    /// in the returned warnings.
+    pub fn write_patched(&self, path: impl AsRef<Path>) -> Result<Vec<String>> {
+        let (data, warnings) = self.apply_patches();
+        fs::write(path, data)?;
+        Ok(warnings)
+    }
+
+    /// Patched file contents plus a warning for each patch that is not
+    /// fully file-backed
+    fn apply_patches(&self) -> (Vec<u8>, Vec<String>) {
+        let mut data = self.data.clone();
+        let mut warnings = Vec::new();
+        for patch in &self.patches {
+            let mut dropped = 0;
+            for (i, &b) in patch.bytes.iter().enumerate() {
+                match self.va_to_file_offset(patch.address + i as u64)
+                    .and_then(|offset| data.get_mut(offset as usize))
+                {
+                    Some(slot) => *slot = b,
+                    None => dropped += 1,
                 }
             }
+            if dropped > 0 {
+                warnings.push(format!(
+                    "Patch at 0x{:x}: {} of {} bytes have no file backing and were not saved",
+                    patch.address, dropped, patch.bytes.len()
+                ));
+            }
+        }
+        (data, warnings)
+    }
+
+    /// Move the image to `new_base`, as if the loader had mapped it there.
+    ///
+    /// The image base, entry point, functions, sections, patches and line
+    /// table all shift by the same delta, so `get_bytes` and the VA/file
+    /// offset conversions agree with the new addresses. Returns the delta
+    /// (wrapping) that was added.
+    pub fn rebase(&mut self, new_base: u64) -> u64 {
+        let delta = new_base.wrapping_sub(self.image_base);
+        self.image_base = new_base;
+        self.entry_point = self.entry_point.wrapping_add(delta);
+        for func in &mut self.functions {
+            func.address = func.address.wrapping_add(delta);
+        }
+        for section in &mut self.sections {
+            section.virtual_address = section.virtual_address.wrapping_add(delta);
+        }
+        for patch in &mut self.patches {
+            patch.address = patch.address.wrapping_add(delta);
+        }
+        self.line_table.rebase(delta);
+        delta
+    }
+
+    /// Estimate the size of a function that has no symbol size.
+    ///
+    /// Disassembles linearly from `addr` and stops after a `ret` that no
+    /// earlier forward branch jumps past, at `int3` padding, or at the next
+    /// known function. Without a terminator the scanned range is returned.
+    pub fn estimate_function_size(&self, addr: u64) -> u64 {
+        let next_func = self.functions.iter()
+            .filter(|f| !f.is_import && f.address > addr)
+            .map(|f| f.address)
+            .min();
+        let limit = match next_func {
+            Some(next) => (next - addr).min(MAX_ESTIMATED_FUNCTION_SIZE),
+            None => MAX_ESTIMATED_FUNCTION_SIZE,
+        };
+        let Some(bytes) = self.get_bytes(addr, limit as usize) else {
+            return 0;
+        };
+
+        let insns = DisasmEngine::for_arch(&self.arch_spec, DisasmSyntax::Intel)
+            .and_then(|engine| engine.disassemble(&bytes, addr));
+        let Ok(insns) = insns else {
+            return bytes.len() as u64;
+        };
+
+        // Furthest forward branch target seen so far
+        let mut furthest = addr;
+        for insn in &insns {
+            let end = insn.address + insn.length as u64;
+            match insn.mnemonic.as_str() {
+                // Padding ends the function; a leading int3 is its own body
+                "int3" if insn.address > addr => return insn.address - addr,
+                "int3" => return end - addr,
+                "ret" | "retn" | "retf" if furthest < end => return end - addr,
+                _ => {}
+            }
+            if let Some(target) = insn.branch_target() {
+                if target > furthest && target < addr + limit {
+                    furthest = target;
+                }
+            }
+        }
+        bytes.len() as u64
+    }
+
+    /// The program's `main` (or `_main`, `wmain`, `WinMain`), if it has a symbol
+    pub fn main_function(&self) -> Option<&FunctionInfo> {
+        MAIN_NAMES.iter()
+            .find_map(|name| self.find_function(name))
+            .filter(|f| !f.is_import)
+    }
+
+    /// Address of `main` as passed to `__libc_start_main` by the entry point.
+    ///
+    /// Follows glibc's x86 `_start`: up to its first call, `main` is the last
+    /// value loaded into `rdi` (`mov rdi, imm` / `lea rdi, [rip + disp]`) or,
+    /// on i386, the last immediate pushed. Only code addresses are accepted.
+    fn crt_main(&self) -> Option<u64> {
+        if !self.arch_spec.starts_with("x86:") {
+            return None;
+        }
+        let bytes = self.get_bytes(self.entry_point, CRT_START_SCAN)?;
+        let engine = DisasmEngine::for_arch(&self.arch_spec, DisasmSyntax::Intel).ok()?;
+        let insns = engine.disassemble(&bytes, self.entry_point).ok()?;
+
+        let mut candidate = None;
+        for insn in &insns {
+            let dst = insn.operands.split(',').next().unwrap_or("").trim();
+            match insn.mnemonic.as_str() {
+                "mov" | "lea" if matches!(dst, "rdi" | "edi") => candidate = insn.data_reference(),
+                "push" => candidate = insn.data_reference(),
+                "call" => break,
+                _ => {}
+            }
+        }
+        candidate.filter(|&va| self.section_at_va(va).is_some_and(|s| s.is_executable))
+    }
+
+    /// Section whose memory image contains `va`
+    pub fn section_at_va(&self, va: u64) -> Option<&SectionInfo> {
+        self.sections.iter()
+            .find(|s| va >= s.virtual_address && va - s.virtual_address < s.mapped_size())
+    }
+
+    /// File offset of a virtual address.
+    ///
+    /// `None` for addresses outside every section or past a section's raw
+    /// data (e.g. `.bss`). Addresses in the headers before the first section
+    /// map back from the image base.
+    pub fn va_to_file_offset(&self, va: u64) -> Option<u64> {
+        self.va_to_file_extent(va).map(|(offset, _)| offset)
+    }
+
+    /// File offset of `va` and how many file-backed bytes follow it
+    fn va_to_file_extent(&self, va: u64) -> Option<(u64, u64)> {
+        if let Some(section) = self.section_at_va(va) {
+            let delta = va - section.virtual_address;
+            let file_backed = section.file_size.min(section.mapped_size());
+            return (delta < file_backed)
+                .then(|| (section.file_offset + delta, file_backed - delta));
+        }
+
+        let headers_end = self.headers_end()?;
+        let delta = va.checked_sub(self.image_base)?;
+        (delta < headers_end).then(|| (delta, headers_end - delta))
+    }
+
+    /// File offset where the first section's raw data starts
+    fn headers_end(&self) -> Option<u64> {
+        self.sections.iter()
+            .filter(|s| s.file_size > 0)
+            .map(|s| s.file_offset)
+            .min()
+    }
+
+    /// Fill in `SectionInfo::entropy` from the file bytes
+    fn compute_section_entropy(&mut self) {
+        for section in &mut self.sections {
+            let start = (section.file_offset as usize).min(self.data.len());
+            let end = start.saturating_add(section.file_size as usize).min(self.data.len());
+            section.entropy = shannon_entropy(&self.data[start..end]);
+        }
+    }
+
+    /// Sections whose entropy exceeds `threshold`
+    pub fn high_entropy_sections(&self, threshold: f64) -> Vec<&SectionInfo> {
+        self.sections.iter().filter(|s| s.entropy > threshold).collect()
+    }
+
+    /// Executable sections that look packed or encrypted
+    pub fn packed_sections(&self) -> Vec<&SectionInfo> {
+        self.high_entropy_sections(PACKED_ENTROPY_THRESHOLD)
+            .into_iter()
+            .filter(|s| s.is_executable)
+            .collect()
+    }
+
+    /// Import address table slots with the DLL of each import (PE only)
+    pub fn iat_slots(&self) -> Vec<IatSlot> {
+        if self.format != "PE" {
+            return Vec::new();
+        }
+        let Ok(pe) = goblin::pe::PE::parse(&self.data) else {
+            return Vec::new();
+        };
+        pe.imports.iter()
+            .map(|import| IatSlot {
+                address: self.image_base + import.rva as u64,
+                module: import.dll.to_string(),
+                name: import.name.to_string(),
+            })
+            .collect()
+    }
+
+    /// Virtual address a file offset is mapped at, if any.
+    ///
+    /// Offsets before the first section (file headers) are assumed to be
+    /// mapped at the image base.
+    pub fn file_offset_to_va(&self, offset: u64) -> Option<u64> {
+        if let Some(section) = self.sections.iter().find(|s| {
+            offset >= s.file_offset && offset < s.file_offset + s.file_size
+        }) {
+            return Some(section.virtual_address + (offset - section.file_offset));
         }
-        None
+
+        (offset < self.headers_end()?).then(|| self.image_base + offset)
+    }
+
+    /// Merge function symbols from a PDB file (PE only).
+    ///
+    /// Returns how many functions were added.
+    #[cfg(feature = "pdb")]
+    pub fn load_pdb<P: AsRef<Path>>(&mut self, path: P) -> Result<usize> {
+        let pe = goblin::pe::PE::parse(&self.data).map_err(|e| anyhow!("Not a PE image: {}", e))?;
+        let guid = pe_codeview(&pe).map(|(_, guid)| guid);
+        let debug = pdb_symbols::load(path.as_ref(), guid, self.image_base)?;
+        Ok(merge_debug_functions(&mut self.functions, debug.into_iter().map(FunctionInfo::from).collect()))
+    }
+
+    #[cfg(not(feature = "pdb"))]
+    pub fn load_pdb<P: AsRef<Path>>(&mut self, _path: P) -> Result<usize> {
+        Err(anyhow!("PDB support not enabled (build with --features pdb)"))
+    }
+
+    /// Source file and line for `addr`, if debug info maps it
+    pub fn address_to_source(&self, addr: u64) -> Option<(String, u32)> {
+        self.line_table.lookup(addr).map(|(file, line)| (file.to_string(), line))
     }
 
     /// Get executable sections only
@@ -435,9 +1422,27 @@ impl LoadedBinary {
         })
     }
 
+    /// Function containing `address`, or else the closest one starting
+    /// before it in the same section, with the offset of `address` into it.
+    ///
+    /// Labels any address as `name+offset`, including ones past the end of
+    /// a sized function or after a zero-size symbol.
+    pub fn nearest_function(&self, address: u64) -> Option<(&FunctionInfo, u64)> {
+        if let Some(func) = self.function_at(address) {
+            return Some((func, address - func.address));
+        }
+        let section_of = |va| self.section_at_va(va).map(|s| s.virtual_address);
+        let section = section_of(address);
+        self.functions.iter()
+            .filter(|f| f.address <= address)
+            .filter(|f| section.is_none() || section_of(f.address) == section)
+            .max_by_key(|f| f.address)
+            .map(|f| (f, address - f.address))
+    }
+
     /// Get summary string
     pub fn summary(&self) -> String {
-        format!(
+        let mut summary = format!(
             "{} {} binary\n\
              Entry: 0x{:x}\n\
              Image Base: 0x{:x}\n\
@@ -449,7 +1454,24 @@ impl LoadedBinary {
             self.image_base,
             self.sections.len(),
             self.functions.len()
-        )
+        );
+        if self.has_tls {
+            let callbacks = self.functions.iter().filter(|f| f.is_tls_callback).count();
+            summary.push_str(&format!("\nTLS: directory present, {} callbacks", callbacks));
+        }
+        if let Some(overlay) = &self.overlay {
+            summary.push_str(&format!(
+                "\nOverlay: {} bytes at file offset 0x{:x}",
+                overlay.end - overlay.start, overlay.start
+            ));
+        }
+        for section in self.packed_sections() {
+            summary.push_str(&format!(
+                "\nWarning: {} has entropy {:.2} (likely packed)",
+                section.name, section.entropy
+            ));
+        }
+        summary
     }
 }
 
@@ -457,6 +1479,286 @@ impl LoadedBinary {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_shannon_entropy() {
+        assert_eq!(shannon_entropy(&[]), 0.0);
+        assert_eq!(shannon_entropy(&[0x90; 64]), 0.0);
+        let all: Vec<u8> = (0..=255).collect();
+        assert!((shannon_entropy(&all) - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_elf_segments() {
+        use goblin::elf::program_header::{PF_R, PF_W, PF_X, PT_DYNAMIC, PT_LOAD};
+        let ph = |p_type, p_flags, p_vaddr, p_memsz| goblin::elf::ProgramHeader {
+            p_type, p_flags, p_vaddr, p_memsz,
+            p_offset: p_vaddr & 0xfff,
+            p_filesz: p_memsz / 2,
+            ..Default::default()
+        };
+        let segments = elf_segments(&[
+            ph(PT_LOAD, PF_R | PF_X, 0x400000, 0x2000),
+            ph(PT_DYNAMIC, PF_R | PF_W, 0x602000, 0x100),
+            ph(PT_LOAD, PF_R | PF_W, 0x601000, 0x1000),
+        ]);
+        let names: Vec<_> = segments.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["seg0", "seg1"]);
+        assert!(segments[0].is_executable && !segments[0].is_writable);
+        assert!(segments[1].is_writable && !segments[1].is_executable);
+        assert_eq!((segments[1].virtual_address, segments[1].file_size), (0x601000, 0x800));
+    }
+
+    #[test]
+    fn test_address_conversion() {
+        let section = |name: &str, va: u64, vsize: u64, offset: u64, size: u64| SectionInfo {
+            name: name.into(),
+            virtual_address: va,
+            virtual_size: vsize,
+            file_offset: offset,
+            file_size: size,
+            is_executable: false,
+            is_readable: true,
+            is_writable: false,
+            entropy: 0.0,
+        };
+        let binary = LoadedBinary {
+            path: String::new(),
+            data: (0..0x600u32).map(|i| i as u8).collect(),
+            arch_spec: String::new(),
+            entry_point: 0,
+            image_base: 0x400000,
+            functions: Vec::new(),
+            sections: vec![
+                // Raw data padded past the virtual size
+                section(".text", 0x401000, 0x180, 0x400, 0x200),
+                // Mostly uninitialized
+                section(".data", 0x402000, 0x1000, 0x200, 0x200),
+            ],
+            is_64bit: true,
+            format: "PE".into(),
+            has_tls: false,
+            line_table: LineTable::default(),
+            patches: Vec::new(),
+            overlay: None,
+        };
+
+        assert_eq!(binary.va_to_file_offset(0x401010), Some(0x410));
+        assert_eq!(binary.va_to_file_offset(0x401180), None);
+        assert_eq!(binary.va_to_file_offset(0x4021ff), Some(0x3ff));
+        assert_eq!(binary.va_to_file_offset(0x402200), None);
+        assert_eq!(binary.va_to_file_offset(0x400010), Some(0x10));
+        assert_eq!(binary.file_offset_to_va(0x410), Some(0x401010));
+        assert_eq!(binary.file_offset_to_va(0x10), Some(0x400010));
+        assert_eq!(binary.file_offset_to_va(0x600), None);
+
+        // Reads past the raw data zero-fill up to the end of the section
+        let bytes = binary.get_bytes(0x4021fe, 4).unwrap();
+        assert_eq!(bytes, vec![0xfe, 0xff, 0, 0]);
+        assert_eq!(binary.get_bytes(0x402300, 4), Some(vec![0; 4]));
+        assert_eq!(binary.get_bytes(0x402ff0, 0x100).map(|b| b.len()), Some(0x10));
+        assert_eq!(binary.get_bytes(0x40117e, 8).map(|b| b.len()), Some(2));
+        assert_eq!(binary.get_bytes(0x403000, 4), None);
+    }
+
+    #[test]
+    fn test_rebase() {
+        let mut binary = LoadedBinary::from_raw((0..0x20).collect(), 0x1000, "x86:LE:64:default").unwrap();
+        binary.functions.push(FunctionInfo {
+            name: "start".into(),
+            address: 0x1010,
+            size: 4,
+            is_export: false,
+            is_import: false,
+            ordinal: None,
+            forwarded_to: None,
+            is_tls_callback: false,
+        });
+        binary.patch(0x1004, &[0xcc]).unwrap();
+
+        assert_eq!(binary.rebase(0x7ff0_0000), 0x7fef_f000);
+        assert_eq!(binary.image_base, 0x7ff0_0000);
+        assert_eq!(binary.entry_point, 0x7ff0_0000);
+        assert_eq!(binary.find_function("start").map(|f| f.address), Some(0x7ff0_0010));
+        assert_eq!(binary.va_to_file_offset(0x7ff0_0010), Some(0x10));
+        assert_eq!(binary.file_offset_to_va(0x10), Some(0x7ff0_0010));
+        assert_eq!(binary.get_bytes(0x7ff0_0003, 2), Some(vec![0x03, 0xcc]));
+        assert_eq!(binary.get_bytes(0x1000, 1), None);
+
+        // Moving back restores the original layout
+        binary.rebase(0x1000);
+        assert_eq!(binary.find_function("start").map(|f| f.address), Some(0x1010));
+        assert_eq!(binary.get_bytes(0x1004, 1), Some(vec![0xcc]));
+    }
+
+    #[test]
+    fn test_nearest_function() {
+        let func = |name: &str, address: u64, size: u64| FunctionInfo {
+            name: name.into(),
+            address,
+            size,
+            is_export: false,
+            is_import: false,
+            ordinal: None,
+            forwarded_to: None,
+            is_tls_callback: false,
+        };
+        let mut binary = LoadedBinary::from_raw(vec![0x90; 0x100], 0x1000, "x86:LE:64:default").unwrap();
+        binary.functions = vec![func("main", 0x1010, 0x20), func("helper", 0x1040, 0), func("late", 0x2000, 0)];
+
+        let nearest = |address| binary.nearest_function(address).map(|(f, offset)| (f.name.as_str(), offset));
+        assert_eq!(nearest(0x102c), Some(("main", 0x1c)));
+        assert_eq!(nearest(0x1030), Some(("main", 0x20)));
+        assert_eq!(nearest(0x1040), Some(("helper", 0)));
+        assert_eq!(nearest(0x1080), Some(("helper", 0x40)));
+        assert_eq!(nearest(0x1008), None);
+        // Outside every section: nearest preceding symbol anywhere
+        assert_eq!(nearest(0x2010), Some(("late", 0x10)));
+    }
+
+    #[test]
+    fn test_crt_main() {
+        let mut code = vec![
+            0x31, 0xed,                               // xor ebp, ebp
+            0x49, 0x89, 0xd1,                         // mov r9, rdx
+            0x5e,                                     // pop rsi
+            0x50,                                     // push rax
+            0x48, 0xc7, 0xc7, 0x20, 0x10, 0x00, 0x00, // mov rdi, 0x1020
+            0xff, 0x15, 0x00, 0x01, 0x00, 0x00,       // call qword ptr [rip + 0x100]
+            0xf4,                                     // hlt
+        ];
+        code.resize(0x20, 0x90);
+        code.extend_from_slice(&[0x31, 0xc0, 0xc3]); // main: xor eax, eax ; ret
+        let binary = LoadedBinary::from_raw(code, 0x1000, "x86:LE:64:default").unwrap();
+        assert_eq!(binary.crt_main(), Some(0x1020));
+        assert!(binary.main_function().is_none());
+
+        // i386: main is the last push before the call
+        let mut code = vec![
+            0x54,                         // push esp
+            0x68, 0x10, 0x10, 0x00, 0x00, // push 0x1010
+            0xe8, 0x00, 0x00, 0x00, 0x00, // call 0x100b
+            0xf4,                         // hlt
+        ];
+        code.resize(0x14, 0x90);
+        let binary = LoadedBinary::from_raw(code, 0x1000, "x86:LE:32:default").unwrap();
+        assert_eq!(binary.crt_main(), Some(0x1010));
+    }
+
+    #[test]
+    fn test_estimate_function_size() {
+        let code = vec![
+            0x55,       // push rbp
+            0x74, 0x01, // je 0x1004
+            0xc3,       // ret (skipped: the je lands after it)
+            0x5d,       // pop rbp
+            0xc3,       // ret
+            0xcc, 0xcc, // int3 padding
+            0x90,       // nop
+        ];
+        let binary = LoadedBinary::from_raw(code, 0x1000, "x86:LE:64:default").unwrap();
+        assert_eq!(binary.estimate_function_size(0x1000), 6);
+        assert_eq!(binary.estimate_function_size(0x1006), 1);
+    }
+
+    #[test]
+    fn test_patch_overlay() {
+        let mut binary = LoadedBinary::from_raw(vec![0x90; 8], 0x1000, "x86:LE:64:default").unwrap();
+        binary.patch(0x1002, &[0xcc, 0xcc]).unwrap();
+        binary.patch(0x1003, &[0xc3]).unwrap();
+
+        assert_eq!(binary.get_bytes(0x1001, 4), Some(vec![0x90, 0xcc, 0xc3, 0x90]));
+        assert_eq!(binary.patches[1].original, vec![0xcc]);
+        assert_eq!(binary.data, vec![0x90; 8]);
+        assert_eq!(&binary.patched_data()[1..5], &[0x90, 0xcc, 0xc3, 0x90]);
+        assert!(binary.patch(0x1006, &[0; 4]).is_err());
+
+        // Patches reaching into a virtual-only tail are saved only in part
+        binary.sections[0].virtual_size = 0x10;
+        binary.patch(0x1007, &[0xaa, 0xbb]).unwrap();
+        let (data, warnings) = binary.apply_patches();
+        assert_eq!(data.len(), 8);
+        assert_eq!(data[7], 0xaa);
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_pe_exports() {
+        // PE32+ with one section at RVA 0x1000 (file 0x200) holding the export directory
+        let mut data = vec![0u8; 0x400];
+        let put = |data: &mut Vec<u8>, offset: usize, bytes: &[u8]| {
+            data[offset..offset + bytes.len()].copy_from_slice(bytes);
+        };
+        put(&mut data, 0, b"MZ");
+        put(&mut data, 0x3c, &0x40u32.to_le_bytes());
+        put(&mut data, 0x40, b"PE\0\0");
+        put(&mut data, 0x44, &0x8664u16.to_le_bytes());           // machine
+        put(&mut data, 0x46, &1u16.to_le_bytes());                // sections
+        put(&mut data, 0x54, &0xf0u16.to_le_bytes());             // optional header size
+        put(&mut data, 0x58, &0x20bu16.to_le_bytes());            // PE32+ magic
+        put(&mut data, 0x70, &0x1_8000_0000u64.to_le_bytes());    // image base
+        put(&mut data, 0x78, &0x1000u32.to_le_bytes());           // section alignment
+        put(&mut data, 0x7c, &0x200u32.to_le_bytes());            // file alignment
+        put(&mut data, 0x90, &0x2000u32.to_le_bytes());           // image size
+        put(&mut data, 0x94, &0x200u32.to_le_bytes());            // header size
+        put(&mut data, 0xc4, &16u32.to_le_bytes());               // data directories
+        put(&mut data, 0xc8, &0x1000u32.to_le_bytes());           // export directory rva
+        put(&mut data, 0xcc, &0x200u32.to_le_bytes());            // export directory size
+        put(&mut data, 0x148, b".edata");
+        for (offset, value) in [(0x150, 0x200u32), (0x154, 0x1000), (0x158, 0x200), (0x15c, 0x200), (0x16c, 0x4000_0040)] {
+            put(&mut data, offset, &value.to_le_bytes());
+        }
+
+        let rva = |rva: usize| rva - 0x1000 + 0x200;
+        // Ordinal base 5, four address slots, two names
+        for (i, value) in [0x1100u32, 5, 4, 2, 0x1028, 0x1038, 0x1040].into_iter().enumerate() {
+            put(&mut data, rva(0x100c) + i * 4, &value.to_le_bytes());
+        }
+        // Slots: alpha, ordinal-only, forwarder named fwd, ordinal-only forwarder
+        for (i, value) in [0x2000u32, 0x2010, 0x1120, 0x1130].into_iter().enumerate() {
+            put(&mut data, rva(0x1028) + i * 4, &value.to_le_bytes());
+        }
+        put(&mut data, rva(0x1038), &0x1110u32.to_le_bytes());
+        put(&mut data, rva(0x103c), &0x1118u32.to_le_bytes());
+        put(&mut data, rva(0x1040), &0u16.to_le_bytes());
+        put(&mut data, rva(0x1042), &2u16.to_le_bytes());
+        put(&mut data, rva(0x1100), b"test.dll\0");
+        put(&mut data, rva(0x1110), b"alpha\0");
+        put(&mut data, rva(0x1118), b"fwd\0");
+        put(&mut data, rva(0x1120), b"OTHER.func\0");
+        put(&mut data, rva(0x1130), b"OTHER.#9\0");
+
+        let pe = goblin::pe::PE::parse(&data).unwrap();
+        let exports: Vec<_> = pe_exports(&pe, &data, 0x1_8000_0000).into_iter()
+            .map(|f| (f.name, f.address, f.ordinal, f.forwarded_to))
+            .collect();
+        assert_eq!(exports, vec![
+            ("alpha".to_string(), 0x1_8000_2000, Some(5), None),
+            ("fwd".to_string(), 0x1_8000_1120, Some(7), Some("OTHER.func".to_string())),
+            ("ordinal_6".to_string(), 0x1_8000_2010, Some(6), None),
+            ("ordinal_8".to_string(), 0x1_8000_1130, Some(8), Some("OTHER.#9".to_string())),
+        ]);
+    }
+
+    #[test]
+    fn test_pe_overlay() {
+        let section = |file_offset, file_size| SectionInfo {
+            name: ".text".into(),
+            virtual_address: 0x1000,
+            virtual_size: file_size,
+            file_offset,
+            file_size,
+            is_executable: true,
+            is_readable: true,
+            is_writable: false,
+            entropy: 0.0,
+        };
+        let sections = [section(0x400, 0x200), section(0x600, 0x400), section(0, 0)];
+
+        assert_eq!(pe_overlay(&sections, 0x400, 0x1000), Some(0xa00..0x1000));
+        assert_eq!(pe_overlay(&sections, 0x400, 0xa00), None);
+        assert_eq!(pe_overlay(&[], 0x400, 0x500), Some(0x400..0x500));
+    }
+
     #[test]
     fn test_parse_self() {
         // Parse the test executable itself
@@ -465,6 +1767,7 @@ mod tests {
         
         if let Ok(binary) = result {
             println!("{}", binary.summary());
+            assert!(binary.sections.iter().all(|s| (0.0..=8.0).contains(&s.entropy)));
             println!("\nFirst 10 functions:");
             for func in binary.functions_sorted().iter().take(10) {
                 println!("  0x{:08x}: {} (size: {})", func.address, func.name, func.size);
@@ -475,4 +1778,47 @@ mod tests {
             println!("Could not parse self: {:?}", result);
         }
     }
+
+    #[test]
+    fn test_progressive_matches_from_bytes() {
+        let exe_path = std::env::current_exe().unwrap();
+        let data = fs::read(&exe_path).unwrap();
+        let Ok(whole) = LoadedBinary::from_bytes(data.clone(), String::new()) else {
+            return;
+        };
+
+        let mut loaded = None;
+        let mut batches = Vec::new();
+        LoadedBinary::from_bytes_progressive(data, String::new(), |b| loaded = Some(b), |b| batches.push(b)).unwrap();
+        let mut binary = loaded.unwrap();
+        assert!(binary.functions.is_empty());
+        assert_eq!(binary.sections.len(), whole.sections.len());
+        for batch in batches {
+            batch.apply(&mut binary);
+        }
+
+        let key = |f: &FunctionInfo| (f.address, f.name.clone(), f.size, f.is_export, f.is_import);
+        let rebuilt: Vec<_> = binary.functions.iter().map(key).collect();
+        let expected: Vec<_> = whole.functions.iter().map(key).collect();
+        assert_eq!(rebuilt, expected);
+    }
+
+    #[test]
+    fn test_debug_batch_merge() {
+        let mut functions = vec![
+            FunctionInfo::from(DebugFunction { name: String::new(), address: 0x1000, size: 0 }),
+            FunctionInfo::from(DebugFunction { name: "kept".into(), address: 0x2000, size: 8 }),
+        ];
+        let mut lines = LineTable::default();
+        FunctionBatch::Debug {
+            functions: vec![
+                FunctionInfo::from(DebugFunction { name: "named".into(), address: 0x1000, size: 0x40 }),
+                FunctionInfo::from(DebugFunction { name: "renamed".into(), address: 0x2000, size: 0 }),
+                FunctionInfo::from(DebugFunction { name: "new".into(), address: 0x3000, size: 4 }),
+            ],
+            lines: None,
+        }.apply_to(&mut functions, &mut lines);
+        let got: Vec<_> = functions.iter().map(|f| (f.address, f.name.as_str(), f.size)).collect();
+        assert_eq!(got, [(0x1000, "named", 0x40), (0x2000, "kept", 8), (0x3000, "new", 4)]);
+    }
 }
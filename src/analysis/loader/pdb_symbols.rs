@@ -0,0 +1,76 @@
+//! PDB symbols (`pdb` feature)
+//!
+//! Reads procedures and public function symbols from an MSVC program
+//! database and maps them to virtual addresses.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use pdb::FallibleIterator;
+
+use super::DebugFunction;
+
+/// Find a PDB next to `binary`: `<stem>.pdb`, then the CodeView file name
+pub fn find_sibling(binary: &Path, codeview_name: Option<&str>) -> Option<PathBuf> {
+    let dir = binary.parent()?;
+    let by_name = codeview_name
+        .and_then(|name| name.rsplit(['/', '\\']).next())
+        .map(|name| dir.join(name));
+
+    std::iter::once(binary.with_extension("pdb"))
+        .chain(by_name)
+        .find(|p| p.is_file())
+}
+
+/// Load function symbols, rejecting a PDB whose GUID does not match `guid`
+pub fn load(path: &Path, guid: Option<[u8; 16]>, image_base: u64) -> Result<Vec<DebugFunction>> {
+    let mut pdb = pdb::PDB::open(File::open(path)?)?;
+
+    if let Some(expected) = guid {
+        let info = pdb.pdb_information()?;
+        if info.guid.to_bytes_le() != expected {
+            return Err(anyhow!("PDB GUID {} does not match the binary", info.guid));
+        }
+    }
+
+    let address_map = pdb.address_map()?;
+    let mut functions = Vec::new();
+
+    // Procedures (with sizes) from each module stream
+    let dbi = pdb.debug_information()?;
+    let mut modules = dbi.modules()?;
+    while let Some(module) = modules.next()? {
+        let Some(info) = pdb.module_info(&module)? else { continue };
+        let mut symbols = info.symbols()?;
+        while let Some(symbol) = symbols.next()? {
+            if let Ok(pdb::SymbolData::Procedure(proc)) = symbol.parse() {
+                let Some(rva) = proc.offset.to_rva(&address_map) else { continue };
+                functions.push(DebugFunction {
+                    name: proc.name.to_string().into_owned(),
+                    address: image_base + rva.0 as u64,
+                    size: proc.len as u64,
+                });
+            }
+        }
+    }
+
+    // Public function symbols cover anything without a procedure record
+    let globals = pdb.global_symbols()?;
+    let mut symbols = globals.iter();
+    while let Some(symbol) = symbols.next()? {
+        if let Ok(pdb::SymbolData::Public(public)) = symbol.parse() {
+            if !public.function {
+                continue;
+            }
+            let Some(rva) = public.offset.to_rva(&address_map) else { continue };
+            functions.push(DebugFunction {
+                name: public.name.to_string().into_owned(),
+                address: image_base + rva.0 as u64,
+                size: 0,
+            });
+        }
+    }
+
+    Ok(functions)
+}
@@ -0,0 +1,73 @@
+//! Source line mapping
+//!
+//! Address-to-line table filled from debug info (DWARF, with the `dwarf`
+//! feature). Empty when the binary carries no line information.
+
+/// One row of a line table
+#[derive(Debug, Clone, Copy)]
+pub struct LineRow {
+    /// First address covered by this row
+    pub address: u64,
+    /// Index into `LineTable::files`; `None` marks the end of a sequence
+    pub file: Option<u32>,
+    /// 1-based line number (0 if unknown)
+    pub line: u32,
+}
+
+/// Sorted address-to-source mapping
+#[derive(Debug, Clone, Default)]
+pub struct LineTable {
+    /// Source file paths referenced by rows
+    pub files: Vec<String>,
+    rows: Vec<LineRow>,
+}
+
+impl LineTable {
+    /// Build a table from unsorted rows
+    pub fn new(files: Vec<String>, mut rows: Vec<LineRow>) -> Self {
+        // End-of-sequence markers sort before a sequence starting at the same address
+        rows.sort_by_key(|r| (r.address, r.file.is_some()));
+        Self { files, rows }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Move every row by `delta` (wrapping), after the image was rebased
+    pub fn rebase(&mut self, delta: u64) {
+        for row in &mut self.rows {
+            row.address = row.address.wrapping_add(delta);
+        }
+    }
+
+    /// Source file and line for `address`
+    pub fn lookup(&self, address: u64) -> Option<(&str, u32)> {
+        let idx = self.rows.partition_point(|r| r.address <= address);
+        let row = self.rows.get(idx.checked_sub(1)?)?;
+        let file = self.files.get(row.file? as usize)?;
+        (row.line > 0).then_some((file.as_str(), row.line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_lookup() {
+        let table = LineTable::new(
+            vec!["main.c".into()],
+            vec![
+                LineRow { address: 0x1010, file: Some(0), line: 5 },
+                LineRow { address: 0x1000, file: Some(0), line: 3 },
+                LineRow { address: 0x1020, file: None, line: 0 },
+            ],
+        );
+        assert_eq!(table.lookup(0x0fff), None);
+        assert_eq!(table.lookup(0x1000), Some(("main.c", 3)));
+        assert_eq!(table.lookup(0x100f), Some(("main.c", 3)));
+        assert_eq!(table.lookup(0x1010), Some(("main.c", 5)));
+        assert_eq!(table.lookup(0x1020), None);
+    }
+}
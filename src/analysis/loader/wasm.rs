@@ -0,0 +1,138 @@
+//! WebAssembly modules (`wasm` feature)
+//!
+//! WASM has no virtual address space for code, so addresses are file
+//! offsets: each section maps at its own offset and a function's address is
+//! the offset of its body in the code section.
+
+use anyhow::{anyhow, Result};
+use wasmparser::{ExternalKind, Parser, Payload, TypeRef};
+
+use super::{FunctionInfo, LineTable, LoadedBinary, SectionInfo};
+
+/// Ghidra-style spec for wasm32 (no decompiler support on the server)
+const WASM_ARCH_SPEC: &str = "wasm:LE:32:default";
+
+/// Name of a standard section by id
+fn section_name(id: u8) -> &'static str {
+    match id {
+        1 => "type",
+        2 => "import",
+        3 => "function",
+        4 => "table",
+        5 => "memory",
+        6 => "global",
+        7 => "export",
+        8 => "start",
+        9 => "element",
+        10 => "code",
+        11 => "data",
+        12 => "datacount",
+        13 => "tag",
+        _ => "unknown",
+    }
+}
+
+impl LoadedBinary {
+    /// Parse a WebAssembly module
+    pub(super) fn parse_wasm(data: Vec<u8>, path: String) -> Result<Self> {
+        let mut sections = Vec::new();
+        let mut functions = Vec::new();
+        let mut imported_funcs = 0u32;
+        // Function index -> export name
+        let mut export_names = std::collections::HashMap::new();
+        let mut start_func = None;
+        let mut bodies = Vec::new();
+
+        for payload in Parser::new(0).parse_all(&data) {
+            let payload = payload.map_err(|e| anyhow!("Invalid WASM module: {}", e))?;
+
+            if let Some((id, range)) = payload.as_section() {
+                let name = match &payload {
+                    Payload::CustomSection(reader) => reader.name().to_string(),
+                    _ => section_name(id).to_string(),
+                };
+                let size = (range.end - range.start) as u64;
+                sections.push(SectionInfo {
+                    name,
+                    virtual_address: range.start as u64,
+                    virtual_size: size,
+                    file_offset: range.start as u64,
+                    file_size: size,
+                    is_executable: id == 10,
+                    is_readable: true,
+                    is_writable: id == 11,
+                    entropy: 0.0,
+                });
+            }
+
+            match payload {
+                Payload::ImportSection(reader) => {
+                    for import in reader.into_iter_with_offsets() {
+                        let (offset, import) = import?;
+                        if let TypeRef::Func(_) = import.ty {
+                            imported_funcs += 1;
+                            functions.push(FunctionInfo {
+                                name: format!("{}!{}", import.module, import.name),
+                                address: offset as u64,
+                                size: 0,
+                                is_export: false,
+                                is_import: true,
+                                ordinal: None,
+                                forwarded_to: None,
+                                is_tls_callback: false,
+                            });
+                        }
+                    }
+                }
+                Payload::ExportSection(reader) => {
+                    for export in reader {
+                        let export = export?;
+                        if export.kind == ExternalKind::Func {
+                            export_names.insert(export.index, export.name.to_string());
+                        }
+                    }
+                }
+                Payload::StartSection { func, .. } => start_func = Some(func),
+                Payload::CodeSectionEntry(body) => bodies.push(body.range()),
+                _ => {}
+            }
+        }
+
+        // Defined functions follow the imported ones in the index space
+        let mut entry_point = 0;
+        for (i, range) in bodies.into_iter().enumerate() {
+            let index = imported_funcs + i as u32;
+            let address = range.start as u64;
+            if start_func == Some(index) {
+                entry_point = address;
+            }
+            let export_name = export_names.remove(&index);
+            functions.push(FunctionInfo {
+                is_export: export_name.is_some(),
+                name: export_name.unwrap_or_else(|| format!("func_{}", index)),
+                address,
+                size: (range.end - range.start) as u64,
+                is_import: false,
+                ordinal: None,
+                forwarded_to: None,
+                is_tls_callback: false,
+            });
+        }
+
+        Ok(Self {
+            path,
+            data,
+            arch_spec: WASM_ARCH_SPEC.to_string(),
+            entry_point,
+            image_base: 0,
+            functions,
+            sections,
+            is_64bit: false,
+            format: "WASM".to_string(),
+            has_tls: false,
+            line_table: LineTable::default(),
+            patches: Vec::new(),
+            overlay: None,
+        })
+    }
+}
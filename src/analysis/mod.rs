@@ -1,9 +1,18 @@
 //! Analysis Module - Binary analysis engines
 //!
-//! Contains decompilation, disassembly, and binary loading.
+//! Contains decompilation, disassembly, binary loading, string extraction,
+//! byte-pattern search, import signatures, YARA scanning, function diffing,
+//! data-reference and stack-variable annotation.
 
 pub mod decomp;
+pub mod diff;
 pub mod disasm;
 pub mod loader;
+pub mod pattern;
+pub mod signatures;
+pub mod stackvars;
+pub mod strings;
+pub mod xrefs;
+pub mod yara;
 
 pub use loader::{LoadedBinary, FunctionInfo, SectionInfo};
@@ -0,0 +1,116 @@
+//! Byte pattern search
+//!
+//! Shared by the Hex View search bar and the CLI `/` and `/x` commands.
+//! A pattern is a byte sequence where `None` matches any byte.
+
+use super::strings::StringEncoding;
+
+/// Parse a search pattern into bytes, `None` marking a wildcard.
+///
+/// Accepts a quoted ASCII string (`"text"`) or hex bytes, either spaced
+/// (`48 8B ??`) or run together (`488b..c0`); `??`, `..` and a lone `?` are
+/// wildcards.
+pub fn parse_search_pattern(input: &str) -> Result<Vec<Option<u8>>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("empty pattern".into());
+    }
+
+    if let Some(rest) = input.strip_prefix('"') {
+        let text = rest.strip_suffix('"').ok_or("unterminated string")?;
+        if text.is_empty() {
+            return Err("empty string".into());
+        }
+        return Ok(text.bytes().map(Some).collect());
+    }
+
+    let mut pattern = Vec::new();
+    for tok in input.split_whitespace() {
+        if tok == "?" {
+            pattern.push(None);
+            continue;
+        }
+        for pair in tok.as_bytes().chunks(2) {
+            let pair = match std::str::from_utf8(pair) {
+                Ok(pair) if pair.len() == 2 => pair,
+                _ => return Err(format!("invalid hex byte '{}'", tok)),
+            };
+            pattern.push(match pair {
+                "??" | ".." => None,
+                _ => Some(
+                    u8::from_str_radix(pair, 16)
+                        .map_err(|_| format!("invalid hex byte '{}'", pair))?,
+                ),
+            });
+        }
+    }
+    Ok(pattern)
+}
+
+/// Find all offsets where `pattern` matches `data`
+pub fn find_pattern(data: &[u8], pattern: &[Option<u8>]) -> Vec<u64> {
+    if pattern.is_empty() || pattern.len() > data.len() {
+        return Vec::new();
+    }
+    data.windows(pattern.len())
+        .enumerate()
+        .filter(|(_, window)| {
+            window.iter().zip(pattern).all(|(b, p)| p.is_none() || *p == Some(*b))
+        })
+        .map(|(i, _)| i as u64)
+        .collect()
+}
+
+/// Find `text` as ASCII/UTF-8 and as UTF-16LE, returning offsets in order
+pub fn find_string(data: &[u8], text: &str) -> Vec<(u64, StringEncoding)> {
+    let ascii: Vec<Option<u8>> = text.bytes().map(Some).collect();
+    let wide: Vec<Option<u8>> = text.encode_utf16().flat_map(u16::to_le_bytes).map(Some).collect();
+
+    let mut hits: Vec<_> = find_pattern(data, &ascii).into_iter()
+        .map(|offset| (offset, StringEncoding::Ascii))
+        .chain(find_pattern(data, &wide).into_iter().map(|offset| (offset, StringEncoding::Utf16Le)))
+        .collect();
+    hits.sort_by_key(|&(offset, _)| offset);
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_pattern() {
+        assert_eq!(
+            parse_search_pattern("48 8b ??").unwrap(),
+            vec![Some(0x48), Some(0x8B), None]
+        );
+        assert_eq!(
+            parse_search_pattern("488b..c0").unwrap(),
+            vec![Some(0x48), Some(0x8B), None, Some(0xC0)]
+        );
+        assert_eq!(
+            parse_search_pattern("\"MZ\"").unwrap(),
+            vec![Some(b'M'), Some(b'Z')]
+        );
+        assert!(parse_search_pattern("\"open").is_err());
+        assert!(parse_search_pattern("4").is_err());
+        assert!(parse_search_pattern("zz").is_err());
+        assert!(parse_search_pattern("4é").is_err());
+    }
+
+    #[test]
+    fn test_find_pattern_wildcard() {
+        let data = [0x48, 0x8B, 0x05, 0x00, 0x48, 0x8B, 0xC0];
+        let pattern = parse_search_pattern("48 8B ??").unwrap();
+        assert_eq!(find_pattern(&data, &pattern), vec![0, 4]);
+    }
+
+    #[test]
+    fn test_find_string() {
+        let data = b"xOKy\0O\0K\0";
+        assert_eq!(
+            find_string(data, "OK"),
+            vec![(1, StringEncoding::Ascii), (5, StringEncoding::Utf16Le)]
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Import signatures
+//!
+//! Static table of API names worth flagging during triage, grouped by what
+//! malware typically uses them for. Matching is by exact name, ignoring a
+//! trailing `A`/`W` (ANSI/wide variants) and any `module!` prefix.
+
+/// What a flagged import is typically used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportCategory {
+    AntiDebug,
+    Injection,
+    Crypto,
+    Network,
+}
+
+impl ImportCategory {
+    pub const ALL: [ImportCategory; 4] = [Self::AntiDebug, Self::Injection, Self::Crypto, Self::Network];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::AntiDebug => "anti-debug",
+            Self::Injection => "injection",
+            Self::Crypto => "crypto",
+            Self::Network => "network",
+        }
+    }
+}
+
+const SIGNATURES: &[(ImportCategory, &[&str])] = &[
+    (ImportCategory::AntiDebug, &[
+        "IsDebuggerPresent",
+        "CheckRemoteDebuggerPresent",
+        "NtQueryInformationProcess",
+        "ZwQueryInformationProcess",
+        "NtSetInformationThread",
+        "ZwSetInformationThread",
+        "NtQuerySystemInformation",
+        "OutputDebugString",
+        "DebugActiveProcess",
+        "BlockInput",
+        "FindWindow",
+        "FindWindowEx",
+        "ptrace",
+    ]),
+    (ImportCategory::Injection, &[
+        "OpenProcess",
+        "VirtualAllocEx",
+        "VirtualProtect",
+        "VirtualProtectEx",
+        "WriteProcessMemory",
+        "ReadProcessMemory",
+        "CreateRemoteThread",
+        "CreateRemoteThreadEx",
+        "NtCreateThreadEx",
+        "RtlCreateUserThread",
+        "QueueUserAPC",
+        "NtQueueApcThread",
+        "SetThreadContext",
+        "NtMapViewOfSection",
+        "NtUnmapViewOfSection",
+        "ZwUnmapViewOfSection",
+        "SetWindowsHookEx",
+        "process_vm_writev",
+    ]),
+    (ImportCategory::Crypto, &[
+        "CryptAcquireContext",
+        "CryptCreateHash",
+        "CryptHashData",
+        "CryptDeriveKey",
+        "CryptGenKey",
+        "CryptImportKey",
+        "CryptExportKey",
+        "CryptEncrypt",
+        "CryptDecrypt",
+        "CryptProtectData",
+        "CryptUnprotectData",
+        "BCryptOpenAlgorithmProvider",
+        "BCryptGenerateSymmetricKey",
+        "BCryptHashData",
+        "BCryptEncrypt",
+        "BCryptDecrypt",
+        "EVP_CipherInit_ex",
+        "EVP_EncryptInit_ex",
+        "EVP_DecryptInit_ex",
+    ]),
+    (ImportCategory::Network, &[
+        "WSAStartup",
+        "socket",
+        "connect",
+        "bind",
+        "listen",
+        "accept",
+        "send",
+        "recv",
+        "sendto",
+        "recvfrom",
+        "gethostbyname",
+        "getaddrinfo",
+        "InternetOpen",
+        "InternetOpenUrl",
+        "InternetConnect",
+        "InternetReadFile",
+        "HttpOpenRequest",
+        "HttpSendRequest",
+        "URLDownloadToFile",
+        "WinHttpOpen",
+        "WinHttpConnect",
+        "WinHttpSendRequest",
+        "WinHttpReadData",
+    ]),
+];
+
+/// Category of an import name, if it is in the signature table
+pub fn classify(name: &str) -> Option<ImportCategory> {
+    let name = name.rsplit('!').next().unwrap_or(name);
+    let lookup = |name: &str| {
+        SIGNATURES.iter()
+            .find(|(_, names)| names.contains(&name))
+            .map(|&(category, _)| category)
+    };
+    lookup(name).or_else(|| lookup(name.strip_suffix(['A', 'W'])?))
+}
+
+/// Number of imports per category, in [`ImportCategory::ALL`] order,
+/// omitting categories with no hits
+pub fn summarize<'a>(names: impl IntoIterator<Item = &'a str>) -> Vec<(ImportCategory, usize)> {
+    let mut counts = [0usize; ImportCategory::ALL.len()];
+    for category in names.into_iter().filter_map(classify) {
+        counts[category as usize] += 1;
+    }
+    ImportCategory::ALL.into_iter()
+        .zip(counts)
+        .filter(|&(_, count)| count > 0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_imports() {
+        assert_eq!(classify("IsDebuggerPresent"), Some(ImportCategory::AntiDebug));
+        assert_eq!(classify("CreateRemoteThread"), Some(ImportCategory::Injection));
+        assert_eq!(classify("InternetOpenUrlW"), Some(ImportCategory::Network));
+        assert_eq!(classify("advapi32!CryptAcquireContextA"), Some(ImportCategory::Crypto));
+        assert_eq!(classify("CreateFileA"), None);
+
+        let names = ["VirtualProtect", "WriteProcessMemory", "connect", "malloc"];
+        assert_eq!(
+            summarize(names),
+            vec![(ImportCategory::Injection, 2), (ImportCategory::Network, 1)]
+        );
+    }
+}
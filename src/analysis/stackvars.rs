@@ -0,0 +1,188 @@
+//! Stack variable annotation
+//!
+//! Names the `[rsp + disp]` / `[rbp - disp]` memory operands of an x86
+//! function after the stack variables the decompiler assigned. Ghidra
+//! reports stack offsets relative to the stack pointer on entry (the return
+//! address sits at 0, `local_20` at -0x20), so the stack pointer and frame
+//! pointer are tracked through the prologue and any later adjustments.
+//! Instructions that change them in ways that cannot be followed leave the
+//! rest of the block unannotated rather than guessed.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::disasm::DisassembledInstruction;
+
+/// A variable the decompiler placed on the stack
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StackVariable {
+    /// Offset from the stack pointer on entry
+    pub offset: i64,
+    pub size: u32,
+    pub name: String,
+}
+
+/// Entry-relative heights of the stack and frame pointer, when known
+#[derive(Debug, Clone, Copy, Default)]
+struct Frame {
+    sp: Option<i64>,
+    fp: Option<i64>,
+}
+
+fn is_sp(reg: &str) -> bool {
+    matches!(reg, "rsp" | "esp")
+}
+
+fn is_fp(reg: &str) -> bool {
+    matches!(reg, "rbp" | "ebp")
+}
+
+fn parse_imm(text: &str) -> Option<i64> {
+    match text.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+/// Base register and displacement of a `[reg ± disp]` operand without an
+/// index register
+fn memory_operand(operands: &str) -> Option<(&str, i64)> {
+    let start = operands.find('[')?;
+    let end = start + operands[start..].find(']')?;
+    let inner = &operands[start + 1..end];
+
+    let mut terms = inner.split_whitespace();
+    let base = terms.next()?;
+    let mut disp = 0i64;
+    while let Some(sign) = terms.next() {
+        let value = parse_imm(terms.next()?)?;
+        disp = match sign {
+            "+" => disp.wrapping_add(value),
+            "-" => disp.wrapping_sub(value),
+            _ => return None,
+        };
+    }
+    Some((base, disp))
+}
+
+/// The variable covering `offset`, as `local_20` or `local_20+0x4`
+fn describe(vars: &[StackVariable], offset: i64) -> Option<String> {
+    let var = vars.iter().find(|v| offset >= v.offset && offset < v.offset + i64::from(v.size.max(1)))?;
+    Some(match offset - var.offset {
+        0 => var.name.clone(),
+        delta => format!("{}+0x{:x}", var.name, delta),
+    })
+}
+
+/// Stack variable names for the memory operands of `insns`, keyed by
+/// instruction address.
+///
+/// `insns` must be x86 in Intel syntax, in address order from the function
+/// entry; `pointer_size` is the size of a pushed register.
+pub fn annotate(insns: &[DisassembledInstruction], vars: &[StackVariable], pointer_size: i64) -> HashMap<u64, String> {
+    let mut notes = HashMap::new();
+    if vars.is_empty() {
+        return notes;
+    }
+
+    // Heights recorded at branch targets, picked up after a jmp or ret
+    let mut at_target: HashMap<u64, Frame> = HashMap::new();
+    let mut frame = Frame { sp: Some(0), fp: None };
+    let mut reachable = true;
+
+    for insn in insns {
+        if !reachable {
+            frame = at_target.get(&insn.address).copied().unwrap_or_default();
+            reachable = true;
+        }
+
+        if let Some((base, disp)) = memory_operand(&insn.operands) {
+            let height = if is_sp(base) { frame.sp } else if is_fp(base) { frame.fp } else { None };
+            if let Some(note) = height.and_then(|h| describe(vars, h + disp)) {
+                notes.insert(insn.address, note);
+            }
+        }
+
+        let mut operands = insn.operands.split(", ");
+        let dst = operands.next().unwrap_or("");
+        let src = operands.next().unwrap_or("");
+        let imm = parse_imm(src);
+        match insn.mnemonic.as_str() {
+            "push" => frame.sp = frame.sp.map(|sp| sp - pointer_size),
+            "pop" => {
+                frame.sp = frame.sp.map(|sp| sp + pointer_size);
+                if is_fp(dst) {
+                    frame.fp = None;
+                }
+            }
+            "leave" => {
+                frame.sp = frame.fp.map(|fp| fp + pointer_size);
+                frame.fp = None;
+            }
+            "sub" if is_sp(dst) => frame.sp = frame.sp.zip(imm).map(|(sp, imm)| sp - imm),
+            "add" if is_sp(dst) => frame.sp = frame.sp.zip(imm).map(|(sp, imm)| sp + imm),
+            "mov" if is_fp(dst) && is_sp(src) => frame.fp = frame.sp,
+            "mov" if is_sp(dst) && is_fp(src) => frame.sp = frame.fp,
+            "lea" if is_fp(dst) || is_sp(dst) => {
+                let height = memory_operand(src).and_then(|(base, disp)| {
+                    let h = if is_sp(base) { frame.sp } else if is_fp(base) { frame.fp } else { None };
+                    h.map(|h| h + disp)
+                });
+                if is_fp(dst) { frame.fp = height } else { frame.sp = height }
+            }
+            "cmp" | "test" | "call" => {}
+            _ if is_sp(dst) => frame.sp = None,
+            _ if is_fp(dst) => frame.fp = None,
+            _ => {}
+        }
+
+        if insn.mnemonic.starts_with('j') {
+            if let Some(target) = insn.target {
+                at_target.entry(target).or_insert(frame);
+            }
+            reachable = insn.mnemonic != "jmp";
+        } else if insn.mnemonic.starts_with("ret") {
+            reachable = false;
+        }
+    }
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::disasm::{DisasmEngine, DisasmSyntax};
+
+    fn var(offset: i64, size: u32, name: &str) -> StackVariable {
+        StackVariable { offset, size, name: name.to_string() }
+    }
+
+    #[test]
+    fn test_annotate_stack_variables() {
+        let code = [
+            0x55,                                     // push rbp
+            0x48, 0x89, 0xe5,                         // mov rbp, rsp
+            0x48, 0x83, 0xec, 0x20,                   // sub rsp, 0x20
+            0x89, 0x7d, 0xec,                         // mov dword ptr [rbp - 0x14], edi
+            0x85, 0xff,                               // test edi, edi
+            0x74, 0x06,                               // je 0x15
+            0x8b, 0x44, 0x24, 0x08,                   // mov eax, dword ptr [rsp + 8]
+            0xc9,                                     // leave
+            0xc3,                                     // ret
+            0xc7, 0x45, 0xf0, 0x01, 0x00, 0x00, 0x00, // mov dword ptr [rbp - 0x10], 1
+            0xc9,                                     // leave
+            0xc3,                                     // ret
+        ];
+        let engine = DisasmEngine::with_syntax(true, DisasmSyntax::Intel).unwrap();
+        let insns = engine.disassemble(&code, 0).unwrap();
+        let vars = [var(-0x1c, 4, "local_1c"), var(-0x20, 4, "local_20"), var(-0x18, 8, "local_18")];
+
+        let notes = annotate(&insns, &vars, 8);
+        assert_eq!(notes.get(&0x8).map(String::as_str), Some("local_1c"));
+        assert_eq!(notes.get(&0xf).map(String::as_str), Some("local_20"));
+        assert_eq!(notes.get(&0x15).map(String::as_str), Some("local_18"));
+        assert_eq!(notes.len(), 3);
+        assert_eq!(describe(&vars, -0x14), Some("local_18+0x4".to_string()));
+    }
+}
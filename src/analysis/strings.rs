@@ -0,0 +1,203 @@
+//! String extraction
+//!
+//! Scans raw bytes for printable ASCII and UTF-16LE runs.
+
+use serde::Serialize;
+
+/// Extracted string from binary
+#[derive(Clone, Serialize)]
+pub struct ExtractedString {
+    /// Offset in binary
+    pub offset: u64,
+    /// String value
+    pub value: String,
+    /// String encoding type
+    pub encoding: StringEncoding,
+}
+
+/// String encoding type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum StringEncoding {
+    Ascii,
+    Utf16Le,
+}
+
+impl StringEncoding {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ascii => "ascii",
+            Self::Utf16Le => "utf16le",
+        }
+    }
+}
+
+/// Which encodings string extraction looks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EncodingFilter {
+    Ascii,
+    Utf16,
+    #[default]
+    Both,
+}
+
+impl EncodingFilter {
+    pub const ALL: [EncodingFilter; 3] = [Self::Ascii, Self::Utf16, Self::Both];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ascii => "ASCII",
+            Self::Utf16 => "UTF-16",
+            Self::Both => "Both",
+        }
+    }
+
+    fn includes(self, encoding: StringEncoding) -> bool {
+        match self {
+            Self::Ascii => encoding == StringEncoding::Ascii,
+            Self::Utf16 => encoding == StringEncoding::Utf16Le,
+            Self::Both => true,
+        }
+    }
+}
+
+/// Extract strings of at least `min_len` characters, sorted by offset
+pub fn extract_strings(data: &[u8], min_len: usize) -> Vec<ExtractedString> {
+    extract_strings_with(data, min_len, EncodingFilter::Both)
+}
+
+/// Extract strings of at least `min_len` characters in the `encodings`
+/// asked for, sorted by offset
+pub fn extract_strings_with(data: &[u8], min_len: usize, encodings: EncodingFilter) -> Vec<ExtractedString> {
+    let mut strings = Vec::new();
+    if encodings.includes(StringEncoding::Ascii) {
+        extract_ascii(data, min_len, &mut strings);
+    }
+    if encodings.includes(StringEncoding::Utf16Le) {
+        extract_utf16le(data, min_len, &mut strings);
+    }
+    strings.sort_by_key(|s| s.offset);
+    strings
+}
+
+fn extract_ascii(data: &[u8], min_len: usize, strings: &mut Vec<ExtractedString>) {
+    let mut current_string = String::new();
+    let mut start_offset: u64 = 0;
+    
+    for (i, &byte) in data.iter().enumerate() {
+        if (0x20..=0x7E).contains(&byte) {
+            if current_string.is_empty() { start_offset = i as u64; }
+            current_string.push(byte as char);
+        } else {
+            if current_string.len() >= min_len {
+                strings.push(ExtractedString {
+                    offset: start_offset,
+                    value: current_string.clone(),
+                    encoding: StringEncoding::Ascii,
+                });
+            }
+            current_string.clear();
+        }
+    }
+    if current_string.len() >= min_len {
+        strings.push(ExtractedString {
+            offset: start_offset,
+            value: current_string,
+            encoding: StringEncoding::Ascii,
+        });
+    }
+}
+
+/// UTF-16LE strings (printable ASCII code unit followed by 0x00)
+fn extract_utf16le(data: &[u8], min_len: usize, strings: &mut Vec<ExtractedString>) {
+    let mut current_string = String::new();
+    let mut start_offset: u64 = 0;
+    let mut i = 0;
+    while i + 1 < data.len() {
+        let (lo, hi) = (data[i], data[i + 1]);
+        if hi == 0 && (0x20..=0x7E).contains(&lo) {
+            if current_string.is_empty() { start_offset = i as u64; }
+            current_string.push(lo as char);
+            i += 2;
+        } else {
+            if current_string.len() >= min_len {
+                strings.push(ExtractedString {
+                    offset: start_offset,
+                    value: current_string.clone(),
+                    encoding: StringEncoding::Utf16Le,
+                });
+            }
+            current_string.clear();
+            i += 1;
+        }
+    }
+    if current_string.len() >= min_len {
+        strings.push(ExtractedString {
+            offset: start_offset,
+            value: current_string,
+            encoding: StringEncoding::Utf16Le,
+        });
+    }
+}
+
+/// Read the string starting at `bytes[0]`: ASCII, or UTF-16LE if the ASCII
+/// run is too short. None if neither reaches `min_len` characters.
+pub fn string_at(bytes: &[u8], min_len: usize) -> Option<String> {
+    let printable = |b: u8| (0x20..=0x7E).contains(&b);
+    let ascii: String = bytes.iter()
+        .take_while(|&&b| printable(b))
+        .map(|&b| b as char)
+        .collect();
+    if ascii.len() >= min_len {
+        return Some(ascii);
+    }
+    let wide: String = bytes.chunks_exact(2)
+        .take_while(|pair| pair[1] == 0 && printable(pair[0]))
+        .map(|pair| pair[0] as char)
+        .collect();
+    (wide.len() >= min_len).then_some(wide)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_strings() {
+        let mut data = b"\x00\x01hello\x00ab\x01".to_vec();
+        data.extend_from_slice(&[b'w', 0, b'i', 0, b'd', 0, b'e', 0, 0xFF]);
+        let strings = extract_strings(&data, 4);
+        assert_eq!(strings.len(), 2);
+        assert_eq!(strings[0].offset, 2);
+        assert_eq!(strings[0].value, "hello");
+        assert!(strings[0].encoding == StringEncoding::Ascii);
+        assert_eq!(strings[1].value, "wide");
+        assert!(strings[1].encoding == StringEncoding::Utf16Le);
+
+        // A run that reaches the end of the data is still reported
+        let strings = extract_strings(b"\x00tail", 4);
+        assert_eq!(strings.len(), 1);
+        assert_eq!(strings[0].offset, 1);
+        assert_eq!(strings[0].value, "tail");
+    }
+
+    #[test]
+    fn test_extract_strings_with() {
+        let mut data = b"abc\x00\x00".to_vec();
+        data.extend_from_slice(&[b'w', 0, b'i', 0, 0xFF]);
+
+        let values = |encodings, min_len| -> Vec<String> {
+            extract_strings_with(&data, min_len, encodings).into_iter().map(|s| s.value).collect()
+        };
+        assert_eq!(values(EncodingFilter::Both, 2), vec!["abc", "wi"]);
+        assert_eq!(values(EncodingFilter::Ascii, 2), vec!["abc"]);
+        assert_eq!(values(EncodingFilter::Utf16, 2), vec!["wi"]);
+        assert!(values(EncodingFilter::Both, 4).is_empty());
+    }
+
+    #[test]
+    fn test_string_at() {
+        assert_eq!(string_at(b"hello\0world", 4).as_deref(), Some("hello"));
+        assert_eq!(string_at(&[b'w', 0, b'i', 0, b'd', 0, b'e', 0, 0, 0], 4).as_deref(), Some("wide"));
+        assert_eq!(string_at(b"ab\0", 4), None);
+    }
+}
@@ -0,0 +1,100 @@
+//! Data references
+//!
+//! Describes what an operand address points at (an import or a string) for
+//! the inline comments in the assembly and decompile views.
+
+use crate::analysis::loader::LoadedBinary;
+use crate::analysis::strings::{string_at, ExtractedString};
+
+/// Shortest string worth annotating (matches the Strings tab)
+const MIN_STRING_LEN: usize = 4;
+/// Characters of a string shown before it is cut off
+const MAX_COMMENT_CHARS: usize = 48;
+/// Bytes read when the strings index has no entry for an address
+const STRING_PROBE_LEN: usize = 256;
+
+/// Describe `va` as `-> name` (import) or `"text"` (string), if it is either.
+///
+/// `strings` is the extracted strings index (sorted by file offset); addresses
+/// it does not cover are read from the binary directly.
+pub fn describe(binary: &LoadedBinary, strings: &[ExtractedString], va: u64) -> Option<String> {
+    if let Some(func) = binary.function_at(va).filter(|f| f.is_import) {
+        return Some(format!("-> {}", func.name));
+    }
+
+    let indexed = binary.va_to_file_offset(va)
+        .and_then(|offset| strings.binary_search_by_key(&offset, |s| s.offset).ok())
+        .map(|i| strings[i].value.clone());
+    let text = match indexed {
+        Some(text) => text,
+        None => string_at(&binary.get_bytes(va, STRING_PROBE_LEN)?, MIN_STRING_LEN)?,
+    };
+
+    let shown: String = text.chars().take(MAX_COMMENT_CHARS).collect();
+    let ellipsis = if shown.len() < text.len() { "..." } else { "" };
+    Some(format!("{:?}{}", shown, ellipsis))
+}
+
+/// Append `// "text"` / `// -> name` to decompiled lines that use Ghidra's
+/// address-suffixed data symbols (`DAT_00403000`, `PTR_DAT_00403000`)
+pub fn annotate_decompiled(code: &str, binary: &LoadedBinary, strings: &[ExtractedString]) -> String {
+    let mut out = String::with_capacity(code.len());
+    for (i, line) in code.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(line);
+
+        let mut notes: Vec<String> = Vec::new();
+        for note in data_symbols(line).filter_map(|va| describe(binary, strings, va)) {
+            if !notes.contains(&note) {
+                notes.push(note);
+            }
+        }
+        if !notes.is_empty() {
+            out.push_str("  // ");
+            out.push_str(&notes.join(", "));
+        }
+    }
+    out
+}
+
+/// Addresses of the `DAT_`/`PTR_` symbols in a line of decompiled C
+fn data_symbols(line: &str) -> impl Iterator<Item = u64> + '_ {
+    line.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .filter(|word| word.starts_with("DAT_") || word.starts_with("PTR_"))
+        .filter_map(|word| u64::from_str_radix(word.rsplit('_').next()?, 16).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::loader::FunctionInfo;
+
+    #[test]
+    fn test_describe_references() {
+        let mut data = vec![0x90; 4];
+        data.extend_from_slice(b"Hello %s\0");
+        let mut binary = LoadedBinary::from_raw(data, 0x1000, "x86:LE:64:default").unwrap();
+        binary.functions.push(FunctionInfo {
+            name: "malloc".into(),
+            address: 0x2000,
+            size: 0,
+            is_export: false,
+            is_import: true,
+            ordinal: None,
+            forwarded_to: None,
+            is_tls_callback: false,
+        });
+
+        assert_eq!(describe(&binary, &[], 0x1004).as_deref(), Some("\"Hello %s\""));
+        assert_eq!(describe(&binary, &[], 0x2000).as_deref(), Some("-> malloc"));
+        assert_eq!(describe(&binary, &[], 0x1000), None);
+
+        let code = "x = DAT_00001004;\nreturn 0;";
+        assert_eq!(
+            annotate_decompiled(code, &binary, &[]),
+            "x = DAT_00001004;  // \"Hello %s\"\nreturn 0;"
+        );
+    }
+}
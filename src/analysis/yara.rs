@@ -0,0 +1,54 @@
+//! YARA rule scanning (`yara` feature)
+//!
+//! Compiles a rules file with YARA-X and scans the loaded binary's file
+//! bytes. Offsets are file offsets, ready for the Hex View.
+
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+/// A rule that matched, with every string hit
+#[derive(Debug, Clone)]
+pub struct YaraMatch {
+    pub rule: String,
+    pub strings: Vec<YaraStringMatch>,
+}
+
+/// One occurrence of a rule string (`$name`)
+#[derive(Debug, Clone)]
+pub struct YaraStringMatch {
+    pub identifier: String,
+    pub offset: u64,
+    pub length: usize,
+}
+
+/// Compile the rules in `rules_path` and scan `data` with them
+#[cfg(feature = "yara")]
+pub fn scan_file(rules_path: &Path, data: &[u8]) -> Result<Vec<YaraMatch>> {
+    let source = std::fs::read_to_string(rules_path)?;
+    let rules = yara_x::compile(source.as_str()).map_err(|e| anyhow!("{}", e))?;
+    let mut scanner = yara_x::Scanner::new(&rules);
+    let results = scanner.scan(data).map_err(|e| anyhow!("{}", e))?;
+
+    Ok(results.matching_rules()
+        .map(|rule| YaraMatch {
+            rule: rule.identifier().to_string(),
+            strings: rule.patterns()
+                .flat_map(|pattern| {
+                    let identifier = pattern.identifier().to_string();
+                    pattern.matches()
+                        .map(|m| YaraStringMatch {
+                            identifier: identifier.clone(),
+                            offset: m.range().start as u64,
+                            length: m.range().len(),
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+#[cfg(not(feature = "yara"))]
+pub fn scan_file(_rules_path: &Path, _data: &[u8]) -> Result<Vec<YaraMatch>> {
+    Err(anyhow!("YARA support not enabled (build with --features yara)"))
+}
@@ -0,0 +1,106 @@
+//! Address expressions
+//!
+//! Evaluates command arguments such as `main+0x10`, `$$-8` or `entry`:
+//! terms joined by `+`/`-`, where a term is a hex (`0x`) or decimal literal,
+//! `$$` for the current seek, or a symbol looked up by the caller.
+
+use thiserror::Error;
+
+/// Why an address expression could not be evaluated
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AddressError {
+    #[error("Empty address expression")]
+    Empty,
+
+    #[error("Invalid number '{0}'")]
+    InvalidNumber(String),
+
+    #[error("Unknown symbol '{0}'")]
+    UnknownSymbol(String),
+
+    #[error("Missing operand in '{0}'")]
+    MissingOperand(String),
+
+    #[error("Address arithmetic overflows in '{0}'")]
+    Overflow(String),
+}
+
+/// Evaluate `expr`, using `seek` for `$$` and `resolve` for symbol names
+pub fn parse_address(
+    expr: &str,
+    seek: u64,
+    resolve: impl Fn(&str) -> Option<u64>,
+) -> Result<u64, AddressError> {
+    let expr = expr.trim();
+    if expr.is_empty() {
+        return Err(AddressError::Empty);
+    }
+
+    let mut total: u64 = 0;
+    let mut op = '+';
+    let mut rest = expr;
+    loop {
+        let end = rest.find(['+', '-']).unwrap_or(rest.len());
+        let term = rest[..end].trim();
+        if term.is_empty() {
+            return Err(AddressError::MissingOperand(expr.to_string()));
+        }
+
+        let value = match term {
+            "$$" => seek,
+            _ if term.starts_with(|c: char| c.is_ascii_digit()) => parse_number(term)?,
+            _ => resolve(term).ok_or_else(|| AddressError::UnknownSymbol(term.to_string()))?,
+        };
+        total = match op {
+            '+' => total.checked_add(value),
+            _ => total.checked_sub(value),
+        }
+        .ok_or_else(|| AddressError::Overflow(expr.to_string()))?;
+
+        match rest[end..].chars().next() {
+            Some(next) => {
+                op = next;
+                rest = &rest[end + 1..];
+            }
+            None => return Ok(total),
+        }
+    }
+}
+
+/// Parse a literal (supports 0x prefix and decimal)
+fn parse_number(s: &str) -> Result<u64, AddressError> {
+    let parsed = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => s.parse(),
+    };
+    parsed.map_err(|_| AddressError::InvalidNumber(s.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_address() {
+        let resolve = |name: &str| match name {
+            "main" => Some(0x401000),
+            "entry" => Some(0x400800),
+            _ => None,
+        };
+        let eval = |expr: &str| parse_address(expr, 0x1000, resolve);
+
+        assert_eq!(eval("0x401000"), Ok(0x401000));
+        assert_eq!(eval("4096"), Ok(4096));
+        assert_eq!(eval("main+0x10"), Ok(0x401010));
+        assert_eq!(eval(" main + 16 - 1 "), Ok(0x40100f));
+        assert_eq!(eval("$$-0x10"), Ok(0xff0));
+        assert_eq!(eval("entry"), Ok(0x400800));
+
+        assert_eq!(eval(""), Err(AddressError::Empty));
+        assert_eq!(eval("0xzz"), Err(AddressError::InvalidNumber("0xzz".into())));
+        assert_eq!(eval("nope+1"), Err(AddressError::UnknownSymbol("nope".into())));
+        assert_eq!(eval("main+"), Err(AddressError::MissingOperand("main+".into())));
+        assert_eq!(eval("-1"), Err(AddressError::MissingOperand("-1".into())));
+        assert_eq!(eval("$$-0x2000"), Err(AddressError::Overflow("$$-0x2000".into())));
+    }
+}
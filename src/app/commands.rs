@@ -1,25 +1,81 @@
 //! Command processing
 //!
-//! Shared command definitions used by both CLI and GUI.
+//! One command language for the reedline CLI and the GUI console: input is
+//! parsed into an [`AppCommand`] and the commands that only need the loaded
+//! binary run through [`execute`], returning a [`CommandOutput`] the frontend
+//! prints or applies. Commands that need frontend resources (loading, the
+//! Ghidra client, the debugger) are handled by the frontend itself.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use super::address::{parse_address as eval_expression, AddressError};
+use super::export::format_listing;
+use crate::analysis::disasm::{DisasmEngine, DisasmSyntax, DisassembledInstruction, MAX_INSN_LEN};
+use crate::analysis::loader::{FunctionInfo, LoadedBinary};
+use crate::analysis::pattern::{find_pattern, find_string, parse_search_pattern};
+use crate::analysis::strings::ExtractedString;
+use crate::debug::dump::{parse_size, MAX_DUMP};
+use crate::debug::memory::{MemoryProtection, MemoryRegion};
+use crate::debug::types::RegisterState;
 
 /// Application commands that can be executed
 #[derive(Debug, Clone)]
 pub enum AppCommand {
-    /// Load a binary file
-    LoadBinary(String),
-    /// List functions
+    /// Navigate to address: s [@] <expr>
+    Seek(String),
+    /// List functions: afl
     ListFunctions,
-    /// Decompile a function at address
-    Decompile(u64),
-    /// Start debugging
-    StartDebug,
-    /// Stop debugging
-    StopDebug,
-    /// Show help
+    /// Rename function: afn <name> [<addr>] (defaults to the current seek)
+    RenameFunction(String, Option<String>),
+    /// Print disassembly of function: pdf @ <name>
+    PrintDisasmFunction(String),
+    /// Write disassembly of function to a file: pdf @ <name> > <path>
+    ExportDisasmFunction(String, String),
+    /// Decompile function: pdc @ <name>
+    PrintDecompiledFunction(String),
+    /// Print N instructions: pd [<n>] [@ <expr>] (defaults to the current seek)
+    PrintDisasm(usize, Option<String>),
+    /// Search for a string (ASCII and UTF-16LE): / <text>
+    SearchString(String),
+    /// Search for bytes: /x <hex> (`..` wildcards)
+    SearchBytes(String),
+    /// Show memory map: dm
+    MemoryMap,
+    /// Patch bytes: wx <hex> [@ <addr>] (defaults to the current seek)
+    WriteHex(Vec<u8>, Option<String>),
+    /// Dump process memory to a file: wtf <addr> <size> <file>
+    DumpMemory(String, String, String),
+    /// Show registers: dr
+    Registers,
+    /// Attach to a process for memory access: attach <pid>
+    Attach(u32),
+    /// Detach from the attached process: detach
+    Detach,
+    /// Set breakpoint: db <expr>
+    BreakpointSet(String),
+    /// Delete breakpoint: db- <expr>
+    BreakpointDelete(String),
+    /// Continue execution: dc
+    Continue,
+    /// Step instruction: ds
+    StepInto,
+    /// Step over: dso
+    StepOver,
+    /// Load a binary file: o <path>
+    LoadBinary(String),
+    /// Move the loaded binary to a new image base: ob <addr>
+    Rebase(String),
+    /// Get/set config variable: e <key>[=<value>]
+    Config(String, Option<String>),
+    /// Python passthrough: .<code>
+    Python(String),
+    /// Show help: ? or help
     Help,
     /// Clear console/logs
     Clear,
-    /// Quit application
+    /// Quit application: q or exit
     Quit,
     /// Unknown command
     Unknown(String),
@@ -28,41 +84,560 @@ pub enum AppCommand {
 /// Parse a command string into AppCommand
 pub fn parse_command(input: &str) -> AppCommand {
     let input = input.trim();
+
+    // Python passthrough (starts with .)
+    if let Some(code) = input.strip_prefix('.') {
+        return AppCommand::Python(code.to_string());
+    }
+
     let parts: Vec<&str> = input.splitn(2, ' ').collect();
     let cmd = parts.first().unwrap_or(&"");
     let arg = parts.get(1).map(|s| s.trim());
 
     match *cmd {
-        "load" | "open" | "o" => {
-            if let Some(path) = arg {
-                AppCommand::LoadBinary(path.to_string())
-            } else {
-                AppCommand::Unknown("load requires a path".into())
+        // Navigation
+        "s" | "seek" => {
+            if let Some(expr) = arg {
+                let expr = expr.strip_prefix('@').unwrap_or(expr).trim();
+                return AppCommand::Seek(expr.to_string());
+            }
+            AppCommand::Unknown(input.to_string())
+        }
+        "entry" | "main" => AppCommand::Seek(cmd.to_string()),
+
+        // Functions
+        "afl" | "funcs" | "functions" => AppCommand::ListFunctions,
+        "afn" => {
+            if let Some(spec) = arg {
+                return match spec.split_once(char::is_whitespace) {
+                    Some((name, expr)) => AppCommand::RenameFunction(name.to_string(), Some(expr.trim().to_string())),
+                    None => AppCommand::RenameFunction(spec.to_string(), None),
+                };
+            }
+            AppCommand::Unknown(input.to_string())
+        }
+
+        // Disassembly
+        "pd" => {
+            let spec = arg.unwrap_or_default();
+            let (count, at) = match spec.split_once('@') {
+                Some((count, expr)) => (count.trim(), Some(expr.trim().to_string())),
+                None => (spec, None),
+            };
+            match count {
+                "" => AppCommand::PrintDisasm(10, at),
+                _ => match count.parse() {
+                    Ok(count) => AppCommand::PrintDisasm(count, at),
+                    Err(_) => AppCommand::Unknown(input.to_string()),
+                },
+            }
+        }
+        "pdf" => {
+            // pdf @ main -> function name is after @
+            if let Some((func_spec, path)) = arg.and_then(|spec| spec.split_once('>')) {
+                let func_name = func_spec.trim().strip_prefix('@').unwrap_or(func_spec).trim();
+                let func_name = if func_name.is_empty() { "main" } else { func_name };
+                return match path.trim() {
+                    "" => AppCommand::Unknown(input.to_string()),
+                    path => AppCommand::ExportDisasmFunction(func_name.to_string(), path.to_string()),
+                };
+            }
+            if let Some(func_spec) = arg {
+                let func_name = func_spec.strip_prefix("@ ").unwrap_or(func_spec);
+                return AppCommand::PrintDisasmFunction(func_name.to_string());
+            }
+            AppCommand::PrintDisasmFunction("main".to_string())
+        }
+        "pdc" | "decompile" | "dec" => {
+            if let Some(func_spec) = arg {
+                let func_name = func_spec.strip_prefix("@ ").unwrap_or(func_spec);
+                return AppCommand::PrintDecompiledFunction(func_name.to_string());
             }
+            AppCommand::PrintDecompiledFunction("main".to_string())
         }
-        "funcs" | "functions" => AppCommand::ListFunctions,
-        "decompile" | "dec" => {
-            if let Some(addr_str) = arg {
-                if let Some(addr) = parse_address(addr_str) {
-                    AppCommand::Decompile(addr)
-                } else {
-                    AppCommand::Unknown("Invalid address".into())
+
+        // Search
+        "/" => {
+            if let Some(text) = arg {
+                return AppCommand::SearchString(text.to_string());
+            }
+            AppCommand::Unknown(input.to_string())
+        }
+        "/x" => {
+            if let Some(pattern) = arg {
+                return AppCommand::SearchBytes(pattern.to_string());
+            }
+            AppCommand::Unknown(input.to_string())
+        }
+
+        // Memory
+        "dm" => AppCommand::MemoryMap,
+        "wx" => {
+            if let Some(spec) = arg {
+                let (hex, at) = match spec.split_once('@') {
+                    Some((hex, expr)) => (hex, Some(expr.trim().to_string())),
+                    None => (spec, None),
+                };
+                if let Some(bytes) = parse_hex_bytes(hex) {
+                    return AppCommand::WriteHex(bytes, at);
                 }
-            } else {
-                AppCommand::Unknown("decompile requires an address".into())
             }
+            AppCommand::Unknown(input.to_string())
         }
-        "start" => AppCommand::StartDebug,
-        "stop" => AppCommand::StopDebug,
-        "help" | "?" => AppCommand::Help,
+        "wtf" => {
+            let mut args = arg.unwrap_or_default().splitn(3, char::is_whitespace);
+            match (args.next(), args.next(), args.next().map(str::trim)) {
+                (Some(addr), Some(size), Some(path)) if !addr.is_empty() && !path.is_empty() => {
+                    AppCommand::DumpMemory(addr.to_string(), size.to_string(), path.to_string())
+                }
+                _ => AppCommand::Unknown(input.to_string()),
+            }
+        }
+
+        // Debug
+        "dr" | "regs" => AppCommand::Registers,
+        "attach" => match arg.map(str::parse) {
+            Some(Ok(pid)) => AppCommand::Attach(pid),
+            _ => AppCommand::Unknown(input.to_string()),
+        },
+        "detach" => AppCommand::Detach,
+        "db" => {
+            if let Some(expr) = arg {
+                return match expr.strip_prefix('-') {
+                    Some(expr) => AppCommand::BreakpointDelete(expr.trim().to_string()),
+                    None => AppCommand::BreakpointSet(expr.to_string()),
+                };
+            }
+            AppCommand::Unknown(input.to_string())
+        }
+        "db-" => {
+            if let Some(expr) = arg {
+                return AppCommand::BreakpointDelete(expr.to_string());
+            }
+            AppCommand::Unknown(input.to_string())
+        }
+        "dc" | "continue" | "c" => AppCommand::Continue,
+        "ds" | "step" => AppCommand::StepInto,
+        "dso" | "next" | "n" => AppCommand::StepOver,
+
+        // File operations
+        "o" | "open" | "load" => {
+            if let Some(path) = arg {
+                return AppCommand::LoadBinary(path.to_string());
+            }
+            AppCommand::Unknown(input.to_string())
+        }
+        "ob" | "rebase" => {
+            if let Some(expr) = arg {
+                return AppCommand::Rebase(expr.to_string());
+            }
+            AppCommand::Unknown(input.to_string())
+        }
+
+        // Configuration
+        "e" => {
+            if let Some(spec) = arg {
+                return match spec.split_once('=') {
+                    Some((key, value)) => {
+                        AppCommand::Config(key.trim().to_string(), Some(value.trim().to_string()))
+                    }
+                    None => AppCommand::Config(spec.to_string(), None),
+                };
+            }
+            AppCommand::Unknown(input.to_string())
+        }
+
+        "?" | "help" => AppCommand::Help,
         "clear" => AppCommand::Clear,
-        "quit" | "exit" | "q" => AppCommand::Quit,
+        "q" | "quit" | "exit" => AppCommand::Quit,
+
         _ => AppCommand::Unknown(input.to_string()),
     }
 }
 
+/// Help text as (section, [(usage, description)])
+pub const HELP: &[(&str, &[(&str, &str)])] = &[
+    ("Navigation", &[
+        ("s <addr>", "Seek to address"),
+        ("s @ <func>", "Seek to function"),
+        ("entry / main", "Seek to the entry point / main"),
+    ]),
+    ("Functions", &[
+        ("afl", "List functions (address, size, name)"),
+        ("afn <name> [a]", "Rename function at seek (or addr)"),
+    ]),
+    ("Disassembly", &[
+        ("pd <n> [@ a]", "Print N instructions at seek (or addr)"),
+        ("pdf @ <func>", "Print disassembly of function"),
+        ("pdf @ f > file", "Write disassembly of function to a file"),
+        ("pdc @ <func>", "Print decompiled C of function"),
+    ]),
+    ("Search", &[
+        ("/ <text>", "Find ASCII/UTF-16 string"),
+        ("/x <hex>", "Find bytes (.. wildcards, e.g. 488b..c0)"),
+    ]),
+    ("Debugging", &[
+        ("attach <pid>", "Attach to a process"),
+        ("detach", "Detach from the process"),
+        ("dr", "Show registers"),
+        ("db <addr>", "Set breakpoint"),
+        ("db- <addr>", "Delete breakpoint"),
+        ("dc", "Continue execution"),
+        ("ds", "Step into"),
+        ("dso", "Step over"),
+    ]),
+    ("Memory", &[
+        ("dm", "Show memory map"),
+        ("wx <hex> [@ a]", "Patch bytes at seek (or @ addr)"),
+        ("wtf <a> <n> <f>", "Dump N bytes of process memory to a file"),
+    ]),
+    ("Files", &[
+        ("o <path>", "Open/load binary"),
+        ("ob <addr>", "Rebase binary to a new image base"),
+    ]),
+    ("Config", &[
+        ("e <key>[=<v>]", "Get/set variable (asm.syntax=intel|att|masm|nasm)"),
+    ]),
+    ("Scripting", &[
+        (".<code>", "Execute Python code"),
+    ]),
+    ("Other", &[
+        ("?", "Show this help"),
+        ("clear", "Clear console"),
+        ("q", "Quit Fission"),
+    ]),
+];
+
+/// Footnote to [`HELP`] on address arguments
+pub const ADDRESS_HELP: &str = "Addresses accept names, entry, registers, $$ (seek) and +/- (e.g. main+0x10, rsp+8)";
+
+/// State the shared commands run against (the CLI session, the GUI state)
+pub trait CommandContext {
+    /// Currently loaded binary
+    fn binary(&self) -> Option<&LoadedBinary>;
+    /// Current address (`$$`, and the default for commands without `@`)
+    fn seek(&self) -> u64;
+    /// User-assigned function names by address
+    fn function_renames(&self) -> &HashMap<u64, String>;
+    fn function_renames_mut(&mut self) -> &mut HashMap<u64, String>;
+    /// Assembly syntax (`e asm.syntax`)
+    fn disasm_syntax(&self) -> DisasmSyntax;
+    fn set_disasm_syntax(&mut self, syntax: DisasmSyntax);
+    /// Registers of the suspended debuggee (`dr`)
+    fn registers(&self) -> Option<RegisterState> {
+        None
+    }
+    /// Strings index the listing comments look addresses up in
+    fn strings(&self) -> &[ExtractedString] {
+        &[]
+    }
+    /// Register value as an address expression term (`rip`, `rsp+8`)
+    fn register_address(&self, name: &str) -> Option<u64> {
+        self.registers()?.get(name)
+    }
+}
+
+/// Row of `afl`
+#[derive(Debug, serde::Serialize)]
+pub struct FunctionRow {
+    pub address: u64,
+    pub size: u64,
+    pub name: String,
+}
+
+/// Match of `/` or `/x`
+#[derive(Debug, serde::Serialize)]
+pub struct SearchHit {
+    /// File offset
+    pub offset: u64,
+    /// Virtual address, if the offset is mapped
+    pub address: Option<u64>,
+    /// `ascii`/`utf16le` for string searches
+    pub encoding: Option<&'static str>,
+}
+
+/// Result of [`execute`]
+#[derive(Debug)]
+pub enum CommandOutput {
+    /// Address to move to (`s`)
+    Seek(u64),
+    Functions(Vec<FunctionRow>),
+    Renamed { old: String, new: String },
+    Disassembly(Vec<DisassembledInstruction>),
+    SearchHits(Vec<SearchHit>),
+    /// Bytes to write (`wx`); the frontend applies the patch
+    Patch { address: u64, bytes: Vec<u8> },
+    /// Process memory to dump to a file (`wtf`); the frontend reads it
+    Dump { address: u64, size: usize, path: String },
+    /// Breakpoint to set or delete (`db`, `db-`); the frontend applies it
+    Breakpoint { address: u64, set: bool },
+    /// New image base (`ob`); the frontend rebases the binary
+    Rebase { base: u64 },
+    /// Function to decompile (`pdc`)
+    Decompile(FunctionInfo),
+    /// Listing written to a file (`pdf > path`)
+    Exported { path: String, instructions: usize },
+    /// Section layout of the loaded binary (`dm`)
+    MemoryMap(Vec<MemoryRegion>),
+    Registers(RegisterState),
+    /// Current value of a config variable (`e`)
+    Config { key: String, value: String },
+}
+
+impl CommandOutput {
+    /// Plain-text rendering, one console line per entry
+    pub fn lines(&self) -> Vec<String> {
+        match self {
+            Self::Seek(address) => vec![format!("[*] Seeking to {:#x}", address)],
+            Self::Functions(rows) => rows.iter()
+                .map(|row| format!("{:#018x}  {:>8}  {}", row.address, row.size, row.name))
+                .chain(std::iter::once(format!("[*] {} functions", rows.len())))
+                .collect(),
+            Self::Renamed { old, new } => vec![format!("[*] Renamed {} -> {}", old, new)],
+            Self::Disassembly(insns) => insns.iter().map(|insn| format!("    {}", insn.format_full())).collect(),
+            Self::SearchHits(hits) => hits.iter()
+                .map(|hit| {
+                    let address = hit.address.map_or_else(|| "-".to_string(), |va| format!("{:#018x}", va));
+                    format!("{:#010x}  {:<18}  {}", hit.offset, address, hit.encoding.unwrap_or_default())
+                })
+                .chain(std::iter::once(format!("[*] {} hits", hits.len())))
+                .collect(),
+            Self::Patch { address, bytes } => vec![format!("[*] Patching {} bytes at {:#x}", bytes.len(), address)],
+            Self::Dump { address, size, path } => vec![format!("[*] Dumping {:#x} bytes at {:#x} to {}", size, address, path)],
+            Self::Breakpoint { address, set: true } => vec![format!("[*] Breakpoint set at {:#x}", address)],
+            Self::Breakpoint { address, set: false } => vec![format!("[*] Breakpoint deleted at {:#x}", address)],
+            Self::Rebase { base } => vec![format!("[*] Rebasing to {:#x}", base)],
+            Self::Decompile(func) => vec![format!("[*] Decompiling function: {}", func.name)],
+            Self::Exported { path, instructions } => vec![format!("[✓] Wrote {} instructions to {}", instructions, path)],
+            Self::MemoryMap(regions) if regions.is_empty() => vec!["    (no regions)".to_string()],
+            Self::MemoryMap(regions) => std::iter::once(format!("    {:<18}  {:<10}  {:<5}  NAME", "BASE", "SIZE", "PERMS"))
+                .chain(regions.iter().map(|region| {
+                    format!(
+                        "    {:#018x}  {:<10}  {:<5}  {}",
+                        region.base_address,
+                        format!("{:#x}", region.size),
+                        region.protection.to_string(),
+                        region.name.as_deref().unwrap_or("")
+                    )
+                }))
+                .collect(),
+            Self::Registers(regs) => [
+                ("RAX", regs.rax), ("RBX", regs.rbx), ("RCX", regs.rcx), ("RDX", regs.rdx),
+                ("RSI", regs.rsi), ("RDI", regs.rdi), ("RBP", regs.rbp), ("RSP", regs.rsp),
+                ("RIP", regs.rip),
+            ]
+            .iter()
+            .map(|(name, value)| format!("    {} = {:#018x}", name, value))
+            .collect(),
+            Self::Config { key, value } => vec![format!("{} = {}", key, value)],
+        }
+    }
+}
+
+/// Run a command that only needs [`CommandContext`].
+///
+/// Loading, decompiling through Ghidra, live memory and debugger control
+/// belong to the frontend; passing those here is an error.
+pub fn execute(ctx: &mut impl CommandContext, cmd: AppCommand) -> Result<CommandOutput> {
+    Ok(match cmd {
+        AppCommand::Seek(expr) => CommandOutput::Seek(eval_address(ctx, &expr)?),
+        AppCommand::ListFunctions => {
+            let binary = loaded(ctx)?;
+            CommandOutput::Functions(binary.functions_sorted().into_iter()
+                .map(|func| FunctionRow {
+                    address: func.address,
+                    size: func.size,
+                    name: display_name(ctx.function_renames(), func),
+                })
+                .collect())
+        }
+        AppCommand::RenameFunction(name, at) => {
+            let address = eval_or_seek(ctx, at.as_deref())?;
+            let func = loaded(ctx)?
+                .function_at(address)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("No function at {:#x}", address))?;
+            let old = display_name(ctx.function_renames(), &func);
+            if name == func.name {
+                ctx.function_renames_mut().remove(&func.address);
+            } else {
+                ctx.function_renames_mut().insert(func.address, name.clone());
+            }
+            CommandOutput::Renamed { old, new: name }
+        }
+        AppCommand::PrintDisasm(count, at) => {
+            let address = eval_or_seek(ctx, at.as_deref())?;
+            CommandOutput::Disassembly(disassemble(ctx, address, count.saturating_mul(MAX_INSN_LEN), count)?)
+        }
+        AppCommand::PrintDisasmFunction(spec) => {
+            let func = resolve_function(ctx, &spec)?;
+            if func.is_import || func.size == 0 {
+                anyhow::bail!("{} has no known code extent", display_name(ctx.function_renames(), &func));
+            }
+            CommandOutput::Disassembly(disassemble(ctx, func.address, func.size as usize, usize::MAX)?)
+        }
+        AppCommand::ExportDisasmFunction(spec, path) => {
+            let func = resolve_function(ctx, &spec)?;
+            if func.is_import || func.size == 0 {
+                anyhow::bail!("{} has no known code extent", display_name(ctx.function_renames(), &func));
+            }
+            let insns = disassemble(ctx, func.address, func.size as usize, usize::MAX)?;
+            let mut lines = format_listing(loaded(ctx)?, ctx.function_renames(), ctx.strings(), &HashMap::new(), &insns);
+            lines.push(String::new());
+            std::fs::write(&path, lines.join("\n"))?;
+            CommandOutput::Exported { path, instructions: insns.len() }
+        }
+        AppCommand::PrintDecompiledFunction(spec) => {
+            let func = resolve_function(ctx, &spec)?;
+            if func.is_import {
+                anyhow::bail!("{} is an import function (no code to decompile)", func.name);
+            }
+            CommandOutput::Decompile(func)
+        }
+        AppCommand::SearchString(text) => {
+            let binary = loaded(ctx)?;
+            CommandOutput::SearchHits(find_string(&binary.data, &text).into_iter()
+                .map(|(offset, encoding)| SearchHit {
+                    offset,
+                    address: binary.file_offset_to_va(offset),
+                    encoding: Some(encoding.name()),
+                })
+                .collect())
+        }
+        AppCommand::SearchBytes(pattern) => {
+            let binary = loaded(ctx)?;
+            let pattern = parse_search_pattern(&pattern)
+                .map_err(|e| anyhow::anyhow!("Invalid pattern: {}", e))?;
+            CommandOutput::SearchHits(find_pattern(&binary.data, &pattern).into_iter()
+                .map(|offset| SearchHit {
+                    offset,
+                    address: binary.file_offset_to_va(offset),
+                    encoding: None,
+                })
+                .collect())
+        }
+        AppCommand::MemoryMap => CommandOutput::MemoryMap(section_regions(loaded(ctx)?)),
+        AppCommand::WriteHex(bytes, at) => {
+            loaded(ctx)?;
+            CommandOutput::Patch { address: eval_or_seek(ctx, at.as_deref())?, bytes }
+        }
+        AppCommand::DumpMemory(expr, size, path) => {
+            let size = parse_size(&size)
+                .filter(|&size| size > 0 && size <= MAX_DUMP)
+                .ok_or_else(|| anyhow::anyhow!("Invalid size '{}' (1 to {:#x} bytes)", size, MAX_DUMP))?;
+            CommandOutput::Dump { address: eval_address(ctx, &expr)?, size, path }
+        }
+        AppCommand::Registers => CommandOutput::Registers(
+            ctx.registers().ok_or_else(|| anyhow::anyhow!("No suspended process"))?,
+        ),
+        AppCommand::Rebase(expr) => {
+            loaded(ctx)?;
+            CommandOutput::Rebase { base: eval_address(ctx, &expr)? }
+        }
+        AppCommand::BreakpointSet(expr) => CommandOutput::Breakpoint { address: eval_address(ctx, &expr)?, set: true },
+        AppCommand::BreakpointDelete(expr) => CommandOutput::Breakpoint { address: eval_address(ctx, &expr)?, set: false },
+        AppCommand::Config(key, value) => match key.as_str() {
+            "asm.syntax" => {
+                if let Some(value) = value {
+                    let syntax = DisasmSyntax::parse(&value).ok_or_else(|| {
+                        anyhow::anyhow!("Unknown syntax '{}' (intel, att, masm, nasm)", value)
+                    })?;
+                    ctx.set_disasm_syntax(syntax);
+                }
+                CommandOutput::Config { key, value: ctx.disasm_syntax().name().to_string() }
+            }
+            _ => anyhow::bail!("Unknown config variable '{}'", key),
+        },
+        AppCommand::Unknown(input) => anyhow::bail!("Unknown command: '{}' (type '?' for help)", input),
+        other => anyhow::bail!("{:?} is handled by the frontend", other),
+    })
+}
+
+/// Evaluate an address expression such as `main+0x10`, `$$-8` or `entry`
+pub fn eval_address(ctx: &impl CommandContext, expr: &str) -> Result<u64, AddressError> {
+    eval_expression(expr, ctx.seek(), |name| symbol_address(ctx, name))
+}
+
+/// Evaluate an optional `@ <expr>` argument, defaulting to the current seek
+fn eval_or_seek(ctx: &impl CommandContext, at: Option<&str>) -> Result<u64, AddressError> {
+    at.map_or(Ok(ctx.seek()), |expr| eval_address(ctx, expr))
+}
+
+/// Address of a function name (user-assigned first), `sub_<hex>`,
+/// `entry` for the entry point, or `main` for a `_main`/`WinMain` symbol
+fn symbol_address(ctx: &impl CommandContext, name: &str) -> Option<u64> {
+    let binary = ctx.binary()?;
+    ctx.function_renames().iter()
+        .find(|(_, renamed)| renamed.as_str() == name)
+        .map(|(&address, _)| address)
+        .or_else(|| binary.find_function(name).map(|f| f.address))
+        .or_else(|| name.strip_prefix("sub_").and_then(|hex| u64::from_str_radix(hex, 16).ok()))
+        .or_else(|| (name == "entry").then_some(binary.entry_point))
+        .or_else(|| binary.main_function().filter(|_| name == "main").map(|f| f.address))
+        .or_else(|| ctx.register_address(name))
+}
+
+/// Resolve the function containing an address expression (`pdc @ main`)
+pub fn resolve_function(ctx: &impl CommandContext, spec: &str) -> Result<FunctionInfo> {
+    let address = eval_address(ctx, spec)?;
+    loaded(ctx)?
+        .function_at(address)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("No function at {:#x}", address))
+}
+
+fn loaded(ctx: &impl CommandContext) -> Result<&LoadedBinary> {
+    ctx.binary().ok_or_else(|| anyhow::anyhow!("No binary loaded"))
+}
+
+/// Disassemble up to `count` instructions in the `len` bytes at `address`
+fn disassemble(
+    ctx: &impl CommandContext,
+    address: u64,
+    len: usize,
+    count: usize,
+) -> Result<Vec<DisassembledInstruction>> {
+    let binary = loaded(ctx)?;
+    let bytes = binary
+        .get_bytes(address, len)
+        .ok_or_else(|| anyhow::anyhow!("{:#x} is not mapped", address))?;
+    let engine = DisasmEngine::for_arch(&binary.arch_spec, ctx.disasm_syntax())?;
+    let mut insns = engine.disassemble(&bytes, address)?;
+    insns.truncate(count);
+    Ok(insns)
+}
+
+/// The binary's sections as memory regions (`dm` without a process)
+pub fn section_regions(binary: &LoadedBinary) -> Vec<MemoryRegion> {
+    binary
+        .sections
+        .iter()
+        .map(|s| MemoryRegion {
+            base_address: s.virtual_address,
+            size: s.mapped_size() as usize,
+            protection: MemoryProtection {
+                read: s.is_readable,
+                write: s.is_writable,
+                execute: s.is_executable,
+            },
+            name: Some(s.name.clone()),
+        })
+        .collect()
+}
+
+/// Display name for a function given the rename overrides
+pub fn display_name(renames: &HashMap<u64, String>, func: &FunctionInfo) -> String {
+    if let Some(name) = renames.get(&func.address) {
+        name.clone()
+    } else if func.name.is_empty() {
+        format!("sub_{:08x}", func.address)
+    } else {
+        func.name.clone()
+    }
+}
+
 /// Parse an address from hex or decimal string
-fn parse_address(s: &str) -> Option<u64> {
+pub fn parse_address(s: &str) -> Option<u64> {
     let s = s.trim();
     if s.starts_with("0x") || s.starts_with("0X") {
         u64::from_str_radix(&s[2..], 16).ok()
@@ -72,3 +647,46 @@ fn parse_address(s: &str) -> Option<u64> {
         s.parse().ok()
     }
 }
+
+/// Parse a hex byte string such as `9090`, `90 90` or `0x90c3`
+pub fn parse_hex_bytes(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+    let digits: Vec<u8> = s.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if digits.is_empty() || !digits.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+    digits
+        .chunks(2)
+        .map(|pair| match *pair {
+            [hi, lo] => u8::from_str_radix(std::str::from_utf8(&[hi, lo]).ok()?, 16).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert!(matches!(parse_command("s main+0x10"), AppCommand::Seek(e) if e == "main+0x10"));
+        assert!(matches!(parse_command("s @ main"), AppCommand::Seek(e) if e == "main"));
+        assert!(matches!(parse_command("entry"), AppCommand::Seek(e) if e == "entry"));
+        assert!(matches!(parse_command("pd 5 @ entry"), AppCommand::PrintDisasm(5, Some(e)) if e == "entry"));
+        assert!(matches!(parse_command("pd"), AppCommand::PrintDisasm(10, None)));
+        assert!(matches!(parse_command("pd x"), AppCommand::Unknown(_)));
+        assert!(matches!(parse_command("db -0x401000"), AppCommand::BreakpointDelete(e) if e == "0x401000"));
+        assert!(matches!(parse_command("wx 90c3 @ $$+1"), AppCommand::WriteHex(b, Some(e)) if b == [0x90, 0xc3] && e == "$$+1"));
+        assert!(matches!(parse_command("pdf @ main > main.txt"), AppCommand::ExportDisasmFunction(f, p) if f == "main" && p == "main.txt"));
+        assert!(matches!(parse_command("pdf > out.txt"), AppCommand::ExportDisasmFunction(f, _) if f == "main"));
+        assert!(matches!(parse_command("load /bin/ls"), AppCommand::LoadBinary(p) if p == "/bin/ls"));
+        assert!(matches!(parse_command("ob 0x7ff600000000"), AppCommand::Rebase(e) if e == "0x7ff600000000"));
+        assert!(matches!(parse_command("funcs"), AppCommand::ListFunctions));
+        assert!(matches!(parse_command("attach 1234"), AppCommand::Attach(1234)));
+        assert!(matches!(parse_command("attach me"), AppCommand::Unknown(_)));
+        assert!(matches!(parse_command("wtf $$+8 0x1000 out/dump 1.bin"), AppCommand::DumpMemory(a, n, p) if a == "$$+8" && n == "0x1000" && p == "out/dump 1.bin"));
+        assert!(matches!(parse_command("wtf 0x401000 0x1000"), AppCommand::Unknown(_)));
+    }
+}
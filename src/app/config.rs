@@ -0,0 +1,25 @@
+//! Configuration paths
+//!
+//! Locates the per-user directory where Fission keeps caches and settings.
+
+use std::path::PathBuf;
+
+/// Per-user Fission config directory
+///
+/// `%APPDATA%\Fission` on Windows, `$XDG_CONFIG_HOME/fission` or
+/// `~/.config/fission` elsewhere. Returns `None` if no home can be found.
+pub fn config_dir() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    {
+        std::env::var_os("APPDATA").map(|p| PathBuf::from(p).join("Fission"))
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+            .map(|p| p.join("fission"))
+    }
+}
@@ -0,0 +1,169 @@
+//! Analysis export
+//!
+//! Serializes a loaded binary's metadata, sections, functions and strings to
+//! JSON for consumption by other tools, and formats disassembly listings as
+//! text with the columns and comments of the assembly view.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::commands::display_name;
+use crate::analysis::disasm::DisassembledInstruction;
+use crate::analysis::loader::{Hashes, LoadedBinary};
+use crate::analysis::strings::{extract_strings, ExtractedString};
+use crate::analysis::xrefs;
+
+/// Minimum string length included in reports (matches the Strings tab)
+pub const MIN_STRING_LEN: usize = 4;
+/// Instruction bytes shown before the bytes column is cut off with `..`
+const LISTING_BYTES: usize = 8;
+/// Width of the bytes column in text listings (`LISTING_BYTES` pairs and `..`)
+const LISTING_BYTES_WIDTH: usize = LISTING_BYTES * 3 + 2;
+
+/// JSON document produced by `--export-json`
+#[derive(Serialize)]
+pub struct AnalysisReport<'a> {
+    /// Binary metadata, sections and functions
+    #[serde(flatten)]
+    pub binary: &'a LoadedBinary,
+    /// Human-readable summary
+    pub summary: String,
+    /// MD5/SHA-1/SHA-256 and, for PE, imphash
+    pub hashes: Hashes,
+    /// Names of executable sections that look packed
+    pub packed_sections: Vec<&'a str>,
+    /// Extracted strings
+    pub strings: Vec<ExtractedString>,
+}
+
+impl<'a> AnalysisReport<'a> {
+    /// Build a report for `binary`, extracting its strings
+    pub fn new(binary: &'a LoadedBinary) -> Self {
+        Self {
+            binary,
+            summary: binary.summary(),
+            hashes: binary.hashes(),
+            packed_sections: binary.packed_sections().iter().map(|s| s.name.as_str()).collect(),
+            strings: extract_strings(&binary.data, MIN_STRING_LEN),
+        }
+    }
+
+    /// Write the report as pretty-printed JSON
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// A `;` comment after an instruction's operands
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListingComment {
+    pub text: String,
+    /// Longer form for a tooltip (the full path of a source file)
+    pub detail: Option<String>,
+}
+
+/// Bytes column of a listing row, e.g. `55 48 89 E5 `
+pub fn listing_bytes(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(LISTING_BYTES_WIDTH);
+    for (i, b) in bytes.iter().enumerate() {
+        if i >= LISTING_BYTES {
+            out.push_str("..");
+            break;
+        }
+        write!(out, "{:02X} ", b).unwrap();
+    }
+    out
+}
+
+/// Comments of a listing row: the function a direct branch targets, the
+/// string or import an operand references, the stack variable in `locals`
+/// and the source line where it changes from `prev`
+pub fn listing_comments(
+    binary: &LoadedBinary,
+    renames: &HashMap<u64, String>,
+    strings: &[ExtractedString],
+    locals: &HashMap<u64, String>,
+    insn: &DisassembledInstruction,
+    prev: Option<&DisassembledInstruction>,
+) -> Vec<ListingComment> {
+    let mut comments = Vec::new();
+    if let Some(func) = insn.branch_target().and_then(|t| binary.function_at(t)) {
+        comments.push(ListingComment { text: format!("; {}", display_name(renames, func)), detail: None });
+    }
+    if let Some(note) = insn.data_reference().and_then(|va| xrefs::describe(binary, strings, va)) {
+        comments.push(ListingComment { text: format!("; {}", note), detail: None });
+    }
+    if let Some(local) = locals.get(&insn.address) {
+        comments.push(ListingComment { text: format!("; {}", local), detail: None });
+    }
+    let source = binary.line_table.lookup(insn.address);
+    let prev_source = prev.and_then(|p| binary.line_table.lookup(p.address));
+    if let Some((file, line)) = source.filter(|_| source != prev_source) {
+        let short = file.rsplit(['/', '\\']).next().unwrap_or(file);
+        comments.push(ListingComment {
+            text: format!("; {}:{}", short, line),
+            detail: Some(format!("{}:{}", file, line)),
+        });
+    }
+    comments
+}
+
+/// Text listing of `insns`, one line per instruction: address, bytes,
+/// mnemonic, operands and comments
+pub fn format_listing(
+    binary: &LoadedBinary,
+    renames: &HashMap<u64, String>,
+    strings: &[ExtractedString],
+    locals: &HashMap<u64, String>,
+    insns: &[DisassembledInstruction],
+) -> Vec<String> {
+    insns.iter().enumerate()
+        .map(|(i, insn)| {
+            let prev = i.checked_sub(1).map(|p| &insns[p]);
+            let mut line = format!(
+                "{:08X}  {:<width$}{:<7} {}",
+                insn.address,
+                listing_bytes(&insn.bytes),
+                insn.mnemonic,
+                insn.operands,
+                width = LISTING_BYTES_WIDTH,
+            );
+            for comment in listing_comments(binary, renames, strings, locals, insn, prev) {
+                line.push_str("  ");
+                line.push_str(&comment.text);
+            }
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::disasm::{DisasmEngine, DisasmSyntax};
+
+    #[test]
+    fn test_format_listing() {
+        // call 0x1008 ; ret ; ... ; ret
+        let code = vec![0xe8, 0x03, 0, 0, 0, 0xc3, 0x90, 0x90, 0xc3];
+        let binary = LoadedBinary::from_raw(code.clone(), 0x1000, "x86:LE:64:default").unwrap();
+        let engine = DisasmEngine::with_syntax(true, DisasmSyntax::Intel).unwrap();
+        let insns = engine.disassemble(&code[..6], 0x1000).unwrap();
+
+        let renames = HashMap::from([(0x1000, "start".to_string())]);
+        let locals = HashMap::from([(0x1005, "local_8".to_string())]);
+        let lines = format_listing(&binary, &renames, &[], &locals, &insns);
+        assert_eq!(lines, vec![
+            "00001000  E8 03 00 00 00            call    0x1008  ; start",
+            "00001005  C3                        ret       ; local_8",
+        ]);
+        assert_eq!(listing_bytes(&[0x90; 10]), "90 90 90 90 90 90 90 90 ..");
+    }
+}
@@ -1,9 +1,17 @@
-//! App module - shared application state and commands
+//! App module - shared commands and persistence
 //!
-//! Provides common state and command processing used by both CLI and GUI.
+//! Provides the command processor used by both CLI and GUI, plus config,
+//! export, session and settings files. Frontend state lives with each
+//! frontend (`ui::cli::CliSession`, `ui::gui::state::AppState`) and is
+//! exposed to commands through `CommandContext`.
 
-mod state;
+mod address;
 mod commands;
+pub mod config;
+pub mod export;
+pub mod recent;
+pub mod session;
+pub mod settings;
 
-pub use state::*;
+pub use address::AddressError;
 pub use commands::*;
@@ -0,0 +1,71 @@
+//! Recently opened files
+//!
+//! Keeps the most recently loaded binary paths in `recent_files` under the
+//! config directory, one path per line, newest first.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Number of paths kept in the list
+pub const MAX_RECENT_FILES: usize = 10;
+
+fn list_path() -> Option<PathBuf> {
+    super::config::config_dir().map(|dir| dir.join("recent_files"))
+}
+
+/// Read the saved list, dropping files that no longer exist
+pub fn load() -> VecDeque<String> {
+    let Some(contents) = list_path().and_then(|p| fs::read_to_string(p).ok()) else {
+        return VecDeque::new();
+    };
+    contents.lines()
+        .filter(|line| !line.is_empty() && Path::new(line).exists())
+        .take(MAX_RECENT_FILES)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Write the list to the config directory
+pub fn save(recent: &VecDeque<String>) -> io::Result<()> {
+    let Some(path) = list_path() else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut contents = String::new();
+    for entry in recent {
+        contents.push_str(entry);
+        contents.push('\n');
+    }
+    fs::write(path, contents)
+}
+
+/// Move `path` to the front of the list, trimming it to `MAX_RECENT_FILES`
+pub fn push(recent: &mut VecDeque<String>, path: &str) {
+    recent.retain(|p| p != path);
+    recent.push_front(path.to_string());
+    recent.truncate(MAX_RECENT_FILES);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_recent() {
+        let mut recent = VecDeque::new();
+        for i in 0..12 {
+            push(&mut recent, &format!("/bin/{}", i));
+        }
+        assert_eq!(recent.len(), MAX_RECENT_FILES);
+        assert_eq!(recent.front().map(String::as_str), Some("/bin/11"));
+
+        push(&mut recent, "/bin/5");
+        assert_eq!(recent.len(), MAX_RECENT_FILES);
+        assert_eq!(recent.front().map(String::as_str), Some("/bin/5"));
+        assert_eq!(recent.iter().filter(|p| *p == "/bin/5").count(), 1);
+    }
+}
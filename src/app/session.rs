@@ -0,0 +1,52 @@
+//! Analysis sessions
+//!
+//! Persists per-target analysis state (renames, breakpoints, bookmarks, last location)
+//! to a JSON file so work can be resumed later.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// A user-marked address with a note
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Bookmark {
+    pub address: u64,
+    pub note: String,
+}
+
+/// Saved analysis state for one binary
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// Path of the analyzed binary
+    pub binary_path: Option<String>,
+    /// SHA-256 of the binary contents when the session was saved
+    pub binary_hash: Option<String>,
+    /// Software breakpoint addresses
+    pub breakpoints: Vec<u64>,
+    /// User-assigned function names (address -> name)
+    pub function_renames: HashMap<u64, String>,
+    /// Bookmarked addresses
+    pub bookmarks: Vec<Bookmark>,
+    /// Last viewed address
+    pub last_seek: Option<u64>,
+}
+
+impl Session {
+    /// Write the session as pretty-printed JSON
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Read a session from a JSON file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+}
@@ -0,0 +1,88 @@
+//! User settings
+//!
+//! Preferences that persist across restarts, stored as `settings.json` in the
+//! config directory.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Smallest and largest UI text scale
+pub const UI_SCALE_RANGE: (f32, f32) = (0.6, 2.5);
+
+/// Smallest and largest assembly listing window, in bytes
+pub const ASM_WINDOW_RANGE: (usize, usize) = (0x40, 0x10000);
+
+/// Bytes decoded for a listing outside known functions, unless changed
+pub const DEFAULT_ASM_WINDOW: usize = 0x200;
+
+/// Persisted user preferences
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Multiplier applied to all text sizes
+    pub ui_scale: f32,
+    /// GUI color theme name (`mocha` or `latte`)
+    pub theme: String,
+    /// Bytes decoded for a raw listing, and added or removed by More/Less
+    pub asm_window: usize,
+    /// Name stack operands in the assembly view after decompiled variables
+    pub stack_var_names: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            ui_scale: 1.0,
+            theme: "mocha".to_string(),
+            asm_window: DEFAULT_ASM_WINDOW,
+            stack_var_names: true,
+        }
+    }
+}
+
+impl Settings {
+    fn path() -> Option<PathBuf> {
+        super::config::config_dir().map(|dir| dir.join("settings.json"))
+    }
+
+    /// Read saved settings, falling back to defaults
+    pub fn load() -> Self {
+        let settings: Self = Self::path()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self {
+            ui_scale: clamp_ui_scale(settings.ui_scale),
+            asm_window: clamp_asm_window(settings.asm_window),
+            ..settings
+        }
+    }
+
+    /// Write settings to the config directory
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Clamp a text scale to `UI_SCALE_RANGE` (NaN becomes 1.0)
+pub fn clamp_ui_scale(scale: f32) -> f32 {
+    if scale.is_nan() {
+        return 1.0;
+    }
+    scale.clamp(UI_SCALE_RANGE.0, UI_SCALE_RANGE.1)
+}
+
+/// Clamp a listing window to `ASM_WINDOW_RANGE`
+pub fn clamp_asm_window(bytes: usize) -> usize {
+    bytes.clamp(ASM_WINDOW_RANGE.0, ASM_WINDOW_RANGE.1)
+}
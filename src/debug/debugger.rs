@@ -183,6 +183,24 @@ impl Debugger {
         Ok(())
     }
 
+    /// Suspend the running target
+    pub fn break_into(&self) -> Result<(), DebugError> {
+        let pid = self.target_pid.filter(|_| self.is_active)
+            .ok_or_else(|| DebugError::EventError("Debugger not active".into()))?;
+
+        #[cfg(target_os = "windows")]
+        {
+            self.break_windows(pid)?;
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            self.break_linux(pid)?;
+        }
+
+        Ok(())
+    }
+
     /// Get current target PID
     pub fn target_pid(&self) -> Option<u32> {
         self.target_pid
@@ -210,6 +228,22 @@ impl Debugger {
         Ok(())
     }
 
+    fn break_windows(&self, pid: u32) -> Result<(), DebugError> {
+        use windows::Win32::Foundation::CloseHandle;
+        use windows::Win32::System::Diagnostics::Debug::DebugBreakProcess;
+        use windows::Win32::System::Threading::{OpenProcess, PROCESS_ALL_ACCESS};
+
+        // Injects a thread that hits int3, reported as a breakpoint event
+        unsafe {
+            let process = OpenProcess(PROCESS_ALL_ACCESS, false, pid)
+                .map_err(|e| DebugError::EventError(format!("OpenProcess failed for pid {}: {}", pid, e)))?;
+            let result = DebugBreakProcess(process)
+                .map_err(|e| DebugError::EventError(format!("Break into {} failed: {}", pid, e)));
+            let _ = CloseHandle(process);
+            result
+        }
+    }
+
     fn detach_windows(&mut self, pid: u32) -> Result<(), DebugError> {
         use windows::Win32::System::Diagnostics::Debug::DebugActiveProcessStop;
 
@@ -239,6 +273,15 @@ impl Debugger {
         Ok(())
     }
 
+    fn break_linux(&self, pid: u32) -> Result<(), DebugError> {
+        use nix::sys::signal::{kill, Signal};
+        use nix::unistd::Pid;
+
+        // A traced process that stops reports the SIGSTOP to waitpid
+        kill(Pid::from_raw(pid as i32), Signal::SIGSTOP)
+            .map_err(|e| DebugError::EventError(format!("SIGSTOP to {} failed: {}", pid, e)))
+    }
+
     fn detach_linux(&mut self, pid: u32) -> Result<(), DebugError> {
         use nix::sys::ptrace;
         use nix::unistd::Pid;
@@ -0,0 +1,121 @@
+//! Memory region dumps
+//!
+//! Carves a range of process memory out to a file, typically an image that
+//! unpacked itself at runtime. The range is read a page at a time so that
+//! one unreadable page (a guard page, a hole between allocations) does not
+//! fail the whole dump: unreadable bytes are written as zeros, keeping file
+//! offsets equal to address offsets, and reported as gaps.
+
+use super::memory::MemoryManager;
+
+/// Read granularity; chunks after the first start on a page boundary
+pub const DUMP_CHUNK: usize = 0x1000;
+
+/// Largest range a single dump reads
+pub const MAX_DUMP: usize = 256 * 1024 * 1024;
+
+/// What a dump could and could not read
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DumpReport {
+    pub address: u64,
+    pub size: usize,
+    /// Bytes actually read from the process
+    pub bytes_read: usize,
+    /// Unreadable ranges as (address, length), zero-filled in the dump
+    pub gaps: Vec<(u64, usize)>,
+}
+
+impl DumpReport {
+    /// One line per unreadable range
+    pub fn gap_lines(&self) -> Vec<String> {
+        self.gaps.iter()
+            .map(|&(start, len)| format!(
+                "Unreadable {:#x}-{:#x} ({:#x} bytes, zero-filled)",
+                start, start + len as u64, len
+            ))
+            .collect()
+    }
+}
+
+/// Read `size` bytes at `address` chunk by chunk through `read`, which
+/// fills the buffer and returns how many bytes it got (None when the read
+/// failed outright).
+pub fn read_range<F>(address: u64, size: usize, mut read: F) -> (Vec<u8>, DumpReport)
+where
+    F: FnMut(u64, &mut [u8]) -> Option<usize>,
+{
+    let mut data = vec![0u8; size];
+    let mut report = DumpReport { address, size, ..Default::default() };
+
+    let mut offset = 0usize;
+    while offset < size {
+        let chunk_address = address.wrapping_add(offset as u64);
+        let to_boundary = DUMP_CHUNK - (chunk_address % DUMP_CHUNK as u64) as usize;
+        let len = to_boundary.min(size - offset);
+
+        let got = read(chunk_address, &mut data[offset..offset + len]).unwrap_or(0).min(len);
+        report.bytes_read += got;
+        if got < len {
+            data[offset + got..offset + len].fill(0);
+            let gap_start = chunk_address + got as u64;
+            match report.gaps.last_mut() {
+                Some((start, gap_len)) if *start + *gap_len as u64 == gap_start => *gap_len += len - got,
+                _ => report.gaps.push((gap_start, len - got)),
+            }
+        }
+        offset += len;
+    }
+    (data, report)
+}
+
+/// Read `size` bytes of the attached process at `address`
+pub fn dump(memory: &MemoryManager, address: u64, size: usize) -> (Vec<u8>, DumpReport) {
+    read_range(address, size, |addr, buffer| memory.read_into(addr, buffer).ok())
+}
+
+/// Parse a dump size: `0x` hex or decimal
+pub fn parse_size(text: &str) -> Option<usize> {
+    let text = text.trim();
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_range_gaps() {
+        // Mapped 0x1000-0x4000 with 0x2000 a guard page; 0x3000 reads short
+        let read = |addr: u64, buffer: &mut [u8]| -> Option<usize> {
+            match addr {
+                0x2000..=0x2fff => None,
+                0x3000..=0x3fff => {
+                    let n = buffer.len().min(0x10);
+                    buffer[..n].fill(0xcc);
+                    Some(n)
+                }
+                _ => {
+                    buffer.fill(0x90);
+                    Some(buffer.len())
+                }
+            }
+        };
+
+        let (data, report) = read_range(0x1800, 0x2000, read);
+        assert_eq!(data.len(), 0x2000);
+        assert_eq!(report.bytes_read, 0x810);
+        assert_eq!(report.gaps, vec![(0x2000, 0x1000), (0x3010, 0x7f0)]);
+        assert_eq!(data[0x7ff], 0x90);
+        assert_eq!(data[0x800], 0);
+        assert_eq!(data[0x1800], 0xcc);
+        assert_eq!(data[0x1810], 0);
+
+        let (_, report) = read_range(0x2800, 0x1000, |_, _| None);
+        assert_eq!(report.gaps, vec![(0x2800, 0x1000)]);
+        assert_eq!(parse_size("0x1000"), Some(0x1000));
+        assert_eq!(parse_size("4096"), Some(4096));
+    }
+}
@@ -0,0 +1,276 @@
+//! Breakpoint condition expressions
+//!
+//! A small evaluator for conditions such as `rax == 0x10 && [rsp+8] != 0`.
+//!
+//! Supported syntax:
+//! - Literals: hex (`0x1f`) and decimal (`31`)
+//! - Registers: 64-bit names (`rax` .. `r15`, `rip`, `rflags`)
+//! - Memory dereference: `[expr]` reads a little-endian pointer-sized value
+//!   (u64, or u32 on 32-bit targets); `byte[expr]`, `word[expr]`,
+//!   `dword[expr]` and `qword[expr]` read 1, 2, 4 and 8 bytes. Reads are
+//!   skipped on the short-circuited side of `&&` / `||`
+//! - Operators (lowest to highest precedence):
+//!   `||`, `&&`, `== != < <= > >=`, `|`, `&`, `+ -`, `*`, unary `- !`
+
+use super::types::RegisterState;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(u64),
+    Ident(String),
+    Op(&'static str),
+}
+
+const OPERATORS: &[&str] = &[
+    "||", "&&", "==", "!=", "<=", ">=", "<", ">", "|", "&", "+", "-", "*", "!", "(", ")", "[", "]",
+];
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = input.trim_start();
+
+    while !rest.is_empty() {
+        let c = rest.chars().next().unwrap();
+        if c.is_ascii_digit() {
+            let end = rest.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(rest.len());
+            let literal = &rest[..end];
+            let value = match literal.strip_prefix("0x").or_else(|| literal.strip_prefix("0X")) {
+                Some(hex) => u64::from_str_radix(hex, 16),
+                None => literal.parse(),
+            }
+            .map_err(|_| format!("invalid number '{}'", literal))?;
+            tokens.push(Token::Number(value));
+            rest = &rest[end..];
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let end = rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(rest.len());
+            tokens.push(Token::Ident(rest[..end].to_ascii_lowercase()));
+            rest = &rest[end..];
+        } else if let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(**op)) {
+            tokens.push(Token::Op(op));
+            rest = &rest[op.len()..];
+        } else {
+            return Err(format!("unexpected character '{}'", c));
+        }
+        rest = rest.trim_start();
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent evaluator over the token stream
+struct Evaluator<'a, F> {
+    tokens: Vec<Token>,
+    pos: usize,
+    regs: &'a RegisterState,
+    /// Size of a plain `[expr]` read in bytes
+    pointer_size: usize,
+    read: F,
+    /// Nesting depth of short-circuited operands (memory is not read there)
+    skip: u32,
+}
+
+/// Read size named by a `byte[..]` style prefix
+fn deref_size(name: &str) -> Option<usize> {
+    match name {
+        "byte" => Some(1),
+        "word" => Some(2),
+        "dword" => Some(4),
+        "qword" => Some(8),
+        _ => None,
+    }
+}
+
+impl<F: Fn(u64, usize) -> Option<u64>> Evaluator<'_, F> {
+    fn peek_op(&self) -> Option<&'static str> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) => Some(op),
+            _ => None,
+        }
+    }
+
+    /// Consume the next token if it is one of `ops`
+    fn eat(&mut self, ops: &[&str]) -> Option<&'static str> {
+        let op = self.peek_op().filter(|op| ops.contains(op))?;
+        self.pos += 1;
+        Some(op)
+    }
+
+    fn expect(&mut self, op: &str) -> Result<(), String> {
+        self.eat(&[op]).map(|_| ()).ok_or_else(|| format!("expected '{}'", op))
+    }
+
+    fn logical_or(&mut self) -> Result<u64, String> {
+        let mut lhs = self.logical_and()?;
+        while self.eat(&["||"]).is_some() {
+            let short = lhs != 0;
+            self.skip += short as u32;
+            let rhs = self.logical_and();
+            self.skip -= short as u32;
+            lhs = (short || rhs? != 0) as u64;
+        }
+        Ok(lhs)
+    }
+
+    fn logical_and(&mut self) -> Result<u64, String> {
+        let mut lhs = self.comparison()?;
+        while self.eat(&["&&"]).is_some() {
+            let short = lhs == 0;
+            self.skip += short as u32;
+            let rhs = self.comparison();
+            self.skip -= short as u32;
+            lhs = (!short && rhs? != 0) as u64;
+        }
+        Ok(lhs)
+    }
+
+    fn comparison(&mut self) -> Result<u64, String> {
+        let mut lhs = self.bit_or()?;
+        while let Some(op) = self.eat(&["==", "!=", "<=", ">=", "<", ">"]) {
+            let rhs = self.bit_or()?;
+            lhs = match op {
+                "==" => lhs == rhs,
+                "!=" => lhs != rhs,
+                "<=" => lhs <= rhs,
+                ">=" => lhs >= rhs,
+                "<" => lhs < rhs,
+                _ => lhs > rhs,
+            } as u64;
+        }
+        Ok(lhs)
+    }
+
+    fn bit_or(&mut self) -> Result<u64, String> {
+        let mut lhs = self.bit_and()?;
+        while self.eat(&["|"]).is_some() {
+            lhs |= self.bit_and()?;
+        }
+        Ok(lhs)
+    }
+
+    fn bit_and(&mut self) -> Result<u64, String> {
+        let mut lhs = self.additive()?;
+        while self.eat(&["&"]).is_some() {
+            lhs &= self.additive()?;
+        }
+        Ok(lhs)
+    }
+
+    fn additive(&mut self) -> Result<u64, String> {
+        let mut lhs = self.multiplicative()?;
+        while let Some(op) = self.eat(&["+", "-"]) {
+            let rhs = self.multiplicative()?;
+            lhs = if op == "+" { lhs.wrapping_add(rhs) } else { lhs.wrapping_sub(rhs) };
+        }
+        Ok(lhs)
+    }
+
+    fn multiplicative(&mut self) -> Result<u64, String> {
+        let mut lhs = self.unary()?;
+        while self.eat(&["*"]).is_some() {
+            lhs = lhs.wrapping_mul(self.unary()?);
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<u64, String> {
+        match self.eat(&["-", "!"]) {
+            Some("-") => Ok(self.unary()?.wrapping_neg()),
+            Some(_) => Ok((self.unary()? == 0) as u64),
+            None => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<u64, String> {
+        let token = self.tokens.get(self.pos).cloned().ok_or("unexpected end of expression")?;
+        self.pos += 1;
+        match token {
+            Token::Number(value) => Ok(value),
+            Token::Ident(name) => match deref_size(&name) {
+                Some(size) if self.eat(&["["]).is_some() => self.deref(size),
+                _ => self.regs.get(&name).ok_or_else(|| format!("unknown register '{}'", name)),
+            },
+            Token::Op("(") => {
+                let value = self.logical_or()?;
+                self.expect(")")?;
+                Ok(value)
+            }
+            Token::Op("[") => self.deref(self.pointer_size),
+            Token::Op(op) => Err(format!("unexpected '{}'", op)),
+        }
+    }
+
+    /// Read `size` bytes at the address inside `[...]`, after the opening bracket
+    fn deref(&mut self, size: usize) -> Result<u64, String> {
+        let address = self.logical_or()?;
+        self.expect("]")?;
+        if self.skip > 0 {
+            return Ok(0);
+        }
+        (self.read)(address, size).ok_or_else(|| format!("cannot read memory at 0x{:x}", address))
+    }
+}
+
+/// Evaluate an expression against register state and process memory.
+///
+/// `read(address, size)` returns the little-endian value of `size` bytes at
+/// `address`; `pointer_size` is the width of a plain `[expr]` read.
+/// Comparisons and logical operators yield 1 or 0; a condition holds when the
+/// result is non-zero.
+pub fn evaluate<F>(expr: &str, regs: &RegisterState, pointer_size: usize, read: F) -> Result<u64, String>
+where
+    F: Fn(u64, usize) -> Option<u64>,
+{
+    let tokens = tokenize(expr)?;
+    if tokens.is_empty() {
+        return Err("empty expression".into());
+    }
+
+    let mut eval = Evaluator { tokens, pos: 0, regs, pointer_size, read, skip: 0 };
+    let value = eval.logical_or()?;
+    if eval.pos < eval.tokens.len() {
+        return Err(format!("unexpected trailing input in '{}'", expr.trim()));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_conditions() {
+        let regs = RegisterState { rax: 0x10, rcx: 3, rsp: 0x1000, ..Default::default() };
+        // Only 0x1008 is readable; reads are truncated to the requested size
+        let read = |addr: u64, size: usize| {
+            (addr == 0x1008).then(|| 0x1234_5678_0000_dead & (u64::MAX >> (64 - 8 * size)))
+        };
+
+        assert_eq!(evaluate("rax == 0x10", &regs, 8, read), Ok(1));
+        assert_eq!(evaluate("RAX + rcx * 2", &regs, 8, read), Ok(0x16));
+        assert_eq!(evaluate("[rsp+8] == 0x123456780000dead && rcx < 4", &regs, 8, read), Ok(1));
+        assert_eq!(evaluate("!(rax > 0x20) || [rsp] == 0", &regs, 8, read), Ok(1));
+        assert_eq!(evaluate("rcx - 4 == -1", &regs, 8, read), Ok(1));
+        assert_eq!(evaluate("rbx != 0 && [rbx] == 1", &regs, 8, read), Ok(0));
+
+        assert!(evaluate("[rsp]", &regs, 8, read).is_err());
+        assert!(evaluate("foo == 1", &regs, 8, read).is_err());
+        assert!(evaluate("rax ==", &regs, 8, read).is_err());
+        assert!(evaluate("(rax", &regs, 8, read).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_deref_width() {
+        let regs = RegisterState { rsp: 0x1000, ..Default::default() };
+        let read = |addr: u64, size: usize| {
+            (addr == 0x1008).then(|| 0x1234_5678_0000_dead & (u64::MAX >> (64 - 8 * size)))
+        };
+
+        // A plain dereference reads a pointer of the target's width
+        assert_eq!(evaluate("[rsp+8] == 0xdead", &regs, 4, read), Ok(1));
+        assert_eq!(evaluate("qword[rsp+8] == 0x123456780000dead", &regs, 4, read), Ok(1));
+        assert_eq!(evaluate("dword[rsp+8] == 0xdead", &regs, 8, read), Ok(1));
+        assert_eq!(evaluate("WORD [rsp+8] == 0xdead", &regs, 8, read), Ok(1));
+        assert_eq!(evaluate("byte[rsp+8] == 0xad", &regs, 8, read), Ok(1));
+        assert!(evaluate("dword == 1", &regs, 8, read).is_err());
+    }
+}
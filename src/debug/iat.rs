@@ -0,0 +1,105 @@
+//! Import address table resolution
+//!
+//! Reads the IAT slots of the attached process and classifies where each
+//! resolved pointer lands. A slot pointing into a module other than the one
+//! the import names is often just a forwarder (`kernel32` → `ntdll`, API
+//! sets); one pointing outside any mapped module is a classic IAT hook.
+
+use std::collections::HashMap;
+
+use super::memory::MemoryRegion;
+use crate::analysis::loader::IatSlot;
+
+/// Where a resolved IAT entry points
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IatTarget {
+    /// Inside the module the import names
+    Expected,
+    /// Inside another module (a forwarder, or a hook placed in a DLL)
+    OtherModule(String),
+    /// Outside every mapped module (heap, private allocation, or unmapped)
+    Unmapped,
+}
+
+/// The runtime value of one IAT slot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedImport {
+    pub address: u64,
+    pub target: IatTarget,
+}
+
+impl ResolvedImport {
+    /// Whether the slot looks hooked rather than bound by the loader
+    pub fn is_suspicious(&self) -> bool {
+        self.target == IatTarget::Unmapped
+    }
+}
+
+/// File name of a region's module, without the directory
+fn module_file(region: &MemoryRegion) -> Option<&str> {
+    let name = region.name.as_deref()?;
+    if name.starts_with('[') {
+        // Linux pseudo-mappings such as [heap] and [stack]
+        return None;
+    }
+    name.rsplit(['/', '\\']).next()
+}
+
+/// Resolve each slot through `read_pointer`, keyed by slot address.
+///
+/// Slots that cannot be read are left out.
+pub fn resolve<F>(slots: &[IatSlot], regions: &[MemoryRegion], read_pointer: F) -> HashMap<u64, ResolvedImport>
+where
+    F: Fn(u64) -> Option<u64>,
+{
+    slots.iter()
+        .filter_map(|slot| {
+            let address = read_pointer(slot.address)?;
+            let module = regions.iter()
+                .find(|r| address >= r.base_address && address - r.base_address < r.size as u64)
+                .and_then(module_file);
+            let target = match module {
+                Some(module) if module.eq_ignore_ascii_case(&slot.module) => IatTarget::Expected,
+                Some(module) => IatTarget::OtherModule(module.to_string()),
+                None => IatTarget::Unmapped,
+            };
+            Some((slot.address, ResolvedImport { address, target }))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debug::memory::MemoryProtection;
+
+    fn region(base_address: u64, size: usize, name: Option<&str>) -> MemoryRegion {
+        MemoryRegion { base_address, size, protection: MemoryProtection::RX, name: name.map(String::from) }
+    }
+
+    fn slot(address: u64, module: &str) -> IatSlot {
+        IatSlot { address, module: module.to_string(), name: String::new() }
+    }
+
+    #[test]
+    fn test_resolve_iat() {
+        let regions = [
+            region(0x7ff0_0000, 0x1000, Some("kernel32.dll")),
+            region(0x7ff1_0000, 0x1000, Some("ntdll.dll")),
+            region(0x0050_0000, 0x1000, None),
+        ];
+        let memory: HashMap<u64, u64> = [
+            (0x1000, 0x7ff0_0010),
+            (0x1008, 0x7ff1_0020),
+            (0x1010, 0x0050_0000),
+        ].into_iter().collect();
+        let slots = [slot(0x1000, "KERNEL32.dll"), slot(0x1008, "KERNEL32.dll"), slot(0x1010, "KERNEL32.dll"), slot(0x1018, "USER32.dll")];
+
+        let resolved = resolve(&slots, &regions, |addr| memory.get(&addr).copied());
+        assert_eq!(resolved[&0x1000].target, IatTarget::Expected);
+        assert_eq!(resolved[&0x1008].target, IatTarget::OtherModule("ntdll.dll".to_string()));
+        assert_eq!(resolved[&0x1010].target, IatTarget::Unmapped);
+        assert!(resolved[&0x1010].is_suspicious());
+        assert!(!resolved.contains_key(&0x1018));
+    }
+}
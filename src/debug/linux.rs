@@ -0,0 +1,51 @@
+//! Linux process enumeration from `/proc`.
+
+use std::fs;
+use std::io::Read;
+
+use super::types::ProcessInfo;
+
+/// ELF class of an executable: true for ELFCLASS64
+fn elf_is_64bit(path: &str) -> Option<bool> {
+    let mut ident = [0u8; 5];
+    fs::File::open(path).ok()?.read_exact(&mut ident).ok()?;
+    (ident[..4] == *b"\x7fELF").then_some(ident[4] == 2)
+}
+
+/// Enumerate all running processes
+///
+/// Processes whose executable cannot be read (other users' processes,
+/// kernel threads) are assumed to match the host's pointer width.
+pub fn enumerate_processes() -> Vec<ProcessInfo> {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    let mut processes: Vec<ProcessInfo> = entries
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<u32>().ok())
+        .map(|pid| {
+            let name = fs::read_to_string(format!("/proc/{}/comm", pid))
+                .map(|comm| comm.trim_end().to_string())
+                .unwrap_or_else(|_| format!("<PID {}>", pid));
+            let is_64bit = elf_is_64bit(&format!("/proc/{}/exe", pid))
+                .unwrap_or(cfg!(target_pointer_width = "64"));
+            ProcessInfo { pid, name, is_64bit }
+        })
+        .collect();
+
+    // Sort by name
+    processes.sort_by_key(|p| p.name.to_lowercase());
+    processes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enumerate_finds_self() {
+        let pid = std::process::id();
+        let me = enumerate_processes().into_iter().find(|p| p.pid == pid).unwrap();
+        assert_eq!(me.is_64bit, cfg!(target_pointer_width = "64"));
+        assert!(!me.name.is_empty());
+    }
+}
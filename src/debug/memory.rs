@@ -55,6 +55,19 @@ impl MemoryProtection {
     };
 }
 
+impl std::fmt::Display for MemoryProtection {
+    /// Format as an `rwx`-style permission string (e.g. `r-x`)
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            if self.read { 'r' } else { '-' },
+            if self.write { 'w' } else { '-' },
+            if self.execute { 'x' } else { '-' }
+        )
+    }
+}
+
 /// Represents a memory region in the target process
 #[derive(Debug, Clone)]
 pub struct MemoryRegion {
@@ -153,7 +166,16 @@ impl MemoryManager {
 
     /// Get memory regions of the target process
     pub fn query_regions(&mut self) -> Result<&[MemoryRegion], MemoryError> {
-        // TODO: Implement platform-specific memory region enumeration
+        #[cfg(target_os = "windows")]
+        {
+            self.regions = self.query_regions_windows()?;
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            self.regions = self.query_regions_linux()?;
+        }
+
         Ok(&self.regions)
     }
 
@@ -224,7 +246,7 @@ impl MemoryManager {
 
     fn write_windows(&self, address: u64, data: &[u8]) -> Result<usize, MemoryError> {
         use windows::Win32::Foundation::HANDLE;
-        use windows::Win32::System::Diagnostics::Debug::WriteProcessMemory;
+        use windows::Win32::System::Diagnostics::Debug::{FlushInstructionCache, WriteProcessMemory};
 
         let handle_val = self.process_handle.ok_or(MemoryError::NoProcess)?;
         // SAFETY: HANDLE is repr(transparent) wrapper around isize
@@ -243,10 +265,76 @@ impl MemoryManager {
                 address,
                 reason: e.to_string(),
             })?;
+            // The bytes may be code (patches, int3); drop stale prefetched copies
+            let _ = FlushInstructionCache(handle, Some(address as *const std::ffi::c_void), bytes_written);
         }
 
         Ok(bytes_written)
     }
+
+    fn query_regions_windows(&self) -> Result<Vec<MemoryRegion>, MemoryError> {
+        use windows::Win32::Foundation::HANDLE;
+        use windows::Win32::System::Memory::{VirtualQueryEx, MEMORY_BASIC_INFORMATION, MEM_COMMIT};
+        use windows::Win32::System::ProcessStatus::GetMappedFileNameW;
+
+        let handle_val = self.process_handle.ok_or(MemoryError::NoProcess)?;
+        // SAFETY: HANDLE is repr(transparent) wrapper around isize
+        let handle: HANDLE = unsafe { std::mem::transmute(handle_val) };
+
+        let mut regions = Vec::new();
+        let mut address: u64 = 0;
+
+        loop {
+            let mut info = MEMORY_BASIC_INFORMATION::default();
+            let written = unsafe {
+                VirtualQueryEx(
+                    handle,
+                    Some(address as *const std::ffi::c_void),
+                    &mut info,
+                    std::mem::size_of::<MEMORY_BASIC_INFORMATION>(),
+                )
+            };
+            if written == 0 || info.RegionSize == 0 {
+                break;
+            }
+
+            let base = info.BaseAddress as u64;
+            if info.State == MEM_COMMIT {
+                // Low byte holds the PAGE_* access value; modifiers (guard, nocache) live above it
+                let protect = info.Protect.0 & 0xFF;
+                let protection = MemoryProtection {
+                    read: protect & 0xEE != 0,
+                    write: protect & 0xCC != 0,
+                    execute: protect & 0xF0 != 0,
+                };
+
+                let mut name_buf = [0u16; 260];
+                let len = unsafe {
+                    GetMappedFileNameW(handle, base as *const std::ffi::c_void, &mut name_buf)
+                };
+                let name = if len > 0 {
+                    let full = String::from_utf16_lossy(&name_buf[..len as usize]);
+                    full.rsplit('\\').next().map(|s| s.to_string())
+                } else {
+                    None
+                };
+
+                regions.push(MemoryRegion {
+                    base_address: base,
+                    size: info.RegionSize,
+                    protection,
+                    name,
+                });
+            }
+
+            address = match base.checked_add(info.RegionSize as u64) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+
+        Ok(regions)
+    }
 }
 
 // Linux-specific implementations
@@ -306,6 +394,63 @@ impl MemoryManager {
 
         Ok(bytes_written)
     }
+
+    fn query_regions_linux(&self) -> Result<Vec<MemoryRegion>, MemoryError> {
+        let pid = self.target_pid.ok_or(MemoryError::NoProcess)?;
+        let maps_path = format!("/proc/{}/maps", pid);
+
+        let maps = std::fs::read_to_string(&maps_path).map_err(|e| MemoryError::ReadFailed {
+            address: 0,
+            reason: e.to_string(),
+        })?;
+
+        Ok(maps.lines().filter_map(parse_maps_line).collect())
+    }
+}
+
+/// Parse a single `/proc/<pid>/maps` line:
+/// `start-end perms offset dev inode [pathname]`
+#[cfg(target_os = "linux")]
+fn parse_maps_line(line: &str) -> Option<MemoryRegion> {
+    let mut fields = line.split_whitespace();
+    let range = fields.next()?;
+    let perms = fields.next()?.as_bytes();
+    let (start, end) = range.split_once('-')?;
+    let start = u64::from_str_radix(start, 16).ok()?;
+    let end = u64::from_str_radix(end, 16).ok()?;
+
+    // Skip offset, dev and inode; whatever remains is the pathname
+    let name = fields.nth(3).map(|first| {
+        let rest: Vec<&str> = fields.collect();
+        if rest.is_empty() {
+            first.to_string()
+        } else {
+            format!("{} {}", first, rest.join(" "))
+        }
+    });
+
+    Some(MemoryRegion {
+        base_address: start,
+        size: end.saturating_sub(start) as usize,
+        protection: MemoryProtection {
+            read: perms.first() == Some(&b'r'),
+            write: perms.get(1) == Some(&b'w'),
+            execute: perms.get(2) == Some(&b'x'),
+        },
+        name,
+    })
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for MemoryManager {
+    fn drop(&mut self) {
+        use windows::Win32::Foundation::{CloseHandle, HANDLE};
+
+        if let Some(handle_val) = self.process_handle.take() {
+            // SAFETY: the handle was opened by `open_process` and is owned here
+            let _ = unsafe { CloseHandle(HANDLE(handle_val)) };
+        }
+    }
 }
 
 impl Default for MemoryManager {
@@ -4,13 +4,24 @@
 //! - Process attach/detach
 //! - Breakpoint management
 //! - Register/memory access
+//! - Memory region dumps
+//! - Runtime IAT resolution
+//! - Vector (SSE/AVX) and x87 register views
 //! - Step execution
 
 pub mod types;
+pub mod dump;
+pub mod expr;
+pub mod iat;
+pub mod unwind;
+pub mod vector;
 
 #[cfg(target_os = "windows")]
 pub mod windows;
 
+#[cfg(target_os = "linux")]
+pub mod linux;
+
 // Legacy modules (to be refactored)
 pub mod debugger;
 pub mod memory;
@@ -21,7 +32,10 @@ pub use windows::WindowsDebugger as PlatformDebugger;
 #[cfg(target_os = "windows")]
 pub use windows::enumerate_processes;
 
-use types::ProcessInfo;
+#[cfg(target_os = "linux")]
+pub use linux::enumerate_processes;
+
+use types::{ProcessInfo, StackFrame};
 
 /// Platform-agnostic debugger trait
 pub trait Debugger {
@@ -46,9 +60,18 @@ pub trait Debugger {
     /// Single step (best-effort)
     fn single_step(&mut self) -> Result<(), String>;
 
+    /// Suspend the running process; the stop arrives as a debug event
+    fn break_into(&mut self) -> Result<(), String>;
+
     /// Set a software breakpoint
     fn set_sw_breakpoint(&mut self, address: u64) -> Result<(), String>;
 
     /// Remove a software breakpoint
     fn remove_sw_breakpoint(&mut self, address: u64) -> Result<(), String>;
+
+    /// Unwind the call stack of the last event thread (innermost frame first)
+    fn call_stack(&self) -> Result<Vec<StackFrame>, String>;
+
+    /// Write a register of the last event thread, by its lowercase 64-bit name
+    fn write_register(&mut self, name: &str, value: u64) -> Result<(), String>;
 }
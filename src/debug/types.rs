@@ -9,13 +9,15 @@ pub struct ProcessInfo {
     pub pid: u32,
     /// Process name (executable name)
     pub name: String,
+    /// 64-bit process (false for 32-bit and WOW64 processes)
+    pub is_64bit: bool,
 }
 
 /// Debug event received from the debugger
 #[derive(Debug, Clone)]
 pub enum DebugEvent {
-    /// Process created/attached
-    ProcessCreated { pid: u32, main_thread_id: u32 },
+    /// Process created/attached; `image_base` is where the main module was loaded
+    ProcessCreated { pid: u32, main_thread_id: u32, image_base: u64 },
     /// Process exited
     ProcessExited { exit_code: u32 },
     /// Thread created
@@ -52,6 +54,21 @@ pub struct Breakpoint {
     pub original_byte: u8,
     /// Is this breakpoint enabled?
     pub enabled: bool,
+    /// Break only when this expression is non-zero (see `debug::expr`)
+    pub condition: Option<String>,
+    /// Number of times this breakpoint has stopped the process
+    pub hit_count: u64,
+    /// Remove after the first hit
+    pub temporary: bool,
+}
+
+/// One frame of an unwound call stack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StackFrame {
+    /// Instruction pointer (current RIP for frame 0, return address otherwise)
+    pub address: u64,
+    /// Frame pointer (RBP) of this frame
+    pub frame_pointer: u64,
 }
 
 /// CPU register state (x64)
@@ -77,6 +94,76 @@ pub struct RegisterState {
     pub rflags: u64,
 }
 
+impl RegisterState {
+    /// Look up a register by its lowercase 64-bit name
+    pub fn get(&self, name: &str) -> Option<u64> {
+        Some(match name {
+            "rax" => self.rax,
+            "rbx" => self.rbx,
+            "rcx" => self.rcx,
+            "rdx" => self.rdx,
+            "rsi" => self.rsi,
+            "rdi" => self.rdi,
+            "rbp" => self.rbp,
+            "rsp" => self.rsp,
+            "r8" => self.r8,
+            "r9" => self.r9,
+            "r10" => self.r10,
+            "r11" => self.r11,
+            "r12" => self.r12,
+            "r13" => self.r13,
+            "r14" => self.r14,
+            "r15" => self.r15,
+            "rip" => self.rip,
+            "rflags" => self.rflags,
+            _ => return None,
+        })
+    }
+
+    /// Set a register by its lowercase 64-bit name; false for an unknown name
+    pub fn set(&mut self, name: &str, value: u64) -> bool {
+        let slot = match name {
+            "rax" => &mut self.rax,
+            "rbx" => &mut self.rbx,
+            "rcx" => &mut self.rcx,
+            "rdx" => &mut self.rdx,
+            "rsi" => &mut self.rsi,
+            "rdi" => &mut self.rdi,
+            "rbp" => &mut self.rbp,
+            "rsp" => &mut self.rsp,
+            "r8" => &mut self.r8,
+            "r9" => &mut self.r9,
+            "r10" => &mut self.r10,
+            "r11" => &mut self.r11,
+            "r12" => &mut self.r12,
+            "r13" => &mut self.r13,
+            "r14" => &mut self.r14,
+            "r15" => &mut self.r15,
+            "rip" => &mut self.rip,
+            "rflags" => &mut self.rflags,
+            _ => return false,
+        };
+        *slot = value;
+        true
+    }
+}
+
+/// SSE/AVX and x87 register state (x64)
+#[derive(Debug, Clone, Default)]
+pub struct VectorRegisters {
+    /// XMM0-XMM15, lane 0 in the low bits
+    pub xmm: [u128; 16],
+    /// Upper halves of YMM0-YMM15, when the OS exposes the AVX state
+    pub ymm_high: Option<[u128; 16]>,
+    pub mxcsr: u32,
+    /// x87 ST0-ST7 as raw 80-bit extended values
+    pub st: [[u8; 10]; 8],
+    pub fpu_control: u16,
+    pub fpu_status: u16,
+    /// Abridged tag word (one bit per register, set when in use)
+    pub fpu_tag: u8,
+}
+
 /// Debug state for GUI
 #[derive(Debug, Clone, Default)]
 pub struct DebugState {
@@ -84,14 +171,24 @@ pub struct DebugState {
     pub attached_pid: Option<u32>,
     /// Main thread ID
     pub main_thread_id: Option<u32>,
-    /// Last event thread ID
+    /// Last event thread ID; in the GUI, the thread whose context is shown
     pub last_thread_id: Option<u32>,
+    /// Live thread IDs, in creation order
+    pub threads: Vec<u32>,
     /// Current debug status
     pub status: DebugStatus,
+    /// Runtime load address of the main module, once the process reported it
+    pub image_base: Option<u64>,
+    /// Whether the attached process is 64-bit, when known (false for WOW64)
+    pub is_64bit: Option<bool>,
     /// Active breakpoints
     pub breakpoints: HashMap<u64, Breakpoint>,
     /// Current register state
     pub registers: Option<RegisterState>,
+    /// Vector and x87 registers of the same thread, when they could be read
+    pub vector_registers: Option<VectorRegisters>,
+    /// Call stack captured at the last suspend
+    pub call_stack: Vec<StackFrame>,
     /// Last event
     pub last_event: Option<String>,
 }
@@ -0,0 +1,77 @@
+//! Stack unwinding
+//!
+//! Frame-pointer based call stack reconstruction.
+
+use super::types::StackFrame;
+
+/// Maximum number of frames walked before giving up
+pub const MAX_FRAMES: usize = 64;
+
+/// Walk the RBP chain starting at the current frame.
+///
+/// Each frame is laid out as `[rbp] = saved rbp`, `[rbp + ptr] = return address`,
+/// where `pointer_size` is 8 for 64-bit targets and 4 for 32-bit (WOW64) ones.
+/// `read_pointer` reads one pointer-sized value. The walk stops on a
+/// null/misaligned frame pointer, a failed read, or a frame pointer that does
+/// not move up the stack.
+pub fn unwind_frame_chain<F>(rip: u64, rbp: u64, pointer_size: u64, read_pointer: F) -> Vec<StackFrame>
+where
+    F: Fn(u64) -> Option<u64>,
+{
+    let mut frames = vec![StackFrame { address: rip, frame_pointer: rbp }];
+    let mut fp = rbp;
+
+    while frames.len() < MAX_FRAMES && fp != 0 && fp & (pointer_size - 1) == 0 {
+        let (Some(saved_fp), Some(ret)) =
+            (read_pointer(fp), read_pointer(fp.wrapping_add(pointer_size)))
+        else {
+            break;
+        };
+        if ret == 0 {
+            break;
+        }
+        frames.push(StackFrame { address: ret, frame_pointer: saved_fp });
+
+        // The stack grows down, so caller frames live at higher addresses
+        if saved_fp <= fp {
+            break;
+        }
+        fp = saved_fp;
+    }
+
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_unwind_frame_chain() {
+        // Three frames: 0x1000 -> 0x1040 -> 0x1080 (terminated by null rbp)
+        let stack: HashMap<u64, u64> = [
+            (0x1000, 0x1040), (0x1008, 0x401234),
+            (0x1040, 0x1080), (0x1048, 0x402000),
+            (0x1080, 0), (0x1088, 0x403000),
+        ].into_iter().collect();
+
+        let frames = unwind_frame_chain(0x400100, 0x1000, 8, |addr| stack.get(&addr).copied());
+        let addrs: Vec<u64> = frames.iter().map(|f| f.address).collect();
+        assert_eq!(addrs, vec![0x400100, 0x401234, 0x402000, 0x403000]);
+
+        // A frame pointer that moves down the stack ends the walk
+        let looped: HashMap<u64, u64> = [(0x1000, 0x1000), (0x1008, 0x401234)].into_iter().collect();
+        let frames = unwind_frame_chain(0x400100, 0x1000, 8, |addr| looped.get(&addr).copied());
+        assert_eq!(frames.len(), 2);
+
+        // 32-bit frames keep the return address 4 bytes above the saved ebp
+        let stack32: HashMap<u64, u64> = [
+            (0x1004, 0x1024), (0x1008, 0x401234),
+            (0x1024, 0), (0x1028, 0x402000),
+        ].into_iter().collect();
+        let frames = unwind_frame_chain(0x400100, 0x1004, 4, |addr| stack32.get(&addr).copied());
+        let addrs: Vec<u64> = frames.iter().map(|f| f.address).collect();
+        assert_eq!(addrs, vec![0x400100, 0x401234, 0x402000]);
+    }
+}
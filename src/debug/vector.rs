@@ -0,0 +1,94 @@
+//! Vector register formatting
+//!
+//! Renders XMM/YMM values as bytes or as packed integer and float lanes,
+//! and converts x87 80-bit extended values for display.
+
+/// How the Registers column interprets a vector register
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VectorFormat {
+    /// Hex bytes in memory order
+    #[default]
+    Bytes,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+}
+
+impl VectorFormat {
+    pub const ALL: [VectorFormat; 5] = [Self::Bytes, Self::Int32, Self::Int64, Self::Float32, Self::Float64];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Bytes => "bytes",
+            Self::Int32 => "i32",
+            Self::Int64 => "i64",
+            Self::Float32 => "f32",
+            Self::Float64 => "f64",
+        }
+    }
+}
+
+/// A 128-bit value as space-separated lanes, lane 0 first
+pub fn format_lanes(value: u128, format: VectorFormat) -> String {
+    let bytes = value.to_le_bytes();
+    let lanes: Vec<String> = match format {
+        VectorFormat::Bytes => bytes.iter().map(|b| format!("{:02X}", b)).collect(),
+        VectorFormat::Int32 => bytes.chunks_exact(4)
+            .map(|c| i32::from_le_bytes(c.try_into().unwrap()).to_string())
+            .collect(),
+        VectorFormat::Int64 => bytes.chunks_exact(8)
+            .map(|c| i64::from_le_bytes(c.try_into().unwrap()).to_string())
+            .collect(),
+        VectorFormat::Float32 => bytes.chunks_exact(4)
+            .map(|c| f32::from_le_bytes(c.try_into().unwrap()).to_string())
+            .collect(),
+        VectorFormat::Float64 => bytes.chunks_exact(8)
+            .map(|c| f64::from_le_bytes(c.try_into().unwrap()).to_string())
+            .collect(),
+    };
+    lanes.join(" ")
+}
+
+/// Value of an x87 80-bit extended float (64-bit mantissa with an explicit
+/// integer bit, 15-bit exponent, sign), rounded to f64
+pub fn x87_to_f64(raw: &[u8; 10]) -> f64 {
+    let mantissa = u64::from_le_bytes(raw[..8].try_into().unwrap());
+    let sign_exp = u16::from_le_bytes([raw[8], raw[9]]);
+    let sign = if sign_exp & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from(sign_exp & 0x7fff);
+
+    if exponent == 0x7fff {
+        return if mantissa << 1 == 0 { sign * f64::INFINITY } else { f64::NAN };
+    }
+    // Denormals use the minimum exponent, without the implicit bias step
+    let exponent = if exponent == 0 { 1 - 16383 } else { exponent - 16383 };
+    sign * (mantissa as f64 / (1u64 << 63) as f64) * 2f64.powi(exponent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_lanes_and_x87() {
+        let value = u128::from_le_bytes([
+            0x00, 0x00, 0x80, 0x3f, // 1.0f
+            0xff, 0xff, 0xff, 0xff, // -1
+            0, 0, 0, 0, 0, 0, 0xf0, 0x3f, // 1.0
+        ]);
+        assert_eq!(format_lanes(value, VectorFormat::Float32).split(' ').next(), Some("1"));
+        assert_eq!(format_lanes(value, VectorFormat::Int32), "1065353216 -1 0 1072693248");
+        assert_eq!(format_lanes(value, VectorFormat::Float64).split(' ').nth(1), Some("1"));
+        assert!(format_lanes(value, VectorFormat::Bytes).starts_with("00 00 80 3F FF"));
+
+        // 1.0, -2.5 and +infinity in 80-bit extended precision
+        let one = [0, 0, 0, 0, 0, 0, 0, 0x80, 0xff, 0x3f];
+        let minus_two_and_half = [0, 0, 0, 0, 0, 0, 0, 0xa0, 0x00, 0xc0];
+        let infinity = [0, 0, 0, 0, 0, 0, 0, 0x80, 0xff, 0x7f];
+        assert_eq!(x87_to_f64(&one), 1.0);
+        assert_eq!(x87_to_f64(&minus_two_and_half), -2.5);
+        assert_eq!(x87_to_f64(&infinity), f64::INFINITY);
+        assert_eq!(x87_to_f64(&[0; 10]), 0.0);
+    }
+}
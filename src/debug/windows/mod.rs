@@ -4,29 +4,57 @@ mod process;
 
 pub use process::enumerate_processes;
 
-use super::types::{DebugState, DebugStatus, ProcessInfo};
+use super::memory::MemoryManager;
+use super::types::{DebugState, DebugStatus, ProcessInfo, RegisterState, StackFrame, VectorRegisters};
 use super::Debugger;
 
+use std::collections::HashMap;
 use std::sync::mpsc::{Receiver, Sender};
 use std::thread;
 use std::time::Duration;
 
 use windows::Win32::System::Diagnostics::Debug::{
-    DebugActiveProcess, DebugActiveProcessStop, WaitForDebugEvent, ContinueDebugEvent,
+    DebugActiveProcess, DebugActiveProcessStop, DebugBreakProcess, WaitForDebugEvent, ContinueDebugEvent,
     DEBUG_EVENT, EXCEPTION_DEBUG_EVENT, CREATE_THREAD_DEBUG_EVENT,
     EXIT_THREAD_DEBUG_EVENT, CREATE_PROCESS_DEBUG_EVENT, EXIT_PROCESS_DEBUG_EVENT,
-    LOAD_DLL_DEBUG_EVENT,
+    LOAD_DLL_DEBUG_EVENT, GetThreadContext, SetThreadContext, CONTEXT, CONTEXT_FLAGS,
+    InitializeContext, LocateXStateFeature, SetXStateFeaturesMask,
+    Wow64GetThreadContext, Wow64SetThreadContext, WOW64_CONTEXT, WOW64_CONTEXT_FLAGS,
 };
-use windows::Win32::Foundation::NTSTATUS;
+use windows::Win32::System::Threading::{
+    OpenProcess, OpenThread, PROCESS_ALL_ACCESS, PROCESS_QUERY_LIMITED_INFORMATION, THREAD_GET_CONTEXT, THREAD_SET_CONTEXT,
+};
+use windows::Win32::Foundation::{CloseHandle, HANDLE, NTSTATUS};
 
 const DBG_CONTINUE: NTSTATUS = NTSTATUS(0x00010002i32);
 const EXCEPTION_BREAKPOINT_CODE: u32 = 0x80000003;
 const EXCEPTION_SINGLE_STEP_CODE: u32 = 0x80000004;
+/// `int3`, planted for software breakpoints
+const INT3: u8 = 0xCC;
+/// EFLAGS trap flag: trap after the next instruction
+const TRAP_FLAG: u64 = 0x100;
+/// CONTEXT_AMD64 | CONTEXT_CONTROL | CONTEXT_INTEGER
+const CONTEXT_CONTROL_INTEGER: CONTEXT_FLAGS = CONTEXT_FLAGS(0x0010_0003);
+/// CONTEXT_AMD64 | CONTEXT_FLOATING_POINT
+const CONTEXT_FLOATING_POINT: CONTEXT_FLAGS = CONTEXT_FLAGS(0x0010_0008);
+/// CONTEXT_AMD64 | CONTEXT_XSTATE
+const CONTEXT_XSTATE: CONTEXT_FLAGS = CONTEXT_FLAGS(0x0010_0040);
+/// WOW64_CONTEXT_i386 | CONTEXT_CONTROL | CONTEXT_INTEGER
+const WOW64_CONTEXT_CONTROL_INTEGER: WOW64_CONTEXT_FLAGS = WOW64_CONTEXT_FLAGS(0x0001_0003);
+/// WOW64_CONTEXT_i386 | CONTEXT_EXTENDED_REGISTERS (the FXSAVE area)
+const WOW64_CONTEXT_EXTENDED_REGISTERS: WOW64_CONTEXT_FLAGS = WOW64_CONTEXT_FLAGS(0x0001_0020);
+/// XSAVE feature holding the upper halves of the YMM registers
+const XSTATE_AVX: u32 = 2;
 
 /// Windows debugger implementation
 pub struct WindowsDebugger {
     /// Current debug state
     state: DebugState,
+    /// Memory access for the attached process
+    memory: MemoryManager,
+    /// Breakpoints whose int3 is put back once the thread (key) has stepped
+    /// over the original instruction
+    rearm: HashMap<u32, u64>,
 }
 
 impl WindowsDebugger {
@@ -34,6 +62,8 @@ impl WindowsDebugger {
     pub fn new() -> Self {
         Self {
             state: DebugState::default(),
+            memory: MemoryManager::new(),
+            rearm: HashMap::new(),
         }
     }
 
@@ -41,6 +71,355 @@ impl WindowsDebugger {
     pub fn state(&self) -> &DebugState {
         &self.state
     }
+
+    /// Memory access for the attached process
+    pub fn memory(&self) -> &MemoryManager {
+        &self.memory
+    }
+
+    /// Mutable memory access (region queries refresh a cache)
+    pub fn memory_mut(&mut self) -> &mut MemoryManager {
+        &mut self.memory
+    }
+
+    /// Record the thread that reported the latest debug event
+    pub fn set_event_thread(&mut self, thread_id: u32) {
+        self.state.last_thread_id = Some(thread_id);
+        self.state.main_thread_id.get_or_insert(thread_id);
+    }
+
+    /// Set or clear the condition of an existing breakpoint
+    pub fn set_breakpoint_condition(&mut self, address: u64, condition: Option<String>) -> Result<(), String> {
+        let bp = self.state.breakpoints.get_mut(&address)
+            .ok_or_else(|| format!("No breakpoint at 0x{:016x}", address))?;
+        bp.condition = condition;
+        Ok(())
+    }
+
+    /// Write `int3` over the instruction at `address`, returning the byte
+    /// it replaced
+    fn write_int3(&self, address: u64) -> Result<u8, String> {
+        let original_byte = self.memory.read_u8(address).map_err(|e| e.to_string())?;
+        if self.memory.write(address, &[INT3]).map_err(|e| e.to_string())? != 1 {
+            return Err(format!("Could not write int3 at 0x{:016x}", address));
+        }
+        Ok(original_byte)
+    }
+
+    /// Plant the int3 of breakpoint `address` unless it is already in place,
+    /// adding a breakpoint of kind `temporary` if there is none.
+    ///
+    /// An existing breakpoint keeps its kind, condition and hit count.
+    fn arm_breakpoint(&mut self, address: u64, temporary: bool) -> Result<(), String> {
+        if self.state.breakpoints.get(&address).is_some_and(|bp| bp.enabled) {
+            return Ok(());
+        }
+        let original_byte = self.write_int3(address)?;
+        let bp = self.state.breakpoints.entry(address).or_insert(super::types::Breakpoint {
+            address,
+            original_byte,
+            enabled: true,
+            condition: None,
+            hit_count: 0,
+            temporary,
+        });
+        bp.original_byte = original_byte;
+        bp.enabled = true;
+        Ok(())
+    }
+
+    /// Set a one-shot breakpoint at `address`.
+    ///
+    /// A breakpoint the user already has there stays as it is, so stepping
+    /// onto it does not remove it.
+    pub fn set_temporary_breakpoint(&mut self, address: u64) -> Result<(), String> {
+        self.arm_breakpoint(address, true)?;
+        self.state.last_event = Some(format!("Temporary breakpoint set 0x{:016x}", address));
+        Ok(())
+    }
+
+    /// Undo the `int3` that thread `tid` just hit: put the original byte back
+    /// and move RIP back onto the instruction. A permanent breakpoint is
+    /// re-armed by `rearm_after_step` once the thread has executed it.
+    ///
+    /// Returns whether the hit was one of ours (and not, say, a `DebugBreak`).
+    pub fn take_breakpoint_hit(&mut self, address: u64, tid: u32) -> Result<bool, String> {
+        let Some(bp) = self.state.breakpoints.get_mut(&address).filter(|bp| bp.enabled) else {
+            return Ok(false);
+        };
+        let (original_byte, temporary) = (bp.original_byte, bp.temporary);
+        // A temporary breakpoint is used up; a permanent one stays enabled
+        bp.enabled = !temporary;
+        self.memory.write(address, &[original_byte]).map_err(|e| e.to_string())?;
+        // The trap leaves RIP after the one-byte int3
+        self.write_thread_register(tid, "rip", address)?;
+        if !temporary {
+            let rflags = self.read_thread_registers(tid)?.rflags;
+            self.write_thread_register(tid, "rflags", rflags | TRAP_FLAG)?;
+            self.rearm.insert(tid, address);
+        }
+        Ok(true)
+    }
+
+    /// Put back the int3 of a breakpoint thread `tid` has just stepped over.
+    ///
+    /// Returns whether the single step was that one, rather than the user's.
+    pub fn rearm_after_step(&mut self, tid: u32) -> Result<bool, String> {
+        let Some(address) = self.rearm.remove(&tid) else {
+            return Ok(false);
+        };
+        // Not if it was removed while the thread was stepping
+        if self.state.breakpoints.contains_key(&address) {
+            self.write_int3(address)?;
+        }
+        Ok(true)
+    }
+
+    /// Count a breakpoint hit, removing it if it is temporary.
+    ///
+    /// Returns the breakpoint as it was after counting the hit.
+    pub fn record_breakpoint_hit(&mut self, address: u64) -> Option<super::types::Breakpoint> {
+        let bp = self.state.breakpoints.get_mut(&address)?;
+        bp.hit_count += 1;
+        let bp = bp.clone();
+        if bp.temporary {
+            let _ = self.remove_sw_breakpoint(address);
+        }
+        Some(bp)
+    }
+
+    /// Read the register state of the last event thread
+    pub fn read_registers(&self) -> Result<RegisterState, String> {
+        let tid = self.state.last_thread_id.or(self.state.main_thread_id).ok_or("No thread id")?;
+        self.read_thread_registers(tid)
+    }
+
+    /// Read the register state of thread `tid`
+    pub fn read_thread_registers(&self, tid: u32) -> Result<RegisterState, String> {
+        unsafe {
+            let thread = OpenThread(THREAD_GET_CONTEXT, false, tid)
+                .map_err(|e| format!("OpenThread failed for tid {}: {:?}", tid, e))?;
+            // A 64-bit debugger's CONTEXT of a WOW64 thread is its 64-bit
+            // side (inside the WOW64 layer), not the code being debugged
+            let result = if self.is_wow64() {
+                let mut ctx = WOW64_CONTEXT {
+                    ContextFlags: WOW64_CONTEXT_CONTROL_INTEGER,
+                    ..Default::default()
+                };
+                Wow64GetThreadContext(thread, &mut ctx)
+                    .map(|()| registers_from_wow64_context(&ctx))
+                    .map_err(|e| format!("Wow64GetThreadContext failed: {:?}", e))
+            } else {
+                let mut ctx = CONTEXT {
+                    ContextFlags: CONTEXT_CONTROL_INTEGER,
+                    ..Default::default()
+                };
+                GetThreadContext(thread, &mut ctx)
+                    .map(|()| registers_from_context(&ctx))
+                    .map_err(|e| format!("GetThreadContext failed: {:?}", e))
+            };
+            let _ = CloseHandle(thread);
+            result
+        }
+    }
+
+    /// Write one register of thread `tid`, by its lowercase 64-bit name
+    pub fn write_thread_register(&self, tid: u32, name: &str, value: u64) -> Result<(), String> {
+        unsafe {
+            let thread = OpenThread(THREAD_GET_CONTEXT | THREAD_SET_CONTEXT, false, tid)
+                .map_err(|e| format!("OpenThread failed for tid {}: {:?}", tid, e))?;
+            let set = |regs: &mut RegisterState| match regs.set(name, value) {
+                true => Ok(()),
+                false => Err(format!("Unknown register {}", name)),
+            };
+            let result = if self.is_wow64() {
+                let mut ctx = WOW64_CONTEXT {
+                    ContextFlags: WOW64_CONTEXT_CONTROL_INTEGER,
+                    ..Default::default()
+                };
+                Wow64GetThreadContext(thread, &mut ctx)
+                    .map_err(|e| format!("Wow64GetThreadContext failed: {:?}", e))
+                    .and_then(|()| {
+                        let mut regs = registers_from_wow64_context(&ctx);
+                        set(&mut regs)?;
+                        apply_wow64_registers(&mut ctx, &regs);
+                        Wow64SetThreadContext(thread, &ctx).map_err(|e| format!("Wow64SetThreadContext failed: {:?}", e))
+                    })
+            } else {
+                let mut ctx = CONTEXT {
+                    ContextFlags: CONTEXT_CONTROL_INTEGER,
+                    ..Default::default()
+                };
+                GetThreadContext(thread, &mut ctx)
+                    .map_err(|e| format!("GetThreadContext failed: {:?}", e))
+                    .and_then(|()| {
+                        let mut regs = registers_from_context(&ctx);
+                        set(&mut regs)?;
+                        apply_registers(&mut ctx, &regs);
+                        SetThreadContext(thread, &ctx).map_err(|e| format!("SetThreadContext failed: {:?}", e))
+                    })
+            };
+            let _ = CloseHandle(thread);
+            result
+        }
+    }
+
+    /// Read the SSE/AVX and x87 registers of thread `tid`
+    pub fn read_thread_vector_registers(&self, tid: u32) -> Result<VectorRegisters, String> {
+        unsafe {
+            let thread = OpenThread(THREAD_GET_CONTEXT, false, tid)
+                .map_err(|e| format!("OpenThread failed for tid {}: {:?}", tid, e))?;
+            if self.is_wow64() {
+                let mut ctx = WOW64_CONTEXT {
+                    ContextFlags: WOW64_CONTEXT_EXTENDED_REGISTERS,
+                    ..Default::default()
+                };
+                let result = Wow64GetThreadContext(thread, &mut ctx);
+                let _ = CloseHandle(thread);
+                result.map_err(|e| format!("Wow64GetThreadContext failed: {:?}", e))?;
+                return Ok(vector_registers_from_fxsave(&ctx.ExtendedRegisters));
+            }
+            let mut ctx = CONTEXT {
+                ContextFlags: CONTEXT_FLOATING_POINT,
+                ..Default::default()
+            };
+            let result = GetThreadContext(thread, &mut ctx);
+            let ymm_high = result.is_ok().then(|| read_ymm_high(thread)).flatten();
+            let _ = CloseHandle(thread);
+            result.map_err(|e| format!("GetThreadContext failed: {:?}", e))?;
+
+            let fx = &ctx.Anonymous.FltSave;
+            let m128 = |m: &windows::Win32::System::Diagnostics::Debug::M128A| {
+                (u128::from(m.High as u64) << 64) | u128::from(m.Low)
+            };
+            let mut regs = VectorRegisters {
+                ymm_high,
+                mxcsr: fx.MxCsr,
+                fpu_control: fx.ControlWord,
+                fpu_status: fx.StatusWord,
+                fpu_tag: fx.TagWord,
+                ..Default::default()
+            };
+            for (dst, src) in regs.xmm.iter_mut().zip(&fx.XmmRegisters) {
+                *dst = m128(src);
+            }
+            for (dst, src) in regs.st.iter_mut().zip(&fx.FloatRegisters) {
+                dst.copy_from_slice(&m128(src).to_le_bytes()[..10]);
+            }
+            Ok(regs)
+        }
+    }
+
+    /// Whether the attached process is 32-bit code under WOW64
+    fn is_wow64(&self) -> bool {
+        self.state.is_64bit == Some(false)
+    }
+
+    /// Unwind the call stack of thread `tid` (innermost frame first)
+    pub fn thread_call_stack(&self, tid: u32) -> Result<Vec<StackFrame>, String> {
+        let regs = self.read_thread_registers(tid)?;
+        Ok(self.unwind(&regs))
+    }
+
+    fn unwind(&self, regs: &RegisterState) -> Vec<StackFrame> {
+        if self.is_wow64() {
+            super::unwind::unwind_frame_chain(regs.rip, regs.rbp, 4, |addr| {
+                self.memory.read_u32(addr).ok().map(u64::from)
+            })
+        } else {
+            super::unwind::unwind_frame_chain(regs.rip, regs.rbp, 8, |addr| {
+                self.memory.read_u64(addr).ok()
+            })
+        }
+    }
+}
+
+fn registers_from_context(ctx: &CONTEXT) -> RegisterState {
+    RegisterState {
+        rax: ctx.Rax, rbx: ctx.Rbx, rcx: ctx.Rcx, rdx: ctx.Rdx,
+        rsi: ctx.Rsi, rdi: ctx.Rdi, rbp: ctx.Rbp, rsp: ctx.Rsp,
+        r8: ctx.R8, r9: ctx.R9, r10: ctx.R10, r11: ctx.R11,
+        r12: ctx.R12, r13: ctx.R13, r14: ctx.R14, r15: ctx.R15,
+        rip: ctx.Rip,
+        rflags: ctx.EFlags as u64,
+    }
+}
+
+fn apply_registers(ctx: &mut CONTEXT, regs: &RegisterState) {
+    ctx.Rax = regs.rax; ctx.Rbx = regs.rbx; ctx.Rcx = regs.rcx; ctx.Rdx = regs.rdx;
+    ctx.Rsi = regs.rsi; ctx.Rdi = regs.rdi; ctx.Rbp = regs.rbp; ctx.Rsp = regs.rsp;
+    ctx.R8 = regs.r8; ctx.R9 = regs.r9; ctx.R10 = regs.r10; ctx.R11 = regs.r11;
+    ctx.R12 = regs.r12; ctx.R13 = regs.r13; ctx.R14 = regs.r14; ctx.R15 = regs.r15;
+    ctx.Rip = regs.rip;
+    ctx.EFlags = regs.rflags as u32;
+}
+
+fn registers_from_wow64_context(ctx: &WOW64_CONTEXT) -> RegisterState {
+    RegisterState {
+        rax: ctx.Eax.into(), rbx: ctx.Ebx.into(), rcx: ctx.Ecx.into(), rdx: ctx.Edx.into(),
+        rsi: ctx.Esi.into(), rdi: ctx.Edi.into(), rbp: ctx.Ebp.into(), rsp: ctx.Esp.into(),
+        rip: ctx.Eip.into(),
+        rflags: ctx.EFlags.into(),
+        ..Default::default()
+    }
+}
+
+fn apply_wow64_registers(ctx: &mut WOW64_CONTEXT, regs: &RegisterState) {
+    ctx.Eax = regs.rax as u32; ctx.Ebx = regs.rbx as u32; ctx.Ecx = regs.rcx as u32; ctx.Edx = regs.rdx as u32;
+    ctx.Esi = regs.rsi as u32; ctx.Edi = regs.rdi as u32; ctx.Ebp = regs.rbp as u32; ctx.Esp = regs.rsp as u32;
+    ctx.Eip = regs.rip as u32;
+    ctx.EFlags = regs.rflags as u32;
+}
+
+/// XMM0-XMM7 and x87 state of a 32-bit thread from its 512-byte FXSAVE image
+fn vector_registers_from_fxsave(fx: &[u8; 512]) -> VectorRegisters {
+    let u16_at = |offset: usize| u16::from_le_bytes([fx[offset], fx[offset + 1]]);
+    let u128_at = |offset: usize| u128::from_le_bytes(fx[offset..offset + 16].try_into().unwrap());
+    let mut regs = VectorRegisters {
+        fpu_control: u16_at(0),
+        fpu_status: u16_at(2),
+        fpu_tag: fx[4],
+        mxcsr: u32::from_le_bytes(fx[24..28].try_into().unwrap()),
+        ..Default::default()
+    };
+    for (i, dst) in regs.st.iter_mut().enumerate() {
+        dst.copy_from_slice(&fx[32 + 16 * i..42 + 16 * i]);
+    }
+    for (i, dst) in regs.xmm.iter_mut().take(8).enumerate() {
+        *dst = u128_at(160 + 16 * i);
+    }
+    regs
+}
+
+/// Upper YMM halves of `thread` from its XSAVE area, if the OS and CPU have AVX
+///
+/// # Safety
+/// `thread` must be a thread handle opened with `THREAD_GET_CONTEXT`.
+unsafe fn read_ymm_high(thread: HANDLE) -> Option<[u128; 16]> {
+    // The first call only reports the buffer size an XSTATE context needs
+    let mut length = 0u32;
+    let _ = InitializeContext(None, CONTEXT_XSTATE, std::ptr::null_mut(), &mut length);
+    if length == 0 {
+        return None;
+    }
+    let mut buffer = vec![0u8; length as usize];
+    let mut ctx: *mut CONTEXT = std::ptr::null_mut();
+    InitializeContext(Some(buffer.as_mut_ptr().cast()), CONTEXT_XSTATE, &mut ctx, &mut length).ok()?;
+    if !SetXStateFeaturesMask(ctx, 1 << XSTATE_AVX).as_bool() {
+        return None;
+    }
+    GetThreadContext(thread, ctx).ok()?;
+
+    let mut feature_length = 0u32;
+    let ymmh = LocateXStateFeature(ctx, XSTATE_AVX, Some(&mut feature_length as *mut _)) as *const u128;
+    if ymmh.is_null() || (feature_length as usize) < 16 * std::mem::size_of::<u128>() {
+        return None;
+    }
+    let mut high = [0u128; 16];
+    for (i, slot) in high.iter_mut().enumerate() {
+        *slot = ymmh.add(i).read_unaligned();
+    }
+    Some(high)
 }
 
 /// Start debug event loop for the attached process
@@ -80,6 +459,7 @@ pub fn start_event_loop(
                     CREATE_PROCESS_DEBUG_EVENT => Some(super::types::DebugEvent::ProcessCreated {
                         pid: proc_id,
                         main_thread_id: thread_id,
+                        image_base: unsafe { debug_event.u.CreateProcessInfo.lpBaseOfImage } as u64,
                     }),
                     EXIT_PROCESS_DEBUG_EVENT => {
                         let exit_code = unsafe { debug_event.u.ExitProcess.dwExitCode };
@@ -131,6 +511,14 @@ impl Debugger for WindowsDebugger {
                 .map_err(|e| format!("Failed to attach to process {}: {:?}", pid, e))?;
         }
         
+        self.memory.open_process(pid).map_err(|e| e.to_string())?;
+        self.state.is_64bit = unsafe {
+            OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok().map(|handle| {
+                let is_64bit = process::is_64bit_process(handle);
+                let _ = CloseHandle(handle);
+                is_64bit
+            })
+        };
         self.state.attached_pid = Some(pid);
         self.state.status = DebugStatus::Running;
         self.state.last_event = Some(format!("Attached to PID {}", pid));
@@ -147,7 +535,13 @@ impl Debugger for WindowsDebugger {
                 .map_err(|e| format!("Failed to detach from process {}: {:?}", pid, e))?;
         }
         
+        // The int3s would crash the process once no debugger handles them
+        for address in self.state.breakpoints.keys().copied().collect::<Vec<_>>() {
+            let _ = self.remove_sw_breakpoint(address);
+        }
+        self.memory = MemoryManager::new();
         self.state.attached_pid = None;
+        self.state.is_64bit = None;
         self.state.main_thread_id = None;
         self.state.last_thread_id = None;
         self.state.status = DebugStatus::Detached;
@@ -180,21 +574,46 @@ impl Debugger for WindowsDebugger {
         self.continue_execution()
     }
 
-    fn set_sw_breakpoint(&mut self, _address: u64) -> Result<(), String> {
-        // Placeholder: record breakpoint in state without patching memory
-        let bp = super::types::Breakpoint {
-            address: _address,
-            original_byte: 0,
-            enabled: true,
-        };
-        self.state.breakpoints.insert(_address, bp);
-        self.state.last_event = Some(format!("Breakpoint set 0x{:016x}", _address));
+    fn break_into(&mut self) -> Result<(), String> {
+        let pid = self.state.attached_pid.ok_or("Not attached")?;
+        // Injects a thread that hits int3, reported as a breakpoint event
+        unsafe {
+            let process = OpenProcess(PROCESS_ALL_ACCESS, false, pid)
+                .map_err(|e| format!("OpenProcess failed for pid {}: {:?}", pid, e))?;
+            let result = DebugBreakProcess(process).map_err(|e| format!("Break failed: {:?}", e));
+            let _ = CloseHandle(process);
+            result
+        }
+    }
+
+    fn set_sw_breakpoint(&mut self, address: u64) -> Result<(), String> {
+        self.arm_breakpoint(address, false)?;
+        // Setting one where a step-over left a temporary one keeps it
+        if let Some(bp) = self.state.breakpoints.get_mut(&address) {
+            bp.temporary = false;
+        }
+        self.state.last_event = Some(format!("Breakpoint set 0x{:016x}", address));
         Ok(())
     }
 
-    fn remove_sw_breakpoint(&mut self, _address: u64) -> Result<(), String> {
-        self.state.breakpoints.remove(&_address);
-        self.state.last_event = Some(format!("Breakpoint removed 0x{:016x}", _address));
+    fn remove_sw_breakpoint(&mut self, address: u64) -> Result<(), String> {
+        // An armed breakpoint has an int3 in the code to undo
+        if let Some(bp) = self.state.breakpoints.get(&address).filter(|bp| bp.enabled) {
+            self.memory.write(address, &[bp.original_byte]).map_err(|e| e.to_string())?;
+        }
+        self.state.breakpoints.remove(&address);
+        self.rearm.retain(|_, pending| *pending != address);
+        self.state.last_event = Some(format!("Breakpoint removed 0x{:016x}", address));
         Ok(())
     }
+
+    fn call_stack(&self) -> Result<Vec<StackFrame>, String> {
+        let regs = self.read_registers()?;
+        Ok(self.unwind(&regs))
+    }
+
+    fn write_register(&mut self, name: &str, value: u64) -> Result<(), String> {
+        let tid = self.state.last_thread_id.or(self.state.main_thread_id).ok_or("No thread id")?;
+        self.write_thread_register(tid, name, value)
+    }
 }
@@ -6,8 +6,11 @@ use windows::Win32::Foundation::{CloseHandle, HANDLE, MAX_PATH};
 use windows::Win32::System::ProcessStatus::{
     EnumProcesses, GetModuleBaseNameW,
 };
+use windows::Win32::System::SystemInformation::{
+    IMAGE_FILE_MACHINE, IMAGE_FILE_MACHINE_AMD64, IMAGE_FILE_MACHINE_ARM64, IMAGE_FILE_MACHINE_UNKNOWN,
+};
 use windows::Win32::System::Threading::{
-    OpenProcess, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ,
+    IsWow64Process2, OpenProcess, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ,
 };
 
 /// Enumerate all running processes
@@ -45,10 +48,11 @@ pub fn enumerate_processes() -> Vec<ProcessInfo> {
 
             // Get process name
             let name = get_process_name(handle).unwrap_or_else(|| format!("<PID {}>", pid));
+            let is_64bit = is_64bit_process(handle);
             
             let _ = CloseHandle(handle);
 
-            processes.push(ProcessInfo { pid, name });
+            processes.push(ProcessInfo { pid, name, is_64bit });
         }
     }
 
@@ -58,6 +62,22 @@ pub fn enumerate_processes() -> Vec<ProcessInfo> {
     processes
 }
 
+/// Whether a process runs as 64-bit code.
+///
+/// WOW64 processes report their guest machine; native ones report
+/// `IMAGE_FILE_MACHINE_UNKNOWN` and take the OS's machine.
+pub(super) fn is_64bit_process(handle: HANDLE) -> bool {
+    let mut process_machine = IMAGE_FILE_MACHINE_UNKNOWN;
+    let mut native_machine = IMAGE_FILE_MACHINE::default();
+    unsafe {
+        if IsWow64Process2(handle, &mut process_machine, Some(&mut native_machine as *mut _)).is_err() {
+            return cfg!(target_pointer_width = "64");
+        }
+    }
+    process_machine == IMAGE_FILE_MACHINE_UNKNOWN
+        && matches!(native_machine, IMAGE_FILE_MACHINE_AMD64 | IMAGE_FILE_MACHINE_ARM64)
+}
+
 /// Get process name from handle
 fn get_process_name(handle: HANDLE) -> Option<String> {
     let mut name_buf = [0u16; MAX_PATH as usize];
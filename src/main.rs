@@ -23,6 +23,10 @@ struct Args {
     #[arg(short, long)]
     target: Option<String>,
 
+    /// Rebase the target to this image base (hex with 0x, or decimal)
+    #[arg(long, value_name = "ADDR", value_parser = parse_base)]
+    base: Option<u64>,
+
     /// Run in headless mode (CLI only, no GUI)
     #[arg(long, default_value_t = false)]
     headless: bool,
@@ -30,6 +34,43 @@ struct Args {
     /// Verbosity level (-v, -vv, -vvv)
     #[arg(short, long, action = clap::ArgAction::Count)]
     verbose: u8,
+
+    /// Ghidra server port (default: $FISSION_GHIDRA_PORT or 50051)
+    #[arg(long)]
+    ghidra_port: Option<u16>,
+
+    /// Ghidra server executable, tried before the built-in search paths
+    #[arg(long)]
+    ghidra_server_path: Option<String>,
+
+    /// Connection attempts while waiting for a spawned Ghidra server
+    #[arg(long, value_name = "N")]
+    ghidra_retries: Option<u32>,
+
+    /// Delay before the first connection retry, doubling each attempt
+    #[arg(long, value_name = "MS")]
+    ghidra_retry_delay_ms: Option<u64>,
+
+    /// Per-function decompile timeout passed to the Ghidra server
+    #[arg(long, value_name = "MS")]
+    decompile_timeout_ms: Option<u32>,
+
+    /// Print command results as JSON (headless mode)
+    #[arg(long, default_value_t = false)]
+    json: bool,
+
+    /// Run this CLI command and exit instead of starting the REPL; repeat
+    /// for several (headless mode)
+    #[arg(short = 'c', long = "command", value_name = "CMD")]
+    commands: Vec<String>,
+
+    /// Write analysis results for the target as JSON to this path and exit
+    #[arg(long, value_name = "PATH")]
+    export_json: Option<String>,
+}
+
+fn parse_base(s: &str) -> Result<u64, String> {
+    app::parse_address(s).ok_or_else(|| format!("invalid address '{}'", s))
 }
 
 fn main() -> anyhow::Result<()> {
@@ -51,11 +92,43 @@ fn main() -> anyhow::Result<()> {
     log::debug!("Target: {:?}", args.target);
     log::debug!("Headless: {}", args.headless);
 
-    // 3. Branch based on execution mode
+    let mut client_config = analysis::decomp::client::ClientConfig::default();
+    if let Some(port) = args.ghidra_port {
+        client_config.port = port;
+    }
+    client_config.server_path = args.ghidra_server_path.clone();
+    if let Some(retries) = args.ghidra_retries {
+        client_config.max_retries = retries;
+    }
+    if let Some(delay) = args.ghidra_retry_delay_ms {
+        client_config.initial_retry_delay_ms = delay;
+    }
+    if let Some(timeout) = args.decompile_timeout_ms {
+        client_config.decompile_timeout_ms = timeout;
+    }
+
+    // 3. One-shot JSON export for tooling
+    if let Some(out_path) = args.export_json.as_deref() {
+        let target = args.target.as_deref()
+            .ok_or_else(|| anyhow::anyhow!("--export-json requires --target"))?;
+        let mut binary = analysis::loader::LoadedBinary::from_file(target)?;
+        if let Some(base) = args.base {
+            binary.rebase(base);
+        }
+        app::export::AnalysisReport::new(&binary).save(out_path)?;
+        println!("[✓] Wrote analysis report to {}", out_path);
+        return Ok(());
+    }
+
+    // 4. Branch based on execution mode
     if args.headless {
-        // CLI mode: Run REPL in main thread
-        println!("[*] Fission v{} - Headless Mode", env!("CARGO_PKG_VERSION"));
-        ui::cli::run_cli()?;
+        // CLI mode: Run REPL in main thread; scripted runs keep stdout for results
+        if args.json || !args.commands.is_empty() {
+            eprintln!("[*] Fission v{} - Headless Mode", env!("CARGO_PKG_VERSION"));
+        } else {
+            println!("[*] Fission v{} - Headless Mode", env!("CARGO_PKG_VERSION"));
+        }
+        ui::cli::run_cli(args.target.as_deref(), args.base, client_config, args.json, &args.commands)?;
     } else {
         // GUI mode: Run GUI in main thread
         println!("[*] Fission v{} - GUI Mode", env!("CARGO_PKG_VERSION"));
@@ -65,7 +138,8 @@ fn main() -> anyhow::Result<()> {
             viewport: egui::ViewportBuilder::default()
                 .with_inner_size([1280.0, 720.0])
                 .with_min_inner_size([800.0, 600.0])
-                .with_title("Fission - Hybrid Analysis Platform"),
+                .with_title("Fission - Hybrid Analysis Platform")
+                .with_drag_and_drop(true),
             ..Default::default()
         };
 
@@ -75,7 +149,7 @@ fn main() -> anyhow::Result<()> {
             Box::new(|cc| {
                 // Enable dark mode by default
                 cc.egui_ctx.set_visuals(egui::Visuals::dark());
-                Box::new(ui::gui::FissionApp::default())
+                Box::new(ui::gui::FissionApp::new(client_config))
             }),
         )
         .map_err(|e| anyhow::anyhow!("GUI Error: {}", e))?;
@@ -3,10 +3,25 @@
 //! Provides the interface for executing Python scripts and
 //! exposing Fission's internal state to Python code.
 
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
+use std::sync::{Arc, Mutex, RwLock};
 use thiserror::Error;
 
+use crate::analysis::decomp::client::{BinaryId, GhidraClient, GhidraError};
+use crate::analysis::loader::LoadedBinary;
+use crate::ui::gui::TOKIO_RUNTIME;
+
+/// Ghidra client shared with the host
+pub type SharedClient = Arc<Mutex<Option<GhidraClient>>>;
+
+/// Analysis target visible to scripts, set by the host via `PythonBridge::set_binary`
+static CURRENT_BINARY: RwLock<Option<Arc<LoadedBinary>>> = RwLock::new(None);
+
+/// Ghidra client used by `FissionAPI.decompile`, set via `PythonBridge::set_ghidra_client`
+static CURRENT_CLIENT: RwLock<Option<SharedClient>> = RwLock::new(None);
+
 /// Python bridge errors
 #[derive(Error, Debug)]
 pub enum ScriptError {
@@ -103,9 +118,39 @@ impl Default for HookContext {
 }
 
 /// Fission API exposed to Python
+///
+/// Binary queries always see the host's current analysis target.
 #[pyclass]
-pub struct FissionAPI {
-    // Internal state references will be added here
+pub struct FissionAPI {}
+
+impl FissionAPI {
+    /// The current analysis target, or a Python error if none is loaded
+    fn binary() -> PyResult<Arc<LoadedBinary>> {
+        CURRENT_BINARY
+            .read()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| PyRuntimeError::new_err("No binary loaded"))
+    }
+
+    /// Decompile the function at `address` on the shared client, uploading
+    /// the binary first if the server does not have it.
+    ///
+    /// The lock is held for the whole request so it does not interleave with
+    /// the host's own use of the client.
+    #[allow(clippy::await_holding_lock)]
+    async fn decompile_on(shared: &SharedClient, binary: &LoadedBinary, address: u64) -> PyResult<String> {
+        let failed = |e: GhidraError| PyRuntimeError::new_err(format!("Decompile of {:#x} failed: {}", address, e));
+        let mut guard = shared.lock().unwrap();
+        let client = guard
+            .as_mut()
+            .ok_or_else(|| PyRuntimeError::new_err("Not connected to the Ghidra server"))?;
+        client
+            .load_binary_if_needed(binary.data.clone(), binary.image_base, &binary.arch_spec, BinaryId::for_binary(binary))
+            .await
+            .map_err(failed)?;
+        client.decompile_function(address).await.map(|r| r.c_code).map_err(failed)
+    }
 }
 
 #[pymethods]
@@ -115,6 +160,64 @@ impl FissionAPI {
         Self {}
     }
 
+    /// All functions as `(address, name)`, sorted by address
+    fn get_functions(&self) -> PyResult<Vec<(u64, String)>> {
+        let binary = Self::binary()?;
+        Ok(binary.functions_sorted().into_iter().map(|f| (f.address, f.name.clone())).collect())
+    }
+
+    /// Imported functions as `(address, name)`, sorted by address
+    fn get_imports(&self) -> PyResult<Vec<(u64, String)>> {
+        let binary = Self::binary()?;
+        Ok(binary
+            .functions_sorted()
+            .into_iter()
+            .filter(|f| f.is_import)
+            .map(|f| (f.address, f.name.clone()))
+            .collect())
+    }
+
+    /// Entry point address of the loaded binary
+    fn get_entry_point(&self) -> PyResult<u64> {
+        Ok(Self::binary()?.entry_point)
+    }
+
+    /// Architecture spec, e.g. `x86:LE:64:default`
+    fn get_arch(&self) -> PyResult<String> {
+        Ok(Self::binary()?.arch_spec.clone())
+    }
+
+    /// Decompile the function containing `address` and return its C code.
+    ///
+    /// Blocks until the Ghidra server answers. `block_on` panics on a thread
+    /// that is already driving a runtime, so such callers wait on a helper
+    /// thread instead.
+    fn decompile(&self, address: u64) -> PyResult<String> {
+        let binary = Self::binary()?;
+        let shared = CURRENT_CLIENT
+            .read()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| PyRuntimeError::new_err("No Ghidra client available"))?;
+        let start = binary.function_at(address).map_or(address, |f| f.address);
+        let request = || TOKIO_RUNTIME.block_on(Self::decompile_on(&shared, &binary, start));
+        if tokio::runtime::Handle::try_current().is_err() {
+            return request();
+        }
+        std::thread::scope(|scope| scope.spawn(request).join())
+            .map_err(|_| PyRuntimeError::new_err("Decompile thread panicked"))?
+    }
+
+    /// Read bytes of the loaded binary (with patches applied) at a virtual address.
+    ///
+    /// Stops at the end of the containing section, so fewer than `size`
+    /// bytes may be returned.
+    fn read_file_bytes(&self, address: u64, size: usize) -> PyResult<Vec<u8>> {
+        Self::binary()?
+            .get_bytes(address, size)
+            .ok_or_else(|| PyValueError::new_err(format!("{:#x} is not mapped", address)))
+    }
+
     /// Print to the Fission console
     fn log(&self, message: &str) {
         println!("[Python] {}", message);
@@ -167,6 +270,16 @@ impl PythonBridge {
         Self { initialized: false }
     }
 
+    /// Set the binary that `FissionAPI` exposes to scripts (None to clear it)
+    pub fn set_binary(&mut self, binary: Option<Arc<LoadedBinary>>) {
+        *CURRENT_BINARY.write().unwrap() = binary;
+    }
+
+    /// Share the host's Ghidra client with `FissionAPI.decompile`
+    pub fn set_ghidra_client(&mut self, client: SharedClient) {
+        *CURRENT_CLIENT.write().unwrap() = Some(client);
+    }
+
     /// Initialize the Python interpreter
     pub fn initialize(&mut self) -> Result<(), ScriptError> {
         if self.initialized {
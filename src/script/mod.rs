@@ -18,6 +18,12 @@ impl PythonBridge {
     pub fn new() -> Self {
         Self
     }
+    pub fn set_binary(&mut self, _binary: Option<std::sync::Arc<crate::analysis::loader::LoadedBinary>>) {}
+    pub fn set_ghidra_client(
+        &mut self,
+        _client: std::sync::Arc<std::sync::Mutex<Option<crate::analysis::decomp::client::GhidraClient>>>,
+    ) {
+    }
     pub fn initialize(&mut self) -> Result<(), String> {
         Err("Python support not compiled. Enable 'python' feature.".into())
     }
@@ -1,7 +1,8 @@
 //! CLI - reedline-based REPL interface
 //!
 //! Provides a powerful command-line interface with autocomplete,
-//! syntax highlighting, and history support.
+//! syntax highlighting, and history support. Commands are parsed and run
+//! by [`crate::app::commands`], shared with the GUI console.
 
 use anyhow::Result;
 use colored::Colorize;
@@ -10,6 +11,19 @@ use reedline::{
     Reedline, Signal,
 };
 use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::analysis::decomp::client::{format_pcode, BinaryId, ClientConfig, GhidraClient};
+use crate::analysis::disasm::DisasmSyntax;
+use crate::analysis::loader::{FunctionInfo, LoadedBinary};
+use crate::analysis::strings::{extract_strings, ExtractedString};
+use crate::app::export::MIN_STRING_LEN;
+use crate::app::{execute, parse_command, AppCommand, CommandContext, CommandOutput, ADDRESS_HELP, HELP};
+use crate::debug::dump::DumpReport;
+use crate::debug::memory::MemoryManager;
+use crate::script::PythonBridge;
 
 /// Custom prompt for Fission CLI
 pub struct FissionPrompt {
@@ -72,253 +86,425 @@ impl Prompt for FissionPrompt {
     }
 }
 
-/// Command parsing result
-#[derive(Debug)]
-pub enum ParsedCommand {
-    /// Navigate to address: s <addr>
-    Seek(u64),
-    /// Print disassembly at function: pdf @ <name>
-    PrintDisasmFunction(String),
-    /// Print N instructions: pd <n>
-    PrintDisasm(usize),
-    /// Show memory map: dm
-    MemoryMap,
-    /// Show registers: dr
-    Registers,
-    /// Set breakpoint: db <addr>
-    BreakpointSet(u64),
-    /// Delete breakpoint: db- <addr>
-    BreakpointDelete(u64),
-    /// Continue execution: dc
-    Continue,
-    /// Step instruction: ds
-    StepInto,
-    /// Step over: dso
-    StepOver,
-    /// Load binary: o <path>
-    Open(String),
-    /// Python passthrough: .<code>
-    Python(String),
-    /// Help: ? or help
-    Help,
-    /// Quit: q or exit
-    Quit,
-    /// Unknown command
-    Unknown(String),
+/// State shared across CLI commands
+pub struct CliSession {
+    /// Currently loaded binary (static analysis), shared with Python scripts
+    pub binary: Option<Arc<LoadedBinary>>,
+    /// Memory access for the attached process
+    pub memory: MemoryManager,
+    /// PID of the attached process, if any
+    pub attached_pid: Option<u32>,
+    /// Current address (`s <addr>`)
+    pub seek: u64,
+    /// User-assigned function names by address (`afn`)
+    pub function_renames: HashMap<u64, String>,
+    /// Assembly syntax (`e asm.syntax`)
+    pub disasm_syntax: DisasmSyntax,
+    /// Ghidra server connection settings
+    pub client_config: ClientConfig,
+    /// Emit machine-readable JSON instead of text (`--json`)
+    pub json: bool,
+    /// Strings of the loaded binary for listing comments, extracted on first use
+    strings: OnceCell<Vec<ExtractedString>>,
+    /// Ghidra client, connected on first decompile; shared with Python scripts
+    ghidra: Arc<Mutex<Option<GhidraClient>>>,
+    /// Runtime driving the async Ghidra client
+    runtime: Option<tokio::runtime::Runtime>,
+    /// Interpreter for `.<code>`, initialized on first use
+    python: PythonBridge,
 }
 
-/// Parse a command string into a structured command
-fn parse_command(input: &str) -> ParsedCommand {
-    let input = input.trim();
+/// Output of `pdc`
+#[derive(serde::Serialize)]
+pub struct Decompiled {
+    pub address: u64,
+    pub c_code: String,
+    pub pcode: String,
+}
 
-    // Python passthrough (starts with .)
-    if let Some(code) = input.strip_prefix('.') {
-        return ParsedCommand::Python(code.to_string());
+impl CliSession {
+    pub fn new() -> Self {
+        let ghidra = Arc::new(Mutex::new(None));
+        let mut python = PythonBridge::new();
+        python.set_ghidra_client(Arc::clone(&ghidra));
+        Self {
+            binary: None,
+            memory: MemoryManager::new(),
+            attached_pid: None,
+            seek: 0,
+            function_renames: HashMap::new(),
+            disasm_syntax: DisasmSyntax::default(),
+            client_config: ClientConfig::default(),
+            json: false,
+            strings: OnceCell::new(),
+            ghidra,
+            runtime: None,
+            python,
+        }
     }
 
-    let parts: Vec<&str> = input.splitn(2, ' ').collect();
-    let cmd = parts.first().unwrap_or(&"");
-    let arg = parts.get(1).map(|s| s.trim());
+    /// Load a binary from disk, replacing the current one
+    pub fn open(&mut self, path: &str) -> Result<()> {
+        let binary = LoadedBinary::from_file(path)?;
+        print_line(self, format!("[+] {}", binary.summary()));
+        self.binary = Some(Arc::new(binary));
+        self.strings = OnceCell::new();
+        self.python.set_binary(self.binary.clone());
+        Ok(())
+    }
 
-    match *cmd {
-        // Navigation
-        "s" | "seek" => {
-            if let Some(addr_str) = arg {
-                if let Ok(addr) = parse_address(addr_str) {
-                    return ParsedCommand::Seek(addr);
-                }
+    /// Loaded binary for in-place changes; copied first if a script still
+    /// holds the previous version
+    fn binary_mut(&mut self) -> Result<&mut LoadedBinary> {
+        let binary = self.binary.as_mut().ok_or_else(|| anyhow::anyhow!("No binary loaded"))?;
+        Ok(Arc::make_mut(binary))
+    }
+
+    /// Move the loaded binary to `new_base` (`ob`), shifting renames and the
+    /// seek along with it
+    pub fn rebase(&mut self, new_base: u64) -> Result<u64> {
+        let binary = self.binary_mut()?;
+        let old_base = binary.image_base;
+        let delta = binary.rebase(new_base);
+        self.python.set_binary(self.binary.clone());
+        self.function_renames = std::mem::take(&mut self.function_renames).into_iter()
+            .map(|(address, name)| (address.wrapping_add(delta), name))
+            .collect();
+        self.seek = self.seek.wrapping_add(delta);
+        Ok(old_base)
+    }
+
+    /// Decompile a function through the Ghidra server (`pdc`)
+    pub fn decompile(&mut self, func: &FunctionInfo) -> Result<Decompiled> {
+        let binary = self.binary.as_ref().ok_or_else(|| anyhow::anyhow!("No binary loaded"))?;
+
+        let runtime = match self.runtime.take() {
+            Some(runtime) => runtime,
+            None => tokio::runtime::Runtime::new()?,
+        };
+        let mut ghidra = self.ghidra.lock().unwrap();
+        let result = runtime.block_on(async {
+            if ghidra.is_none() {
+                *ghidra = Some(GhidraClient::connect_with_config(self.client_config.clone()).await?);
             }
-            ParsedCommand::Unknown(input.to_string())
-        }
+            let client = ghidra.as_mut().unwrap();
+            client.ensure_connected().await?;
+
+            let id = BinaryId::for_binary(binary);
+            client
+                .load_binary_if_needed(binary.data.clone(), binary.image_base, &binary.arch_spec, id)
+                .await?;
+            client.decompile_function(func.address).await
+        });
+        self.runtime = Some(runtime);
+
+        let response = result?;
+        Ok(Decompiled {
+            address: func.address,
+            pcode: format_pcode(&response),
+            c_code: response.c_code,
+        })
+    }
 
-        // Disassembly
-        "pd" => {
-            let count = arg.and_then(|s| s.parse().ok()).unwrap_or(10);
-            ParsedCommand::PrintDisasm(count)
+    /// Patch bytes at `address` (`wx`).
+    ///
+    /// The loaded binary's overlay is always updated; an attached process is
+    /// written as well. Returns whether process memory was changed.
+    fn write_bytes(&mut self, address: u64, bytes: &[u8]) -> Result<bool> {
+        self.binary_mut()?.patch(address, bytes)?;
+        self.python.set_binary(self.binary.clone());
+        if self.attached_pid.is_none() {
+            return Ok(false);
         }
-        "pdf" => {
-            // pdf @ main -> function name is after @
-            if let Some(func_spec) = arg {
-                let func_name = func_spec.strip_prefix("@ ").unwrap_or(func_spec);
-                return ParsedCommand::PrintDisasmFunction(func_name.to_string());
-            }
-            ParsedCommand::PrintDisasmFunction("main".to_string())
+        let written = self.memory.write(address, bytes)?;
+        if written != bytes.len() {
+            anyhow::bail!("Only {} of {} bytes written to process memory", written, bytes.len());
         }
+        Ok(true)
+    }
 
-        // Memory
-        "dm" => ParsedCommand::MemoryMap,
+    /// Open `pid` for memory access (`attach`); `dm`, `wx` and `wtf` then
+    /// work on the live process
+    pub fn attach(&mut self, pid: u32) -> Result<()> {
+        let mut memory = MemoryManager::new();
+        memory.open_process(pid)?;
+        self.memory = memory;
+        self.attached_pid = Some(pid);
+        Ok(())
+    }
 
-        // Debug
-        "dr" | "regs" => ParsedCommand::Registers,
-        "db" => {
-            if let Some(addr_str) = arg {
-                if let Some(addr_str) = addr_str.strip_prefix('-') {
-                    if let Ok(addr) = parse_address(addr_str.trim()) {
-                        return ParsedCommand::BreakpointDelete(addr);
-                    }
-                } else if let Ok(addr) = parse_address(addr_str) {
-                    return ParsedCommand::BreakpointSet(addr);
-                }
-            }
-            ParsedCommand::Unknown(input.to_string())
+    /// Drop the attached process (`detach`)
+    pub fn detach(&mut self) -> Option<u32> {
+        self.memory = MemoryManager::new();
+        self.attached_pid.take()
+    }
+
+    /// Dump process memory to `path` (`wtf`), zero-filling unreadable pages
+    fn dump_memory(&self, address: u64, size: usize, path: &str) -> Result<DumpReport> {
+        if self.attached_pid.is_none() {
+            anyhow::bail!("No process attached");
         }
-        "dc" | "continue" | "c" => ParsedCommand::Continue,
-        "ds" | "step" => ParsedCommand::StepInto,
-        "dso" | "next" | "n" => ParsedCommand::StepOver,
-
-        // File operations
-        "o" | "open" | "load" => {
-            if let Some(path) = arg {
-                return ParsedCommand::Open(path.to_string());
-            }
-            ParsedCommand::Unknown(input.to_string())
+        let (data, report) = crate::debug::dump::dump(&self.memory, address, size);
+        if report.bytes_read == 0 {
+            anyhow::bail!("Nothing readable at {:#x}", address);
         }
+        std::fs::write(path, data)?;
+        Ok(report)
+    }
+}
+
+impl Default for CliSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandContext for CliSession {
+    fn binary(&self) -> Option<&LoadedBinary> {
+        self.binary.as_deref()
+    }
+
+    fn seek(&self) -> u64 {
+        self.seek
+    }
+
+    fn function_renames(&self) -> &HashMap<u64, String> {
+        &self.function_renames
+    }
+
+    fn function_renames_mut(&mut self) -> &mut HashMap<u64, String> {
+        &mut self.function_renames
+    }
+
+    fn disasm_syntax(&self) -> DisasmSyntax {
+        self.disasm_syntax
+    }
+
+    fn set_disasm_syntax(&mut self, syntax: DisasmSyntax) {
+        self.disasm_syntax = syntax;
+    }
 
-        // Help
-        "?" | "help" => ParsedCommand::Help,
+    fn strings(&self) -> &[ExtractedString] {
+        self.strings.get_or_init(|| {
+            self.binary.as_ref()
+                .map(|binary| extract_strings(&binary.data, MIN_STRING_LEN))
+                .unwrap_or_default()
+        })
+    }
+}
 
-        // Quit
-        "q" | "quit" | "exit" => ParsedCommand::Quit,
+/// Print the help message
+fn print_help(session: &CliSession) {
+    print_line(session, "Fission CLI Commands".bold().cyan());
+    print_line(session, "═".repeat(50).cyan());
+
+    for (section, entries) in HELP {
+        print_line(session, format!("\n{}", format!("{}:", section).bold().yellow()));
+        for (usage, description) in *entries {
+            print_line(session, format!("  {}  {}", format!("{:<15}", usage).green(), description));
+        }
+    }
+    print_line(session, format!("\n{}", ADDRESS_HELP.dimmed()));
+}
 
-        _ => ParsedCommand::Unknown(input.to_string()),
+/// Print a JSON document, or report why it could not be encoded
+fn print_json(value: &impl serde::Serialize) {
+    match serde_json::to_string(value) {
+        Ok(doc) => println!("{}", doc),
+        Err(e) => eprintln!("{} Failed to encode JSON: {}", "[!]".red(), e),
     }
 }
 
-/// Parse an address string (supports 0x prefix and decimal)
-fn parse_address(s: &str) -> Result<u64, std::num::ParseIntError> {
-    let s = s.trim();
-    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
-        u64::from_str_radix(hex, 16)
+/// Print a line of text output; in JSON mode it goes to stderr, keeping
+/// stdout for the JSON documents
+fn print_line(session: &CliSession, line: impl std::fmt::Display) {
+    if session.json {
+        eprintln!("{}", line);
     } else {
-        s.parse()
+        println!("{}", line);
     }
 }
 
-/// Print the help message
-fn print_help() {
-    println!("{}", "Fission CLI Commands".bold().cyan());
-    println!("{}", "═".repeat(50).cyan());
-
-    println!("\n{}", "Navigation:".bold().yellow());
-    println!("  {}         Seek to address", "s <addr>".green());
-
-    println!("\n{}", "Disassembly:".bold().yellow());
-    println!("  {}          Print N disassembly lines", "pd <n>".green());
-    println!(
-        "  {}  Print disassembly of function",
-        "pdf @ <func>".green()
-    );
-
-    println!("\n{}", "Debugging:".bold().yellow());
-    println!("  {}             Show registers", "dr".green());
-    println!("  {}      Set breakpoint", "db <addr>".green());
-    println!("  {}     Delete breakpoint", "db- <addr>".green());
-    println!("  {}             Continue execution", "dc".green());
-    println!("  {}             Step into", "ds".green());
-    println!("  {}            Step over", "dso".green());
-
-    println!("\n{}", "Memory:".bold().yellow());
-    println!("  {}             Show memory map", "dm".green());
-
-    println!("\n{}", "Files:".bold().yellow());
-    println!("  {}      Open/load binary", "o <path>".green());
-
-    println!("\n{}", "Scripting:".bold().yellow());
-    println!("  {}     Execute Python code", ".<code>".green());
-
-    println!("\n{}", "Other:".bold().yellow());
-    println!("  {}            Show this help", "?".green());
-    println!("  {}            Quit Fission", "q".green());
+/// Report an error
+fn print_error(session: &CliSession, message: impl std::fmt::Display) {
+    print_line(session, format!("{} {}", "[!]".red(), message));
 }
 
 /// Execute a parsed command
-fn execute_command(cmd: ParsedCommand) {
+fn execute_command(cmd: AppCommand, session: &mut CliSession) {
     match cmd {
-        ParsedCommand::Seek(addr) => {
-            println!("[*] Seeking to {:#x}", addr);
-            // TODO: Update disassembly view
+        AppCommand::MemoryMap if session.attached_pid.is_some() => {
+            print_line(session, "[*] Memory Map:");
+            match session.memory.query_regions() {
+                Ok(regions) => {
+                    for line in CommandOutput::MemoryMap(regions.to_vec()).lines() {
+                        print_line(session, line);
+                    }
+                }
+                Err(e) => print_error(session, e),
+            }
         }
-        ParsedCommand::PrintDisasm(count) => {
-            println!("[*] Disassembling {} instructions...", count);
-            // TODO: Use DisassemblyEngine
-            println!("    (not implemented yet)");
+        AppCommand::Attach(pid) => match session.attach(pid) {
+            Ok(()) => print_line(session, format!("[*] Attached to pid {}", pid)),
+            Err(e) => print_error(session, format!("Attach failed: {}", e)),
+        },
+        AppCommand::Detach => match session.detach() {
+            Some(pid) => print_line(session, format!("[*] Detached from pid {}", pid)),
+            None => print_error(session, "No process attached"),
+        },
+        AppCommand::Continue => {
+            print_line(session, "[*] Continuing execution...");
         }
-        ParsedCommand::PrintDisasmFunction(name) => {
-            println!("[*] Disassembling function: {}", name);
-            // TODO: Look up function and disassemble
-            println!("    (not implemented yet)");
+        AppCommand::StepInto => {
+            print_line(session, "[*] Stepping into...");
         }
-        ParsedCommand::MemoryMap => {
-            println!("[*] Memory Map:");
-            println!("    {}", "(not implemented yet)".dimmed());
+        AppCommand::StepOver => {
+            print_line(session, "[*] Stepping over...");
         }
-        ParsedCommand::Registers => {
-            println!("[*] Registers:");
-            println!("    RAX = {:#018x}", 0u64);
-            println!("    RBX = {:#018x}", 0u64);
-            println!("    RCX = {:#018x}", 0u64);
-            println!("    RDX = {:#018x}", 0u64);
-            println!("    RSI = {:#018x}", 0u64);
-            println!("    RDI = {:#018x}", 0u64);
-            println!("    RBP = {:#018x}", 0u64);
-            println!("    RSP = {:#018x}", 0u64);
-            println!("    RIP = {:#018x}", 0u64);
+        AppCommand::LoadBinary(path) => {
+            print_line(session, format!("[*] Loading binary: {}", path));
+            if let Err(e) = session.open(&path) {
+                print_line(session, format!("{} Failed to load: {}", "[!]".red(), e));
+            }
         }
-        ParsedCommand::BreakpointSet(addr) => {
-            println!("[*] Breakpoint set at {:#x}", addr);
+        AppCommand::Python(code) => {
+            let result = session.python.initialize()
+                .map_err(|e| e.to_string())
+                .and_then(|()| session.python.execute(&code).map_err(|e| e.to_string()));
+            match result {
+                Ok(value) => print_line(session, value),
+                Err(e) => print_error(session, e),
+            }
         }
-        ParsedCommand::BreakpointDelete(addr) => {
-            println!("[*] Breakpoint deleted at {:#x}", addr);
+        AppCommand::Help => {
+            print_help(session);
         }
-        ParsedCommand::Continue => {
-            println!("[*] Continuing execution...");
+        AppCommand::Clear if !session.json => {
+            print!("\x1b[2J\x1b[H");
         }
-        ParsedCommand::StepInto => {
-            println!("[*] Stepping into...");
+        AppCommand::Clear => {}
+        AppCommand::Quit => {
+            print_line(session, "[*] Shutting down...");
+            std::process::exit(0);
         }
-        ParsedCommand::StepOver => {
-            println!("[*] Stepping over...");
+        cmd => match execute(session, cmd) {
+            Ok(output) => print_output(output, session),
+            Err(e) => print_error(session, e),
+        },
+    }
+}
+
+/// Print (or apply, for `s`, `wx`, `wtf`, `ob` and `pdc`) the result of a shared command
+fn print_output(output: CommandOutput, session: &mut CliSession) {
+    match output {
+        CommandOutput::Seek(address) => {
+            print_line(session, format!("[*] Seeking to {:#x}", address));
+            session.seek = address;
         }
-        ParsedCommand::Open(path) => {
-            println!("[*] Loading binary: {}", path);
-            // TODO: Use goblin to parse
+        CommandOutput::Functions(rows) if session.json => print_json(&rows),
+        CommandOutput::SearchHits(hits) if session.json => print_json(&hits),
+        CommandOutput::Patch { address, bytes } => match session.write_bytes(address, &bytes) {
+            Ok(live) => print_line(session, format!(
+                "[*] Patched {} bytes at {:#x}{}",
+                bytes.len(),
+                address,
+                if live { " (binary and process)" } else { "" }
+            )),
+            Err(e) => print_line(session, format!("{} Patch failed: {}", "[!]".red(), e)),
+        },
+        CommandOutput::Dump { address, size, path } => match session.dump_memory(address, size, &path) {
+            Ok(report) => {
+                print_line(session, format!("[*] Dumped {:#x} of {:#x} bytes at {:#x} to {}", report.bytes_read, size, address, path));
+                for line in report.gap_lines() {
+                    print_line(session, format!("{} {}", "[!]".yellow(), line));
+                }
+            }
+            Err(e) => print_error(session, format!("Dump failed: {}", e)),
+        },
+        CommandOutput::Rebase { base } => match session.rebase(base) {
+            Ok(old_base) => print_line(session, format!("[*] Rebased {:#x} -> {:#x}", old_base, base)),
+            Err(e) => print_error(session, e),
+        },
+        CommandOutput::Decompile(func) => {
+            print_line(session, format!("[*] Decompiling function: {}", func.name));
+            match session.decompile(&func) {
+                Ok(result) if session.json => print_json(&result),
+                Ok(result) => print_line(session, result.c_code),
+                Err(e) => print_error(session, format!("Decompile failed: {}", e)),
+            }
         }
-        ParsedCommand::Python(code) => {
-            println!("[*] Executing Python: {}", code);
-            // TODO: Use PythonBridge
+        output => {
+            if matches!(output, CommandOutput::MemoryMap(_)) {
+                print_line(session, "[*] Memory Map:");
+            }
+            for line in output.lines() {
+                print_line(session, line);
+            }
         }
-        ParsedCommand::Help => {
-            print_help();
+    }
+}
+
+/// Run the CLI, optionally loading `target` up front.
+///
+/// With `commands`, each is run in order and the CLI exits. In JSON mode
+/// there is no banner or prompt: commands are read line by line from stdin.
+pub fn run_cli(
+    target: Option<&str>,
+    base: Option<u64>,
+    client_config: ClientConfig,
+    json: bool,
+    commands: &[String],
+) -> Result<()> {
+    let mut session = CliSession::new();
+    session.client_config = client_config;
+    session.json = json;
+    let interactive = commands.is_empty() && !json;
+
+    if interactive {
+        println!(
+            "{}",
+            "╔══════════════════════════════════════════════════════════════╗".cyan()
+        );
+        println!(
+            "{}",
+            "║  Fission CLI - Type '?' for help, 'q' to quit                ║".cyan()
+        );
+        println!(
+            "{}",
+            "╚══════════════════════════════════════════════════════════════╝".cyan()
+        );
+    }
+
+    if let Some(path) = target {
+        execute_command(AppCommand::LoadBinary(path.to_string()), &mut session);
+        if session.binary.is_none() && !interactive {
+            anyhow::bail!("Could not load {}", path);
         }
-        ParsedCommand::Quit => {
-            println!("[*] Shutting down...");
-            std::process::exit(0);
+        if let Some(base) = base.filter(|_| session.binary.is_some()) {
+            print_output(CommandOutput::Rebase { base }, &mut session);
         }
-        ParsedCommand::Unknown(input) => {
-            println!("{} Unknown command: '{}'", "[!]".red(), input);
-            println!("    Type '?' for help");
+    }
+
+    if !commands.is_empty() {
+        for command in commands {
+            execute_command(parse_command(command.trim()), &mut session);
         }
+        return Ok(());
     }
-}
 
-/// Run the CLI REPL
-pub fn run_cli() -> Result<()> {
-    let mut line_editor = Reedline::create();
-    let prompt = FissionPrompt::new();
-
-    println!(
-        "{}",
-        "╔══════════════════════════════════════════════════════════════╗".cyan()
-    );
-    println!(
-        "{}",
-        "║  Fission CLI - Type '?' for help, 'q' to quit                ║".cyan()
-    );
-    println!(
-        "{}",
-        "╚══════════════════════════════════════════════════════════════╝".cyan()
-    );
+    if json {
+        for line in std::io::stdin().lines() {
+            let line = line?;
+            let input = line.trim();
+            if !input.is_empty() {
+                execute_command(parse_command(input), &mut session);
+            }
+        }
+        return Ok(());
+    }
 
+    let mut line_editor = Reedline::create();
+    let mut prompt = FissionPrompt::new();
     loop {
         let sig = line_editor.read_line(&prompt)?;
         match sig {
@@ -329,7 +515,9 @@ pub fn run_cli() -> Result<()> {
                 }
 
                 let cmd = parse_command(input);
-                execute_command(cmd);
+                execute_command(cmd, &mut session);
+                prompt.set_address(session.seek);
+                prompt.set_debugging(session.attached_pid.is_some());
             }
             Signal::CtrlD | Signal::CtrlC => {
                 println!("\n[*] Interrupted");
@@ -340,3 +528,37 @@ pub fn run_cli() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    static PATTERN: [u8; 64] = {
+        let mut bytes = [0u8; 64];
+        let mut i = 0;
+        while i < bytes.len() {
+            bytes[i] = i as u8 ^ 0x5a;
+            i += 1;
+        }
+        bytes
+    };
+
+    #[test]
+    fn test_wtf_dumps_attached_process() {
+        let mut session = CliSession::new();
+        let path = std::env::temp_dir().join(format!("fission-wtf-{}.bin", std::process::id()));
+        let address = PATTERN.as_ptr() as u64;
+        let command = format!("wtf {:#x} {} {}", address, PATTERN.len(), path.display());
+
+        // Without a process the dump is refused and nothing is written
+        execute_command(parse_command(&command), &mut session);
+        assert!(!path.exists());
+
+        execute_command(parse_command(&format!("attach {}", std::process::id())), &mut session);
+        assert_eq!(session.attached_pid, Some(std::process::id()));
+        execute_command(parse_command(&command), &mut session);
+        let dumped = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(dumped, PATTERN);
+    }
+}
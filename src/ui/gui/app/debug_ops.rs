@@ -1,33 +1,54 @@
 //! Debug operations - Process attach/detach, debug actions, breakpoints.
 
 use eframe::egui;
-use crate::ui::gui::state::{AppState, DebugAction, DebugBpAction};
+use crate::ui::gui::state::{AppState, DebugAction, DebugBpAction, LiveBytes, ProcessSort};
 
 #[cfg(target_os = "windows")]
 use crate::debug::PlatformDebugger;
 #[cfg(target_os = "windows")]
 use crate::debug::Debugger;
+#[cfg(target_os = "windows")]
+use crate::debug::dump::{parse_size, MAX_DUMP};
+#[cfg(target_os = "windows")]
+use crate::ui::gui::messages::AsyncMessage;
+#[cfg(target_os = "windows")]
+use std::sync::mpsc::Sender;
 
 /// Handle a debug event from the event loop
 pub fn handle_debug_event(state: &mut AppState, evt: crate::debug::types::DebugEvent) {
     use crate::debug::types::DebugEvent::*;
     match evt {
-        ProcessCreated { pid, main_thread_id } => {
+        ProcessCreated { pid, main_thread_id, image_base } => {
             state.debug_state.attached_pid = Some(pid);
             state.debug_state.main_thread_id = Some(main_thread_id);
             state.debug_state.last_thread_id = Some(main_thread_id);
+            state.debug_state.threads = vec![main_thread_id];
             state.debug_state.status = crate::debug::types::DebugStatus::Running;
-            state.log(format!("[*] Process created pid={} tid={}", pid, main_thread_id));
+            state.debug_state.image_base = Some(image_base);
+            state.log(format!("[*] Process created pid={} tid={} base=0x{:x}", pid, main_thread_id, image_base));
+            if let (Some(_), Some(binary)) = (state.aslr_slide(), &state.loaded_binary) {
+                let file_base = binary.image_base;
+                state.log(format!("[*] Image relocated from its file base 0x{:x} (ASLR)", file_base));
+            }
         }
         ProcessExited { exit_code } => {
             state.debug_state.status = crate::debug::types::DebugStatus::Terminated;
+            state.debug_state.threads.clear();
             state.log(format!("[*] Process exited code={}", exit_code));
         }
         ThreadCreated { thread_id } => {
+            if !state.debug_state.threads.contains(&thread_id) {
+                state.debug_state.threads.push(thread_id);
+            }
             state.log(format!("[*] Thread created tid={}", thread_id));
         }
         ThreadExited { thread_id } => {
+            state.debug_state.threads.retain(|&tid| tid != thread_id);
             state.log(format!("[*] Thread exited tid={}", thread_id));
+            if state.debug_state.last_thread_id == Some(thread_id) {
+                state.debug_state.last_thread_id = state.debug_state.main_thread_id;
+                state.pending_stack_refresh = true;
+            }
         }
         DllLoaded { base_address, name } => {
             state.log(format!("[*] DLL loaded {name} @0x{base_address:016x}"));
@@ -36,12 +57,14 @@ pub fn handle_debug_event(state: &mut AppState, evt: crate::debug::types::DebugE
             state.debug_state.status = crate::debug::types::DebugStatus::Suspended;
             state.debug_state.last_thread_id = Some(thread_id);
             state.debug_state.last_event = Some(format!("BP hit 0x{address:016x} tid={thread_id}"));
+            state.pending_stack_refresh = true;
             state.log(state.debug_state.last_event.clone().unwrap_or_default());
         }
         SingleStep { thread_id } => {
             state.debug_state.status = crate::debug::types::DebugStatus::Suspended;
             state.debug_state.last_thread_id = Some(thread_id);
             state.debug_state.last_event = Some(format!("[*] Single step tid={}", thread_id));
+            state.pending_stack_refresh = true;
             state.log(state.debug_state.last_event.clone().unwrap_or_default());
         }
         Exception { code, address, first_chance, .. } => {
@@ -50,7 +73,119 @@ pub fn handle_debug_event(state: &mut AppState, evt: crate::debug::types::DebugE
                 "[!] Exception code=0x{:x} addr=0x{:016x} first_chance={}",
                 code, address, first_chance
             ));
-            state.log(state.debug_state.last_event.clone().unwrap_or_default());
+            state.pending_stack_refresh = true;
+            state.warn(format!(
+                "Exception code=0x{:x} addr=0x{:016x} first_chance={}",
+                code, address, first_chance
+            ));
+        }
+    }
+}
+
+/// Sync the event thread into the debugger and evaluate breakpoint conditions.
+///
+/// Returns false if the event is a conditional breakpoint whose condition is
+/// false, or the single step that re-arms a breakpoint; the process has then
+/// already been resumed and the event should be dropped.
+#[cfg(target_os = "windows")]
+pub fn prepare_debug_event(
+    state: &mut AppState,
+    debugger: &mut Option<PlatformDebugger>,
+    evt: &crate::debug::types::DebugEvent,
+) -> bool {
+    use crate::debug::types::DebugEvent::*;
+    let Some(dbg) = debugger.as_mut() else {
+        return true;
+    };
+    match *evt {
+        ProcessCreated { main_thread_id, image_base, .. } => {
+            dbg.set_event_thread(main_thread_id);
+            // Session breakpoints are file VAs; install them once the load base is known
+            state.debug_state.image_base = Some(image_base);
+            for addr in std::mem::take(&mut state.saved_breakpoints) {
+                let runtime = state.to_runtime(addr);
+                if let Err(e) = dbg.set_sw_breakpoint(runtime) {
+                    state.warn(format!("Failed to restore breakpoint 0x{:x}: {}", runtime, e));
+                }
+            }
+            state.debug_state.breakpoints = dbg.state().breakpoints.clone();
+            true
+        }
+        SingleStep { thread_id } => {
+            dbg.set_event_thread(thread_id);
+            // Stepping over a breakpoint's instruction is not a stop of its own
+            match dbg.rearm_after_step(thread_id) {
+                Ok(false) => true,
+                Ok(true) => {
+                    if let Err(e) = dbg.continue_execution() {
+                        state.error(format!("Failed to resume after a breakpoint: {}", e));
+                    }
+                    false
+                }
+                Err(e) => {
+                    state.error(format!("Failed to re-arm a breakpoint: {}", e));
+                    true
+                }
+            }
+        }
+        BreakpointHit { address, thread_id } => {
+            dbg.set_event_thread(thread_id);
+            let temporary = dbg.state().breakpoints.get(&address).is_some_and(|bp| bp.temporary);
+            if let Err(e) = dbg.take_breakpoint_hit(address, thread_id) {
+                state.error(format!("Failed to undo breakpoint 0x{:x}: {}", address, e));
+            }
+            if condition_met(state, dbg, address) {
+                if let Some(bp) = dbg.record_breakpoint_hit(address) {
+                    if bp.temporary {
+                        state.log(format!("[*] Temporary breakpoint 0x{:x} removed", address));
+                    }
+                    state.debug_state.breakpoints = dbg.state().breakpoints.clone();
+                }
+                return true;
+            }
+            if temporary {
+                let _ = dbg.remove_sw_breakpoint(address);
+                state.debug_state.breakpoints = dbg.state().breakpoints.clone();
+            }
+            if let Err(e) = dbg.continue_execution() {
+                state.error(format!("Failed to resume after breakpoint 0x{:x}: {}", address, e));
+            }
+            false
+        }
+        _ => true,
+    }
+}
+
+/// Read a little-endian value of `size` bytes (1, 2, 4 or 8) from the target
+#[cfg(target_os = "windows")]
+fn read_value(dbg: &PlatformDebugger, address: u64, size: usize) -> Option<u64> {
+    let memory = dbg.memory();
+    match size {
+        1 => memory.read_u8(address).ok().map(u64::from),
+        2 => memory.read_u16(address).ok().map(u64::from),
+        4 => memory.read_u32(address).ok().map(u64::from),
+        _ => memory.read_u64(address).ok(),
+    }
+}
+
+/// Evaluate a breakpoint's condition; unconditional breakpoints and evaluation errors stop.
+#[cfg(target_os = "windows")]
+fn condition_met(state: &mut AppState, dbg: &PlatformDebugger, address: u64) -> bool {
+    let condition = state.debug_state.breakpoints.get(&address)
+        .and_then(|bp| bp.condition.clone());
+    let Some(condition) = condition else {
+        return true;
+    };
+
+    let pointer_size = if state.target_is_64bit() { 8 } else { 4 };
+    let result = dbg.read_registers().and_then(|regs| {
+        crate::debug::expr::evaluate(&condition, &regs, pointer_size, |addr, size| read_value(dbg, addr, size))
+    });
+    match result {
+        Ok(value) => value != 0,
+        Err(e) => {
+            state.warn(format!("Condition '{}' at 0x{:x} failed: {}", condition, address, e));
+            true
         }
     }
 }
@@ -69,8 +204,12 @@ pub fn attach_to_process(
     match dbg.attach(pid) {
         Ok(_) => {
             state.is_debugging = true;
+            state.iat_resolved.clear();
+            state.success(format!("Attached to PID {}", pid));
             state.debug_state = dbg.state().clone();
-            state.log(format!("[✓] Attached to PID {}", pid));
+            state.debug_state.is_64bit = state.process_list.iter()
+                .find(|p| p.pid == pid)
+                .map(|p| p.is_64bit);
 
             // Start event loop
             let (tx_evt, rx_evt) = std::sync::mpsc::channel();
@@ -81,14 +220,14 @@ pub fn attach_to_process(
         }
         Err(e) => {
             state.is_debugging = false;
-            state.log(format!("[✗] Attach failed: {}", e));
+            state.error(format!("Attach failed: {}", e));
         }
     }
 }
 
 #[cfg(not(target_os = "windows"))]
 pub fn attach_to_process(state: &mut AppState, _pid: u32) {
-    state.log("[!] Debug attach is only supported on Windows builds right now.");
+    state.warn("Debug attach is only supported on Windows builds right now.");
 }
 
 /// Detach from the current process (Windows builds only)
@@ -102,7 +241,7 @@ pub fn detach_process(
         if let Some(pid) = dbg.attached_pid() {
             state.log(format!("[*] Detaching from PID {}...", pid));
         } else {
-            state.log("[!] Not attached to any process");
+            state.warn("Not attached to any process");
             return;
         }
 
@@ -117,17 +256,17 @@ pub fn detach_process(
                 }
             }
             Err(e) => {
-                state.log(format!("[✗] Detach failed: {}", e));
+                state.error(format!("Detach failed: {}", e));
             }
         }
     } else {
-        state.log("[!] Debugger not initialized");
+        state.warn("Debugger not initialized");
     }
 }
 
 #[cfg(not(target_os = "windows"))]
 pub fn detach_process(state: &mut AppState) {
-    state.log("[!] Debug detach is only supported on Windows builds right now.");
+    state.warn("Debug detach is only supported on Windows builds right now.");
 }
 
 /// Handle debug control actions (Windows only)
@@ -138,27 +277,389 @@ pub fn handle_debug_action(
     action: DebugAction,
 ) {
     if !state.dynamic_mode {
-        state.log("[!] Debug control is disabled in static mode");
+        state.warn("Debug control is disabled in static mode");
+        return;
+    }
+    // Only the view changes; the process stays as it is
+    if let DebugAction::SelectThread(tid) = action {
+        state.debug_state.last_thread_id = Some(tid);
+        state.register_edits.clear();
+        refresh_stack(state, debugger);
+        return;
+    }
+    // The process stays suspended; only the shown thread's context changes
+    if let DebugAction::WriteRegister(name, value) = action {
+        write_register(state, debugger, name, value);
         return;
     }
     if let Some(dbg) = debugger.as_mut() {
         let result = match action {
             DebugAction::Continue => dbg.continue_execution(),
             DebugAction::Step => dbg.single_step(),
+            DebugAction::StepOver => step_over(state, dbg),
+            // Suspended (and registers refreshed) once the break event arrives
+            DebugAction::Break => {
+                match dbg.break_into() {
+                    Ok(()) => state.log("[*] Break requested"),
+                    Err(e) => state.error(format!("Break failed: {}", e)),
+                }
+                return;
+            }
+            DebugAction::SelectThread(_) | DebugAction::WriteRegister(..) => return,
         };
         if let Err(e) = result {
-            state.log(format!("[✗] Debug action failed: {}", e));
+            state.error(format!("Debug action failed: {}", e));
         } else {
+            state.debug_state.breakpoints = dbg.state().breakpoints.clone();
+            state.debug_state.call_stack.clear();
+            state.register_edits.clear();
+            state.debug_state.status = crate::debug::types::DebugStatus::Running;
             state.log("[*] Debug action sent");
         }
     } else {
-        state.log("[!] Debugger not initialized");
+        state.warn("Debugger not initialized");
+    }
+}
+
+/// Write a register of the shown thread and re-read its context (Windows only)
+#[cfg(target_os = "windows")]
+fn write_register(state: &mut AppState, debugger: &Option<PlatformDebugger>, name: &'static str, value: u64) {
+    let Some(dbg) = debugger.as_ref().filter(|d| d.is_attached()) else {
+        state.warn("Debugger not attached");
+        return;
+    };
+    if state.debug_state.status != crate::debug::types::DebugStatus::Suspended {
+        state.warn("Registers can only be written while the process is suspended");
+        return;
+    }
+    let Some(tid) = state.debug_state.last_thread_id.or(state.debug_state.main_thread_id) else {
+        state.warn("No thread to write registers of");
+        return;
+    };
+    match dbg.write_thread_register(tid, name, value) {
+        Ok(()) => {
+            state.register_edits.remove(name);
+            state.log(format!("[*] {} = 0x{:X} (tid {})", name.to_uppercase(), value, tid));
+            refresh_stack(state, debugger);
+        }
+        Err(e) => state.error(format!("Failed to write {}: {}", name.to_uppercase(), e)),
+    }
+}
+
+/// Step over a call by running to a one-shot breakpoint on the instruction
+/// after it; anything else is a single step (Windows only)
+#[cfg(target_os = "windows")]
+fn step_over(state: &AppState, dbg: &mut PlatformDebugger) -> Result<(), String> {
+    use crate::analysis::disasm::{DisasmEngine, MAX_INSN_LEN};
+
+    let Some(ip) = state.debug_ip() else {
+        return dbg.single_step();
+    };
+    let bytes = dbg.memory().read(ip, MAX_INSN_LEN).map_err(|e| e.to_string())?;
+    let engine = DisasmEngine::new(state.target_is_64bit()).map_err(|e| e.to_string())?;
+    let after_call = engine.disassemble(&bytes, ip).map_err(|e| e.to_string())?
+        .first()
+        .filter(|insn| insn.mnemonic.starts_with("call"))
+        .map(|insn| ip + insn.length as u64);
+    match after_call {
+        // A breakpoint the user already has there stops the run just the same
+        Some(next) if dbg.state().breakpoints.contains_key(&next) => dbg.continue_execution(),
+        Some(next) => {
+            dbg.set_temporary_breakpoint(next)?;
+            dbg.continue_execution()
+        }
+        None => dbg.single_step(),
     }
 }
 
 #[cfg(not(target_os = "windows"))]
 pub fn handle_debug_action(state: &mut AppState, _action: DebugAction) {
-    state.log("[!] Debug control is only supported on Windows builds right now.");
+    state.warn("Debug control is only supported on Windows builds right now.");
+}
+
+/// Re-read registers and the call stack after the process suspends (Windows only)
+#[cfg(target_os = "windows")]
+pub fn refresh_stack(state: &mut AppState, debugger: &Option<PlatformDebugger>) {
+    let Some(dbg) = debugger.as_ref().filter(|d| d.is_attached()) else {
+        return;
+    };
+    // The selected thread, falling back to the main thread once it has exited
+    let main = state.debug_state.main_thread_id;
+    let Some(mut tid) = state.debug_state.last_thread_id.or(main) else {
+        return;
+    };
+    let regs = match (dbg.read_thread_registers(tid), main.filter(|&main| main != tid)) {
+        (Err(e), Some(main)) => {
+            state.warn(format!("Thread {} is unavailable ({}); showing main thread {}", tid, e, main));
+            state.debug_state.threads.retain(|&t| t != tid);
+            state.debug_state.last_thread_id = Some(main);
+            tid = main;
+            dbg.read_thread_registers(tid)
+        }
+        (regs, _) => regs,
+    };
+    match regs {
+        Ok(regs) => state.debug_state.registers = Some(regs),
+        Err(e) => state.warn(format!("Failed to read registers: {}", e)),
+    }
+    state.debug_state.vector_registers = dbg.read_thread_vector_registers(tid).ok();
+    match dbg.thread_call_stack(tid) {
+        Ok(frames) => state.debug_state.call_stack = frames,
+        Err(e) => {
+            state.debug_state.call_stack.clear();
+            state.warn(format!("Failed to unwind call stack: {}", e));
+        }
+    }
+
+    // Keep the memory view in sync with the suspended process
+    if !state.mem_addr_input.trim().is_empty() {
+        read_memory(state, debugger);
+    }
+}
+
+/// Largest read accepted by the memory view
+const MAX_MEM_READ: usize = 0x1000;
+
+/// Read the memory view range from the live process (Windows only)
+#[cfg(target_os = "windows")]
+pub fn read_memory(state: &mut AppState, debugger: &Option<PlatformDebugger>) {
+    let Some(dbg) = debugger.as_ref().filter(|d| d.is_attached()) else {
+        state.mem_dump = "Not attached to a process".into();
+        return;
+    };
+
+    let address = match mem_view_address(state, dbg) {
+        Ok(address) => address,
+        Err(e) => {
+            state.mem_dump = e;
+            return;
+        }
+    };
+    let len = parse_size(&state.mem_len_input).unwrap_or(64).clamp(1, MAX_MEM_READ);
+
+    state.mem_dump = match dbg.memory().read(address, len) {
+        Ok(bytes) => hex_dump(address, &bytes),
+        Err(e) => e.to_string(),
+    };
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn read_memory(state: &mut AppState) {
+    state.mem_dump = "Memory reads are only supported on Windows builds right now.".into();
+}
+
+/// Evaluate the memory view's address input against the shown registers (Windows only)
+#[cfg(target_os = "windows")]
+fn mem_view_address(state: &AppState, dbg: &PlatformDebugger) -> Result<u64, String> {
+    let regs = state.debug_state.registers.clone().unwrap_or_default();
+    let pointer_size = if state.target_is_64bit() { 8 } else { 4 };
+    crate::debug::expr::evaluate(&state.mem_addr_input, &regs, pointer_size, |addr, size| read_value(dbg, addr, size))
+        .map_err(|e| format!("Invalid address '{}': {}", state.mem_addr_input.trim(), e))
+}
+
+/// Read a range of the live process and save it to a file: `range` from the
+/// console's `wtf`, or the memory view's range with a save dialog (Windows only)
+#[cfg(target_os = "windows")]
+pub fn dump_memory(
+    state: &mut AppState,
+    debugger: &Option<PlatformDebugger>,
+    range: Option<(u64, usize, String)>,
+    tx: Sender<AsyncMessage>,
+) {
+    let Some(dbg) = debugger.as_ref().filter(|d| d.is_attached()) else {
+        state.warn("Not attached to a process");
+        return;
+    };
+    let (address, size, path) = match range {
+        Some((address, size, path)) => (address, size, Some(path)),
+        None => {
+            let address = match mem_view_address(state, dbg) {
+                Ok(address) => address,
+                Err(e) => {
+                    state.warn(e);
+                    return;
+                }
+            };
+            let Some(size) = parse_size(&state.mem_len_input).filter(|&size| size > 0 && size <= MAX_DUMP) else {
+                state.warn(format!("Invalid dump size '{}' (1 to {:#x} bytes)", state.mem_len_input.trim(), MAX_DUMP));
+                return;
+            };
+            (address, size, None)
+        }
+    };
+
+    let Some(pid) = dbg.attached_pid() else {
+        return;
+    };
+    state.log(format!("[*] Dumping 0x{:x} bytes at 0x{:x}...", size, address));
+    super::file_ops::export_memory_dump(pid, address, size, path, tx);
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn dump_memory(state: &mut AppState) {
+    state.warn("Memory dumps are only supported on Windows builds right now.");
+}
+
+/// Read the hex view's live range from the attached process (Windows only)
+#[cfg(target_os = "windows")]
+pub fn read_hex_live(state: &mut AppState, debugger: &Option<PlatformDebugger>, offset: u64, len: usize) {
+    let (Some(dbg), Some(binary)) = (
+        debugger.as_ref().filter(|d| d.is_attached()),
+        state.loaded_binary.as_ref(),
+    ) else {
+        state.hex_live = None;
+        return;
+    };
+    let slide = state.aslr_slide().unwrap_or(0);
+    state.hex_live = Some(read_live_bytes(binary, dbg.memory(), slide, offset, len));
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn read_hex_live(state: &mut AppState, _offset: u64, _len: usize) {
+    state.hex_live = None;
+}
+
+/// Log the attached process's memory map for the console's `dm` (Windows only)
+#[cfg(target_os = "windows")]
+pub fn log_memory_map(state: &mut AppState, debugger: &mut Option<PlatformDebugger>) {
+    let Some(dbg) = debugger.as_mut().filter(|d| d.is_attached()) else {
+        state.warn("Not attached to a process");
+        return;
+    };
+    match dbg.memory_mut().query_regions() {
+        Ok(regions) => {
+            let lines = crate::app::CommandOutput::MemoryMap(regions.to_vec()).lines();
+            state.log("[*] Memory Map:");
+            for line in lines {
+                state.log(line);
+            }
+        }
+        Err(e) => state.warn(format!("Failed to query memory regions: {}", e)),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn log_memory_map(state: &mut AppState) {
+    state.warn("Live memory maps are only supported on Windows builds right now.");
+}
+
+/// Read the resolved address in each IAT slot of the attached process (Windows only)
+#[cfg(target_os = "windows")]
+pub fn read_iat(state: &mut AppState, debugger: &mut Option<PlatformDebugger>) {
+    let (Some(dbg), Some(binary)) = (
+        debugger.as_mut().filter(|d| d.is_attached()),
+        state.loaded_binary.as_ref(),
+    ) else {
+        state.iat_resolved.clear();
+        return;
+    };
+    let slots = binary.iat_slots();
+    let is_64bit = binary.is_64bit;
+    let slide = state.aslr_slide().unwrap_or(0);
+    let regions = match dbg.memory_mut().query_regions() {
+        Ok(regions) => regions.to_vec(),
+        Err(e) => {
+            state.warn(format!("Failed to query memory regions: {}", e));
+            Vec::new()
+        }
+    };
+    let memory = dbg.memory();
+    state.iat_resolved = crate::debug::iat::resolve(&slots, &regions, |slot| if is_64bit {
+        memory.read_u64(slot.wrapping_add(slide)).ok()
+    } else {
+        memory.read_u32(slot.wrapping_add(slide)).ok().map(u64::from)
+    });
+
+    let suspicious = state.iat_resolved.values().filter(|r| r.is_suspicious()).count();
+    if suspicious > 0 {
+        state.warn(format!("{} IAT entries point outside any loaded module", suspicious));
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn read_iat(state: &mut AppState) {
+    state.iat_resolved.clear();
+    state.warn("IAT resolution is only supported on Windows builds right now.");
+}
+
+/// Read `len` bytes of code at `address` from the attached process for a raw listing (Windows only)
+#[cfg(target_os = "windows")]
+pub fn read_code(debugger: &Option<PlatformDebugger>, address: u64, len: usize) -> Option<Vec<u8>> {
+    let dbg = debugger.as_ref().filter(|d| d.is_attached())?;
+    dbg.memory().read(address, len).ok()
+}
+
+/// Mirror a patch into the attached process, if any (Windows only)
+#[cfg(target_os = "windows")]
+pub fn write_patch(state: &mut AppState, debugger: &Option<PlatformDebugger>, address: u64, bytes: &[u8]) {
+    let Some(dbg) = debugger.as_ref().filter(|d| d.is_attached()) else {
+        return;
+    };
+    match dbg.memory().write(address, bytes) {
+        Ok(written) if written == bytes.len() => {
+            state.success(format!("Wrote {} bytes to process memory at 0x{:x}", written, address));
+        }
+        Ok(written) => state.warn(format!(
+            "Only {} of {} bytes written to process memory at 0x{:x}",
+            written, bytes.len(), address
+        )),
+        Err(e) => state.error(format!("Failed to write process memory: {}", e)),
+    }
+    state.hex_live = None;
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn write_patch(_state: &mut AppState, _address: u64, _bytes: &[u8]) {}
+
+/// Read the process memory backing `len` bytes of the file starting at `offset`.
+///
+/// Each 16-byte row is mapped to its virtual address separately, since
+/// consecutive file offsets may belong to different sections; `slide` moves
+/// those to where the image was loaded.
+fn read_live_bytes(
+    binary: &crate::analysis::loader::LoadedBinary,
+    memory: &crate::debug::memory::MemoryManager,
+    slide: u64,
+    offset: u64,
+    len: usize,
+) -> LiveBytes {
+    let mut bytes = vec![None; len];
+    for (i, row) in bytes.chunks_mut(16).enumerate() {
+        let Some(va) = binary.file_offset_to_va(offset + (i * 16) as u64) else {
+            continue;
+        };
+        let mut buffer = [0u8; 16];
+        let buffer = &mut buffer[..row.len()];
+        if let Ok(read) = memory.read_into(va.wrapping_add(slide), buffer) {
+            for (slot, &b) in row.iter_mut().zip(&buffer[..read]) {
+                *slot = Some(b);
+            }
+        }
+    }
+    LiveBytes { offset, bytes, read_at: std::time::Instant::now() }
+}
+
+/// Format bytes as `address  hex bytes  |ascii|` lines, 16 bytes per line
+fn hex_dump(base: u64, data: &[u8]) -> String {
+    use std::fmt::Write;
+
+    let mut out = String::with_capacity(data.len() * 4 + data.len() / 16 * 20);
+    for (i, chunk) in data.chunks(16).enumerate() {
+        let _ = write!(out, "{:016X}  ", base + (i * 16) as u64);
+        for j in 0..16 {
+            match chunk.get(j) {
+                Some(b) => { let _ = write!(out, "{:02X} ", b); }
+                None => out.push_str("   "),
+            }
+            if j == 7 {
+                out.push(' ');
+            }
+        }
+        out.push_str(" |");
+        out.extend(chunk.iter().map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' }));
+        out.push_str("|\n");
+    }
+    out
 }
 
 /// Handle breakpoint actions (Windows only)
@@ -169,26 +670,33 @@ pub fn handle_bp_action(
     action: DebugBpAction,
 ) {
     if !state.dynamic_mode {
-        state.log("[!] Breakpoints are disabled in static mode");
+        state.warn("Breakpoints are disabled in static mode");
         return;
     }
     if let Some(dbg) = debugger.as_mut() {
+        // Actions carry file VAs; the process has the image at its load base
         let result = match action {
-            DebugBpAction::Add(addr) => dbg.set_sw_breakpoint(addr),
-            DebugBpAction::Remove(addr) => dbg.remove_sw_breakpoint(addr),
+            DebugBpAction::Add(addr) => dbg.set_sw_breakpoint(state.to_runtime(addr)),
+            DebugBpAction::AddTemporary(addr) => {
+                let addr = state.to_runtime(addr);
+                dbg.set_temporary_breakpoint(addr)
+            }
+            DebugBpAction::Remove(addr) => dbg.remove_sw_breakpoint(state.to_runtime(addr)),
+            DebugBpAction::SetCondition(addr, condition) => dbg.set_breakpoint_condition(state.to_runtime(addr), condition),
         };
         match result {
             Ok(_) => state.log("[*] Breakpoint action applied"),
-            Err(e) => state.log(format!("[✗] Breakpoint action failed: {}", e)),
+            Err(e) => state.error(format!("Breakpoint action failed: {}", e)),
         }
+        state.debug_state.breakpoints = dbg.state().breakpoints.clone();
     } else {
-        state.log("[!] Debugger not initialized");
+        state.warn("Debugger not initialized");
     }
 }
 
 #[cfg(not(target_os = "windows"))]
 pub fn handle_bp_action(state: &mut AppState, _action: DebugBpAction) {
-    state.log("[!] Breakpoints are only supported on Windows builds right now.");
+    state.warn("Breakpoints are only supported on Windows builds right now.");
 }
 
 /// Render "Attach to Process" dialog
@@ -199,6 +707,7 @@ pub fn render_attach_dialog(state: &mut AppState, ctx: &egui::Context) -> Option
 
     let mut open = state.show_attach_dialog;
     let mut attached_pid = None;
+    let mut refresh = false;
 
     egui::Window::new("Attach to Process")
         .open(&mut open)
@@ -207,11 +716,38 @@ pub fn render_attach_dialog(state: &mut AppState, ctx: &egui::Context) -> Option
         .default_width(400.0)
         .default_height(500.0)
         .show(ctx, |ui| {
+            let filter = state.process_filter.to_lowercase();
+            let mut processes: Vec<_> = state.process_list.iter()
+                .filter(|p| filter.is_empty()
+                    || p.name.to_lowercase().contains(&filter)
+                    || p.pid.to_string().contains(&filter))
+                .collect();
+            match state.process_sort {
+                ProcessSort::Pid => processes.sort_by_key(|p| p.pid),
+                ProcessSort::Name => processes.sort_by_key(|p| (p.name.to_lowercase(), p.pid)),
+            }
+
             ui.horizontal(|ui| {
                 if ui.button("🔄 Refresh").clicked() {
-                    state.process_list = crate::debug::enumerate_processes();
+                    refresh = true;
+                }
+                if processes.len() == state.process_list.len() {
+                    ui.label(format!("{} processes found", state.process_list.len()));
+                } else {
+                    ui.label(format!("{} of {} processes", processes.len(), state.process_list.len()));
                 }
-                ui.label(format!("{} processes found", state.process_list.len()));
+            });
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut state.process_filter)
+                    .desired_width(200.0)
+                    .hint_text("Filter by name or PID..."));
+                if !state.process_filter.is_empty() && ui.small_button("✖").clicked() {
+                    state.process_filter.clear();
+                }
+                ui.separator();
+                ui.label("Sort:");
+                ui.selectable_value(&mut state.process_sort, ProcessSort::Pid, "PID");
+                ui.selectable_value(&mut state.process_sort, ProcessSort::Name, "Name");
             });
             
             ui.separator();
@@ -220,16 +756,18 @@ pub fn render_attach_dialog(state: &mut AppState, ctx: &egui::Context) -> Option
             egui::ScrollArea::vertical().show(ui, |ui| {
                 egui::Grid::new("process_list")
                     .striped(true)
-                    .num_columns(3)
+                    .num_columns(4)
                     .show(ui, |ui| {
                         ui.strong("PID");
                         ui.strong("Name");
+                        ui.strong("Arch");
                         ui.strong("Action");
                         ui.end_row();
 
-                        for process in &state.process_list {
+                        for process in processes {
                             ui.label(format!("{}", process.pid));
                             ui.label(&process.name);
+                            ui.label(if process.is_64bit { "x64" } else { "x86" });
                             if ui.button("Attach").clicked() {
                                 attached_pid = Some(process.pid);
                             }
@@ -239,6 +777,9 @@ pub fn render_attach_dialog(state: &mut AppState, ctx: &egui::Context) -> Option
             });
         });
 
+    if refresh {
+        state.process_list = crate::debug::enumerate_processes();
+    }
     state.show_attach_dialog = open;
     attached_pid
 }
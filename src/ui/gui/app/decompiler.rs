@@ -1,14 +1,20 @@
 //! Decompiler operations - Function decompilation with caching.
 
-use std::fs;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
 
-use crate::analysis::decomp::client::{GhidraClient, BinaryId};
-use crate::analysis::disasm::DisasmEngine;
-use crate::analysis::loader::FunctionInfo;
+use crate::analysis::decomp::CacheEntry;
+use crate::analysis::decomp::client::{format_pcode, BinaryId, ClientConfig, GhidraClient, GhidraError};
+use crate::analysis::decomp::client::ghidra_service::{DecompileResponse, Instruction};
+use crate::analysis::diff::FunctionDiff;
+use crate::analysis::disasm::{DisasmEngine, DisasmSyntax, DisassembledInstruction};
+use crate::analysis::disasm::search::{self, InsnMatch, InsnQuery};
+use crate::analysis::loader::{FunctionInfo, MAX_ESTIMATED_FUNCTION_SIZE};
+use crate::analysis::stackvars::{self, StackVariable};
 use crate::ui::gui::state::{AppState, CachedDecompile};
 use crate::ui::gui::messages::AsyncMessage;
 
@@ -24,7 +30,7 @@ pub fn decompile_function(
 ) {
     // Skip import functions
     if func.is_import {
-        state.log(format!("[!] {} is an import function (no code to decompile)", func.name));
+        state.warn(format!("{} is an import function (no code to decompile)", func.name));
         state.decompiled_code = format!(
             "// {} is an imported function\n// Address: 0x{:x}\n// No code available - this is a stub pointing to external library",
             func.name, func.address
@@ -34,72 +40,95 @@ pub fn decompile_function(
     
     let _start_time = Instant::now();
 
+    // Abort any in-flight request so the shared client is released promptly
+    cancel_decompile(state);
+    state.asm_selected = None;
+
     // Check cache first
     let address = func.address;
-    if let Some(cached) = state.decompile_cache.get(&address) {
+    let key = state.cache_key(address);
+    if let Some(cached) = key.as_ref().and_then(|key| state.decompile_cache.get(key)) {
         let c_code = cached.c_code.clone();
+        let pcode = cached.pcode.clone();
         let asm = cached.asm_instructions.clone();
+        state.ghidra_asm = cached.ghidra_asm.clone();
+        state.stack_vars = cached.stack_vars.clone();
+        state.c_line_map = cached.line_map.clone();
+        state.c_line_selected = None;
         state.log(format!("[*] Using cached result for 0x{:x}", address));
         state.decompiled_code = c_code;
-        state.asm_instructions = asm;
+        state.decompiled_pcode = pcode;
+        state.asm_instructions = if asm.is_empty() {
+            // Dropped after a syntax change; rebuild it
+            let asm = disassemble_function(state, func);
+            if let Some(cached) = key.and_then(|key| state.decompile_cache.get_mut(&key)) {
+                cached.asm_instructions = asm.clone();
+            }
+            asm
+        } else {
+            asm
+        };
+        refresh_stack_notes(state);
         return;
     }
     
-    if state.loaded_binary.is_none() {
-        state.log("[!] No binary loaded");
+    let (Some(_), Some(cache_id)) = (&state.loaded_binary, state.binary_id.clone()) else {
+        state.warn("No binary loaded");
         return;
-    }
+    };
     
-    let (arch, bin_id, bin_bytes, bin_base, bytes, is_64bit) = {
+    let (arch, bin_id, bin_bytes, bin_base, bytes) = {
         let binary = state.loaded_binary.as_ref().unwrap();
         let arch = binary.arch_spec.clone();
-        let bin_size = binary.data.len() as u64;
-        let bin_mtime = fs::metadata(&binary.path).ok()
-            .and_then(|m| m.modified().ok())
-            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|d| d.as_secs());
-        let bin_id = BinaryId::new(Some(binary.path.clone()), bin_size, arch.clone(), bin_mtime);
+        let bin_id = BinaryId::for_binary(binary);
         let bin_bytes = binary.data.clone();
         let bin_base = binary.image_base;
         
-        // Get function bytes (estimate 4KB for function body)
-        let func_size = if func.size > 0 { func.size as usize } else { 4096 };
+        // Get function bytes (estimate the body when the symbol has no size)
+        let func_size = if func.size > 0 { func.size } else { binary.estimate_function_size(address) } as usize;
         let bytes = match binary.get_bytes(address, func_size) {
             Some(b) => b,
             None => {
-                state.log(format!("[!] Cannot read bytes at 0x{:x}", address));
+                state.warn(format!("Cannot read bytes at 0x{:x}", address));
                 return;
             }
         };
-        (arch, bin_id, bin_bytes, bin_base, bytes, binary.is_64bit)
+        if func.size == 0 && func_size as u64 == MAX_ESTIMATED_FUNCTION_SIZE {
+            state.warn(format!("{} has no size; listing capped at 0x{:x} bytes (use More to extend)", func.name, func_size));
+        }
+        (arch, bin_id, bin_bytes, bin_base, bytes)
     };
     
     // Disassemble bytes
     let _disasm_start = Instant::now();
-    match DisasmEngine::new(is_64bit) {
-        Ok(engine) => {
-            match engine.disassemble(&bytes, address) {
-                Ok(insns) => {
-                    state.asm_instructions = insns;
-                }
-                Err(e) => {
-                    state.log(format!("[!] Disassembly error: {}", e));
-                    state.asm_instructions.clear();
-                }
-            }
-        }
-        Err(e) => {
-            state.log(format!("[!] Failed to initialize disassembler: {}", e));
-            state.asm_instructions.clear();
-        }
+    state.ghidra_asm.clear();
+    state.stack_vars.clear();
+    state.stack_notes.clear();
+    state.c_line_map.clear();
+    state.c_line_selected = None;
+    state.asm_instructions = disassemble_bytes(state, &bytes, address, &arch);
+
+    // Then the disk cache
+    let disk_hit = match (&state.disk_cache, state.disk_cache_key()) {
+        (Some(cache), Some(hash)) => cache.get(&hash, address),
+        _ => None,
+    };
+    if let Some(entry) = disk_hit {
+        state.log(format!("[*] Using disk-cached result for 0x{:x}", address));
+        cache_decompile_result(state, cache_id, address, entry.c_code, entry.pcode, Vec::new(), entry.stack_vars, entry.line_map);
+        return;
     }
 
+    let cancel = Arc::new(AtomicBool::new(false));
+    state.decompile_cancel = Some(cancel.clone());
     state.decompiling = true;
     state.decompiled_code = format!("// Decompiling 0x{:x}...", address);
+    state.decompiled_pcode.clear();
     state.log(format!("[*] Decompiling 0x{:x} ({} bytes)", address, bytes.len()));
     
     // Spawn async task for decompilation
     let shared_client = ghidra_client;
+    let config = state.client_config.clone();
     let bin_bytes_clone = bin_bytes.clone();
     let bin_id_clone = bin_id.clone();
     let arch_clone = arch.clone();
@@ -109,69 +138,74 @@ pub fn decompile_function(
 
             let mut guard = shared_client.lock().unwrap();
 
-            // Try reuse; if missing or failed ensure, reconnect with short backoff
-            let mut need_new = guard.is_none();
-            if !need_new {
-                if let Some(client) = guard.as_mut() {
-                    if client.ensure_connected().await.is_err() {
-                        need_new = true;
-                    }
-                }
-            }
-
-            if need_new {
-                let (prev_id, prev_funcs) = guard
-                    .as_ref()
-                    .map(|c| c.snapshot_state())
-                    .unwrap_or((None, Vec::new()));
-
-                let mut new_client = None;
-                let delays = [Duration::from_millis(0), Duration::from_millis(200), Duration::from_millis(500)];
-                for d in delays {
-                    if d.as_millis() > 0 {
-                        sleep(d).await;
-                    }
-                    match GhidraClient::connect().await {
-                        Ok(mut c) => {
-                            c.restore_state(prev_id.clone(), prev_funcs.clone());
-                            new_client = Some(c);
-                            break;
-                        }
-                        Err(_) => continue,
-                    }
-                }
-
-                if let Some(c) = new_client {
-                    *guard = Some(c);
-                } else {
+            // Make sure the shared client is usable before sending the request
+            let health = match guard.as_mut() {
+                Some(client) => Some(client.ensure_connected().await),
+                None => None,
+            };
+            if !matches!(health, Some(Ok(()))) {
+                let server_died = matches!(health, Some(Err(GhidraError::ServerDied)));
+                if !reconnect(&mut guard, &config, server_died).await {
                     let _ = tx.send(AsyncMessage::DecompileError { 
                         address, 
                         error: "Server reconnection failed".to_string() 
                     });
                     return;
                 }
+                if health.is_some() {
+                    let _ = tx.send(AsyncMessage::ClientReconnected { respawned: server_died });
+                }
             }
 
-            let client = guard.as_mut().unwrap();
+            // One retry if the server goes away mid-request
+            let mut attempt = 0;
+            let outcome = loop {
+                let client = guard.as_mut().unwrap();
 
-            // Load the binary bytes only if needed
-            if let Err(e) = match client.load_binary_if_needed(bin_bytes_clone.clone(), bin_base, &arch_clone, bin_id_clone.clone()).await {
-                Ok(_) => Ok(()),
-                Err(err) => Err(err),
-            } {
-                let _ = tx.send(AsyncMessage::DecompileError { 
-                    address, 
-                    error: e.to_string() 
-                });
+                // Load the binary bytes only if needed, then decompile, racing
+                // both calls against the cancel flag
+                let outcome = tokio::select! {
+                    result = async {
+                        client.load_binary_if_needed(bin_bytes_clone.clone(), bin_base, &arch_clone, bin_id_clone.clone()).await?;
+                        client.decompile_function(address).await
+                    } => Some(result),
+                    _ = wait_for_cancel(&cancel) => None,
+                };
+
+                let lost = matches!(
+                    outcome,
+                    Some(Err(GhidraError::ServerDied | GhidraError::TransportError(_)))
+                );
+                if !lost || attempt > 0 || cancel.load(Ordering::Relaxed) {
+                    break outcome;
+                }
+                attempt += 1;
+
+                let server_died = !matches!(client.ensure_connected().await, Ok(()));
+                if !reconnect(&mut guard, &config, server_died).await {
+                    break outcome;
+                }
+                let _ = tx.send(AsyncMessage::ClientReconnected { respawned: server_died });
+            };
+
+            let Some(outcome) = outcome else {
+                let _ = tx.send(AsyncMessage::DecompileCancelled { address });
                 return;
-            }
-            
-            // Decompile
-            match client.decompile_function(address).await {
+            };
+
+            match outcome {
                 Ok(result) => {
+                    let pcode = format_pcode(&result);
+                    let stack_vars = response_stack_vars(&result);
+                    let line_map = response_line_map(&result);
                     let _ = tx.send(AsyncMessage::DecompileResult { 
+                        id: cache_id,
                         address, 
-                        c_code: result.c_code 
+                        c_code: result.c_code,
+                        pcode,
+                        asm: result.blocks.into_iter().flat_map(|b| b.instructions).collect(),
+                        stack_vars,
+                        line_map,
                     });
                 }
                 Err(e) => {
@@ -185,18 +219,693 @@ pub fn decompile_function(
     });
 }
 
-/// Store decompile result in cache
-pub fn cache_decompile_result(state: &mut AppState, address: u64, c_code: String) {
+/// Decompile every non-import function that is not cached yet
+/// ("Analyze All Functions").
+///
+/// Disk-cache hits are filled in directly; the rest go to the server one at a
+/// time on a background thread, reporting progress as they finish.
+pub fn decompile_all(
+    state: &mut AppState,
+    tx: Sender<AsyncMessage>,
+    ghidra_client: Arc<Mutex<Option<GhidraClient>>>,
+) {
+    if state.batch_progress.is_some() {
+        return;
+    }
+    let Some(binary) = state.loaded_binary.as_ref() else {
+        state.warn("No binary loaded");
+        return;
+    };
+    let arch = binary.arch_spec.clone();
+    let bin_id = BinaryId::for_binary(binary);
+    let bin_bytes = binary.data.clone();
+    let bin_base = binary.image_base;
+    let Some(cache_id) = state.binary_id.clone() else {
+        return;
+    };
+    let mut targets: Vec<u64> = binary.functions.iter()
+        .filter(|f| !f.is_import && !state.decompile_cache.contains_key(&(cache_id.clone(), f.address)))
+        .map(|f| f.address)
+        .collect();
+    targets.sort_unstable();
+    targets.dedup();
+
+    let mut pending = Vec::new();
+    let mut from_disk = 0;
+    for address in targets {
+        let disk_hit = match (&state.disk_cache, state.disk_cache_key()) {
+            (Some(cache), Some(hash)) => cache.get(&hash, address),
+            _ => None,
+        };
+        match disk_hit {
+            Some(entry) => {
+                state.decompile_cache.insert((cache_id.clone(), address), CachedDecompile {
+                    c_code: entry.c_code,
+                    pcode: entry.pcode,
+                    asm_instructions: Vec::new(),
+                    ghidra_asm: Vec::new(),
+                    stack_vars: entry.stack_vars,
+                    line_map: entry.line_map,
+                    timestamp: Instant::now(),
+                });
+                from_disk += 1;
+            }
+            None => pending.push(address),
+        }
+    }
+    if from_disk > 0 {
+        state.log(format!("[*] Restored {} functions from the disk cache", from_disk));
+    }
+    if pending.is_empty() {
+        state.success("All functions are already decompiled");
+        return;
+    }
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    state.batch_cancel = Some(cancel.clone());
+    state.batch_progress = Some((0, pending.len()));
+    state.log(format!("[*] Analyzing {} functions...", pending.len()));
+
+    let config = state.client_config.clone();
+    let handle = TOKIO_RUNTIME.handle().clone();
+    std::thread::spawn(move || {
+        handle.block_on(run_batch_decompile(
+            ghidra_client, config, (bin_bytes, bin_base, arch, bin_id), pending, cancel, tx,
+        ));
+    });
+}
+
+/// Background half of `decompile_all`.
+///
+/// The shared client is locked per request (held across the await on
+/// purpose, like the interactive path), so clicking a function mid-batch
+/// only waits for the current request.
+#[allow(clippy::await_holding_lock)]
+async fn run_batch_decompile(
+    shared_client: Arc<Mutex<Option<GhidraClient>>>,
+    config: ClientConfig,
+    (bin_bytes, bin_base, arch, bin_id): (Vec<u8>, u64, String, BinaryId),
+    pending: Vec<u64>,
+    cancel: Arc<AtomicBool>,
+    tx: Sender<AsyncMessage>,
+) {
+    let total = pending.len();
+    let mut decompiled = 0;
+    let mut failed = 0;
+    let finish = |decompiled, failed, error: Option<String>| {
+        let _ = tx.send(AsyncMessage::BatchFinished {
+            decompiled,
+            failed,
+            cancelled: cancel.load(Ordering::Relaxed),
+            error,
+        });
+    };
+
+    // Upload the binary once up front
+    {
+        let mut guard = shared_client.lock().unwrap();
+        if guard.is_none() {
+            *guard = connect_with_backoff(&config).await;
+        }
+        let Some(client) = guard.as_mut() else {
+            finish(0, 0, Some("Could not connect to the Ghidra server".to_string()));
+            return;
+        };
+        if let Err(e) = client.load_binary_if_needed(bin_bytes, bin_base, &arch, bin_id).await {
+            finish(0, 0, Some(e.to_string()));
+            return;
+        }
+    }
+
+    for (i, &address) in pending.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        let result = {
+            let mut guard = shared_client.lock().unwrap();
+            match guard.as_mut() {
+                Some(client) => client.decompile_function(address).await,
+                None => Err(GhidraError::ServerDied),
+            }
+        };
+        match result {
+            Ok(response) => {
+                decompiled += 1;
+                let _ = tx.send(AsyncMessage::BatchDecompiled {
+                    address,
+                    pcode: format_pcode(&response),
+                    stack_vars: response_stack_vars(&response),
+                    line_map: response_line_map(&response),
+                    c_code: response.c_code,
+                });
+            }
+            Err(e @ (GhidraError::ServerDied | GhidraError::TransportError(_))) => {
+                finish(decompiled, failed, Some(e.to_string()));
+                return;
+            }
+            Err(e) => {
+                failed += 1;
+                log::warn!("Batch decompile of 0x{:x} failed: {}", address, e);
+            }
+        }
+        let _ = tx.send(AsyncMessage::BatchProgress { done: i + 1, total });
+    }
+    finish(decompiled, failed, None);
+}
+
+/// Decompile both sides of diff row `index` for the side-by-side view.
+///
+/// Each binary is uploaded in turn; the next interactive decompile uploads
+/// the primary again. Not available during "Analyze All Functions", which
+/// relies on the primary staying loaded on the server.
+pub fn decompile_diff(
+    state: &mut AppState,
+    tx: Sender<AsyncMessage>,
+    ghidra_client: Arc<Mutex<Option<GhidraClient>>>,
+    index: usize,
+) {
+    if state.batch_progress.is_some() {
+        state.warn("Wait for Analyze All Functions to finish before comparing functions");
+        return;
+    }
+    let Some(&FunctionDiff { primary: Some(primary_addr), secondary: Some(secondary_addr), .. }) =
+        state.function_diff.get(index)
+    else {
+        return;
+    };
+    let (Some(primary), Some(secondary)) = (&state.loaded_binary, &state.secondary_binary) else {
+        return;
+    };
+    let jobs: Vec<DiffJob> = [(primary, primary_addr), (secondary, secondary_addr)].into_iter()
+        .map(|(binary, address)| {
+            ((binary.data.clone(), binary.image_base, binary.arch_spec.clone(), BinaryId::for_binary(binary)), address)
+        })
+        .collect();
+
+    state.diff_selected = Some(index);
+    state.diff_code = None;
+
+    let config = state.client_config.clone();
+    let handle = TOKIO_RUNTIME.handle().clone();
+    std::thread::spawn(move || {
+        let mut code = handle.block_on(run_diff_decompile(ghidra_client, config, jobs)).into_iter();
+        let _ = tx.send(AsyncMessage::DiffDecompiled {
+            index,
+            primary: code.next().unwrap_or_default(),
+            secondary: code.next().unwrap_or_default(),
+        });
+    });
+}
+
+/// Binary to upload (bytes, base, arch, id) and the function to decompile in it
+type DiffJob = ((Vec<u8>, u64, String, BinaryId), u64);
+
+/// Background half of `decompile_diff`: C code per job, or the error as a comment
+#[allow(clippy::await_holding_lock)]
+async fn run_diff_decompile(
+    shared_client: Arc<Mutex<Option<GhidraClient>>>,
+    config: ClientConfig,
+    jobs: Vec<DiffJob>,
+) -> Vec<String> {
+    let mut guard = shared_client.lock().unwrap();
+    let health = match guard.as_mut() {
+        Some(client) => Some(client.ensure_connected().await),
+        None => None,
+    };
+    if !matches!(health, Some(Ok(()))) {
+        let server_died = matches!(health, Some(Err(GhidraError::ServerDied)));
+        if !reconnect(&mut guard, &config, server_died).await {
+            return vec!["// Server reconnection failed".to_string(); jobs.len()];
+        }
+    }
+    let client = guard.as_mut().unwrap();
+
+    let mut code = Vec::new();
+    for ((bin_bytes, bin_base, arch, bin_id), address) in jobs {
+        let result = match client.load_binary_if_needed(bin_bytes, bin_base, &arch, bin_id).await {
+            Ok(_) => client.decompile_function(address).await.map(|response| response.c_code),
+            Err(e) => Err(e),
+        };
+        code.push(result.unwrap_or_else(|e| format!("// Decompile of 0x{:x} failed: {}", address, e)));
+    }
+    code
+}
+
+/// Replace the shared client with a fresh connection, restoring its cached
+/// binary state.
+///
+/// The old client is dropped first so a server it spawned is shut down rather
+/// than orphaned. The binary id is only kept when the new client talks to a
+/// server that already held the binary; after a respawn it is cleared so the
+/// next request re-uploads the binary. Returns false if no connection could be
+/// made.
+async fn reconnect(
+    guard: &mut Option<GhidraClient>,
+    config: &ClientConfig,
+    server_died: bool,
+) -> bool {
+    let old = guard.take();
+    let owned_server = old.as_ref().is_some_and(|c| c.owns_server());
+    let (prev_id, prev_funcs) = old
+        .as_ref()
+        .map(|c| c.snapshot_state())
+        .unwrap_or((None, Vec::new()));
+    drop(old);
+
+    let Some(mut client) = connect_with_backoff(config).await else {
+        return false;
+    };
+    let keep_id = !server_died && !owned_server && !client.owns_server();
+    client.restore_state(if keep_id { prev_id } else { None }, prev_funcs);
+    *guard = Some(client);
+    true
+}
+
+/// Convert Ghidra's listing into instructions for the assembly panel, in
+/// address order.
+///
+/// Bytes the server left out are read from the loaded binary.
+pub fn ghidra_listing(state: &AppState, asm: Vec<Instruction>) -> Vec<DisassembledInstruction> {
+    let Some(binary) = state.loaded_binary.as_ref() else {
+        return Vec::new();
+    };
+    let Ok(engine) = DisasmEngine::for_arch(&binary.arch_spec, state.disasm_syntax) else {
+        return Vec::new();
+    };
+    let mut insns: Vec<_> = asm.into_iter()
+        .map(|insn| {
+            let bytes = if insn.raw_bytes.is_empty() {
+                binary.get_bytes(insn.address, insn.length as usize).unwrap_or_default()
+            } else {
+                insn.raw_bytes
+            };
+            engine.external_instruction(insn.address, bytes, insn.mnemonic, insn.operands)
+        })
+        .collect();
+    insns.sort_by_key(|insn| insn.address);
+    insns.dedup_by_key(|insn| insn.address);
+    insns
+}
+
+/// Disassemble bytes with the configured syntax, logging failures (empty on error)
+fn disassemble_bytes(
+    state: &mut AppState,
+    bytes: &[u8],
+    address: u64,
+    arch_spec: &str,
+) -> Vec<DisassembledInstruction> {
+    match DisasmEngine::for_arch(arch_spec, state.disasm_syntax) {
+        Ok(engine) => match engine.disassemble(bytes, address) {
+            Ok(insns) => {
+                warn_undecodable(state, &insns, address);
+                insns
+            }
+            Err(e) => {
+                state.warn(format!("Disassembly error: {}", e));
+                Vec::new()
+            }
+        },
+        Err(e) => {
+            state.warn(format!("Failed to initialize disassembler: {}", e));
+            Vec::new()
+        }
+    }
+}
+
+/// Log where a listing starting at `start` first hit bytes that did not decode
+fn warn_undecodable(state: &mut AppState, insns: &[DisassembledInstruction], start: u64) {
+    let Some(first) = insns.iter().find(|insn| insn.is_skipped_data()) else {
+        return;
+    };
+    let count = insns.iter().filter(|insn| insn.is_skipped_data()).count();
+    state.warn(format!(
+        "Decoding failed at 0x{:x} (+0x{:x}); {} undecodable byte(s) shown as db",
+        first.address, first.address - start, count
+    ));
+}
+
+/// Disassemble a function body from the loaded binary
+fn disassemble_function(state: &mut AppState, func: &FunctionInfo) -> Vec<DisassembledInstruction> {
+    let Some((bytes, arch)) = state.loaded_binary.as_ref()
+        .and_then(|b| {
+            let func_size = if func.size > 0 { func.size } else { b.estimate_function_size(func.address) };
+            b.get_bytes(func.address, func_size as usize).map(|bytes| (bytes, b.arch_spec.clone()))
+        })
+    else {
+        return Vec::new();
+    };
+    disassemble_bytes(state, &bytes, func.address, &arch)
+}
+
+/// Re-disassemble after the syntax setting or the patched bytes changed.
+///
+/// Cached listings are dropped and rebuilt lazily; the current view is redone now.
+pub fn refresh_disassembly(state: &mut AppState) {
+    for cached in state.decompile_cache.values_mut() {
+        cached.asm_instructions.clear();
+    }
+
+    let scroll = state.asm_selected;
+    match state.selected_function.clone() {
+        Some(func) => {
+            let asm = disassemble_function(state, &func);
+            if let Some(cached) = state.cache_key(func.address).and_then(|key| state.decompile_cache.get_mut(&key)) {
+                cached.asm_instructions = asm.clone();
+            }
+            state.asm_instructions = asm;
+            refresh_stack_notes(state);
+        }
+        None => {
+            if let Some(start) = state.asm_instructions.first().map(|i| i.address) {
+                disassemble_at(state, start);
+            }
+        }
+    }
+    state.asm_scroll_to = scroll;
+}
+
+/// Most matches listed by Find Instructions
+const MAX_INSN_MATCHES: usize = 10_000;
+
+/// Run the Find Instructions query.
+///
+/// The current listing is searched in place; all executable sections are
+/// decoded on a background thread.
+pub fn search_instructions(state: &mut AppState, tx: Sender<AsyncMessage>) {
+    let query = match InsnQuery::new(&state.insn_search_query, state.insn_search_regex) {
+        Ok(query) => query,
+        Err(e) => {
+            state.insn_search_status = format!("Invalid regex: {}", e);
+            return;
+        }
+    };
+
+    if !state.insn_search_all {
+        let matches = state.listing().iter()
+            .filter(|insn| !insn.is_skipped_data() && query.matches(insn))
+            .take(MAX_INSN_MATCHES)
+            .map(InsnMatch::from)
+            .collect();
+        show_insn_search_results(state, matches);
+        return;
+    }
+
+    let Some(binary) = state.loaded_binary.as_ref() else {
+        state.insn_search_status = "No binary loaded".into();
+        return;
+    };
+    let regions: Vec<(u64, Vec<u8>)> = binary.sections.iter()
+        .filter(|s| s.is_executable)
+        .filter_map(|s| {
+            let len = s.file_size.min(s.mapped_size()) as usize;
+            binary.get_bytes(s.virtual_address, len).map(|bytes| (s.virtual_address, bytes))
+        })
+        .collect();
+    let arch = binary.arch_spec.clone();
+    let syntax = state.disasm_syntax;
+
+    state.insn_search_running = true;
+    state.insn_search_status = "Searching...".into();
+    std::thread::spawn(move || {
+        let result = DisasmEngine::for_arch(&arch, syntax)
+            .and_then(|engine| {
+                let regions = regions.iter().map(|(base, bytes)| (*base, bytes.as_slice()));
+                search::search(&engine, regions, &query, MAX_INSN_MATCHES)
+            })
+            .map_err(|e| format!("Disassembly error: {}", e));
+        let _ = tx.send(AsyncMessage::InsnSearchFinished(result));
+    });
+}
+
+/// Store Find Instructions matches and summarize them
+pub fn show_insn_search_results(state: &mut AppState, matches: Vec<InsnMatch>) {
+    state.insn_search_status = if matches.len() >= MAX_INSN_MATCHES {
+        format!("{} matches (stopped at the limit)", matches.len())
+    } else {
+        format!("{} matches", matches.len())
+    };
+    state.insn_search_results = matches;
+}
+
+/// Disassemble `state.asm_window` bytes at an address outside known functions.
+///
+/// Returns false if the address is not mapped in the loaded binary.
+pub fn disassemble_at(state: &mut AppState, address: u64) -> bool {
+    let Some(binary) = state.loaded_binary.as_ref() else {
+        return false;
+    };
+    let Some(bytes) = binary.get_bytes(address, state.asm_window) else {
+        state.warn(format!("0x{:x} is not mapped", address));
+        return false;
+    };
+    show_raw_listing(state, &bytes, address)
+}
+
+/// Show `bytes` (e.g. read from the live process) as a listing starting at `address`
+pub fn show_raw_listing(state: &mut AppState, bytes: &[u8], address: u64) -> bool {
+    let arch = state.loaded_binary.as_ref()
+        .map(|b| b.arch_spec.clone())
+        .unwrap_or_else(|| "x86:LE:64:default".into());
+
+    match DisasmEngine::for_arch(&arch, state.disasm_syntax)
+        .and_then(|e| e.disassemble(bytes, address))
+    {
+        Ok(insns) => {
+            warn_undecodable(state, &insns, address);
+            cancel_decompile(state);
+            state.selected_function = None;
+            state.asm_instructions = insns;
+            state.ghidra_asm.clear();
+            state.stack_vars.clear();
+            state.stack_notes.clear();
+            state.c_line_map.clear();
+            state.c_line_selected = None;
+            state.asm_scroll_to = Some(address);
+            state.decompiled_code = format!("// 0x{:x} is not inside a known function", address);
+            state.decompiled_pcode.clear();
+            true
+        }
+        Err(e) => {
+            state.warn(format!("Disassembly error: {}", e));
+            false
+        }
+    }
+}
+
+/// Grow or shrink the local listing by `state.asm_window` bytes.
+///
+/// The bytes are re-read from the listing start, so a function whose size
+/// was estimated short can be decoded past its end. The listing never
+/// shrinks below one window.
+pub fn resize_listing(state: &mut AppState, grow: bool) {
+    let (Some(first), Some(last)) = (state.asm_instructions.first(), state.asm_instructions.last()) else {
+        return;
+    };
+    let start = first.address;
+    let span = (last.address + last.length as u64 - start) as usize;
+    let len = if grow {
+        span.saturating_add(state.asm_window)
+    } else {
+        span.saturating_sub(state.asm_window).max(state.asm_window)
+    };
+    let Some(binary) = state.loaded_binary.as_ref() else {
+        return;
+    };
+    let Some(bytes) = binary.get_bytes(start, len) else {
+        state.warn(format!("Cannot read bytes at 0x{:x}", start));
+        return;
+    };
+    if grow && bytes.len() <= span {
+        state.warn(format!("Listing already reaches the end of the section at 0x{:x}", start + span as u64));
+        return;
+    }
+    let arch = binary.arch_spec.clone();
+    let insns = disassemble_bytes(state, &bytes, start, &arch);
+    if !insns.is_empty() {
+        state.log(format!("[*] Listing at 0x{:x}: 0x{:x} bytes, {} instructions", start, bytes.len(), insns.len()));
+        state.asm_instructions = insns;
+    }
+}
+
+/// Prepend instructions decoded backwards from the top of the listing.
+///
+/// Existing rows are kept as they are, so the listing never re-aligns.
+/// Returns how many instructions were added.
+pub fn extend_backward(state: &mut AppState) -> usize {
+    const BACK_WINDOW: u64 = 0x100;
+
+    let Some(first) = state.asm_instructions.first().map(|i| i.address) else {
+        return 0;
+    };
+    let Some(binary) = state.loaded_binary.as_ref() else {
+        return 0;
+    };
+    // Stay within the section of the byte just before the listing
+    let Some(section) = first.checked_sub(1).and_then(|va| binary.section_at_va(va)) else {
+        return 0;
+    };
+    let window = BACK_WINDOW.min(first - section.virtual_address);
+    let Some(bytes) = binary.get_bytes(first - window, window as usize) else {
+        return 0;
+    };
+
+    let insns = DisasmEngine::for_arch(&binary.arch_spec, state.disasm_syntax)
+        .ok()
+        .and_then(|engine| {
+            let start = engine.backward_start(&bytes, first)?;
+            let skip = (start - (first - window)) as usize;
+            engine.disassemble(&bytes[skip..], start).ok()
+        })
+        .unwrap_or_default();
+
+    let added = insns.len();
+    state.asm_instructions.splice(0..0, insns);
+    added
+}
+
+/// Stop a running `decompile_all` whose results no longer apply (another
+/// binary is being loaded); results still in flight are discarded
+pub fn abandon_batch(state: &mut AppState) {
+    if let Some(flag) = state.batch_cancel.take() {
+        flag.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Signal the in-flight decompile task (if any) to stop
+pub fn cancel_decompile(state: &mut AppState) {
+    if let Some(flag) = state.decompile_cancel.take() {
+        flag.store(true, Ordering::Relaxed);
+        state.decompiling = false;
+    }
+}
+
+/// Resolve once `flag` has been set
+async fn wait_for_cancel(flag: &AtomicBool) {
+    while !flag.load(Ordering::Relaxed) {
+        sleep(Duration::from_millis(50)).await;
+    }
+}
+
+/// Recompute `state.stack_notes` for the current function.
+///
+/// The function is re-decoded in Intel syntax from its entry to the end of
+/// the listing, since the annotation follows the stack pointer from there.
+pub fn refresh_stack_notes(state: &mut AppState) {
+    state.stack_notes.clear();
+    if !state.stack_var_names || state.stack_vars.is_empty() {
+        return;
+    }
+    let (Some(binary), Some(func), Some(last)) =
+        (&state.loaded_binary, &state.selected_function, state.asm_instructions.last()) else {
+        return;
+    };
+    if !binary.arch_spec.starts_with("x86:") {
+        return;
+    }
+    let end = last.address + last.length as u64;
+    let Some(bytes) = binary.get_bytes(func.address, end.saturating_sub(func.address) as usize) else {
+        return;
+    };
+    let insns = DisasmEngine::for_arch(&binary.arch_spec, DisasmSyntax::Intel)
+        .and_then(|engine| engine.disassemble(&bytes, func.address));
+    if let Ok(insns) = insns {
+        let pointer_size = if binary.is_64bit { 8 } else { 4 };
+        state.stack_notes = stackvars::annotate(&insns, &state.stack_vars, pointer_size);
+    }
+}
+
+/// Stack variables of a decompile response
+fn response_stack_vars(response: &DecompileResponse) -> Vec<StackVariable> {
+    response.stack_vars.iter()
+        .map(|v| StackVariable { offset: v.offset, size: v.size, name: v.name.clone() })
+        .collect()
+}
+
+/// Instruction addresses behind each C line of a decompile response
+fn response_line_map(response: &DecompileResponse) -> HashMap<usize, Vec<u64>> {
+    response.line_map.iter()
+        .map(|l| (l.line as usize, l.addresses.clone()))
+        .collect()
+}
+
+/// Write a result to the disk cache, if there is one
+fn store_on_disk(state: &AppState, address: u64, entry: &CacheEntry) {
+    if let (Some(cache), Some(hash)) = (&state.disk_cache, state.disk_cache_key()) {
+        if let Err(e) = cache.put(&hash, address, entry) {
+            log::warn!("Failed to write disk cache for 0x{:x}: {}", address, e);
+        }
+    }
+}
+
+/// Store a result from `decompile_all` without touching the current view.
+///
+/// The listing is left empty and rebuilt when the function is opened.
+pub fn cache_batch_result(
+    state: &mut AppState,
+    address: u64,
+    c_code: String,
+    pcode: String,
+    stack_vars: Vec<StackVariable>,
+    line_map: HashMap<usize, Vec<u64>>,
+) {
+    let entry = CacheEntry { c_code, pcode, stack_vars, line_map };
+    store_on_disk(state, address, &entry);
+    let Some(key) = state.cache_key(address) else {
+        return;
+    };
+    state.decompile_cache.entry(key).or_insert(CachedDecompile {
+        c_code: entry.c_code,
+        pcode: entry.pcode,
+        asm_instructions: Vec::new(),
+        ghidra_asm: Vec::new(),
+        stack_vars: entry.stack_vars,
+        line_map: entry.line_map,
+        timestamp: Instant::now(),
+    });
+}
+
+/// Store decompile result in cache and show it.
+///
+/// Results for a binary other than the loaded one (a request still in flight
+/// when another file was opened) are dropped.
+#[allow(clippy::too_many_arguments)]
+pub fn cache_decompile_result(
+    state: &mut AppState,
+    id: BinaryId,
+    address: u64,
+    c_code: String,
+    pcode: String,
+    ghidra_asm: Vec<DisassembledInstruction>,
+    stack_vars: Vec<StackVariable>,
+    line_map: HashMap<usize, Vec<u64>>,
+) {
+    if state.binary_id.as_ref() != Some(&id) {
+        log::debug!("Dropping decompile result for 0x{:x} of a previous binary", address);
+        return;
+    }
+    let entry = CacheEntry { c_code, pcode, stack_vars, line_map };
+    store_on_disk(state, address, &entry);
     if let Some(func) = &state.selected_function {
         if func.address == address {
-            state.decompile_cache.insert(address, CachedDecompile {
-                c_code: c_code.clone(),
+            state.decompile_cache.insert((id, address), CachedDecompile {
+                c_code: entry.c_code.clone(),
+                pcode: entry.pcode.clone(),
                 asm_instructions: state.asm_instructions.clone(),
+                ghidra_asm: ghidra_asm.clone(),
+                stack_vars: entry.stack_vars.clone(),
+                line_map: entry.line_map.clone(),
                 timestamp: Instant::now(),
             });
+            state.ghidra_asm = ghidra_asm;
+            state.stack_vars = entry.stack_vars;
+            state.c_line_map = entry.line_map;
+            state.c_line_selected = None;
+            refresh_stack_notes(state);
         }
     }
-    state.decompiled_code = c_code;
+    state.decompiled_code = entry.c_code;
+    state.decompiled_pcode = entry.pcode;
     state.decompiling = false;
+    state.decompile_cancel = None;
 }
 
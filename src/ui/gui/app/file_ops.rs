@@ -1,14 +1,21 @@
 //! File operations - Binary loading, server connection, recovery.
 
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
+use eframe::egui;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
 
-use crate::analysis::decomp::client::{GhidraClient, BinaryId};
+use crate::analysis::decomp::client::{GhidraClient, BinaryId, ClientConfig};
 use crate::analysis::decomp::client::ghidra_service::FunctionMeta;
-use crate::analysis::loader::{LoadedBinary, FunctionInfo};
+use crate::analysis::diff;
+use crate::analysis::loader::{FunctionBatch, LoadedBinary, FunctionInfo, RAW_ARCH_SPECS};
+use crate::app::parse_address;
+use crate::app::session::Session;
+use crate::debug::dump;
+use crate::debug::memory::MemoryManager;
 use crate::ui::gui::state::AppState;
 use crate::ui::gui::messages::AsyncMessage;
 
@@ -28,25 +35,537 @@ pub fn open_file_dialog(tx: Sender<AsyncMessage>) {
     });
 }
 
+/// Open native file dialog to select a headerless blob
+pub fn open_raw_dialog(tx: Sender<AsyncMessage>) {
+    std::thread::spawn(move || {
+        let file = rfd::FileDialog::new()
+            .set_title("Open Raw")
+            .add_filter("Raw Binary", &["bin", "raw", "sc"])
+            .add_filter("All Files", &["*"])
+            .pick_file();
+
+        let path = file.map(|p| p.to_string_lossy().to_string());
+        let _ = tx.send(AsyncMessage::RawFileSelected(path));
+    });
+}
+
+/// Base address / architecture prompt for a pending raw open.
+///
+/// Returns `(path, base, arch_spec)` once the user confirms.
+pub fn render_raw_open_dialog(state: &mut AppState, ctx: &egui::Context) -> Option<(String, u64, String)> {
+    let request = state.raw_open.as_mut()?;
+
+    let mut open = true;
+    let mut confirmed = false;
+    egui::Window::new("Open Raw")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label(&request.path);
+            ui.separator();
+            egui::Grid::new("raw_open").num_columns(2).show(ui, |ui| {
+                ui.label("Base address");
+                ui.add(egui::TextEdit::singleline(&mut request.base_input)
+                    .desired_width(160.0)
+                    .font(egui::TextStyle::Monospace));
+                ui.end_row();
+
+                ui.label("Architecture");
+                egui::ComboBox::from_id_source("raw_open_arch")
+                    .selected_text(request.arch_spec)
+                    .show_ui(ui, |ui| {
+                        for spec in RAW_ARCH_SPECS {
+                            ui.selectable_value(&mut request.arch_spec, spec, *spec);
+                        }
+                    });
+                ui.end_row();
+            });
+            ui.separator();
+            let base_valid = parse_address(&request.base_input).is_some();
+            if ui.add_enabled(base_valid, egui::Button::new("Open")).clicked() {
+                confirmed = true;
+            }
+        });
+
+    if confirmed {
+        let request = state.raw_open.take()?;
+        let base = parse_address(&request.base_input)?;
+        return Some((request.path, base, request.arch_spec.to_string()));
+    }
+    if !open {
+        state.raw_open = None;
+    }
+    None
+}
+
+/// Load a headerless blob at `base`
+pub fn load_raw_binary(state: &mut AppState, tx: Sender<AsyncMessage>, path: String, base: u64, arch_spec: String) {
+    state.decompile_cache.clear();
+    super::decompiler::abandon_batch(state);
+    super::decompiler::cancel_decompile(state);
+    // Recovery reload goes through the format parsers, which reject raw blobs
+    state.last_binary_path = None;
+
+    state.log(format!("[*] Loading {} as raw {} at 0x{:x}...", path, arch_spec, base));
+
+    std::thread::spawn(move || {
+        let started = Instant::now();
+        let result = fs::read(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|data| LoadedBinary::from_raw(data, base, &arch_spec))
+            .map(|mut binary| {
+                binary.path = path;
+                binary
+            });
+        send_loaded(&tx, result, started.elapsed());
+    });
+}
+
+/// Open native file dialog to select a PDB for the loaded binary
+pub fn open_pdb_dialog(tx: Sender<AsyncMessage>) {
+    std::thread::spawn(move || {
+        let file = rfd::FileDialog::new()
+            .set_title("Load PDB")
+            .add_filter("Program Database", &["pdb"])
+            .add_filter("All Files", &["*"])
+            .pick_file();
+
+        let path = file.map(|p| p.to_string_lossy().to_string());
+        let _ = tx.send(AsyncMessage::PdbSelected(path));
+    });
+}
+
+/// Merge symbols from a PDB into the loaded binary
+pub fn load_pdb(state: &mut AppState, path: &str) {
+    let Some(binary) = state.loaded_binary.as_mut() else {
+        return;
+    };
+    match binary.load_pdb(path) {
+        Ok(added) => state.success(format!("Loaded PDB {}: {} new functions", path, added)),
+        Err(e) => state.error(format!("Failed to load PDB: {}", e)),
+    }
+    // Symbols may rename existing functions without changing their count
+    state.function_rows.key = None;
+    state.annotated_code.key = None;
+    state.publish_binary();
+}
+
+/// New image base prompt for File → Rebase.
+///
+/// Returns the base once the user confirms.
+pub fn render_rebase_dialog(state: &mut AppState, ctx: &egui::Context) -> Option<u64> {
+    let input = state.rebase_input.as_mut()?;
+
+    let mut open = true;
+    let mut confirmed = false;
+    egui::Window::new("Rebase")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Image base");
+                let response = ui.add(egui::TextEdit::singleline(input)
+                    .desired_width(160.0)
+                    .font(egui::TextStyle::Monospace));
+                let valid = parse_address(input).is_some();
+                let entered = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui.add_enabled(valid, egui::Button::new("Rebase")).clicked() || (entered && valid) {
+                    confirmed = true;
+                }
+            });
+        });
+
+    if confirmed {
+        return parse_address(&state.rebase_input.take()?);
+    }
+    if !open {
+        state.rebase_input = None;
+    }
+    None
+}
+
+/// Move the loaded binary to `new_base`.
+///
+/// Renames, bookmarks, saved breakpoints and history move with it; results
+/// tied to the old addresses are dropped and the Ghidra server reloads the
+/// binary at the new base. Returns the address to show again.
+pub fn rebase_binary(state: &mut AppState, ghidra_client: Arc<Mutex<Option<GhidraClient>>>, new_base: u64) -> Option<u64> {
+    let Some(old_base) = state.loaded_binary.as_ref().map(|b| b.image_base) else {
+        state.warn("No binary loaded");
+        return None;
+    };
+    if old_base == new_base {
+        state.log(format!("[*] Already based at 0x{:x}", new_base));
+        return None;
+    }
+
+    super::decompiler::abandon_batch(state);
+    super::decompiler::cancel_decompile(state);
+    let current = state.current_address();
+    let binary = state.loaded_binary.as_mut()?;
+    let delta = binary.rebase(new_base);
+    state.binary_id = Some(BinaryId::for_binary(binary));
+    state.rebased = true;
+    state.publish_binary();
+
+    let shift = |address: u64| address.wrapping_add(delta);
+    state.function_renames = std::mem::take(&mut state.function_renames).into_iter()
+        .map(|(address, name)| (shift(address), name))
+        .collect();
+    for bookmark in &mut state.bookmarks {
+        bookmark.address = shift(bookmark.address);
+    }
+    for address in state.saved_breakpoints.iter_mut().chain(&mut state.nav_history) {
+        *address = shift(*address);
+    }
+    state.selected_function = None;
+    state.asm_selected = None;
+    state.renaming = None;
+    state.bookmark_draft = None;
+    state.patch_draft = None;
+    state.insn_search_results.clear();
+    state.insn_search_status.clear();
+    state.function_diff.clear();
+    state.diff_selected = None;
+    state.diff_code = None;
+
+    state.success(format!("Rebased 0x{:x} -> 0x{:x}", old_base, new_base));
+    preload_server_binary(state, ghidra_client);
+    current.map(shift)
+}
+
+/// Open native file dialog to select a second version of the binary to diff against
+pub fn open_secondary_dialog(tx: Sender<AsyncMessage>) {
+    std::thread::spawn(move || {
+        let file = rfd::FileDialog::new()
+            .set_title("Diff Against Binary")
+            .add_filter("Executables", &["exe", "dll", "so", "dylib", "bin"])
+            .add_filter("All Files", &["*"])
+            .pick_file();
+
+        let path = file.map(|p| p.to_string_lossy().to_string());
+        let _ = tx.send(AsyncMessage::SecondaryFileSelected(path));
+    });
+}
+
+/// Load `path` as the secondary binary and diff its functions against the
+/// loaded binary in the background
+pub fn load_secondary_binary(state: &mut AppState, tx: Sender<AsyncMessage>, path: String) {
+    let Some(binary) = state.loaded_binary.as_ref() else {
+        return;
+    };
+    let primary_arch = binary.arch_spec.clone();
+    let primary_bodies = diff::function_bodies(binary);
+
+    state.log(format!("[*] Diffing against {}...", path));
+    state.diff_loading = true;
+    std::thread::spawn(move || {
+        let result = LoadedBinary::from_file(&path)
+            .map(|secondary| {
+                let primary = diff::fingerprint_all(&primary_arch, &primary_bodies);
+                let other = diff::fingerprint_all(&secondary.arch_spec, &diff::function_bodies(&secondary));
+                let rows = diff::diff_functions(&primary, &other);
+                (secondary, rows)
+            })
+            .map_err(|e| e.to_string());
+        let _ = tx.send(AsyncMessage::SecondaryLoaded(result));
+    });
+}
+
+/// Open native file dialog to select YARA rules to scan the loaded binary with
+pub fn open_yara_dialog(tx: Sender<AsyncMessage>) {
+    std::thread::spawn(move || {
+        let file = rfd::FileDialog::new()
+            .set_title("YARA Scan")
+            .add_filter("YARA Rules", &["yar", "yara"])
+            .add_filter("All Files", &["*"])
+            .pick_file();
+
+        let path = file.map(|p| p.to_string_lossy().to_string());
+        let _ = tx.send(AsyncMessage::YaraRulesSelected(path));
+    });
+}
+
+/// Scan the loaded binary's file bytes with the rules in `path` in the background
+pub fn start_yara_scan(state: &mut AppState, tx: Sender<AsyncMessage>, path: String) {
+    let Some(binary) = state.loaded_binary.as_ref() else {
+        return;
+    };
+    let data = binary.data.clone();
+
+    state.log(format!("[*] Scanning with YARA rules {}...", path));
+    state.yara_rules_path = Some(path.clone());
+    state.yara_results.clear();
+    state.yara_running = true;
+    state.yara_status = "Scanning...".into();
+    std::thread::spawn(move || {
+        let result = crate::analysis::yara::scan_file(Path::new(&path), &data)
+            .map_err(|e| e.to_string());
+        let _ = tx.send(AsyncMessage::YaraScanFinished(result));
+    });
+}
+
+/// Ask for a destination and write exported strings, one per line
+pub fn export_strings(lines: Vec<String>, tx: Sender<AsyncMessage>) {
+    std::thread::spawn(move || {
+        let file = rfd::FileDialog::new()
+            .set_title("Export Strings")
+            .set_file_name("strings.txt")
+            .add_filter("Text", &["txt"])
+            .save_file();
+
+        let result = file.map(|path| {
+            let mut contents = lines.join("\n");
+            contents.push('\n');
+            fs::write(&path, contents)
+                .map(|_| (path.to_string_lossy().to_string(), lines.len()))
+                .map_err(|e| e.to_string())
+        });
+        let _ = tx.send(AsyncMessage::StringsExported(result));
+    });
+}
+
+/// Ask where to save a disassembly listing (`<name>.txt`) and write it there
+pub fn export_disassembly(lines: Vec<String>, name: &str, tx: Sender<AsyncMessage>) {
+    let file_name: String = name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '.' { c } else { '_' })
+        .collect();
+    std::thread::spawn(move || {
+        let file = rfd::FileDialog::new()
+            .set_title("Export Disassembly")
+            .set_file_name(format!("{}.txt", file_name))
+            .add_filter("Text", &["txt"])
+            .save_file();
+
+        let result = file.map(|path| {
+            let mut contents = lines.join("\n");
+            contents.push('\n');
+            fs::write(&path, contents)
+                .map(|_| (path.to_string_lossy().to_string(), lines.len()))
+                .map_err(|e| e.to_string())
+        });
+        let _ = tx.send(AsyncMessage::DisassemblyExported(result));
+    });
+}
+
+/// Ask where to save the overlay (`<name>.overlay`) and write it there
+pub fn export_overlay(binary: &LoadedBinary, tx: Sender<AsyncMessage>) {
+    let Some(overlay) = binary.overlay.clone() else {
+        return;
+    };
+    let bytes = binary.data[overlay.start as usize..overlay.end as usize].to_vec();
+    let stem = Path::new(&binary.path).file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    std::thread::spawn(move || {
+        let file = rfd::FileDialog::new()
+            .set_title("Extract Overlay")
+            .set_file_name(format!("{}.overlay", stem))
+            .save_file();
+
+        let result = file.map(|path| {
+            fs::write(&path, &bytes)
+                .map(|_| (path.to_string_lossy().to_string(), bytes.len()))
+                .map_err(|e| e.to_string())
+        });
+        let _ = tx.send(AsyncMessage::OverlayExported(result));
+    });
+}
+
+/// Dump `size` bytes of process `pid` at `address` to `path`, or ask where
+/// to save it (`<address>.bin`) when there is none.
+///
+/// The read runs on the dialog thread through its own process handle, so a
+/// large range does not stall the UI.
+pub fn export_memory_dump(pid: u32, address: u64, size: usize, path: Option<String>, tx: Sender<AsyncMessage>) {
+    std::thread::spawn(move || {
+        let path = path.map(PathBuf::from).or_else(|| {
+            rfd::FileDialog::new()
+                .set_title("Dump Memory")
+                .set_file_name(format!("{:x}.bin", address))
+                .save_file()
+        });
+
+        let result = path.map(|path| {
+            let mut memory = MemoryManager::new();
+            memory.open_process(pid).map_err(|e| e.to_string())?;
+            let (data, report) = dump::dump(&memory, address, size);
+            if report.bytes_read == 0 {
+                return Err(format!("Nothing readable at 0x{:x}", address));
+            }
+            fs::write(&path, &data)
+                .map(|_| (path.to_string_lossy().to_string(), report))
+                .map_err(|e| e.to_string())
+        });
+        let _ = tx.send(AsyncMessage::MemoryDumped(result));
+    });
+}
+
+/// Ask where to save the patched binary, defaulting to `<name>.patched.<ext>`
+pub fn save_patched_dialog(source_path: &str, tx: Sender<AsyncMessage>) {
+    let source = Path::new(source_path);
+    let stem = source.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let file_name = match source.extension() {
+        Some(ext) => format!("{}.patched.{}", stem, ext.to_string_lossy()),
+        None => format!("{}.patched", stem),
+    };
+    std::thread::spawn(move || {
+        let file = rfd::FileDialog::new()
+            .set_title("Save Patched As")
+            .set_file_name(file_name)
+            .save_file();
+
+        let path = file.map(|p| p.to_string_lossy().to_string());
+        let _ = tx.send(AsyncMessage::PatchedPathSelected(path));
+    });
+}
+
+/// Write the loaded binary with its patches applied to `path`
+pub fn write_patched(state: &mut AppState, path: &str) {
+    let Some(binary) = state.loaded_binary.as_ref() else {
+        return;
+    };
+    let count = binary.patches.len();
+    match binary.write_patched(path) {
+        Ok(warnings) => {
+            for warning in warnings {
+                state.warn(warning);
+            }
+            state.success(format!("Saved {} patches to {}", count, path));
+        }
+        Err(e) => state.error(format!("Failed to save patched binary: {}", e)),
+    }
+}
+
+/// Ask for a destination and write the session JSON
+pub fn save_session(session: Session, tx: Sender<AsyncMessage>) {
+    std::thread::spawn(move || {
+        let file = rfd::FileDialog::new()
+            .set_title("Save Session")
+            .set_file_name("session.fission.json")
+            .add_filter("Fission Session", &["json"])
+            .save_file();
+
+        let result = file.map(|path| {
+            session.save(&path)
+                .map(|_| path.to_string_lossy().to_string())
+                .map_err(|e| e.to_string())
+        });
+        let _ = tx.send(AsyncMessage::SessionSaved(result));
+    });
+}
+
+/// Pick a session file and read it
+pub fn load_session(tx: Sender<AsyncMessage>) {
+    std::thread::spawn(move || {
+        let file = rfd::FileDialog::new()
+            .set_title("Load Session")
+            .add_filter("Fission Session", &["json"])
+            .add_filter("All Files", &["*"])
+            .pick_file();
+
+        let result = file.map(|path| Session::load(&path).map_err(|e| e.to_string()));
+        let _ = tx.send(AsyncMessage::SessionLoaded(result));
+    });
+}
+
+/// Re-apply a session's overrides once its binary is loaded
+pub fn apply_session(state: &mut AppState, session: Session) -> Option<FunctionInfo> {
+    if session.binary_hash.is_some() && session.binary_hash != state.binary_hash {
+        state.warn("Binary contents differ from the saved session; overrides not applied");
+        return None;
+    }
+
+    state.function_renames = session.function_renames;
+    state.saved_breakpoints = session.breakpoints;
+    state.bookmarks = session.bookmarks;
+    state.success(format!(
+        "Session restored: {} renames, {} breakpoints, {} bookmarks",
+        state.function_renames.len(),
+        state.saved_breakpoints.len(),
+        state.bookmarks.len()
+    ));
+
+    let seek = session.last_seek?;
+    let func = state.loaded_binary.as_ref()?.function_at(seek).cloned()?;
+    state.asm_scroll_to = Some(seek);
+    Some(func)
+}
+
 /// Load a binary file
 pub fn load_binary(state: &mut AppState, tx: Sender<AsyncMessage>, path: &str) {
     let path = path.to_string();
     
     // Clear cache on new binary load
     state.decompile_cache.clear();
+    super::decompiler::abandon_batch(state);
+    super::decompiler::cancel_decompile(state);
     // Save path for recovery reload
     state.last_binary_path = Some(path.clone());
     
     state.log(format!("[*] Loading {}...", path));
     
     std::thread::spawn(move || {
-        match LoadedBinary::from_file(&path) {
-            Ok(binary) => { let _ = tx.send(AsyncMessage::BinaryLoaded(Ok(binary))); }
-            Err(e) => { let _ = tx.send(AsyncMessage::BinaryLoaded(Err(e.to_string()))); }
-        }
+        let started = Instant::now();
+        let result = fs::read(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|data| LoadedBinary::from_bytes_progressive(
+                data,
+                path,
+                |binary| {
+                    let _ = tx.send(AsyncMessage::BinaryLoaded { result: Ok(binary), elapsed: started.elapsed() });
+                },
+                |batch| send_batch(&tx, batch),
+            ));
+        let _ = tx.send(match result {
+            Ok(()) => AsyncMessage::LoadComplete,
+            Err(e) => AsyncMessage::BinaryLoaded { result: Err(e.to_string()), elapsed: started.elapsed() },
+        });
     });
 }
 
+/// Functions per `AsyncMessage::FunctionsBatch`
+const FUNCTION_BATCH_SIZE: usize = 1000;
+
+/// Forward a batch from the loader, split so that a large symbol table
+/// still fills the functions list a frame at a time
+fn send_batch(tx: &Sender<AsyncMessage>, batch: FunctionBatch) {
+    match batch {
+        FunctionBatch::Found(functions) => {
+            let mut functions = functions.into_iter().peekable();
+            while functions.peek().is_some() {
+                let chunk = functions.by_ref().take(FUNCTION_BATCH_SIZE).collect();
+                let _ = tx.send(AsyncMessage::FunctionsBatch(FunctionBatch::Found(chunk)));
+            }
+        }
+        debug => {
+            let _ = tx.send(AsyncMessage::FunctionsBatch(debug));
+        }
+    }
+}
+
+/// Hand a binary parsed in one go (a raw blob) to the UI thread the way
+/// progressive loads arrive: the binary, its functions, then `LoadComplete`
+fn send_loaded(tx: &Sender<AsyncMessage>, result: anyhow::Result<LoadedBinary>, elapsed: Duration) {
+    let mut binary = match result {
+        Ok(binary) => binary,
+        Err(e) => {
+            let _ = tx.send(AsyncMessage::BinaryLoaded { result: Err(e.to_string()), elapsed });
+            return;
+        }
+    };
+
+    let functions = std::mem::take(&mut binary.functions);
+    if tx.send(AsyncMessage::BinaryLoaded { result: Ok(binary), elapsed }).is_err() {
+        return;
+    }
+    send_batch(tx, FunctionBatch::Found(functions));
+    let _ = tx.send(AsyncMessage::LoadComplete);
+}
+
 /// Ensure server has the current binary loaded and cache functions from server metadata.
 pub fn preload_server_binary(state: &mut AppState, ghidra_client: Arc<Mutex<Option<GhidraClient>>>) {
     let Some(binary) = state.loaded_binary.as_ref() else {
@@ -54,20 +573,16 @@ pub fn preload_server_binary(state: &mut AppState, ghidra_client: Arc<Mutex<Opti
     };
 
     let arch = binary.arch_spec.clone();
-    let bin_size = binary.data.len() as u64;
-    let bin_mtime = fs::metadata(&binary.path).ok()
-        .and_then(|m| m.modified().ok())
-        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-        .map(|d| d.as_secs());
-    let bin_id = BinaryId::new(Some(binary.path.clone()), bin_size, arch.clone(), bin_mtime);
+    let bin_id = BinaryId::for_binary(binary);
     let bin_bytes = binary.data.clone();
     let bin_base = binary.image_base;
 
     let shared_client = ghidra_client;
+    let config = state.client_config.clone();
     let funcs = TOKIO_RUNTIME.block_on(async move {
         let mut guard = shared_client.lock().unwrap();
         if guard.is_none() {
-            *guard = connect_with_backoff().await;
+            *guard = connect_with_backoff(&config).await;
         }
         let Some(client) = guard.as_mut() else { return None; };
         match client.load_binary_if_needed(bin_bytes, bin_base, &arch, bin_id).await {
@@ -80,6 +595,7 @@ pub fn preload_server_binary(state: &mut AppState, ghidra_client: Arc<Mutex<Opti
         if !server_funcs.is_empty() {
             let converted: Vec<FunctionInfo> = server_funcs.into_iter().map(convert_meta).collect();
             state.loaded_binary.as_mut().map(|b| b.functions = converted);
+            state.function_rows.key = None;
         }
     }
 }
@@ -92,23 +608,93 @@ pub fn convert_meta(m: FunctionMeta) -> FunctionInfo {
         size: m.size as u64,
         is_export: false,
         is_import: m.is_import,
+        ordinal: None,
+        forwarded_to: None,
+        is_tls_callback: false,
     }
 }
 
 /// Connect with backoff retry
-pub async fn connect_with_backoff() -> Option<GhidraClient> {
+pub async fn connect_with_backoff(config: &ClientConfig) -> Option<GhidraClient> {
     let delays = [Duration::from_millis(0), Duration::from_millis(200), Duration::from_millis(500)];
     for d in delays {
         if d.as_millis() > 0 {
             sleep(d).await;
         }
-        if let Ok(c) = GhidraClient::connect().await {
+        if let Ok(c) = GhidraClient::connect_with_config(config.clone()).await {
             return Some(c);
         }
     }
     None
 }
 
+/// How often the server monitor pings the shared client
+const SERVER_PING_INTERVAL: Duration = Duration::from_secs(5);
+/// Longest a single ping may hold the shared client
+const SERVER_PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Periodically ping the shared client and report changes as `ServerStatus`
+pub fn spawn_server_monitor(ghidra_client: Arc<Mutex<Option<GhidraClient>>>, tx: Sender<AsyncMessage>) {
+    std::thread::spawn(move || {
+        let mut last_status = None;
+        loop {
+            // A busy lock means a request is in flight; skip this round
+            let alive = match ghidra_client.try_lock() {
+                Ok(mut guard) => Some(match guard.as_mut() {
+                    Some(client) => TOKIO_RUNTIME.block_on(async {
+                        matches!(
+                            tokio::time::timeout(SERVER_PING_TIMEOUT, client.ping()).await,
+                            Ok(Ok(true))
+                        )
+                    }),
+                    None => false,
+                }),
+                Err(_) => None,
+            };
+
+            if let Some(alive) = alive.filter(|a| last_status != Some(*a)) {
+                if tx.send(AsyncMessage::ServerStatus(alive)).is_err() {
+                    return; // UI is gone
+                }
+                last_status = Some(alive);
+            }
+            std::thread::sleep(SERVER_PING_INTERVAL);
+        }
+    });
+}
+
+/// Drop the shared client, killing the server it started, and connect again
+/// with the current settings (spawning a server if none is running)
+pub fn restart_server(state: &mut AppState, tx: Sender<AsyncMessage>, ghidra_client: Arc<Mutex<Option<GhidraClient>>>) {
+    if state.server_restarting {
+        return;
+    }
+    if state.batch_progress.is_some() {
+        state.warn("Cancel Analyze All before restarting the server");
+        return;
+    }
+    super::decompiler::cancel_decompile(state);
+    state.server_restarting = true;
+    state.log("[*] Restarting Ghidra server...");
+    let config = state.client_config.clone();
+    std::thread::spawn(move || {
+        let result = TOKIO_RUNTIME.block_on(reconnect_fresh(ghidra_client, config));
+        let _ = tx.send(AsyncMessage::ServerRestarted(result));
+    });
+}
+
+/// Replace the shared client with a new connection; holds the lock
+/// throughout so no request reaches the old server meanwhile
+#[allow(clippy::await_holding_lock)]
+async fn reconnect_fresh(ghidra_client: Arc<Mutex<Option<GhidraClient>>>, config: ClientConfig) -> Result<bool, String> {
+    let mut guard = ghidra_client.lock().unwrap();
+    // Dropping the client kills a server process it owns
+    let killed = guard.take().is_some_and(|client| client.owns_server());
+    let client = GhidraClient::connect_with_config(config).await.map_err(|e| e.to_string())?;
+    *guard = Some(client);
+    Ok(killed)
+}
+
 /// Attempt to recover server connection with exponential backoff
 pub fn attempt_server_recovery(state: &mut AppState, tx: Sender<AsyncMessage>) {
     if state.recovering {
@@ -116,6 +702,7 @@ pub fn attempt_server_recovery(state: &mut AppState, tx: Sender<AsyncMessage>) {
     }
     
     state.recovering = true;
+    let config = state.client_config.clone();
     
     std::thread::spawn(move || {
         let rt = tokio::runtime::Runtime::new().unwrap();
@@ -125,7 +712,7 @@ pub fn attempt_server_recovery(state: &mut AppState, tx: Sender<AsyncMessage>) {
                 let wait_time = Duration::from_secs(1 << attempt);
                 tokio::time::sleep(wait_time).await;
                 
-                match GhidraClient::connect().await {
+                match GhidraClient::connect_with_config(config.clone()).await {
                     Ok(_client) => {
                         let _ = tx.send(AsyncMessage::ServerRecovered);
                         return;
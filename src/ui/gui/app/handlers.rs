@@ -1,10 +1,17 @@
 //! Message and command handlers.
 
+use std::collections::HashMap;
 use std::sync::mpsc::{Receiver, Sender};
 use std::sync::{Arc, Mutex};
 
-use crate::analysis::decomp::client::GhidraClient;
-use crate::ui::gui::state::AppState;
+use crate::analysis::decomp::client::{BinaryId, GhidraClient};
+use crate::analysis::diff::DiffKind;
+use crate::analysis::disasm::DisasmSyntax;
+use crate::analysis::loader::{FunctionInfo, LoadedBinary, RAW_ARCH_SPECS};
+use crate::analysis::strings::ExtractedString;
+use crate::app::{execute, parse_command, AppCommand, CommandContext, CommandOutput, ADDRESS_HELP, HELP};
+use crate::debug::types::RegisterState;
+use crate::ui::gui::state::{AppState, BottomTab, DebugAction, DebugBpAction, RawOpenRequest};
 use crate::ui::gui::messages::AsyncMessage;
 
 use super::debug_ops;
@@ -19,36 +26,120 @@ pub fn process_messages(
     ghidra_client: Arc<Mutex<Option<GhidraClient>>>,
     #[cfg(target_os = "windows")]
     dbg_event_rx: &Option<std::sync::mpsc::Receiver<crate::debug::types::DebugEvent>>,
+    #[cfg(target_os = "windows")]
+    debugger: &mut Option<crate::debug::PlatformDebugger>,
 ) {
     while let Ok(msg) = rx.try_recv() {
         match msg {
-            AsyncMessage::BinaryLoaded(Ok(binary)) => {
-                state.log(format!("[✓] Loaded: {}", binary.path));
+            AsyncMessage::BinaryLoaded { result: Ok(binary), elapsed } => {
+                state.success(format!("Loaded: {}", binary.path));
                 state.log(format!("    {} {} | Entry: 0x{:x}", 
                     if binary.is_64bit { "64-bit" } else { "32-bit" },
                     binary.format,
                     binary.entry_point));
-                state.log(format!("    {} functions found", binary.functions.len()));
+                state.log(format!("    {} | parsed in {:.2?}", format_size(binary.data.len()), elapsed));
+                for section in binary.packed_sections() {
+                    state.warn(format!("Looks packed: {} has entropy {:.2}", section.name, section.entropy));
+                }
+                let hashes = binary.hashes();
+                state.binary_hash = Some(hashes.sha256.clone());
+                state.binary_id = Some(BinaryId::for_binary(&binary));
+                state.rebased = false;
+                state.file_hashes = Some(hashes);
+                // Raw blobs need a base and arch, so they can't be reopened from the list
+                if binary.format != "Raw" {
+                    crate::app::recent::push(&mut state.recent_files, &binary.path);
+                    if let Err(e) = crate::app::recent::save(&state.recent_files) {
+                        log::warn!("Failed to save recent files: {}", e);
+                    }
+                }
+                state.insn_search_results.clear();
+                state.insn_search_status.clear();
+                state.yara_results.clear();
+                state.yara_status.clear();
+                state.iat_resolved.clear();
+                state.strings_section = None;
+                // Offsets into the previous file's bytes
+                state.hex_back.clear();
+                state.secondary_binary = None;
+                state.function_diff.clear();
+                state.diff_selected = None;
+                state.diff_code = None;
                 state.loaded_binary = Some(binary);
+                state.functions_loading = true;
+                state.publish_binary();
+            }
+            AsyncMessage::FunctionsBatch(batch) => {
+                if let Some(binary) = state.loaded_binary.as_mut() {
+                    batch.apply(binary);
+                }
+                // Debug-info batches can rename and resize without adding rows
+                state.function_rows.key = None;
+                // One batch per frame so the functions list repaints in between
+                break;
+            }
+            AsyncMessage::LoadComplete => {
+                state.functions_loading = false;
+                if let Some(binary) = state.loaded_binary.as_ref() {
+                    let count = |pred: fn(&FunctionInfo) -> bool| binary.functions.iter().filter(|f| pred(f)).count();
+                    state.log(format!("    {} functions found ({} imports, {} exports, {} discovered)",
+                        binary.functions.len(),
+                        count(|f| f.is_import),
+                        count(|f| f.is_export),
+                        count(|f| !f.is_import && !f.is_export)));
+                }
                 file_ops::preload_server_binary(state, ghidra_client.clone());
+                state.publish_binary();
+                state.pending_rebase = state.rebase_after_load.take();
+
+                if let Some(session) = state.pending_session.take() {
+                    if let Some(func) = file_ops::apply_session(state, session) {
+                        state.selected_function = Some(func.clone());
+                        decompiler::decompile_function(state, tx.clone(), ghidra_client.clone(), &func);
+                    }
+                }
+            }
+            AsyncMessage::BinaryLoaded { result: Err(e), .. } => {
+                state.error(format!("Failed to load binary: {}", e));
+            }
+            AsyncMessage::DecompileResult { id, address, c_code, pcode, asm, stack_vars, line_map } => {
+                let ghidra_asm = decompiler::ghidra_listing(state, asm);
+                decompiler::cache_decompile_result(state, id, address, c_code, pcode, ghidra_asm, stack_vars, line_map);
+                state.success(format!("Decompiled 0x{:x} (cached)", address));
+            }
+            AsyncMessage::BatchDecompiled { address, c_code, pcode, stack_vars, line_map } => {
+                // Abandoned batches belong to a previously loaded binary
+                if state.batch_cancel.is_some() {
+                    decompiler::cache_batch_result(state, address, c_code, pcode, stack_vars, line_map);
+                }
             }
-            AsyncMessage::BinaryLoaded(Err(e)) => {
-                state.log(format!("[✗] Failed to load binary: {}", e));
+            AsyncMessage::BatchProgress { done, total } => {
+                state.batch_progress = Some((done, total));
             }
-            AsyncMessage::DecompileResult { address, c_code } => {
-                decompiler::cache_decompile_result(state, address, c_code.clone());
-                state.log(format!("[✓] Decompiled 0x{:x} (cached)", address));
+            AsyncMessage::BatchFinished { decompiled, failed, cancelled, error } => {
+                state.batch_progress = None;
+                state.batch_cancel = None;
+                match error {
+                    Some(e) => state.error(format!("Analysis stopped after {} functions: {}", decompiled, e)),
+                    None if cancelled => state.log(format!("[*] Analysis cancelled after {} functions", decompiled)),
+                    None => state.success(format!("Analyzed {} functions ({} failed)", decompiled, failed)),
+                }
             }
             AsyncMessage::DecompileError { address: _, error } => {
                 state.decompiled_code = format!("// Error: {}", error);
                 state.decompiling = false;
-                state.log(format!("[✗] Decompile error: {}", error));
+                state.decompile_cancel = None;
+                state.error(format!("Decompile error: {}", error));
                 
                 // Check if this is a connection error
                 if error.contains("transport") || error.contains("connection") {
                     let _ = tx.send(AsyncMessage::ServerDisconnected);
                 }
             }
+            AsyncMessage::DecompileCancelled { address } => {
+                // UI state was already reset when the cancel was requested
+                state.log(format!("[*] Decompilation of 0x{:x} cancelled", address));
+            }
             AsyncMessage::ServerStatus(connected) => {
                 state.server_connected = connected;
             }
@@ -58,29 +149,198 @@ pub fn process_messages(
             AsyncMessage::FileSelected(None) => {
                 // User cancelled
             }
+            AsyncMessage::PdbSelected(Some(path)) => {
+                file_ops::load_pdb(state, &path);
+            }
+            AsyncMessage::PdbSelected(None) => {
+                // User cancelled
+            }
+            AsyncMessage::RawFileSelected(Some(path)) => {
+                state.raw_open = Some(RawOpenRequest {
+                    path,
+                    base_input: "0x0".to_string(),
+                    arch_spec: RAW_ARCH_SPECS[0],
+                });
+            }
+            AsyncMessage::RawFileSelected(None) => {
+                // User cancelled
+            }
             AsyncMessage::ServerDisconnected => {
                 state.server_connected = false;
-                state.log("[!] Server disconnected. Attempting recovery...");
+                state.warn("Server disconnected. Attempting recovery...");
                 file_ops::attempt_server_recovery(state, tx.clone());
             }
+            AsyncMessage::ClientReconnected { respawned } => {
+                state.server_connected = true;
+                if respawned {
+                    state.warn("Ghidra server exited; restarted it and reconnected");
+                } else {
+                    state.log("[*] Reconnected to Ghidra server");
+                }
+            }
             AsyncMessage::ServerRecovered => {
                 state.server_connected = true;
                 state.recovering = false;
-                state.log("[✓] Server reconnected successfully");
+                state.success("Server reconnected successfully");
                 
                 // Reload binary if we had one loaded
                 if let Some(path) = state.last_binary_path.clone() {
                     state.log("[*] Reloading binary...");
+                    state.rebase_after_load = state.loaded_binary.as_ref()
+                        .filter(|_| state.rebased)
+                        .map(|b| b.image_base);
                     file_ops::load_binary(state, tx.clone(), &path);
                 }
             }
             AsyncMessage::RecoveryFailed(reason) => {
                 state.recovering = false;
-                state.log(format!("[✗] Server recovery failed: {}", reason));
+                state.error(format!("Server recovery failed: {}", reason));
+            }
+            AsyncMessage::ServerRestarted(result) => {
+                state.server_restarting = false;
+                match result {
+                    Ok(true) => {
+                        state.server_connected = true;
+                        state.success("Ghidra server restarted");
+                    }
+                    Ok(false) => {
+                        state.server_connected = true;
+                        state.success("Connected to Ghidra server (it was not started by Fission, so it was left running)");
+                    }
+                    Err(e) => {
+                        state.server_connected = false;
+                        state.error(format!("Server restart failed: {}", e));
+                    }
+                }
             }
             AsyncMessage::DebugEvent(evt) => {
                 debug_ops::handle_debug_event(state, evt);
             }
+            AsyncMessage::PatchedPathSelected(Some(path)) => {
+                file_ops::write_patched(state, &path);
+            }
+            AsyncMessage::PatchedPathSelected(None) => {
+                // User cancelled
+            }
+            AsyncMessage::SessionSaved(Some(Ok(path))) => {
+                state.success(format!("Session saved to {}", path));
+            }
+            AsyncMessage::SessionSaved(Some(Err(e))) => {
+                state.error(format!("Failed to save session: {}", e));
+            }
+            AsyncMessage::SessionLoaded(Some(Ok(session))) => {
+                match session.binary_path.clone() {
+                    Some(path) => {
+                        state.pending_session = Some(session);
+                        file_ops::load_binary(state, tx.clone(), &path);
+                    }
+                    None => state.warn("Session has no binary path"),
+                }
+            }
+            AsyncMessage::SessionLoaded(Some(Err(e))) => {
+                state.error(format!("Failed to load session: {}", e));
+            }
+            AsyncMessage::SessionSaved(None) | AsyncMessage::SessionLoaded(None) => {
+                // User cancelled
+            }
+            AsyncMessage::StringsExported(Some(Ok((path, count)))) => {
+                state.success(format!("Exported {} strings to {}", count, path));
+            }
+            AsyncMessage::StringsExported(Some(Err(e))) => {
+                state.error(format!("Failed to export strings: {}", e));
+            }
+            AsyncMessage::StringsExported(None) => {
+                // User cancelled
+            }
+            AsyncMessage::DisassemblyExported(Some(Ok((path, count)))) => {
+                state.success(format!("Exported {} instructions to {}", count, path));
+            }
+            AsyncMessage::DisassemblyExported(Some(Err(e))) => {
+                state.error(format!("Failed to export disassembly: {}", e));
+            }
+            AsyncMessage::DisassemblyExported(None) => {
+                // User cancelled
+            }
+            AsyncMessage::InsnSearchFinished(result) => {
+                state.insn_search_running = false;
+                match result {
+                    Ok(matches) => decompiler::show_insn_search_results(state, matches),
+                    Err(e) => state.insn_search_status = e,
+                }
+            }
+            AsyncMessage::SecondaryFileSelected(Some(path)) => {
+                file_ops::load_secondary_binary(state, tx.clone(), path);
+            }
+            AsyncMessage::SecondaryFileSelected(None) => {
+                // User cancelled
+            }
+            AsyncMessage::SecondaryLoaded(Ok((binary, rows))) => {
+                state.diff_loading = false;
+                let count = |kind| rows.iter().filter(|d| d.kind == kind).count();
+                state.success(format!("Diffed against {}: {} added, {} removed, {} changed",
+                    binary.path, count(DiffKind::Added), count(DiffKind::Removed), count(DiffKind::Changed)));
+                state.secondary_binary = Some(binary);
+                state.function_diff = rows;
+                state.diff_selected = None;
+                state.diff_code = None;
+                state.show_diff = true;
+            }
+            AsyncMessage::SecondaryLoaded(Err(e)) => {
+                state.diff_loading = false;
+                state.error(format!("Failed to load binary to diff: {}", e));
+            }
+            AsyncMessage::DiffDecompiled { index, primary, secondary } => {
+                // Ignore results for a row that is no longer selected
+                if state.diff_selected == Some(index) {
+                    state.diff_code = Some((primary, secondary));
+                }
+            }
+            AsyncMessage::YaraRulesSelected(Some(path)) => {
+                file_ops::start_yara_scan(state, tx.clone(), path);
+            }
+            AsyncMessage::YaraRulesSelected(None) => {
+                // User cancelled
+            }
+            AsyncMessage::YaraScanFinished(result) => {
+                state.yara_running = false;
+                match result {
+                    Ok(matches) => {
+                        let hits: usize = matches.iter().map(|m| m.strings.len()).sum();
+                        state.yara_status = format!("{} rules matched, {} string hits", matches.len(), hits);
+                        state.success(format!("YARA: {}", state.yara_status));
+                        state.yara_results = matches;
+                    }
+                    Err(e) => {
+                        state.error(format!("YARA scan failed: {}", e));
+                        state.yara_status = e;
+                    }
+                }
+                state.bottom_tab = BottomTab::Yara;
+            }
+            AsyncMessage::OverlayExported(Some(Ok((path, len)))) => {
+                state.success(format!("Saved {} overlay bytes to {}", len, path));
+            }
+            AsyncMessage::OverlayExported(Some(Err(e))) => {
+                state.error(format!("Failed to save overlay: {}", e));
+            }
+            AsyncMessage::OverlayExported(None) => {
+                // User cancelled
+            }
+            AsyncMessage::MemoryDumped(Some(Ok((path, report)))) => {
+                state.success(format!(
+                    "Dumped 0x{:x} of 0x{:x} bytes at 0x{:x} to {}",
+                    report.bytes_read, report.size, report.address, path
+                ));
+                for line in report.gap_lines() {
+                    state.warn(line);
+                }
+            }
+            AsyncMessage::MemoryDumped(Some(Err(e))) => {
+                state.error(format!("Failed to save memory dump: {}", e));
+            }
+            AsyncMessage::MemoryDumped(None) => {
+                // User cancelled
+            }
         }
     }
 
@@ -93,52 +353,154 @@ pub fn process_messages(
             }
         }
         for evt in pending {
-            debug_ops::handle_debug_event(state, evt);
+            if debug_ops::prepare_debug_event(state, debugger, &evt) {
+                debug_ops::handle_debug_event(state, evt);
+            }
         }
     }
 }
 
-/// Process a CLI command
+/// Process a console command through the shared command processor
 pub fn process_command(
     state: &mut AppState,
     tx: Sender<AsyncMessage>,
     cmd: &str,
 ) {
-    match cmd {
-        "help" | "?" => {
+    let output = match parse_command(cmd) {
+        AppCommand::Help => {
             state.log("Available commands:");
-            state.log("  load <path>  : Load a binary for analysis");
-            state.log("  funcs        : List functions");
-            state.log("  clear        : Clear console");
-            state.log("  exit         : Quit Fission");
-        }
-        "funcs" | "functions" => {
-            if let Some(ref binary) = state.loaded_binary {
-                let funcs: Vec<_> = binary.functions.iter()
-                    .map(|f| (f.address, f.name.clone()))
-                    .collect();
-                state.log(format!("[*] {} functions:", funcs.len()));
-                for (addr, name) in funcs {
-                    state.log(format!("  0x{:08x} {}", addr, name));
+            for (_, entries) in HELP {
+                for (usage, description) in *entries {
+                    state.log(format!("  {:<15}  {}", usage, description));
                 }
-            } else {
-                state.log("[!] No binary loaded");
             }
+            state.log(ADDRESS_HELP);
+            return;
         }
-        "clear" => {
+        AppCommand::Clear => {
             state.clear_logs();
-            state.log("[*] Console cleared");
+            return;
+        }
+        AppCommand::Quit => std::process::exit(0),
+        AppCommand::LoadBinary(path) => {
+            file_ops::load_binary(state, tx, &path);
+            return;
+        }
+        AppCommand::Attach(pid) => {
+            state.pending_attach = Some(pid);
+            return;
+        }
+        AppCommand::Detach => {
+            state.pending_detach = true;
+            return;
+        }
+        // The section layout is the fallback without a process
+        AppCommand::MemoryMap if state.is_debugging => {
+            state.pending_memory_map = true;
+            return;
+        }
+        AppCommand::Continue => {
+            state.pending_debug_action = Some(DebugAction::Continue);
+            return;
+        }
+        AppCommand::StepInto => {
+            state.pending_debug_action = Some(DebugAction::Step);
+            return;
+        }
+        AppCommand::StepOver => {
+            state.pending_debug_action = Some(DebugAction::StepOver);
+            return;
+        }
+        AppCommand::Python(code) => {
+            let result = state.python.initialize()
+                .map_err(|e| e.to_string())
+                .and_then(|()| state.python.execute(&code).map_err(|e| e.to_string()));
+            match result {
+                Ok(value) => state.log(value),
+                Err(e) => state.error(e),
+            }
+            return;
         }
-        "exit" | "quit" => {
-            std::process::exit(0);
+        command => execute(state, command),
+    };
+
+    match output {
+        // Applied later this frame, like requests from the other panels
+        Ok(CommandOutput::Seek(address)) => state.pending_goto = Some(address),
+        Ok(CommandOutput::Decompile(func)) => state.pending_goto = Some(func.address),
+        Ok(CommandOutput::Patch { address, bytes }) => state.pending_patch = Some((address, bytes)),
+        Ok(CommandOutput::Dump { address, size, path }) => state.pending_mem_dump_to = Some((address, size, path)),
+        Ok(CommandOutput::Rebase { base }) => state.pending_rebase = Some(base),
+        Ok(CommandOutput::Breakpoint { address, set }) => {
+            state.pending_bp_action = Some(if set {
+                DebugBpAction::Add(address)
+            } else {
+                DebugBpAction::Remove(address)
+            });
         }
-        _ if cmd.starts_with("load ") => {
-            let path = cmd.trim_start_matches("load ").trim();
-            file_ops::load_binary(state, tx, path);
+        Ok(output) => {
+            for line in output.lines() {
+                state.log(line);
+            }
         }
-        _ => {
-            state.log(format!("[!] Unknown command: {}", cmd));
+        Err(e) => state.warn(e),
+    }
+}
+
+impl CommandContext for AppState {
+    fn binary(&self) -> Option<&LoadedBinary> {
+        self.loaded_binary.as_ref()
+    }
+
+    fn seek(&self) -> u64 {
+        self.current_address().unwrap_or_default()
+    }
+
+    fn function_renames(&self) -> &HashMap<u64, String> {
+        &self.function_renames
+    }
+
+    fn function_renames_mut(&mut self) -> &mut HashMap<u64, String> {
+        &mut self.function_renames
+    }
+
+    fn disasm_syntax(&self) -> DisasmSyntax {
+        self.disasm_syntax
+    }
+
+    fn set_disasm_syntax(&mut self, syntax: DisasmSyntax) {
+        if self.disasm_syntax != syntax {
+            self.disasm_syntax = syntax;
+            decompiler::refresh_disassembly(self);
         }
     }
+
+    fn registers(&self) -> Option<RegisterState> {
+        self.debug_state.registers.clone()
+    }
+
+    fn strings(&self) -> &[ExtractedString] {
+        &self.extracted_strings
+    }
+
+    /// In the file's address space, like the rest of the commands see
+    fn register_address(&self, name: &str) -> Option<u64> {
+        self.debug_state.registers.as_ref()?.get(name).map(|value| self.to_static(value))
+    }
 }
 
+/// Human-readable byte count, e.g. `1.5 MiB`
+fn format_size(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
@@ -10,19 +10,25 @@ pub mod handlers;
 
 use eframe::egui;
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
 
-use crate::analysis::decomp::client::GhidraClient;
+use crate::analysis::decomp::client::{ClientConfig, GhidraClient};
 use crate::analysis::loader::FunctionInfo;
+use crate::app::export;
 #[cfg(target_os = "windows")]
 use crate::debug::PlatformDebugger;
 
-use super::state::AppState;
+use super::state::{AppState, DebugAction};
 use super::messages::AsyncMessage;
 use super::menu::{self, MenuAction};
 use super::status_bar;
-use super::panels::{functions, assembly, decompile, bottom_tabs};
+use super::panels::{functions, assembly, decompile, bottom_tabs, diff, ghidra_settings, insn_search};
 use super::panels::bottom_tabs::ConsoleAction;
+use super::panels::assembly::AssemblyAction;
+use super::panels::diff::DiffAction;
+use super::panels::ghidra_settings::GhidraSettingsAction;
+use super::panels::insn_search::InsnSearchAction;
 
 use once_cell::sync::Lazy;
 use tokio::runtime::Runtime;
@@ -63,9 +69,23 @@ pub struct FissionApp {
 
 impl Default for FissionApp {
     fn default() -> Self {
+        Self::new(ClientConfig::default())
+    }
+}
+
+impl FissionApp {
+    /// Create the app with explicit Ghidra server settings
+    pub fn new(client_config: ClientConfig) -> Self {
         let (tx, rx) = channel();
+        let ghidra_client = Arc::new(Mutex::new(None));
+        file_ops::spawn_server_monitor(Arc::clone(&ghidra_client), tx.clone());
+        let mut state = AppState {
+            client_config,
+            ..AppState::default()
+        };
+        state.python.set_ghidra_client(Arc::clone(&ghidra_client));
         Self {
-            state: AppState::default(),
+            state,
             rx,
             tx,
             #[cfg(target_os = "windows")]
@@ -74,7 +94,7 @@ impl Default for FissionApp {
             dbg_event_rx: None,
             #[cfg(target_os = "windows")]
             dbg_stop_tx: None,
-            ghidra_client: Arc::new(Mutex::new(None)),
+            ghidra_client,
             theme_initialized: false,
         }
     }
@@ -84,10 +104,55 @@ impl eframe::App for FissionApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Initialize theme on first frame
         if !self.theme_initialized {
-            super::theme::init(ctx);
+            super::theme::init(ctx, self.state.theme, self.state.ui_scale);
             self.theme_initialized = true;
         }
 
+        // Text size shortcuts: Ctrl + / Ctrl - / Ctrl 0
+        let scale_key = ctx.input_mut(|i| {
+            let cmd = egui::Modifiers::COMMAND;
+            if i.consume_key(cmd, egui::Key::Plus) || i.consume_key(cmd, egui::Key::Equals) {
+                Some(self.state.ui_scale + menu::UI_SCALE_STEP)
+            } else if i.consume_key(cmd, egui::Key::Minus) {
+                Some(self.state.ui_scale - menu::UI_SCALE_STEP)
+            } else if i.consume_key(cmd, egui::Key::Num0) {
+                Some(1.0)
+            } else {
+                None
+            }
+        });
+        if let Some(scale) = scale_key {
+            self.set_ui_scale(ctx, scale);
+        }
+
+        // Dropped files load like File > Open (only the first is used)
+        let dropped: Vec<_> = ctx.input(|i| i.raw.dropped_files.iter().filter_map(|f| f.path.clone()).collect());
+        if let Some(path) = dropped.first() {
+            if dropped.len() > 1 {
+                self.state.warn(format!("{} files dropped; loading only {}", dropped.len(), path.display()));
+            }
+            let _ = self.tx.send(AsyncMessage::FileSelected(Some(path.display().to_string())));
+        }
+
+        // Debugger shortcuts: F5 continue, F10 step over, F11 step into
+        if self.state.debug_state.attached_pid.is_some() && !ctx.wants_keyboard_input() {
+            let action = ctx.input_mut(|i| {
+                let none = egui::Modifiers::NONE;
+                if i.consume_key(none, egui::Key::F5) {
+                    Some(DebugAction::Continue)
+                } else if i.consume_key(none, egui::Key::F10) {
+                    Some(DebugAction::StepOver)
+                } else if i.consume_key(none, egui::Key::F11) {
+                    Some(DebugAction::Step)
+                } else {
+                    None
+                }
+            });
+            if action.is_some() {
+                self.state.pending_debug_action = action;
+            }
+        }
+
         // Process async messages
         #[cfg(target_os = "windows")]
         handlers::process_messages(
@@ -96,6 +161,7 @@ impl eframe::App for FissionApp {
             &self.tx,
             self.ghidra_client.clone(),
             &self.dbg_event_rx,
+            &mut self.debugger,
         );
         #[cfg(not(target_os = "windows"))]
         handlers::process_messages(
@@ -105,9 +171,16 @@ impl eframe::App for FissionApp {
             self.ghidra_client.clone(),
         );
 
+        // Keep draining function batches without waiting for input
+        if self.state.functions_loading {
+            ctx.request_repaint();
+        } else if self.state.batch_progress.is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+
         // Render menu bar and handle actions
         let menu_action = menu::render(ctx, &mut self.state);
-        self.handle_menu_action(menu_action);
+        self.handle_menu_action(ctx, menu_action);
 
         // Render status bar
         status_bar::render(ctx, &self.state);
@@ -125,12 +198,81 @@ impl eframe::App for FissionApp {
 
         // Process pending debug control requests
         self.handle_pending_debug_actions();
+
+        // Process pending strings export
+        if let Some(lines) = self.state.pending_strings_export.take() {
+            file_ops::export_strings(lines, self.tx.clone());
+        }
+
+        // Process pending overlay extraction
+        if std::mem::take(&mut self.state.pending_overlay_export) {
+            if let Some(binary) = self.state.loaded_binary.as_ref() {
+                file_ops::export_overlay(binary, self.tx.clone());
+            }
+        }
         
         // Fixed right panel - Decompile
         decompile::render(ctx, &mut self.state);
         
         // Main content - Assembly
-        assembly::render(ctx, &self.state);
+        match assembly::render(ctx, &mut self.state) {
+            AssemblyAction::Follow { from, target } => {
+                if self.navigate_to(target) {
+                    self.state.nav_history.push(from);
+                } else {
+                    self.state.warn(format!("0x{:x} is not inside a known function", target));
+                }
+            }
+            AssemblyAction::GoTo(target) => self.go_to(target),
+            AssemblyAction::ExtendBack { from_row } => {
+                let added = decompiler::extend_backward(&mut self.state);
+                let row = (from_row + added).saturating_sub(assembly::PAGE_ROWS);
+                if let Some(insn) = self.state.asm_instructions.get(row) {
+                    self.state.asm_scroll_to = Some(insn.address);
+                }
+            }
+            AssemblyAction::Resize { grow } => {
+                decompiler::resize_listing(&mut self.state, grow);
+            }
+            AssemblyAction::Export => {
+                if let Some(binary) = self.state.loaded_binary.as_ref() {
+                    let state = &self.state;
+                    let lines = export::format_listing(binary, &state.function_renames, &state.extracted_strings, &state.stack_notes, state.listing());
+                    let name = match (&state.selected_function, state.listing().first()) {
+                        (Some(func), _) => state.function_name(func),
+                        (None, Some(first)) => format!("listing_{:08x}", first.address),
+                        (None, None) => "listing".to_string(),
+                    };
+                    file_ops::export_disassembly(lines, &name, self.tx.clone());
+                }
+            }
+            AssemblyAction::Back => {
+                if let Some(addr) = self.state.nav_history.pop() {
+                    self.navigate_to(addr);
+                }
+            }
+            AssemblyAction::None => {}
+        }
+
+        // Patch confirmed in the assembly view
+        if let Some((address, bytes)) = self.state.pending_patch.take() {
+            self.apply_patch(address, &bytes);
+        }
+
+        // Rebase requested from the File menu or the console
+        if let Some(base) = self.state.pending_rebase.take() {
+            let ghidra_client = self.ghidra_client.clone();
+            if let Some(address) = file_ops::rebase_binary(&mut self.state, ghidra_client, base) {
+                if !self.navigate_to(address) {
+                    decompiler::disassemble_at(&mut self.state, address);
+                }
+            }
+        }
+
+        // Navigation requested from other panels
+        if let Some(target) = self.state.pending_goto.take() {
+            self.go_to(target);
+        }
 
         // Handle function click
         if let Some(func) = clicked_func {
@@ -138,28 +280,133 @@ impl eframe::App for FissionApp {
             self.decompile_function(&func);
         }
 
+        // Find Instructions window
+        match insn_search::render(ctx, &mut self.state) {
+            InsnSearchAction::Search => decompiler::search_instructions(&mut self.state, self.tx.clone()),
+            InsnSearchAction::GoTo(address) => self.go_to(address),
+            InsnSearchAction::None => {}
+        }
+
+        // Binary Diff window
+        match diff::render(ctx, &mut self.state) {
+            DiffAction::Compare(index) => decompiler::decompile_diff(
+                &mut self.state, self.tx.clone(), self.ghidra_client.clone(), index,
+            ),
+            DiffAction::GoTo(address) => self.go_to(address),
+            DiffAction::None => {}
+        }
+
+        // Ghidra Settings window
+        if let GhidraSettingsAction::RestartServer = ghidra_settings::render(ctx, &mut self.state) {
+            file_ops::restart_server(&mut self.state, self.tx.clone(), self.ghidra_client.clone());
+        }
+
         // Render attach dialog
         self.render_attach_dialog(ctx);
+
+        // Render rebase prompt
+        if let Some(base) = file_ops::render_rebase_dialog(&mut self.state, ctx) {
+            self.state.pending_rebase = Some(base);
+        }
+
+        // Render raw-open prompt
+        if let Some((path, base, arch)) = file_ops::render_raw_open_dialog(&mut self.state, ctx) {
+            file_ops::load_raw_binary(&mut self.state, self.tx.clone(), path, base, arch);
+        }
     }
 }
 
 impl FissionApp {
-    fn handle_menu_action(&mut self, action: MenuAction) {
+    fn handle_menu_action(&mut self, ctx: &egui::Context, action: MenuAction) {
         match action {
             MenuAction::OpenFile => file_ops::open_file_dialog(self.tx.clone()),
+            MenuAction::OpenRecent(path) => {
+                if std::path::Path::new(&path).exists() {
+                    file_ops::load_binary(&mut self.state, self.tx.clone(), &path);
+                } else {
+                    self.state.recent_files.retain(|p| *p != path);
+                    let _ = crate::app::recent::save(&self.state.recent_files);
+                    self.state.error(format!("{} no longer exists", path));
+                }
+            }
+            MenuAction::ClearRecent => {
+                self.state.recent_files.clear();
+                let _ = crate::app::recent::save(&self.state.recent_files);
+            }
+            MenuAction::OpenRaw => file_ops::open_raw_dialog(self.tx.clone()),
+            MenuAction::LoadPdb => file_ops::open_pdb_dialog(self.tx.clone()),
+            MenuAction::Rebase => {
+                self.state.rebase_input = self.state.loaded_binary.as_ref()
+                    .map(|b| format!("0x{:x}", b.image_base));
+            }
+            MenuAction::SavePatched => {
+                if let Some(binary) = &self.state.loaded_binary {
+                    file_ops::save_patched_dialog(&binary.path, self.tx.clone());
+                }
+            }
+            MenuAction::SaveSession => {
+                file_ops::save_session(self.state.to_session(), self.tx.clone());
+            }
+            MenuAction::LoadSession => file_ops::load_session(self.tx.clone()),
             MenuAction::AttachToProcess => {
                 self.state.show_attach_dialog = true;
                 self.state.process_list = crate::debug::enumerate_processes();
             }
             MenuAction::DetachProcess => self.detach_process(),
-            MenuAction::ClearConsole => {
-                self.state.clear_logs();
-                self.state.log("[*] Console cleared");
-            }
+            MenuAction::ClearConsole => self.state.clear_logs(),
+            MenuAction::ResetConsole => self.state.reset_logs(),
             MenuAction::ClearCache => {
                 let count = self.state.decompile_cache.len();
                 self.state.decompile_cache.clear();
                 self.state.log(format!("[*] Cleared {} cached items", count));
+                if let Some(cache) = &self.state.disk_cache {
+                    match cache.clear() {
+                        Ok(n) => self.state.log(format!("[*] Cleared {} disk cache entries", n)),
+                        Err(e) => self.state.warn(format!("Failed to clear disk cache: {}", e)),
+                    }
+                }
+            }
+            MenuAction::AnalyzeAll => {
+                decompiler::decompile_all(&mut self.state, self.tx.clone(), self.ghidra_client.clone());
+            }
+            MenuAction::FindInstructions => self.state.show_insn_search = true,
+            MenuAction::YaraScan => file_ops::open_yara_dialog(self.tx.clone()),
+            MenuAction::DiffAgainst => file_ops::open_secondary_dialog(self.tx.clone()),
+            MenuAction::ShowDiff => self.state.show_diff = true,
+            MenuAction::ShowGhidraSettings => self.state.show_ghidra_settings = true,
+            MenuAction::CancelAnalyzeAll => {
+                if let Some(flag) = &self.state.batch_cancel {
+                    flag.store(true, Ordering::Relaxed);
+                    self.state.log("[*] Cancelling analysis...");
+                }
+            }
+            MenuAction::SetSyntax(syntax) => {
+                if self.state.disasm_syntax != syntax {
+                    self.state.disasm_syntax = syntax;
+                    decompiler::refresh_disassembly(&mut self.state);
+                    self.state.log(format!("[*] Assembly syntax: {}", syntax));
+                }
+            }
+            MenuAction::SetUiScale(scale) => self.set_ui_scale(ctx, scale),
+            MenuAction::SetTheme(flavor) => {
+                if self.state.theme != flavor {
+                    self.state.theme = flavor;
+                    super::theme::init(ctx, flavor, self.state.ui_scale);
+                    self.save_settings();
+                }
+            }
+            MenuAction::SetAsmWindow(bytes) => {
+                let bytes = crate::app::settings::clamp_asm_window(bytes);
+                if self.state.asm_window != bytes {
+                    self.state.asm_window = bytes;
+                    self.save_settings();
+                    self.state.log(format!("[*] Listing window: 0x{:x} bytes", bytes));
+                }
+            }
+            MenuAction::SetStackVarNames(enabled) => {
+                self.state.stack_var_names = enabled;
+                decompiler::refresh_stack_notes(&mut self.state);
+                self.save_settings();
             }
             MenuAction::ShowAbout => {
                 self.state.log("[*] Fission v0.1.0 - Ghidra-Powered Analysis Platform");
@@ -169,7 +416,66 @@ impl FissionApp {
         }
     }
 
+    /// Write `bytes` into the binary's patch overlay (and the attached
+    /// process), then redo the listing so it shows the new instructions
+    fn apply_patch(&mut self, address: u64, bytes: &[u8]) {
+        let Some(binary) = self.state.loaded_binary.as_mut() else {
+            return;
+        };
+        if let Err(e) = binary.patch(address, bytes) {
+            self.state.error(format!("Patch failed: {}", e));
+            return;
+        }
+        self.state.success(format!("Patched {} bytes at 0x{:x}", bytes.len(), address));
+        self.state.publish_binary();
+        // Strings the decompiled code points at may have been overwritten
+        self.state.annotated_code.key = None;
+
+        #[cfg(target_os = "windows")]
+        debug_ops::write_patch(&mut self.state, &self.debugger, address, bytes);
+        #[cfg(not(target_os = "windows"))]
+        debug_ops::write_patch(&mut self.state, address, bytes);
+
+        decompiler::refresh_disassembly(&mut self.state);
+    }
+
+    /// Rebuild text styles at a new scale and remember it
+    fn set_ui_scale(&mut self, ctx: &egui::Context, scale: f32) {
+        let scale = crate::app::settings::clamp_ui_scale(scale);
+        if (scale - self.state.ui_scale).abs() < f32::EPSILON {
+            return;
+        }
+        self.state.ui_scale = scale;
+        super::theme::configure_fonts(ctx, scale);
+        self.save_settings();
+    }
+
+    /// Persist the GUI preferences
+    fn save_settings(&mut self) {
+        let settings = crate::app::settings::Settings {
+            ui_scale: self.state.ui_scale,
+            theme: self.state.theme.name().to_string(),
+            asm_window: self.state.asm_window,
+            stack_var_names: self.state.stack_var_names,
+        };
+        if let Err(e) = settings.save() {
+            self.state.warn(format!("Failed to save settings: {}", e));
+        }
+    }
+
     fn handle_pending_debug_actions(&mut self) {
+        if let Some(pid) = self.state.pending_attach.take() {
+            self.attach_to_process(pid);
+        }
+        if std::mem::take(&mut self.state.pending_detach) {
+            self.detach_process();
+        }
+        if std::mem::take(&mut self.state.pending_memory_map) {
+            #[cfg(target_os = "windows")]
+            debug_ops::log_memory_map(&mut self.state, &mut self.debugger);
+            #[cfg(not(target_os = "windows"))]
+            debug_ops::log_memory_map(&mut self.state);
+        }
         if let Some(action) = self.state.pending_debug_action.take() {
             #[cfg(target_os = "windows")]
             debug_ops::handle_debug_action(&mut self.state, &mut self.debugger, action);
@@ -182,6 +488,96 @@ impl FissionApp {
             #[cfg(not(target_os = "windows"))]
             debug_ops::handle_bp_action(&mut self.state, bp_action);
         }
+        if std::mem::take(&mut self.state.pending_stack_refresh) {
+            #[cfg(target_os = "windows")]
+            debug_ops::refresh_stack(&mut self.state, &self.debugger);
+            // Keep an IAT view that was already read in sync with the process
+            self.state.pending_iat_read |= !self.state.iat_resolved.is_empty();
+            self.follow_ip();
+        }
+        if std::mem::take(&mut self.state.pending_iat_read) {
+            #[cfg(target_os = "windows")]
+            debug_ops::read_iat(&mut self.state, &mut self.debugger);
+            #[cfg(not(target_os = "windows"))]
+            debug_ops::read_iat(&mut self.state);
+        }
+        if std::mem::take(&mut self.state.pending_mem_read) {
+            #[cfg(target_os = "windows")]
+            debug_ops::read_memory(&mut self.state, &self.debugger);
+            #[cfg(not(target_os = "windows"))]
+            debug_ops::read_memory(&mut self.state);
+        }
+        let dump = self.state.pending_mem_dump_to.take()
+            .map(Some)
+            .or_else(|| std::mem::take(&mut self.state.pending_mem_dump).then_some(None));
+        if let Some(range) = dump {
+            #[cfg(target_os = "windows")]
+            debug_ops::dump_memory(&mut self.state, &self.debugger, range, self.tx.clone());
+            #[cfg(not(target_os = "windows"))]
+            debug_ops::dump_memory(&mut self.state);
+        }
+        if let Some((offset, len)) = self.state.pending_hex_live_read.take() {
+            #[cfg(target_os = "windows")]
+            debug_ops::read_hex_live(&mut self.state, &self.debugger, offset, len);
+            #[cfg(not(target_os = "windows"))]
+            debug_ops::read_hex_live(&mut self.state, offset, len);
+        }
+    }
+
+    /// Bring the suspended debuggee's current instruction into the assembly view.
+    ///
+    /// Addresses in the loaded binary use its bytes; anything else (e.g. a DLL)
+    /// is decoded from process memory.
+    fn follow_ip(&mut self) {
+        let Some(ip) = self.state.debug_ip().filter(|_| self.state.follow_ip) else {
+            return;
+        };
+        let ip = self.state.to_static(ip);
+        if self.state.listing().iter().any(|insn| insn.address == ip) {
+            self.state.asm_scroll_to = Some(ip);
+            return;
+        }
+
+        let mapped = self.state.loaded_binary.as_ref().is_some_and(|b| b.get_bytes(ip, 1).is_some());
+        let shown = self.navigate_to(ip) || (mapped && decompiler::disassemble_at(&mut self.state, ip));
+        #[cfg(target_os = "windows")]
+        if !shown {
+            if let Some(bytes) = debug_ops::read_code(&self.debugger, ip, self.state.asm_window) {
+                decompiler::show_raw_listing(&mut self.state, &bytes, ip);
+            }
+        }
+    }
+
+    /// Select the function containing `address` and scroll the assembly view to it.
+    ///
+    /// Returns false if no known function contains the address.
+    fn navigate_to(&mut self, address: u64) -> bool {
+        let func = self.state.loaded_binary.as_ref()
+            .and_then(|b| b.function_at(address))
+            .cloned();
+        let Some(func) = func else {
+            return false;
+        };
+
+        let already_selected = self.state.selected_function.as_ref()
+            .map(|f| f.address == func.address)
+            .unwrap_or(false);
+        if !already_selected {
+            self.state.selected_function = Some(func.clone());
+            self.decompile_function(&func);
+        }
+        self.state.asm_scroll_to = Some(address);
+        true
+    }
+
+    /// Jump to an arbitrary address, falling back to raw disassembly, and record history.
+    fn go_to(&mut self, target: u64) {
+        let from = self.state.current_address();
+        let found = self.navigate_to(target)
+            || decompiler::disassemble_at(&mut self.state, target);
+        if let (true, Some(from)) = (found, from) {
+            self.state.nav_history.push(from);
+        }
     }
 
     fn decompile_function(&mut self, func: &FunctionInfo) {
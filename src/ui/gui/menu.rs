@@ -2,15 +2,41 @@
 
 use eframe::egui;
 use super::state::AppState;
-use super::theme::catppuccin;
+use super::theme::{catppuccin, Flavor};
+use crate::analysis::disasm::DisasmSyntax;
+use crate::app::settings::DEFAULT_ASM_WINDOW;
+
+/// Text scale change per menu click or shortcut
+pub const UI_SCALE_STEP: f32 = 0.1;
 
 /// Actions triggered from menu
 pub enum MenuAction {
     OpenFile,
+    OpenRecent(String),
+    OpenRaw,
+    ClearRecent,
+    LoadPdb,
+    Rebase,
+    SavePatched,
+    SaveSession,
+    LoadSession,
     AttachToProcess,
     DetachProcess,
     ClearConsole,
+    ResetConsole,
     ClearCache,
+    AnalyzeAll,
+    FindInstructions,
+    YaraScan,
+    DiffAgainst,
+    ShowDiff,
+    ShowGhidraSettings,
+    CancelAnalyzeAll,
+    SetSyntax(DisasmSyntax),
+    SetUiScale(f32),
+    SetTheme(Flavor),
+    SetAsmWindow(usize),
+    SetStackVarNames(bool),
     ShowAbout,
     Exit,
     None,
@@ -26,23 +52,78 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> MenuAction {
         .exact_height(28.0)
         .show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
-                ui.menu_button(egui::RichText::new("File").color(catppuccin::TEXT), |ui| {
+                ui.menu_button(egui::RichText::new("File").color(catppuccin::text()), |ui| {
                     if ui.button(egui::RichText::new("📂 Open Binary...")
-                        .color(catppuccin::BLUE)).clicked() {
+                        .color(catppuccin::blue())).clicked() {
                         action = MenuAction::OpenFile;
                         ui.close_menu();
                     }
+                    ui.add_enabled_ui(!state.recent_files.is_empty(), |ui| {
+                        ui.menu_button(egui::RichText::new("🕘 Open Recent")
+                            .color(catppuccin::blue()), |ui| {
+                            for path in &state.recent_files {
+                                if ui.button(egui::RichText::new(path).color(catppuccin::text())).clicked() {
+                                    action = MenuAction::OpenRecent(path.clone());
+                                    ui.close_menu();
+                                }
+                            }
+                            ui.separator();
+                            if ui.button(egui::RichText::new("Clear Recent")
+                                .color(catppuccin::subtext0())).clicked() {
+                                action = MenuAction::ClearRecent;
+                                ui.close_menu();
+                            }
+                        });
+                    });
+                    if ui.button(egui::RichText::new("📄 Open Raw...")
+                        .color(catppuccin::blue())).clicked() {
+                        action = MenuAction::OpenRaw;
+                        ui.close_menu();
+                    }
+                    let is_pe = state.loaded_binary.as_ref().is_some_and(|b| b.format == "PE");
+                    if ui.add_enabled(is_pe, egui::Button::new(
+                        egui::RichText::new("🔣 Load PDB...").color(catppuccin::blue())
+                    )).clicked() {
+                        action = MenuAction::LoadPdb;
+                        ui.close_menu();
+                    }
+                    let can_rebase = state.loaded_binary.is_some() && !state.functions_loading;
+                    if ui.add_enabled(can_rebase, egui::Button::new(
+                        egui::RichText::new("📐 Rebase...").color(catppuccin::blue())
+                    )).clicked() {
+                        action = MenuAction::Rebase;
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    let patched = state.loaded_binary.as_ref().is_some_and(|b| !b.patches.is_empty());
+                    if ui.add_enabled(patched, egui::Button::new(
+                        egui::RichText::new("🩹 Save Patched As...").color(catppuccin::teal())
+                    )).clicked() {
+                        action = MenuAction::SavePatched;
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(state.loaded_binary.is_some(), egui::Button::new(
+                        egui::RichText::new("💾 Save Session...").color(catppuccin::teal())
+                    )).clicked() {
+                        action = MenuAction::SaveSession;
+                        ui.close_menu();
+                    }
+                    if ui.button(egui::RichText::new("📥 Load Session...")
+                        .color(catppuccin::teal())).clicked() {
+                        action = MenuAction::LoadSession;
+                        ui.close_menu();
+                    }
                     ui.separator();
                     if ui.button(egui::RichText::new("🚪 Exit")
-                        .color(catppuccin::RED)).clicked() {
+                        .color(catppuccin::red())).clicked() {
                         action = MenuAction::Exit;
                     }
                 });
 
-                ui.menu_button(egui::RichText::new("Debug").color(catppuccin::TEXT), |ui| {
+                ui.menu_button(egui::RichText::new("Debug").color(catppuccin::text()), |ui| {
                     if state.debug_state.attached_pid.is_some() {
                         if ui.button(egui::RichText::new("⏹ Detach")
-                            .color(catppuccin::RED)).clicked() {
+                            .color(catppuccin::red())).clicked() {
                             action = MenuAction::DetachProcess;
                             ui.close_menu();
                         }
@@ -53,13 +134,18 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> MenuAction {
                             "● Switch to Dynamic Mode"
                         };
                         if ui.button(egui::RichText::new(mode_text)
-                            .color(catppuccin::TEAL)).clicked() {
+                            .color(catppuccin::teal())).clicked() {
                             state.dynamic_mode = !state.dynamic_mode;
                             ui.close_menu();
                         }
+                        let slide = state.aslr_slide();
+                        let runtime_base = state.debug_state.image_base.unwrap_or_default();
+                        ui.add_enabled(slide.is_some(), egui::Checkbox::new(&mut state.runtime_addresses, "Runtime addresses"))
+                            .on_hover_text(format!("Show addresses where the image is loaded (0x{:x}) instead of file VAs", runtime_base))
+                            .on_disabled_hover_text("The image is loaded at its file base");
                     } else {
                         if ui.button(egui::RichText::new("🔗 Attach to Process...")
-                            .color(catppuccin::GREEN)).clicked() {
+                            .color(catppuccin::green())).clicked() {
                             action = MenuAction::AttachToProcess;
                             ui.close_menu();
                         }
@@ -70,24 +156,29 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> MenuAction {
                             "● Switch to Dynamic Mode"
                         };
                         if ui.button(egui::RichText::new(mode_text)
-                            .color(catppuccin::TEAL)).clicked() {
+                            .color(catppuccin::teal())).clicked() {
                             state.dynamic_mode = !state.dynamic_mode;
                             ui.close_menu();
                         }
                     }
                 });
 
-                ui.menu_button(egui::RichText::new("View").color(catppuccin::TEXT), |ui| {
+                ui.menu_button(egui::RichText::new("View").color(catppuccin::text()), |ui| {
                     ui.label(egui::RichText::new("Bottom Panel:")
-                        .color(catppuccin::SUBTEXT0).small());
+                        .color(catppuccin::subtext0()).small());
                     use super::state::BottomTab;
                     
                     let tabs = [
-                        (BottomTab::Console, "Console", catppuccin::BLUE),
-                        (BottomTab::HexView, "Hex View", catppuccin::PEACH),
-                        (BottomTab::Strings, "Strings", catppuccin::GREEN),
-                        (BottomTab::Imports, "Imports", catppuccin::MAUVE),
-                        (BottomTab::Debug, "Debug", catppuccin::RED),
+                        (BottomTab::Console, "Console", catppuccin::blue()),
+                        (BottomTab::HexView, "Hex View", catppuccin::peach()),
+                        (BottomTab::Strings, "Strings", catppuccin::green()),
+                        (BottomTab::Imports, "Imports", catppuccin::mauve()),
+                        (BottomTab::Sections, "Sections", catppuccin::teal()),
+                        (BottomTab::FileInfo, "File Info", catppuccin::lavender()),
+                        (BottomTab::Bookmarks, "Bookmarks", catppuccin::yellow()),
+                        (BottomTab::Debug, "Debug", catppuccin::red()),
+                        (BottomTab::Memory, "Memory", catppuccin::sapphire()),
+                        (BottomTab::Yara, "YARA", catppuccin::flamingo()),
                     ];
                     
                     for (tab, label, color) in tabs {
@@ -95,7 +186,7 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> MenuAction {
                         let text = if is_selected {
                             egui::RichText::new(format!("● {}", label)).color(color)
                         } else {
-                            egui::RichText::new(format!("  {}", label)).color(catppuccin::SUBTEXT0)
+                            egui::RichText::new(format!("  {}", label)).color(catppuccin::subtext0())
                         };
                         if ui.selectable_label(is_selected, text).clicked() {
                             state.bottom_tab = tab;
@@ -103,25 +194,133 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> MenuAction {
                         }
                     }
                     
+                    ui.separator();
+                    ui.label(egui::RichText::new("Assembly Syntax:")
+                        .color(catppuccin::subtext0()).small());
+                    for syntax in DisasmSyntax::ALL {
+                        if ui.radio(state.disasm_syntax == syntax, syntax.to_string()).clicked() {
+                            action = MenuAction::SetSyntax(syntax);
+                            ui.close_menu();
+                        }
+                    }
+                    
+                    ui.separator();
+                    ui.label(egui::RichText::new("Theme:")
+                        .color(catppuccin::subtext0()).small());
+                    for flavor in Flavor::ALL {
+                        if ui.radio(state.theme == flavor, flavor.to_string()).clicked() {
+                            action = MenuAction::SetTheme(flavor);
+                            ui.close_menu();
+                        }
+                    }
+
+                    ui.separator();
+                    ui.label(egui::RichText::new("Text Size:")
+                        .color(catppuccin::subtext0()).small());
+                    ui.horizontal(|ui| {
+                        if ui.button("A−").on_hover_text("Ctrl+-").clicked() {
+                            action = MenuAction::SetUiScale(state.ui_scale - UI_SCALE_STEP);
+                        }
+                        if ui.button(format!("{:.0}%", state.ui_scale * 100.0))
+                            .on_hover_text("Reset (Ctrl+0)").clicked() {
+                            action = MenuAction::SetUiScale(1.0);
+                        }
+                        if ui.button("A+").on_hover_text("Ctrl++").clicked() {
+                            action = MenuAction::SetUiScale(state.ui_scale + UI_SCALE_STEP);
+                        }
+                    });
+
+                    ui.separator();
+                    ui.label(egui::RichText::new("Listing Window:")
+                        .color(catppuccin::subtext0()).small());
+                    ui.horizontal(|ui| {
+                        if ui.button("−").on_hover_text("Halve").clicked() {
+                            action = MenuAction::SetAsmWindow(state.asm_window / 2);
+                        }
+                        if ui.button(format!("0x{:x} bytes", state.asm_window))
+                            .on_hover_text("Reset").clicked() {
+                            action = MenuAction::SetAsmWindow(DEFAULT_ASM_WINDOW);
+                        }
+                        if ui.button("+").on_hover_text("Double").clicked() {
+                            action = MenuAction::SetAsmWindow(state.asm_window * 2);
+                        }
+                    });
+                    let mut stack_var_names = state.stack_var_names;
+                    if ui.checkbox(&mut stack_var_names, "Stack variable names")
+                        .on_hover_text("Annotate stack operands with the decompiler's local names")
+                        .clicked() {
+                        action = MenuAction::SetStackVarNames(stack_var_names);
+                    }
+
                     ui.separator();
                     if ui.button(egui::RichText::new("🗑 Clear Console")
-                        .color(catppuccin::YELLOW)).clicked() {
+                        .color(catppuccin::yellow())).clicked() {
                         action = MenuAction::ClearConsole;
                         ui.close_menu();
                     }
+                    if ui.button(egui::RichText::new("⟲ Reset Console")
+                        .color(catppuccin::subtext0()))
+                        .on_hover_text("Restore the startup banner and clear the filters")
+                        .clicked() {
+                        action = MenuAction::ResetConsole;
+                        ui.close_menu();
+                    }
                 });
 
-                ui.menu_button(egui::RichText::new("Tools").color(catppuccin::TEXT), |ui| {
+                ui.menu_button(egui::RichText::new("Tools").color(catppuccin::text()), |ui| {
+                    if state.batch_progress.is_some() {
+                        if ui.button(egui::RichText::new("⏹ Cancel Analysis")
+                            .color(catppuccin::red())).clicked() {
+                            action = MenuAction::CancelAnalyzeAll;
+                            ui.close_menu();
+                        }
+                    } else if ui.add_enabled(state.loaded_binary.is_some(), egui::Button::new(
+                        egui::RichText::new("⚙ Analyze All Functions").color(catppuccin::green())
+                    )).clicked() {
+                        action = MenuAction::AnalyzeAll;
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(state.loaded_binary.is_some(), egui::Button::new(
+                        egui::RichText::new("🔎 Find Instructions...").color(catppuccin::sapphire())
+                    )).clicked() {
+                        action = MenuAction::FindInstructions;
+                        ui.close_menu();
+                    }
+                    let can_diff = state.loaded_binary.is_some() && !state.functions_loading && !state.diff_loading;
+                    if ui.add_enabled(can_diff, egui::Button::new(
+                        egui::RichText::new("🔀 Diff Against Binary...").color(catppuccin::mauve())
+                    )).clicked() {
+                        action = MenuAction::DiffAgainst;
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(state.secondary_binary.is_some(), egui::Button::new(
+                        egui::RichText::new("🔀 Show Diff").color(catppuccin::mauve())
+                    )).clicked() {
+                        action = MenuAction::ShowDiff;
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(state.loaded_binary.is_some() && !state.yara_running, egui::Button::new(
+                        egui::RichText::new("🧬 YARA Scan...").color(catppuccin::flamingo())
+                    )).clicked() {
+                        action = MenuAction::YaraScan;
+                        ui.close_menu();
+                    }
                     if ui.button(egui::RichText::new("🗑 Clear Decompile Cache")
-                        .color(catppuccin::YELLOW)).clicked() {
+                        .color(catppuccin::yellow())).clicked() {
                         action = MenuAction::ClearCache;
                         ui.close_menu();
                     }
+                    ui.separator();
+                    if ui.button(egui::RichText::new("🛠 Ghidra Settings...")
+                        .color(catppuccin::peach())).clicked() {
+                        action = MenuAction::ShowGhidraSettings;
+                        ui.close_menu();
+                    }
                 });
 
-                ui.menu_button(egui::RichText::new("Help").color(catppuccin::TEXT), |ui| {
+                ui.menu_button(egui::RichText::new("Help").color(catppuccin::text()), |ui| {
                     if ui.button(egui::RichText::new("ℹ About")
-                        .color(catppuccin::SAPPHIRE)).clicked() {
+                        .color(catppuccin::sapphire())).clicked() {
                         action = MenuAction::ShowAbout;
                         ui.close_menu();
                     }
@@ -130,7 +329,7 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> MenuAction {
                 // Right-aligned title
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     ui.label(egui::RichText::new("FISSION")
-                        .color(catppuccin::LAVENDER)
+                        .color(catppuccin::lavender())
                         .strong());
                 });
             });
@@ -2,18 +2,49 @@
 //!
 //! These messages are sent from background threads to the main UI thread.
 
-use crate::analysis::loader::LoadedBinary;
+use crate::analysis::decomp::client::BinaryId;
+use crate::analysis::decomp::client::ghidra_service::Instruction;
+use crate::analysis::disasm::search::InsnMatch;
+use crate::analysis::loader::{FunctionBatch, FunctionInfo, LoadedBinary};
+use crate::analysis::stackvars::StackVariable;
+use crate::analysis::yara::YaraMatch;
+use crate::analysis::diff::FunctionDiff;
+use crate::app::session::Session;
+use crate::debug::dump::DumpReport;
 use crate::debug::types::DebugEvent;
+use std::collections::HashMap;
+use std::time::Duration;
 
 /// Message types for async operations between threads and UI
 pub enum AsyncMessage {
-    /// Binary file was loaded (success or failure)
-    BinaryLoaded(Result<LoadedBinary, String>),
+    /// Binary file was loaded (success or failure), with the time spent reading and parsing.
+    ///
+    /// On success the functions follow as `FunctionsBatch` messages.
+    BinaryLoaded {
+        result: Result<LoadedBinary, String>,
+        elapsed: Duration,
+    },
+
+    /// Next batch of functions for the binary from the last `BinaryLoaded`,
+    /// sent as the loader reads them
+    FunctionsBatch(FunctionBatch),
+
+    /// All functions of the loaded binary have been delivered
+    LoadComplete,
     
     /// Decompilation completed successfully
     DecompileResult { 
+        /// Binary the request was made for
+        id: BinaryId,
         address: u64, 
         c_code: String,
+        pcode: String,
+        /// Ghidra's listing of the function
+        asm: Vec<Instruction>,
+        /// Variables Ghidra placed on the stack
+        stack_vars: Vec<StackVariable>,
+        /// Instruction addresses behind each 0-based line of `c_code`
+        line_map: HashMap<usize, Vec<u64>>,
     },
     
     /// Decompilation failed
@@ -21,15 +52,52 @@ pub enum AsyncMessage {
         address: u64, 
         error: String,
     },
+
+    /// "Analyze All Functions" decompiled one function
+    BatchDecompiled {
+        address: u64,
+        c_code: String,
+        pcode: String,
+        stack_vars: Vec<StackVariable>,
+        line_map: HashMap<usize, Vec<u64>>,
+    },
+
+    /// "Analyze All Functions" progress: functions attempted so far
+    BatchProgress {
+        done: usize,
+        total: usize,
+    },
+
+    /// "Analyze All Functions" stopped (finished, cancelled, or lost the server)
+    BatchFinished {
+        decompiled: usize,
+        failed: usize,
+        cancelled: bool,
+        error: Option<String>,
+    },
+
+    /// Decompilation was cancelled by the user
+    DecompileCancelled { 
+        address: u64,
+    },
     
     /// Server connection status changed
     ServerStatus(bool),
     
     /// File was selected from dialog (None if cancelled)
     FileSelected(Option<String>),
+
+    /// PDB file was selected from dialog (None if cancelled)
+    PdbSelected(Option<String>),
+
+    /// Raw blob was selected from the "Open Raw" dialog (None if cancelled)
+    RawFileSelected(Option<String>),
     
     /// Server connection was lost
     ServerDisconnected,
+
+    /// Decompile path replaced a dead client (respawned: server process had exited)
+    ClientReconnected { respawned: bool },
     
     /// Server recovery completed successfully
     ServerRecovered,
@@ -37,6 +105,53 @@ pub enum AsyncMessage {
     /// Server recovery failed
     RecoveryFailed(String),
 
+    /// Start/Restart Server finished (Ok(true) when a server Fission had
+    /// started was killed first)
+    ServerRestarted(Result<bool, String>),
+
     /// Debug event from debugger loop
     DebugEvent(DebugEvent),
+
+    /// Destination picked for "Save Patched As" (None if cancelled)
+    PatchedPathSelected(Option<String>),
+
+    /// Session file written: path or error (None if cancelled)
+    SessionSaved(Option<Result<String, String>>),
+
+    /// Session file read: session or error (None if cancelled)
+    SessionLoaded(Option<Result<Session, String>>),
+
+    /// Strings export finished: (path, count) or error (None if cancelled)
+    StringsExported(Option<Result<(String, usize), String>>),
+
+    /// Disassembly export finished: (path, instructions) or error (None if cancelled)
+    DisassemblyExported(Option<Result<(String, usize), String>>),
+
+    /// Whole-binary instruction search finished: matches or error
+    InsnSearchFinished(Result<Vec<InsnMatch>, String>),
+
+    /// Secondary binary was selected for a diff (None if cancelled)
+    SecondaryFileSelected(Option<String>),
+
+    /// Secondary binary loaded and diffed against the primary, or error
+    SecondaryLoaded(Result<(LoadedBinary, Vec<FunctionDiff>), String>),
+
+    /// Side-by-side decompile of diff row `index` finished
+    DiffDecompiled {
+        index: usize,
+        primary: String,
+        secondary: String,
+    },
+
+    /// Rules file was selected for a YARA scan (None if cancelled)
+    YaraRulesSelected(Option<String>),
+
+    /// YARA scan finished: matching rules or error
+    YaraScanFinished(Result<Vec<YaraMatch>, String>),
+
+    /// Overlay extraction finished: (path, bytes written) or error (None if cancelled)
+    OverlayExported(Option<Result<(String, usize), String>>),
+
+    /// Memory dump saved: (path, what was read) or error (None if cancelled)
+    MemoryDumped(Option<Result<(String, DumpReport), String>>),
 }
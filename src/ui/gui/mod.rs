@@ -11,7 +11,7 @@ mod panels;
 mod widgets;
 pub mod theme;
 
-pub use app::FissionApp;
+pub use app::{FissionApp, TOKIO_RUNTIME};
 pub use state::AppState;
 pub use messages::AsyncMessage;
 pub use widgets::*;
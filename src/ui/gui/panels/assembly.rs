@@ -2,30 +2,184 @@
 
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
-use super::super::state::AppState;
+use crate::app::{parse_address, parse_hex_bytes};
+use crate::analysis::disasm::{OperandKind, OperandToken};
+use crate::app::export::{listing_bytes, listing_comments};
+use crate::app::session::Bookmark;
+use super::super::state::{AppState, AsmSource, DebugBpAction};
 use super::super::theme::{catppuccin, code};
 
+/// Navigation requested from the assembly view
+pub enum AssemblyAction {
+    /// Follow a call/jump to its target address (from the given instruction)
+    Follow { from: u64, target: u64 },
+    /// Return to the previous location
+    Back,
+    /// Jump to an address resolved from the "Go to" box
+    GoTo(u64),
+    /// PageUp past the first row: decode more instructions before the listing
+    /// (row index of the selection when the key was pressed)
+    ExtendBack { from_row: usize },
+    /// More/Less: re-read the local listing one window longer or shorter
+    Resize { grow: bool },
+    /// Save the shown listing to a text file
+    Export,
+    None,
+}
+
+/// Rows moved by PageUp / PageDown
+pub const PAGE_ROWS: usize = 20;
+
+/// Cover the central panel with a drop target while files are dragged over the window
+fn render_drop_hint(ctx: &egui::Context) {
+    if ctx.input(|i| i.raw.hovered_files.is_empty()) {
+        return;
+    }
+    let rect = ctx.available_rect();
+    let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("file_drop")));
+    painter.rect_filled(rect, 0.0, catppuccin::crust().gamma_multiply(0.85));
+    painter.rect_stroke(rect.shrink(8.0), 8.0, egui::Stroke::new(2.0, catppuccin::lavender()));
+    painter.text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        "Drop a binary to analyze",
+        egui::FontId::proportional(20.0),
+        catppuccin::lavender(),
+    );
+}
+
 /// Render the assembly view in the central panel with virtualized scrolling.
-pub fn render(ctx: &egui::Context, state: &AppState) {
+pub fn render(ctx: &egui::Context, state: &mut AppState) -> AssemblyAction {
+    let mut action = AssemblyAction::None;
+    render_drop_hint(ctx);
+    
     egui::CentralPanel::default().show(ctx, |ui| {
         ui.horizontal(|ui| {
-            ui.heading(egui::RichText::new("Assembly").color(catppuccin::LAVENDER));
+            let can_go_back = !state.nav_history.is_empty();
+            if ui.add_enabled(can_go_back, egui::Button::new("◀").small())
+                .on_hover_text("Back")
+                .clicked()
+            {
+                action = AssemblyAction::Back;
+            }
+            ui.heading(egui::RichText::new("Assembly").color(catppuccin::lavender()));
             ui.separator();
-            ui.label(egui::RichText::new(format!("{} instructions", state.asm_instructions.len()))
-                .color(catppuccin::SUBTEXT0)
+            ui.label(egui::RichText::new(format!("{} instructions", state.listing().len()))
+                .color(catppuccin::subtext0())
                 .small());
+            ui.separator();
+            if ui.selectable_label(state.asm_source == AsmSource::Local, "Local")
+                .on_hover_text("Disassemble locally")
+                .clicked()
+            {
+                state.asm_source = AsmSource::Local;
+            }
+            let has_ghidra = !state.ghidra_asm.is_empty();
+            if ui.add_enabled(has_ghidra, egui::SelectableLabel::new(state.asm_source == AsmSource::Ghidra, "Ghidra"))
+                .on_hover_text("Show the listing Ghidra returned with the decompile")
+                .on_disabled_hover_text("Available once the function is decompiled")
+                .clicked()
+            {
+                state.asm_source = AsmSource::Ghidra;
+            }
+            ui.separator();
+            let local = !state.showing_ghidra_asm() && !state.asm_instructions.is_empty();
+            let span = match (state.asm_instructions.first(), state.asm_instructions.last()) {
+                (Some(first), Some(last)) => (last.address + last.length as u64 - first.address) as usize,
+                _ => 0,
+            };
+            if ui.add_enabled(local, egui::Button::new("More").small())
+                .on_hover_text(format!("Decode 0x{:x} more bytes", state.asm_window))
+                .clicked()
+            {
+                action = AssemblyAction::Resize { grow: true };
+            }
+            if ui.add_enabled(local && span > state.asm_window, egui::Button::new("Less").small())
+                .on_hover_text(format!("Decode 0x{:x} fewer bytes", state.asm_window))
+                .clicked()
+            {
+                action = AssemblyAction::Resize { grow: false };
+            }
+            ui.separator();
+            if ui.add_enabled(!state.listing().is_empty(), egui::Button::new("Export").small())
+                .on_hover_text("Save this listing to a text file")
+                .clicked()
+            {
+                action = AssemblyAction::Export;
+            }
+            ui.separator();
+            let (entry, main) = match &state.loaded_binary {
+                Some(binary) => (Some(binary.entry_point), binary.main_function().map(|f| f.address)),
+                None => (None, None),
+            };
+            if ui.add_enabled(entry.is_some(), egui::Button::new("Entry").small())
+                .on_hover_text("Go to the entry point")
+                .clicked()
+            {
+                action = entry.map_or(AssemblyAction::None, AssemblyAction::GoTo);
+            }
+            if ui.add_enabled(main.is_some(), egui::Button::new("main").small())
+                .on_hover_text("Go to main")
+                .on_disabled_hover_text("No main function found")
+                .clicked()
+            {
+                action = main.map_or(AssemblyAction::None, AssemblyAction::GoTo);
+            }
+            
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let current = state.current_address();
+                if let Some(addr) = current {
+                    ui.label(egui::RichText::new(state.address_label(addr))
+                        .monospace()
+                        .color(code::address()))
+                        .on_hover_text(format!("0x{:x}", addr));
+                    ui.separator();
+                }
+                let bookmark_clicked = ui.add_enabled(current.is_some(), egui::Button::new("🔖"))
+                    .on_hover_text("Bookmark current address (Ctrl+B)")
+                    .clicked();
+                let bookmark_key = ui.input(|i| i.modifiers.command && i.key_pressed(egui::Key::B));
+                if let (true, Some(addr)) = (bookmark_clicked || bookmark_key, current) {
+                    state.bookmark_draft = Some((addr, String::new()));
+                }
+                
+                let goto_id = egui::Id::new("asm_goto");
+                let go_clicked = ui.button("Go").clicked();
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut state.goto_input)
+                        .id(goto_id)
+                        .desired_width(160.0)
+                        .font(egui::TextStyle::Monospace)
+                        .hint_text("Go to (Ctrl+G)")
+                );
+                
+                if ui.input(|i| i.modifiers.command && i.key_pressed(egui::Key::G)) {
+                    response.request_focus();
+                }
+                
+                let enter_pressed = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if go_clicked || enter_pressed {
+                    match resolve_goto(state, &state.goto_input) {
+                        Some(addr) => action = AssemblyAction::GoTo(addr),
+                        None => state.warn(format!("Cannot resolve '{}'", state.goto_input.trim())),
+                    }
+                }
+            });
         });
+        
+        render_bookmark_draft(ui, state);
+        render_patch_draft(ui, state);
         ui.separator();
 
-        if state.asm_instructions.is_empty() {
+        if state.listing().is_empty() {
             ui.vertical_centered(|ui| {
                 ui.add_space(40.0);
                 ui.label(egui::RichText::new("No disassembly available")
-                    .color(catppuccin::OVERLAY0)
+                    .color(catppuccin::overlay0())
                     .size(16.0));
                 ui.add_space(8.0);
                 ui.label(egui::RichText::new("Select a function to view assembly")
-                    .color(catppuccin::OVERLAY0)
+                    .color(catppuccin::overlay0())
                     .small());
             });
             return;
@@ -33,75 +187,115 @@ pub fn render(ctx: &egui::Context, state: &AppState) {
 
         let available_height = ui.available_height();
         let row_height = 20.0;
-        let total_rows = state.asm_instructions.len();
+        let total_rows = state.listing().len();
+        
+        // Pending scroll request (consumed once the row exists)
+        let scroll_row = state.asm_scroll_to.take().map(|target| {
+            state.listing()
+                .partition_point(|insn| insn.address < target)
+                .min(total_rows - 1)
+        });
+        if let Some(row) = scroll_row {
+            state.asm_selected = Some(state.listing()[row].address);
+        }
+        let selected = state.asm_selected;
+        let linked = state.linked_instructions();
+        let current_ip = state.debug_ip().map(|ip| state.to_static(ip));
+        let mut clicked_addr = None;
+        let mut patch_request = None;
+        let mut bp_toggle = None;
+
+        // Rows handle clicks themselves; keep labels from swallowing them
+        ui.style_mut().interaction.selectable_labels = false;
 
         // Use TableBuilder for efficient virtual scrolling
-        TableBuilder::new(ui)
+        let mut table = TableBuilder::new(ui).sense(egui::Sense::click());
+        if let Some(row) = scroll_row {
+            table = table.scroll_to_row(row, Some(egui::Align::Center));
+        }
+        table
             .striped(true)
             .resizable(true)
             .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-            .column(Column::exact(90.0))  // Address
+            .column(Column::exact(14.0))  // Breakpoint gutter
+            .column(Column::exact(104.0))  // Address (and current-instruction arrow)
             .column(Column::initial(140.0).at_least(80.0))  // Bytes
             .column(Column::initial(80.0).at_least(50.0))   // Mnemonic
             .column(Column::remainder())  // Operands
             .min_scrolled_height(0.0)
             .max_scroll_height(available_height)
             .header(22.0, |mut header| {
+                header.col(|_| {});
                 header.col(|ui| {
                     ui.label(egui::RichText::new("Address")
                         .strong()
-                        .color(catppuccin::TEXT));
+                        .color(catppuccin::text()));
                 });
                 header.col(|ui| {
                     ui.label(egui::RichText::new("Bytes")
                         .strong()
-                        .color(catppuccin::TEXT));
+                        .color(catppuccin::text()));
                 });
                 header.col(|ui| {
                     ui.label(egui::RichText::new("Mnemonic")
                         .strong()
-                        .color(catppuccin::TEXT));
+                        .color(catppuccin::text()));
                 });
                 header.col(|ui| {
                     ui.label(egui::RichText::new("Operands")
                         .strong()
-                        .color(catppuccin::TEXT));
+                        .color(catppuccin::text()));
                 });
             })
             .body(|body| {
                 body.rows(row_height, total_rows, |mut row| {
                     let row_index = row.index();
-                    let insn = &state.asm_instructions[row_index];
+                    let insn = &state.listing()[row_index];
+                    
+                    let target = insn.branch_target();
+                    if selected == Some(insn.address) || linked.contains(&insn.address) {
+                        row.set_selected(true);
+                    }
                     
-                    // Address column
+                    // Breakpoint gutter; clicking toggles a breakpoint here
+                    row.col(|ui| {
+                        let has_bp = state.debug_state.breakpoints.contains_key(&state.to_runtime(insn.address));
+                        let (rect, response) = ui.allocate_exact_size(ui.available_size(), egui::Sense::click());
+                        if has_bp {
+                            ui.painter().circle_filled(rect.center(), 4.0, catppuccin::red());
+                        }
+                        if response.on_hover_text("Toggle breakpoint").clicked() {
+                            bp_toggle = Some((insn.address, has_bp));
+                        }
+                    });
+
+                    // Address column, with an arrow at the debuggee's current instruction
                     row.col(|ui| {
-                        ui.label(egui::RichText::new(format!("{:08X}", insn.address))
-                            .color(code::ADDRESS)
+                        if current_ip == Some(insn.address) {
+                            ui.label(egui::RichText::new("▶")
+                                .color(catppuccin::yellow())
+                                .monospace());
+                        }
+                        ui.label(egui::RichText::new(format!("{:08X}", state.shown_address(insn.address)))
+                            .color(code::address())
                             .monospace());
                     });
                     
                     // Bytes column (truncate if too long)
                     row.col(|ui| {
-                        let mut bytes_str = String::with_capacity(32);
-                        for (i, b) in insn.bytes.iter().enumerate() {
-                            if i >= 8 {
-                                bytes_str.push_str("..");
-                                break;
-                            }
-                            use std::fmt::Write;
-                            write!(bytes_str, "{:02X} ", b).unwrap();
-                        }
-                        ui.label(egui::RichText::new(bytes_str)
-                            .color(code::HEX_BYTE)
+                        ui.label(egui::RichText::new(listing_bytes(&insn.bytes))
+                            .color(code::hex_byte())
                             .monospace());
                     });
                     
                     // Mnemonic column with color coding
                     row.col(|ui| {
-                        let color = if insn.is_flow_control {
-                            code::MNEMONIC_FLOW
+                        let color = if insn.is_skipped_data() {
+                            code::mnemonic_data()
+                        } else if insn.is_flow_control {
+                            code::mnemonic_flow()
                         } else {
-                            code::MNEMONIC_NORMAL
+                            code::mnemonic_normal()
                         };
                         ui.label(egui::RichText::new(&insn.mnemonic)
                             .color(color)
@@ -111,31 +305,191 @@ pub fn render(ctx: &egui::Context, state: &AppState) {
                     
                     // Operands column with syntax highlighting
                     row.col(|ui| {
-                        let text = highlight_operands(&insn.operands);
-                        ui.label(text);
+                        ui.label(highlight_operands(ui, &insn.tokens));
+                        
+                        // Branch target names, referenced strings/imports, stack variables and source lines
+                        if let Some(binary) = state.loaded_binary.as_ref() {
+                            let prev = row_index.checked_sub(1).map(|i| &state.listing()[i]);
+                            let comments = listing_comments(binary, &state.function_renames, &state.extracted_strings, &state.stack_notes, insn, prev);
+                            for comment in comments {
+                                let label = ui.label(egui::RichText::new(comment.text)
+                                    .color(code::comment())
+                                    .monospace());
+                                if let Some(detail) = comment.detail {
+                                    label.on_hover_text(detail);
+                                }
+                            }
+                        }
+                    });
+                    
+                    let response = row.response();
+                    if response.clicked() {
+                        clicked_addr = Some(insn.address);
+                    }
+                    response.context_menu(|ui| {
+                        if ui.button("Patch bytes...").clicked() {
+                            patch_request = Some((insn.address, hex_string(&insn.bytes)));
+                            ui.close_menu();
+                        }
                     });
+                    if let Some(target) = target {
+                        if response.on_hover_text("Double-click to follow").double_clicked() {
+                            action = AssemblyAction::Follow { from: insn.address, target };
+                        }
+                    }
                 });
             });
+        
+        if clicked_addr.is_some() {
+            state.asm_selected = clicked_addr;
+            state.c_line_selected = None;
+        }
+        if patch_request.is_some() {
+            state.patch_draft = patch_request;
+        }
+        if let Some((address, has_bp)) = bp_toggle {
+            state.pending_bp_action = Some(if has_bp {
+                DebugBpAction::Remove(address)
+            } else {
+                DebugBpAction::Add(address)
+            });
+        }
+
+        // Page through the listing; PageUp at the top decodes backwards
+        if !ui.ctx().wants_keyboard_input() {
+            let (page_up, page_down) = ui.input(|i| {
+                (i.key_pressed(egui::Key::PageUp), i.key_pressed(egui::Key::PageDown))
+            });
+            let row = selected
+                .map(|addr| state.listing().partition_point(|insn| insn.address < addr))
+                .unwrap_or(0)
+                .min(total_rows - 1);
+            if page_up {
+                if row >= PAGE_ROWS {
+                    state.asm_scroll_to = Some(state.listing()[row - PAGE_ROWS].address);
+                } else if !state.showing_ghidra_asm() {
+                    // Ghidra's listing only covers the function
+                    action = AssemblyAction::ExtendBack { from_row: row };
+                }
+            } else if page_down {
+                let next = (row + PAGE_ROWS).min(total_rows - 1);
+                state.asm_scroll_to = Some(state.listing()[next].address);
+            }
+        }
     });
+    
+    action
 }
 
-/// Apply syntax highlighting to operands
-fn highlight_operands(operands: &str) -> egui::RichText {
-    // Simple highlighting - in a full implementation you'd parse and color each token
-    // For now, color registers differently
-    let color = if operands.contains("rax") || operands.contains("rbx") || 
-                   operands.contains("rcx") || operands.contains("rdx") ||
-                   operands.contains("rsi") || operands.contains("rdi") ||
-                   operands.contains("rbp") || operands.contains("rsp") ||
-                   operands.contains("eax") || operands.contains("ebx") {
-        code::REGISTER
-    } else if operands.starts_with("0x") || operands.contains("0x") {
-        code::NUMBER
-    } else {
-        catppuccin::TEXT
+/// Note editor shown while a bookmark is being created
+fn render_bookmark_draft(ui: &mut egui::Ui, state: &mut AppState) {
+    let Some((addr, note)) = state.bookmark_draft.as_mut() else {
+        return;
     };
+    let addr = *addr;
     
-    egui::RichText::new(operands)
-        .color(color)
-        .monospace()
+    let mut commit = false;
+    let mut cancel = false;
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(format!("🔖 {:08X}", addr))
+            .color(code::address())
+            .monospace());
+        let response = ui.add(
+            egui::TextEdit::singleline(note)
+                .desired_width(240.0)
+                .hint_text("Note...")
+        );
+        if !response.has_focus() && !response.lost_focus() {
+            response.request_focus();
+        }
+        commit = ui.button("Add").clicked()
+            || (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)));
+        cancel = ui.button("Cancel").clicked() || ui.input(|i| i.key_pressed(egui::Key::Escape));
+    });
+    
+    if cancel {
+        state.bookmark_draft = None;
+    } else if commit {
+        if let Some((address, note)) = state.bookmark_draft.take() {
+            state.bookmarks.retain(|b| b.address != address);
+            state.bookmarks.push(Bookmark { address, note: note.trim().to_string() });
+            state.bookmarks.sort_by_key(|b| b.address);
+            state.success(format!("Bookmarked 0x{:x}", address));
+        }
+    }
+}
+
+/// Hex editor shown while an instruction is being patched
+fn render_patch_draft(ui: &mut egui::Ui, state: &mut AppState) {
+    let Some((addr, hex)) = state.patch_draft.as_mut() else {
+        return;
+    };
+    let addr = *addr;
+
+    let mut commit = false;
+    let mut cancel = false;
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(format!("✏ {:08X}", addr))
+            .color(code::address())
+            .monospace());
+        let response = ui.add(
+            egui::TextEdit::singleline(hex)
+                .desired_width(240.0)
+                .font(egui::TextStyle::Monospace)
+                .hint_text("Hex bytes, e.g. 90 90")
+        );
+        if !response.has_focus() && !response.lost_focus() {
+            response.request_focus();
+        }
+        commit = ui.button("Patch").clicked()
+            || (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)));
+        cancel = ui.button("Cancel").clicked() || ui.input(|i| i.key_pressed(egui::Key::Escape));
+    });
+
+    if cancel {
+        state.patch_draft = None;
+    } else if commit {
+        let bytes = state.patch_draft.as_ref().and_then(|(_, hex)| parse_hex_bytes(hex));
+        match bytes {
+            Some(bytes) => {
+                state.patch_draft = None;
+                state.pending_patch = Some((addr, bytes));
+            }
+            None => state.warn("Patch must be an even number of hex digits".to_string()),
+        }
+    }
+}
+
+/// Space-separated hex bytes, as accepted by the patch editor
+fn hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")
+}
+
+/// Resolve a "Go to" entry: function name first, then hex/decimal address
+fn resolve_goto(state: &AppState, input: &str) -> Option<u64> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    state.loaded_binary.as_ref()
+        .and_then(|b| b.find_function(input))
+        .map(|f| f.address)
+        .or_else(|| parse_address(input))
+}
+
+/// Color each operand token by its kind
+fn highlight_operands(ui: &egui::Ui, tokens: &[OperandToken]) -> egui::text::LayoutJob {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let mut job = egui::text::LayoutJob::default();
+    for token in tokens {
+        let color = match token.kind {
+            OperandKind::Register => code::register(),
+            OperandKind::Immediate => code::number(),
+            OperandKind::Memory => code::type_name(),
+            OperandKind::Address => code::function(),
+            OperandKind::Punctuation => code::operator(),
+        };
+        job.append(&token.text, 0.0, egui::TextFormat::simple(font_id.clone(), color));
+    }
+    job
 }
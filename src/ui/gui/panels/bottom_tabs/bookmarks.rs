@@ -0,0 +1,82 @@
+//! Bookmarks tab panel - User-marked addresses with notes.
+
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+use crate::ui::gui::state::AppState;
+use crate::ui::gui::theme::{catppuccin, code};
+
+/// Render bookmarks tab content.
+///
+/// Returns the address of a clicked bookmark, if any.
+pub fn render(ui: &mut egui::Ui, state: &mut AppState) -> Option<u64> {
+    if state.bookmarks.is_empty() {
+        ui.vertical_centered(|ui| {
+            ui.add_space(20.0);
+            ui.label(egui::RichText::new("No bookmarks - press Ctrl+B in the assembly view")
+                .color(catppuccin::overlay0()));
+        });
+        return None;
+    }
+
+    let mut clicked = None;
+    let mut removed = None;
+
+    ui.label(egui::RichText::new(format!("{} bookmarks", state.bookmarks.len()))
+        .color(catppuccin::subtext0()));
+    ui.separator();
+
+    let available_height = ui.available_height();
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::exact(90.0))
+        .column(Column::initial(180.0).at_least(80.0))
+        .column(Column::remainder())
+        .column(Column::exact(24.0))
+        .min_scrolled_height(0.0)
+        .max_scroll_height(available_height)
+        .header(20.0, |mut header| {
+            header.col(|ui| { ui.strong("Address"); });
+            header.col(|ui| { ui.strong("Function"); });
+            header.col(|ui| { ui.strong("Note"); });
+            header.col(|_| {});
+        })
+        .body(|body| {
+            body.rows(18.0, state.bookmarks.len(), |mut row| {
+                let index = row.index();
+                let bookmark = &state.bookmarks[index];
+                row.col(|ui| {
+                    let label = ui.add(egui::Label::new(
+                        egui::RichText::new(format!("{:08X}", bookmark.address))
+                            .monospace()
+                            .color(code::address())
+                    ).sense(egui::Sense::click()));
+                    if label.on_hover_text("Go to address").clicked() {
+                        clicked = Some(bookmark.address);
+                    }
+                });
+                row.col(|ui| {
+                    let func = state.loaded_binary.as_ref()
+                        .and_then(|b| b.function_at(bookmark.address));
+                    if let Some(func) = func {
+                        ui.label(egui::RichText::new(state.function_name(func))
+                            .color(catppuccin::blue()));
+                    }
+                });
+                row.col(|ui| {
+                    ui.label(egui::RichText::new(&bookmark.note).color(catppuccin::text()));
+                });
+                row.col(|ui| {
+                    if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                        removed = Some(index);
+                    }
+                });
+            });
+        });
+
+    if let Some(index) = removed {
+        state.bookmarks.remove(index);
+    }
+    clicked
+}
@@ -2,7 +2,7 @@
 
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
-use crate::ui::gui::state::AppState;
+use crate::ui::gui::state::{AppState, LogFilter, LogLevel};
 use crate::ui::gui::theme::catppuccin;
 
 /// Actions that can be triggered from the console
@@ -15,45 +15,75 @@ pub enum ConsoleAction {
 pub fn render(ui: &mut egui::Ui, state: &mut AppState) -> ConsoleAction {
     let mut action = ConsoleAction::None;
     
+    // Lines passing the level filter
+    let shown: Vec<usize> = state.log_buffer.iter()
+        .enumerate()
+        .filter(|(_, entry)| state.log_visible(entry))
+        .map(|(i, _)| i)
+        .collect();
+
     // Header buttons
     ui.horizontal(|ui| {
-        if ui.small_button(egui::RichText::new("Clear").color(catppuccin::RED)).clicked() {
-            state.log_buffer.clear();
+        if ui.small_button(egui::RichText::new("Clear").color(catppuccin::red()))
+            .on_hover_text("Clear messages (View > Reset Console restores the startup state)")
+            .clicked()
+        {
+            state.clear_logs();
+        }
+        if ui.small_button(egui::RichText::new("📋 Copy").color(catppuccin::blue())).clicked() {
+            let all_logs: Vec<&str> = shown.iter().map(|&i| state.log_buffer[i].text.as_str()).collect();
+            ui.output_mut(|o| o.copied_text = all_logs.join("\n"));
+        }
+        ui.separator();
+        for filter in LogFilter::ALL {
+            ui.selectable_value(&mut state.log_filter, filter, filter.label());
         }
-        if ui.small_button(egui::RichText::new("📋 Copy").color(catppuccin::BLUE)).clicked() {
-            let all_logs = state.log_buffer.join("\n");
-            ui.output_mut(|o| o.copied_text = all_logs);
+        ui.add(egui::TextEdit::singleline(&mut state.log_search)
+            .desired_width(160.0)
+            .hint_text("Filter lines"));
+        if !state.log_search.is_empty() && ui.small_button("✖").on_hover_text("Clear filter").clicked() {
+            state.log_search.clear();
         }
+        ui.checkbox(&mut state.log_timestamps, "Time");
         ui.separator();
-        ui.label(egui::RichText::new(format!("{} lines", state.log_buffer.len()))
-            .color(catppuccin::SUBTEXT0).small());
+        let count = if shown.len() == state.log_buffer.len() {
+            format!("{} lines", shown.len())
+        } else {
+            format!("{} of {} lines", shown.len(), state.log_buffer.len())
+        };
+        ui.label(egui::RichText::new(count).color(catppuccin::subtext0()).small());
     });
 
     // Virtual scrolling table for console logs
     let available_height = ui.available_height() - 35.0;
-    let num_logs = state.log_buffer.len();
     
     TableBuilder::new(ui)
         .striped(false)
         .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .columns(Column::exact(80.0), usize::from(state.log_timestamps))
         .column(Column::remainder())
         .min_scrolled_height(available_height.max(50.0))
         .max_scroll_height(available_height.max(50.0))
         .body(|body| {
-            body.rows(16.0, num_logs, |mut row| {
-                let idx = row.index();
+            body.rows(16.0, shown.len(), |mut row| {
+                let entry = &state.log_buffer[shown[row.index()]];
+                if state.log_timestamps {
+                    row.col(|ui| {
+                        let elapsed = entry.timestamp.saturating_duration_since(state.log_started);
+                        ui.label(egui::RichText::new(format_elapsed(elapsed))
+                            .color(catppuccin::overlay0())
+                            .monospace());
+                    });
+                }
                 row.col(|ui| {
-                    if let Some(log) = state.log_buffer.get(idx) {
-                        let color = get_log_color(log);
-                        ui.label(egui::RichText::new(log).color(color).monospace());
-                    }
+                    ui.label(egui::RichText::new(&entry.text).color(level_color(entry.level)).monospace());
                 });
             });
         });
 
     // CLI input
     ui.horizontal(|ui| {
-        ui.label(egui::RichText::new(">").color(catppuccin::MAUVE).strong());
+        ui.label(egui::RichText::new(">").color(catppuccin::mauve()).strong());
         let response = ui.add(
             egui::TextEdit::singleline(&mut state.cli_input)
                 .desired_width(ui.available_width() - 60.0)
@@ -64,17 +94,17 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) -> ConsoleAction {
         if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
             let cmd = state.cli_input.trim().to_string();
             if !cmd.is_empty() {
-                state.log_buffer.push(format!("> {}", cmd));
+                state.log_at(LogLevel::Command, &cmd);
                 action = ConsoleAction::Command(cmd);
                 state.cli_input.clear();
             }
             response.request_focus();
         }
 
-        if ui.button(egui::RichText::new("Run").color(catppuccin::GREEN)).clicked() {
+        if ui.button(egui::RichText::new("Run").color(catppuccin::green())).clicked() {
             let cmd = state.cli_input.trim().to_string();
             if !cmd.is_empty() {
-                state.log_buffer.push(format!("> {}", cmd));
+                state.log_at(LogLevel::Command, &cmd);
                 action = ConsoleAction::Command(cmd);
                 state.cli_input.clear();
             }
@@ -84,17 +114,20 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) -> ConsoleAction {
     action
 }
 
-fn get_log_color(log: &str) -> egui::Color32 {
-    if log.starts_with("[✓]") {
-        catppuccin::GREEN
-    } else if log.starts_with("[✗]") || log.starts_with("[!]") {
-        catppuccin::RED
-    } else if log.starts_with("[*]") || log.starts_with("[>]") {
-        catppuccin::BLUE
-    } else if log.starts_with(">") {
-        catppuccin::MAUVE
-    } else {
-        catppuccin::SUBTEXT0
+/// Text color of a console line
+pub fn level_color(level: LogLevel) -> egui::Color32 {
+    match level {
+        LogLevel::Command => catppuccin::mauve(),
+        LogLevel::Info => catppuccin::blue(),
+        LogLevel::Success => catppuccin::green(),
+        LogLevel::Warning => catppuccin::yellow(),
+        LogLevel::Error => catppuccin::red(),
     }
 }
 
+/// Time since the session started as `mm:ss.mmm`
+fn format_elapsed(elapsed: std::time::Duration) -> String {
+    let millis = elapsed.as_millis();
+    format!("{:02}:{:02}.{:03}", millis / 60_000, millis / 1000 % 60, millis % 1000)
+}
+
@@ -1,8 +1,12 @@
-//! Debug tab panel - Debugger controls, events, breakpoints, and registers.
+//! Debug tab panel - Debugger controls, events, breakpoints, registers, and call stack.
 
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
-use crate::ui::gui::state::{AppState, DebugAction, DebugBpAction};
+use std::collections::HashMap;
+use crate::app::{eval_address, ADDRESS_HELP};
+use crate::debug::types::{RegisterState, VectorRegisters};
+use crate::debug::vector::{format_lanes, x87_to_f64, VectorFormat};
+use crate::ui::gui::state::{AppState, DebugAction, DebugBpAction, LogEntry, LogLevel};
 use crate::ui::gui::theme::{catppuccin, code};
 
 /// Render debug tab with improved layout
@@ -13,16 +17,16 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
     // TOP CONTROL BAR
     // ═══════════════════════════════════════════════════════════════
     egui::Frame::none()
-        .fill(catppuccin::SURFACE0)
+        .fill(catppuccin::surface0())
         .inner_margin(egui::Margin::symmetric(8.0, 4.0))
         .rounding(4.0)
         .show(ui, |ui| {
             ui.horizontal(|ui| {
                 // Mode toggle with icon
                 let (mode_icon, mode_text, mode_color) = if state.dynamic_mode {
-                    ("⚡", "Dynamic", catppuccin::GREEN)
+                    ("⚡", "Dynamic", catppuccin::green())
                 } else {
-                    ("📖", "Static", catppuccin::OVERLAY1)
+                    ("📖", "Static", catppuccin::overlay1())
                 };
                 if ui.button(egui::RichText::new(format!("{} {}", mode_icon, mode_text))
                     .color(mode_color).strong()).clicked() {
@@ -33,11 +37,11 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                 
                 // Status badge
                 let (status_icon, status_text, status_color) = match state.debug_state.status {
-                    crate::debug::types::DebugStatus::Running => ("▶", "Running", catppuccin::GREEN),
-                    crate::debug::types::DebugStatus::Suspended => ("⏸", "Suspended", catppuccin::YELLOW),
-                    crate::debug::types::DebugStatus::Terminated => ("⏹", "Terminated", catppuccin::RED),
-                    crate::debug::types::DebugStatus::Attaching => ("🔗", "Attaching", catppuccin::BLUE),
-                    _ => ("○", "Detached", catppuccin::OVERLAY0),
+                    crate::debug::types::DebugStatus::Running => ("▶", "Running", catppuccin::green()),
+                    crate::debug::types::DebugStatus::Suspended => ("⏸", "Suspended", catppuccin::yellow()),
+                    crate::debug::types::DebugStatus::Terminated => ("⏹", "Terminated", catppuccin::red()),
+                    crate::debug::types::DebugStatus::Attaching => ("🔗", "Attaching", catppuccin::blue()),
+                    _ => ("○", "Detached", catppuccin::overlay0()),
                 };
                 
                 egui::Frame::none()
@@ -53,7 +57,7 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                 if let Some(pid) = state.debug_state.attached_pid {
                     ui.add_space(4.0);
                     ui.label(egui::RichText::new(format!("PID: {}", pid))
-                        .color(catppuccin::SUBTEXT0).small());
+                        .color(catppuccin::subtext0()).small());
                 }
                 
                 // Last event (truncated)
@@ -61,28 +65,53 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                     ui.add_space(8.0);
                     let display = if ev.len() > 40 { format!("{}...", &ev[..40]) } else { ev.clone() };
                     ui.label(egui::RichText::new(display)
-                        .color(catppuccin::YELLOW).small().italics());
+                        .color(catppuccin::yellow()).small().italics());
                 }
                 
                 // Right-aligned control buttons
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     // Step button
                     if ui.add(egui::Button::new(
-                        egui::RichText::new("⏭ Step").color(catppuccin::SAPPHIRE))
-                        .fill(catppuccin::SURFACE1)
-                    ).clicked() {
+                        egui::RichText::new("⏭ Step").color(catppuccin::sapphire()))
+                        .fill(catppuccin::surface1())
+                    ).on_hover_text("Step into (F11)").clicked() {
                         state.pending_debug_action = Some(DebugAction::Step);
                     }
+
+                    ui.add_space(4.0);
+
+                    // Step over button
+                    if ui.add(egui::Button::new(
+                        egui::RichText::new("↷ Step Over").color(catppuccin::sky()))
+                        .fill(catppuccin::surface1())
+                    ).on_hover_text("Step over calls (F10)").clicked() {
+                        state.pending_debug_action = Some(DebugAction::StepOver);
+                    }
                     
                     ui.add_space(4.0);
                     
                     // Continue button
                     if ui.add(egui::Button::new(
-                        egui::RichText::new("▶ Continue").color(catppuccin::GREEN))
-                        .fill(catppuccin::SURFACE1)
-                    ).clicked() {
+                        egui::RichText::new("▶ Continue").color(catppuccin::green()))
+                        .fill(catppuccin::surface1())
+                    ).on_hover_text("Continue (F5)").clicked() {
                         state.pending_debug_action = Some(DebugAction::Continue);
                     }
+
+                    ui.add_space(4.0);
+
+                    // Break button (only meaningful while running)
+                    let running = state.debug_state.status == crate::debug::types::DebugStatus::Running;
+                    if ui.add_enabled(running, egui::Button::new(
+                        egui::RichText::new("⏸ Break").color(catppuccin::peach()))
+                        .fill(catppuccin::surface1())
+                    ).on_hover_text("Suspend the running process").clicked() {
+                        state.pending_debug_action = Some(DebugAction::Break);
+                    }
+
+                    ui.add_space(8.0);
+                    ui.checkbox(&mut state.follow_ip, "Follow RIP")
+                        .on_hover_text("Show the current instruction in the assembly view when the process suspends");
                 });
             });
         });
@@ -90,12 +119,12 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
     ui.add_space(4.0);
     
     // ═══════════════════════════════════════════════════════════════
-    // MAIN CONTENT - 3 Column Layout
+    // MAIN CONTENT - 4 Column Layout
     // ═══════════════════════════════════════════════════════════════
     let content_height = (available_height - 50.0).max(80.0);
     
     ui.horizontal(|ui| {
-        let panel_width = (ui.available_width() - 16.0) / 3.0;
+        let panel_width = (ui.available_width() - 28.0) / 4.0;
         
         // ─────────────────────────────────────────────────────────
         // COLUMN 1: Events Log
@@ -115,12 +144,19 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
         // COLUMN 3: Registers
         // ─────────────────────────────────────────────────────────
         render_registers_column(ui, state, panel_width, content_height);
+        
+        ui.add_space(4.0);
+        
+        // ─────────────────────────────────────────────────────────
+        // COLUMN 4: Call Stack
+        // ─────────────────────────────────────────────────────────
+        render_call_stack_column(ui, state, panel_width, content_height);
     });
 }
 
 fn render_events_column(ui: &mut egui::Ui, state: &AppState, panel_width: f32, content_height: f32) {
     egui::Frame::none()
-        .fill(catppuccin::MANTLE)
+        .fill(catppuccin::mantle())
         .inner_margin(6.0)
         .rounding(4.0)
         .show(ui, |ui| {
@@ -128,10 +164,10 @@ fn render_events_column(ui: &mut egui::Ui, state: &AppState, panel_width: f32, c
             
             ui.horizontal(|ui| {
                 ui.label(egui::RichText::new("📋 Events")
-                    .color(catppuccin::LAVENDER).strong());
+                    .color(catppuccin::lavender()).strong());
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     ui.label(egui::RichText::new(format!("{}", state.log_buffer.len()))
-                        .color(catppuccin::OVERLAY0).small());
+                        .color(catppuccin::overlay0()).small());
                 });
             });
             
@@ -145,12 +181,16 @@ fn render_events_column(ui: &mut egui::Ui, state: &AppState, panel_width: f32, c
                     .min_scrolled_height(0.0)
                     .max_scroll_height(content_height - 30.0)
                     .body(|body| {
-                        let logs: Vec<_> = state.log_buffer.iter().rev().take(100).collect();
+                        let logs: Vec<_> = state.log_buffer.iter()
+                            .rev()
+                            .filter(|entry| state.log_visible(entry))
+                            .take(100)
+                            .collect();
                         body.rows(16.0, logs.len(), |mut row| {
-                            let log = logs[row.index()];
+                            let entry = logs[row.index()];
                             row.col(|ui| {
-                                let (icon, color) = get_log_style(log);
-                                ui.label(egui::RichText::new(format!("{} {}", icon, log))
+                                let (icon, color) = get_log_style(entry);
+                                ui.label(egui::RichText::new(format!("{} {}", icon, entry.text))
                                     .color(color).small());
                             });
                         });
@@ -161,7 +201,7 @@ fn render_events_column(ui: &mut egui::Ui, state: &AppState, panel_width: f32, c
 
 fn render_breakpoints_column(ui: &mut egui::Ui, state: &mut AppState, panel_width: f32, content_height: f32) {
     egui::Frame::none()
-        .fill(catppuccin::MANTLE)
+        .fill(catppuccin::mantle())
         .inner_margin(6.0)
         .rounding(4.0)
         .show(ui, |ui| {
@@ -169,10 +209,10 @@ fn render_breakpoints_column(ui: &mut egui::Ui, state: &mut AppState, panel_widt
             
             ui.horizontal(|ui| {
                 ui.label(egui::RichText::new("🎯 Breakpoints")
-                    .color(catppuccin::PEACH).strong());
+                    .color(catppuccin::peach()).strong());
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     ui.label(egui::RichText::new(format!("{}", state.debug_state.breakpoints.len()))
-                        .color(catppuccin::OVERLAY0).small());
+                        .color(catppuccin::overlay0()).small());
                 });
             });
             
@@ -180,24 +220,33 @@ fn render_breakpoints_column(ui: &mut egui::Ui, state: &mut AppState, panel_widt
             
             // Add breakpoint input
             ui.horizontal(|ui| {
-                ui.label(egui::RichText::new("0x").color(catppuccin::OVERLAY1).monospace());
                 let response = ui.add(
                     egui::TextEdit::singleline(&mut state.breakpoint_input)
                         .id(egui::Id::new("bp_addr_input"))
-                        .desired_width(ui.available_width() - 30.0)
+                        .desired_width(ui.available_width() - 64.0)
                         .font(egui::TextStyle::Monospace)
-                        .hint_text("address...")
-                );
+                        .hint_text("address or expression...")
+                )
+                .on_hover_text(ADDRESS_HELP);
                 
+                ui.checkbox(&mut state.breakpoint_temporary, "")
+                    .on_hover_text("Temporary (remove after first hit)");
                 if ui.add(egui::Button::new(
-                    egui::RichText::new("+").color(catppuccin::GREEN).strong())
+                    egui::RichText::new("+").color(catppuccin::green()).strong())
                     .min_size(egui::vec2(24.0, 20.0))
                 ).clicked() || (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))) {
-                    if let Ok(addr) = u64::from_str_radix(
-                        state.breakpoint_input.trim_start_matches("0x"), 16
-                    ) {
-                        state.pending_bp_action = Some(DebugBpAction::Add(addr));
-                        state.breakpoint_input.clear();
+                    match eval_address(state, &state.breakpoint_input) {
+                        Ok(addr) => {
+                            // Typed in the address space the views show
+                            let addr = if state.runtime_addresses { state.to_static(addr) } else { addr };
+                            state.pending_bp_action = Some(if state.breakpoint_temporary {
+                                DebugBpAction::AddTemporary(addr)
+                            } else {
+                                DebugBpAction::Add(addr)
+                            });
+                            state.breakpoint_input.clear();
+                        }
+                        Err(e) => state.warn(format!("Breakpoint address: {}", e)),
                     }
                 }
             });
@@ -210,33 +259,87 @@ fn render_breakpoints_column(ui: &mut egui::Ui, state: &mut AppState, panel_widt
                     .striped(true)
                     .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
                     .column(Column::exact(20.0))  // Status
-                    .column(Column::remainder())  // Address
+                    .column(Column::exact(150.0)) // Address
+                    .column(Column::exact(40.0))  // Hits
+                    .column(Column::remainder())  // Condition
                     .column(Column::exact(24.0))  // Delete
                     .min_scrolled_height(0.0)
                     .max_scroll_height(content_height - 60.0)
                     .body(|body| {
-                        let bps: Vec<_> = state.debug_state.breakpoints.iter().collect();
+                        // Keyed by process address; actions take the file VA
+                        let mut bps: Vec<_> = state.debug_state.breakpoints.iter()
+                            .map(|(addr, bp)| {
+                                let file_va = state.to_static(*addr);
+                                (addr, file_va, state.shown_address(file_va), bp)
+                            })
+                            .collect();
+                        bps.sort_by_key(|(addr, ..)| **addr);
                         body.rows(20.0, bps.len(), |mut row| {
-                            let (addr, bp) = bps[row.index()];
+                            let (addr, file_va, shown, bp) = bps[row.index()];
                             
                             row.col(|ui| {
-                                let (icon, color) = if bp.enabled {
-                                    ("●", catppuccin::RED)
+                                let (icon, color, hint) = if !bp.enabled {
+                                    ("○", catppuccin::overlay0(), "Disabled")
+                                } else if bp.temporary {
+                                    ("◎", catppuccin::peach(), "Temporary")
                                 } else {
-                                    ("○", catppuccin::OVERLAY0)
+                                    ("●", catppuccin::red(), "Enabled")
                                 };
-                                ui.label(egui::RichText::new(icon).color(color));
+                                ui.label(egui::RichText::new(icon).color(color)).on_hover_text(hint);
+                            });
+                            
+                            row.col(|ui| {
+                                ui.label(egui::RichText::new(format!("0x{:016X}", shown))
+                                    .color(catppuccin::subtext1()).monospace());
                             });
                             
                             row.col(|ui| {
-                                ui.label(egui::RichText::new(format!("0x{:016X}", addr))
-                                    .color(catppuccin::SUBTEXT1).monospace());
+                                ui.label(egui::RichText::new(format!("×{}", bp.hit_count))
+                                    .color(catppuccin::subtext0()).monospace())
+                                    .on_hover_text("Hit count");
+                            });
+                            
+                            // Condition (click to edit inline)
+                            row.col(|ui| {
+                                if let Some((edit_addr, buffer)) = state.bp_condition_edit.as_mut() {
+                                    if edit_addr == addr {
+                                        let response = ui.add(
+                                            egui::TextEdit::singleline(buffer)
+                                                .desired_width(f32::INFINITY)
+                                                .font(egui::TextStyle::Monospace)
+                                                .hint_text("e.g. rax == 0 && [rsp+8] > 0x10")
+                                        );
+                                        if !response.has_focus() && !response.lost_focus() {
+                                            response.request_focus();
+                                        }
+                                        if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                                            state.bp_condition_edit = None;
+                                        } else if response.lost_focus() {
+                                            let condition = buffer.trim();
+                                            let condition = (!condition.is_empty()).then(|| condition.to_string());
+                                            state.pending_bp_action = Some(DebugBpAction::SetCondition(file_va, condition));
+                                            state.bp_condition_edit = None;
+                                        }
+                                        return;
+                                    }
+                                }
+                                
+                                let text = match &bp.condition {
+                                    Some(cond) => egui::RichText::new(cond)
+                                        .color(catppuccin::yellow()).monospace(),
+                                    None => egui::RichText::new("always")
+                                        .color(catppuccin::overlay0()).italics(),
+                                };
+                                let label = ui.add(egui::Label::new(text).sense(egui::Sense::click()));
+                                if label.on_hover_text("Click to edit condition").clicked() {
+                                    state.bp_condition_edit = Some((*addr, bp.condition.clone().unwrap_or_default()));
+                                }
                             });
                             
                             row.col(|ui| {
                                 if ui.small_button(egui::RichText::new("×")
-                                    .color(catppuccin::RED)).clicked() {
-                                    state.pending_bp_action = Some(DebugBpAction::Remove(*addr));
+                                    .color(catppuccin::red())).clicked() {
+                                    state.pending_bp_action = Some(DebugBpAction::Remove(file_va));
                                 }
                             });
                         });
@@ -247,25 +350,29 @@ fn render_breakpoints_column(ui: &mut egui::Ui, state: &mut AppState, panel_widt
                 ui.vertical_centered(|ui| {
                     ui.add_space(20.0);
                     ui.label(egui::RichText::new("No breakpoints set")
-                        .color(catppuccin::OVERLAY0).italics());
+                        .color(catppuccin::overlay0()).italics());
                 });
             }
         });
 }
 
-fn render_registers_column(ui: &mut egui::Ui, state: &AppState, panel_width: f32, content_height: f32) {
+fn render_registers_column(ui: &mut egui::Ui, state: &mut AppState, panel_width: f32, content_height: f32) {
     egui::Frame::none()
-        .fill(catppuccin::MANTLE)
+        .fill(catppuccin::mantle())
         .inner_margin(6.0)
         .rounding(4.0)
         .show(ui, |ui| {
             ui.set_width(panel_width);
             
-            ui.label(egui::RichText::new("📊 Registers")
-                .color(catppuccin::SAPPHIRE).strong());
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("📊 Registers")
+                    .color(catppuccin::sapphire()).strong());
+                render_thread_selector(ui, state);
+            });
             
             ui.separator();
             
+            let is_64bit = state.target_is_64bit();
             if let Some(regs) = &state.debug_state.registers {
                 egui::ScrollArea::vertical()
                     .id_source("registers_scroll")
@@ -276,61 +383,288 @@ fn render_registers_column(ui: &mut egui::Ui, state: &AppState, panel_width: f32
                             .spacing([8.0, 4.0])
                             .striped(true)
                             .show(ui, |ui| {
-                                let registers = [
-                                    ("RAX", regs.rax), ("RBX", regs.rbx),
-                                    ("RCX", regs.rcx), ("RDX", regs.rdx),
-                                    ("RSI", regs.rsi), ("RDI", regs.rdi),
-                                    ("RBP", regs.rbp), ("RSP", regs.rsp),
-                                    ("R8 ", regs.r8),  ("R9 ", regs.r9),
-                                    ("R10", regs.r10), ("R11", regs.r11),
-                                    ("R12", regs.r12), ("R13", regs.r13),
-                                    ("R14", regs.r14), ("R15", regs.r15),
-                                    ("RIP", regs.rip), ("FLG", regs.rflags),
-                                ];
-                                
-                                for (name, value) in registers {
-                                    ui.label(egui::RichText::new(name)
-                                        .color(code::REGISTER).strong().monospace());
-                                    ui.label(egui::RichText::new(format!("{:016X}", value))
-                                        .color(catppuccin::TEXT).monospace());
+                                for (label, name, value) in register_rows(regs, is_64bit) {
+                                    ui.label(egui::RichText::new(label)
+                                        .color(code::register()).strong().monospace());
+                                    if let Some(write) = register_field(ui, &mut state.register_edits, name, value, is_64bit) {
+                                        state.pending_debug_action = Some(write);
+                                    }
                                     ui.end_row();
                                 }
                             });
+                        if let Some(vector) = &state.debug_state.vector_registers {
+                            render_vector_registers(ui, vector, &mut state.vector_format, is_64bit);
+                        }
                     });
             } else {
                 ui.vertical_centered(|ui| {
                     ui.add_space(30.0);
                     ui.label(egui::RichText::new("⏸")
-                        .color(catppuccin::OVERLAY0).size(24.0));
+                        .color(catppuccin::overlay0()).size(24.0));
                     ui.add_space(8.0);
                     ui.label(egui::RichText::new("No register data")
-                        .color(catppuccin::OVERLAY0));
+                        .color(catppuccin::overlay0()));
                     ui.label(egui::RichText::new("Attach to a process to view registers")
-                        .color(catppuccin::OVERLAY0).small().italics());
+                        .color(catppuccin::overlay0()).small().italics());
+                });
+            }
+        });
+}
+
+/// Collapsible SSE/AVX and x87 section below the general registers
+fn render_vector_registers(ui: &mut egui::Ui, vector: &VectorRegisters, format: &mut VectorFormat, is_64bit: bool) {
+    egui::CollapsingHeader::new(egui::RichText::new("Vector").color(catppuccin::sapphire()))
+        .id_source("vector_registers")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                for f in VectorFormat::ALL {
+                    ui.selectable_value(format, f, f.label());
+                }
+            });
+
+            // A 32-bit target only has XMM0-XMM7
+            let count = if is_64bit { 16 } else { 8 };
+            egui::Grid::new("vector_regs_grid")
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    for (i, &xmm) in vector.xmm.iter().enumerate().take(count) {
+                        let mut vector_row = |name: String, value: u128| {
+                            ui.label(egui::RichText::new(name)
+                                .color(code::register()).strong().monospace());
+                            ui.label(egui::RichText::new(format_lanes(value, *format))
+                                .color(catppuccin::text()).monospace());
+                            ui.end_row();
+                        };
+                        vector_row(format!("XMM{}", i), xmm);
+                        if let Some(high) = &vector.ymm_high {
+                            vector_row(format!("YMM{}H", i), high[i]);
+                        }
+                    }
+
+                    let mut scalar_row = |name: &str, text: String| {
+                        ui.label(egui::RichText::new(name)
+                            .color(code::register()).strong().monospace());
+                        ui.label(egui::RichText::new(text)
+                            .color(catppuccin::text()).monospace());
+                        ui.end_row();
+                    };
+                    scalar_row("MXCSR", format!("{:08X}", vector.mxcsr));
+                    scalar_row("FCW", format!("{:04X}", vector.fpu_control));
+                    scalar_row("FSW", format!("{:04X}", vector.fpu_status));
+                    scalar_row("FTW", format!("{:02X}", vector.fpu_tag));
+                    for (i, st) in vector.st.iter().enumerate() {
+                        scalar_row(&format!("ST{}", i), x87_to_f64(st).to_string());
+                    }
                 });
+        });
+}
+
+/// Editable hex value of register `name`; typed values stay highlighted
+/// until Enter applies them, Escape discards them
+fn register_field(
+    ui: &mut egui::Ui,
+    edits: &mut HashMap<&'static str, String>,
+    name: &'static str,
+    value: u64,
+    is_64bit: bool,
+) -> Option<DebugAction> {
+    let current = if is_64bit {
+        format!("{:016X}", value)
+    } else {
+        format!("{:08X}", value as u32)
+    };
+    let edited = edits.contains_key(name);
+    let mut text = edits.get(name).cloned().unwrap_or_else(|| current.clone());
+    let response = ui.add(egui::TextEdit::singleline(&mut text)
+        .font(egui::TextStyle::Monospace)
+        .text_color(if edited { catppuccin::yellow() } else { catppuccin::text() })
+        .desired_width(if is_64bit { 140.0 } else { 76.0 }));
+    if response.changed() {
+        if text == current {
+            edits.remove(name);
+        } else {
+            edits.insert(name, text.clone());
+        }
+    }
+
+    if !response.lost_focus() {
+        return None;
+    }
+    if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+        edits.remove(name);
+        return None;
+    }
+    if !ui.input(|i| i.key_pressed(egui::Key::Enter)) || !edits.contains_key(name) {
+        return None;
+    }
+    let digits = text.trim().trim_start_matches("0x").trim_start_matches("0X");
+    match u64::from_str_radix(digits, 16) {
+        // A 32-bit write zero-extends, as it does on the CPU
+        Ok(value) if is_64bit => Some(DebugAction::WriteRegister(name, value)),
+        Ok(value) if value <= u64::from(u32::MAX) => Some(DebugAction::WriteRegister(name, value)),
+        _ => {
+            response.on_hover_text("Not a valid hex value for this register");
+            None
+        }
+    }
+}
+
+/// Registers shown for the target, in display order, as (label, lowercase
+/// 64-bit name, value); a 32-bit target has the E-names and no R8-R15
+fn register_rows(regs: &RegisterState, is_64bit: bool) -> Vec<(&'static str, &'static str, u64)> {
+    if !is_64bit {
+        return vec![
+            ("EAX", "rax", regs.rax), ("EBX", "rbx", regs.rbx),
+            ("ECX", "rcx", regs.rcx), ("EDX", "rdx", regs.rdx),
+            ("ESI", "rsi", regs.rsi), ("EDI", "rdi", regs.rdi),
+            ("EBP", "rbp", regs.rbp), ("ESP", "rsp", regs.rsp),
+            ("EIP", "rip", regs.rip), ("FLG", "rflags", regs.rflags),
+        ];
+    }
+    vec![
+        ("RAX", "rax", regs.rax), ("RBX", "rbx", regs.rbx),
+        ("RCX", "rcx", regs.rcx), ("RDX", "rdx", regs.rdx),
+        ("RSI", "rsi", regs.rsi), ("RDI", "rdi", regs.rdi),
+        ("RBP", "rbp", regs.rbp), ("RSP", "rsp", regs.rsp),
+        ("R8 ", "r8", regs.r8),   ("R9 ", "r9", regs.r9),
+        ("R10", "r10", regs.r10), ("R11", "r11", regs.r11),
+        ("R12", "r12", regs.r12), ("R13", "r13", regs.r13),
+        ("R14", "r14", regs.r14), ("R15", "r15", regs.r15),
+        ("RIP", "rip", regs.rip), ("FLG", "rflags", regs.rflags),
+    ]
+}
+
+/// Thread whose context the Registers and Call Stack columns show
+fn render_thread_selector(ui: &mut egui::Ui, state: &mut AppState) {
+    let debug = &state.debug_state;
+    if debug.threads.is_empty() {
+        return;
+    }
+    let thread_label = |tid: u32| {
+        if debug.main_thread_id == Some(tid) {
+            format!("tid {} (main)", tid)
+        } else {
+            format!("tid {}", tid)
+        }
+    };
+    let current = debug.last_thread_id.or(debug.main_thread_id);
+    let mut selected = current;
+    egui::ComboBox::from_id_source("debug_thread")
+        .selected_text(current.map(thread_label).unwrap_or_default())
+        .show_ui(ui, |ui| {
+            for &tid in &debug.threads {
+                ui.selectable_value(&mut selected, Some(tid), thread_label(tid));
+            }
+        });
+    if let Some(tid) = selected.filter(|_| selected != current) {
+        state.pending_debug_action = Some(DebugAction::SelectThread(tid));
+    }
+}
+
+fn render_call_stack_column(ui: &mut egui::Ui, state: &mut AppState, panel_width: f32, content_height: f32) {
+    egui::Frame::none()
+        .fill(catppuccin::mantle())
+        .inner_margin(6.0)
+        .rounding(4.0)
+        .show(ui, |ui| {
+            ui.set_width(panel_width);
+            
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("🧱 Call Stack")
+                    .color(catppuccin::mauve()).strong());
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(egui::RichText::new(format!("{}", state.debug_state.call_stack.len()))
+                        .color(catppuccin::overlay0()).small());
+                });
+            });
+            
+            ui.separator();
+            
+            if state.debug_state.call_stack.is_empty() {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(20.0);
+                    ui.label(egui::RichText::new("No call stack")
+                        .color(catppuccin::overlay0()).italics());
+                });
+                return;
+            }
+            
+            let mut clicked = None;
+            ui.push_id("call_stack", |ui| {
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                    .column(Column::exact(20.0))  // Frame index
+                    .column(Column::exact(130.0)) // Address
+                    .column(Column::remainder())  // Function
+                    .min_scrolled_height(0.0)
+                    .max_scroll_height(content_height - 30.0)
+                    .body(|body| {
+                        body.rows(18.0, state.debug_state.call_stack.len(), |mut row| {
+                            let index = row.index();
+                            let frame = state.debug_state.call_stack[index];
+                            let address = state.to_static(frame.address);
+                            
+                            row.col(|ui| {
+                                ui.label(egui::RichText::new(format!("{}", index))
+                                    .color(catppuccin::overlay1()).monospace());
+                            });
+                            
+                            row.col(|ui| {
+                                let label = ui.add(egui::Label::new(
+                                    egui::RichText::new(format!("0x{:012X}", state.shown_address(address)))
+                                        .color(code::address()).monospace()
+                                ).sense(egui::Sense::click()));
+                                if label.on_hover_text("Show in assembly").clicked() {
+                                    clicked = Some(address);
+                                }
+                            });
+                            
+                            row.col(|ui| {
+                                let func = state.loaded_binary.as_ref()
+                                    .and_then(|b| b.function_at(address));
+                                let text = match func {
+                                    Some(func) => egui::RichText::new(state.function_name(func))
+                                        .color(catppuccin::blue()),
+                                    None => egui::RichText::new("???")
+                                        .color(catppuccin::overlay0()),
+                                };
+                                ui.label(text.small());
+                            });
+                        });
+                    });
+            });
+            
+            if clicked.is_some() {
+                state.pending_goto = clicked;
             }
         });
 }
 
-fn get_log_style(log: &str) -> (&'static str, egui::Color32) {
+fn get_log_style(entry: &LogEntry) -> (&'static str, egui::Color32) {
+    let log = entry.text.as_str();
     if log.contains("BP hit") || log.contains("Breakpoint") {
-        ("🔴", catppuccin::RED)
+        ("🔴", catppuccin::red())
     } else if log.contains("Exception") {
-        ("⚠", catppuccin::MAROON)
+        ("⚠", catppuccin::maroon())
     } else if log.contains("Single step") {
-        ("→", catppuccin::YELLOW)
+        ("→", catppuccin::yellow())
     } else if log.contains("Process") {
-        ("📦", catppuccin::BLUE)
+        ("📦", catppuccin::blue())
     } else if log.contains("Thread") {
-        ("🧵", catppuccin::TEAL)
+        ("🧵", catppuccin::teal())
     } else if log.contains("DLL") || log.contains("Loaded") {
-        ("📚", catppuccin::PEACH)
-    } else if log.starts_with("[✓]") {
-        ("✓", catppuccin::GREEN)
-    } else if log.starts_with("[✗]") || log.starts_with("[!]") {
-        ("✗", catppuccin::RED)
+        ("📚", catppuccin::peach())
     } else {
-        ("·", catppuccin::SUBTEXT0)
+        match entry.level {
+            LogLevel::Success => ("✓", catppuccin::green()),
+            LogLevel::Warning => ("!", catppuccin::yellow()),
+            LogLevel::Error => ("✗", catppuccin::red()),
+            _ => ("·", catppuccin::subtext0()),
+        }
     }
 }
 
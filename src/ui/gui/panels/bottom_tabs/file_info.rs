@@ -0,0 +1,53 @@
+//! File Info tab panel - Binary summary and file hashes.
+
+use eframe::egui;
+use crate::ui::gui::state::AppState;
+use crate::ui::gui::theme::{catppuccin, code};
+
+/// Render file info tab content
+pub fn render(ui: &mut egui::Ui, state: &AppState) {
+    let (Some(binary), Some(hashes)) = (state.loaded_binary.as_ref(), state.file_hashes.as_ref()) else {
+        ui.vertical_centered(|ui| {
+            ui.add_space(20.0);
+            ui.label(egui::RichText::new("No binary loaded").color(catppuccin::overlay0()));
+        });
+        return;
+    };
+
+    let mut rows = vec![
+        ("MD5", hashes.md5.as_str()),
+        ("SHA-1", hashes.sha1.as_str()),
+        ("SHA-256", hashes.sha256.as_str()),
+    ];
+    if let Some(imphash) = &hashes.imphash {
+        rows.push(("Imphash", imphash.as_str()));
+    }
+
+    egui::ScrollArea::vertical().auto_shrink([false; 2]).show(ui, |ui| {
+        ui.label(egui::RichText::new(&binary.path).color(catppuccin::text()).strong());
+        ui.label(egui::RichText::new(binary.summary()).color(catppuccin::subtext0()).monospace());
+        ui.separator();
+
+        egui::Grid::new("file_hashes")
+            .num_columns(3)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                for (name, value) in &rows {
+                    ui.label(egui::RichText::new(*name).color(catppuccin::subtext1()));
+                    ui.label(egui::RichText::new(*value).color(code::number()).monospace());
+                    if ui.small_button("📋").on_hover_text("Copy").clicked() {
+                        ui.output_mut(|o| o.copied_text = value.to_string());
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if ui.button("📋 Copy All").clicked() {
+            let text = rows.iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect::<Vec<_>>()
+                .join("\n");
+            ui.output_mut(|o| o.copied_text = text);
+        }
+    });
+}
@@ -2,16 +2,22 @@
 
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
-use crate::ui::gui::state::AppState;
+use std::time::Duration;
+use crate::analysis::pattern::{find_pattern, parse_search_pattern};
+use crate::ui::gui::panels::copy_menu_button;
+use crate::ui::gui::state::{AppState, BottomTab, HexSource, LiveBytes, LogEntry, LogLevel};
 use crate::ui::gui::theme::{catppuccin, code};
 
+/// Minimum time between live-memory reads of the same range
+const LIVE_REFRESH: Duration = Duration::from_millis(500);
+
 /// Render hex view tab content with virtual scrolling
 pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
     let Some(ref binary) = state.loaded_binary else {
         ui.vertical_centered(|ui| {
             ui.add_space(20.0);
             ui.label(egui::RichText::new("No binary loaded")
-                .color(catppuccin::OVERLAY0)
+                .color(catppuccin::overlay0())
                 .size(14.0));
         });
         return;
@@ -21,9 +27,49 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
     let rows_per_page = 64;
     let total_rows = (data_len / 16) + if data_len % 16 != 0 { 1 } else { 0 };
     
+    // Keyboard navigation (skipped while a text field has focus); Shift extends the selection
+    let cursor_before = state.hex_cursor;
+    if !ui.ctx().wants_keyboard_input() && data_len > 0 {
+        let page = 16 * 16;
+        let shift = ui.input(|i| i.modifiers.shift);
+        ui.input(|i| {
+            if i.key_pressed(egui::Key::ArrowLeft) {
+                state.hex_cursor = state.hex_cursor.saturating_sub(1);
+            }
+            if i.key_pressed(egui::Key::ArrowRight) {
+                state.hex_cursor += 1;
+            }
+            if i.key_pressed(egui::Key::ArrowUp) {
+                state.hex_cursor = state.hex_cursor.saturating_sub(16);
+            }
+            if i.key_pressed(egui::Key::ArrowDown) {
+                state.hex_cursor += 16;
+            }
+            if i.key_pressed(egui::Key::PageUp) {
+                state.hex_cursor = state.hex_cursor.saturating_sub(page);
+            }
+            if i.key_pressed(egui::Key::PageDown) {
+                state.hex_cursor += page;
+            }
+        });
+        state.hex_cursor = state.hex_cursor.min(data_len - 1);
+        if state.hex_cursor != cursor_before {
+            state.hex_anchor = if shift { state.hex_anchor.or(Some(cursor_before)) } else { None };
+        }
+    }
+    let cursor_moved = state.hex_cursor != cursor_before;
+    let (sel_start, sel_end) = state.hex_selection();
+    
+    let live_mode = state.hex_source == HexSource::Live;
+
     // Controls
     ui.horizontal(|ui| {
-        ui.label(egui::RichText::new("Offset:").color(catppuccin::SUBTEXT0));
+        ui.selectable_value(&mut state.hex_source, HexSource::File, "File");
+        ui.selectable_value(&mut state.hex_source, HexSource::Live, "Live")
+            .on_hover_text("Show memory of the attached process");
+        ui.separator();
+
+        ui.label(egui::RichText::new("Offset:").color(catppuccin::subtext0()));
         let mut offset_str = format!("{:08X}", state.hex_offset);
         if ui.add(
             egui::TextEdit::singleline(&mut offset_str)
@@ -50,10 +96,93 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
         if ui.small_button("End").clicked() {
             state.hex_offset = (total_rows.saturating_sub(rows_per_page as u64)) * 16;
         }
+        if ui.add_enabled(!state.hex_back.is_empty(), egui::Button::new("◀ Back").small())
+            .on_hover_text("Return to where the last followed pointer was read")
+            .clicked()
+        {
+            if let Some(back) = state.hex_back.pop() {
+                state.hex_cursor = back;
+                state.hex_anchor = None;
+                state.hex_offset = (back / 16) * 16;
+            }
+        }
         
         ui.separator();
         ui.label(egui::RichText::new(format!("{} / {} bytes", state.hex_offset, data_len))
-            .color(catppuccin::SUBTEXT0).small());
+            .color(catppuccin::subtext0()).small());
+        
+        if let Some(&byte) = binary.data.get(state.hex_cursor as usize) {
+            ui.separator();
+            let ch = if (0x20..=0x7E).contains(&byte) { byte as char } else { '.' };
+            ui.label(egui::RichText::new(format!(
+                "Cursor {:08X}: {:02X} | {} | '{}'",
+                state.hex_cursor, byte, byte, ch
+            )).color(catppuccin::yellow()).monospace().small());
+        }
+        if sel_end > sel_start {
+            ui.separator();
+            ui.label(egui::RichText::new(format!(
+                "Selection {:08X}-{:08X} ({} bytes)",
+                sel_start, sel_end, sel_end - sel_start + 1
+            )).color(catppuccin::sky()).monospace().small());
+        }
+
+        if live_mode && !state.is_debugging {
+            ui.separator();
+            ui.label(egui::RichText::new("Not attached - showing file bytes")
+                .color(catppuccin::overlay0()).small());
+        }
+    });
+
+    // Search bar
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Search:").color(catppuccin::subtext0()));
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut state.hex_search_input)
+                .desired_width(220.0)
+                .font(egui::TextStyle::Monospace)
+                .hint_text("48 8B ?? or \"text\"")
+        );
+        let enter_pressed = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if ui.button(egui::RichText::new("Find").color(catppuccin::green())).clicked() || enter_pressed {
+            match parse_search_pattern(&state.hex_search_input) {
+                Ok(pattern) => {
+                    state.hex_search_matches = find_pattern(&binary.data, &pattern);
+                    state.hex_search_len = pattern.len();
+                    state.hex_search_index = 0;
+                    if let Some(&first) = state.hex_search_matches.first() {
+                        state.hex_cursor = first;
+                        state.hex_anchor = None;
+                        state.hex_offset = (first / 16) * 16;
+                    }
+                    state.log_buffer.push(LogEntry::new(format!("[*] Hex search: {} matches", state.hex_search_matches.len())));
+                }
+                Err(e) => {
+                    state.hex_search_matches.clear();
+                    state.log_buffer.push(LogEntry::at(LogLevel::Warning, format!("Invalid search pattern: {}", e)));
+                }
+            }
+        }
+        
+        let match_count = state.hex_search_matches.len();
+        if match_count > 0 {
+            let mut jump = None;
+            if ui.small_button("◀ Prev").clicked() {
+                jump = Some((state.hex_search_index + match_count - 1) % match_count);
+            }
+            if ui.small_button("Next ▶").clicked() {
+                jump = Some((state.hex_search_index + 1) % match_count);
+            }
+            if let Some(index) = jump {
+                let target = state.hex_search_matches[index];
+                state.hex_search_index = index;
+                state.hex_cursor = target;
+                state.hex_anchor = None;
+                state.hex_offset = (target / 16) * 16;
+            }
+            ui.label(egui::RichText::new(format!("{} / {}", state.hex_search_index + 1, match_count))
+                .color(catppuccin::subtext0()).small());
+        }
     });
 
     ui.separator();
@@ -62,11 +191,45 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
     let row_height = 18.0;
     
     // Calculate which rows to show based on current offset
-    let start_row = (state.hex_offset / 16) as usize;
     let visible_rows = ((available_height / row_height) as usize).min(rows_per_page).max(8);
+    
+    // Keep the cursor row on screen after keyboard movement
+    if cursor_moved {
+        let cursor_row = state.hex_cursor / 16;
+        let first_row = state.hex_offset / 16;
+        // Leave one row for the header
+        let body_rows = (visible_rows as u64).saturating_sub(1).max(1);
+        if cursor_row < first_row {
+            state.hex_offset = cursor_row * 16;
+        } else if cursor_row >= first_row + body_rows {
+            state.hex_offset = (cursor_row + 1 - body_rows) * 16;
+        }
+    }
+    
+    let start_row = (state.hex_offset / 16) as usize;
     let end_row = (start_row + visible_rows).min(total_rows as usize);
     let display_rows = end_row - start_row;
 
+    // Refresh the live range when it moved or went stale
+    let live = if live_mode && state.is_debugging {
+        let wanted = ((start_row * 16) as u64, display_rows * 16);
+        let stale = match &state.hex_live {
+            Some(l) => l.offset != wanted.0 || l.bytes.len() != wanted.1 || l.read_at.elapsed() >= LIVE_REFRESH,
+            None => true,
+        };
+        if stale {
+            state.pending_hex_live_read = Some(wanted);
+        }
+        ui.ctx().request_repaint_after(LIVE_REFRESH);
+        state.hex_live.as_ref()
+    } else {
+        None
+    };
+
+    let is_64bit = state.target_is_64bit();
+    // Cell and width (8 or 4 bytes) of a pointer to follow, from a context menu
+    let mut follow: Option<(u64, usize)> = None;
+
     // Use TableBuilder for virtual scrolling hex view
     TableBuilder::new(ui)
         .striped(true)
@@ -80,15 +243,15 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
         .header(20.0, |mut header| {
             header.col(|ui| {
                 ui.label(egui::RichText::new("Offset")
-                    .strong().color(catppuccin::TEXT));
+                    .strong().color(catppuccin::text()));
             });
             header.col(|ui| {
                 ui.label(egui::RichText::new("00 01 02 03 04 05 06 07  08 09 0A 0B 0C 0D 0E 0F")
-                    .strong().color(catppuccin::TEXT).monospace());
+                    .strong().color(catppuccin::text()).monospace());
             });
             header.col(|ui| {
                 ui.label(egui::RichText::new("ASCII")
-                    .strong().color(catppuccin::TEXT));
+                    .strong().color(catppuccin::text()));
             });
         })
         .body(|body| {
@@ -100,55 +263,238 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                     return;
                 }
                 
-                // Offset column
+                // Offset column (virtual address in live mode)
                 row.col(|ui| {
-                    ui.label(egui::RichText::new(format!("{:08X}", row_offset))
-                        .color(code::ADDRESS).monospace());
+                    let address = match binary.file_offset_to_va(row_offset) {
+                        Some(va) if live.is_some() => format!("{:08X}", va),
+                        _ => format!("{:08X}", row_offset),
+                    };
+                    ui.label(egui::RichText::new(address)
+                        .color(code::address()).monospace());
                 });
                 
-                // Hex bytes column
+                let start = row_offset as usize;
+                let end = ((row_offset + 16).min(data_len) as usize).min(binary.data.len());
+                let bytes: Vec<(u8, Option<egui::Color32>)> = binary.data[start..end].iter()
+                    .enumerate()
+                    .map(|(i, &file_byte)| live_byte(live, row_offset + i as u64, file_byte))
+                    .collect();
+                
+                // Hex bytes column (one clickable cell per byte)
                 row.col(|ui| {
-                    let mut hex_str = String::with_capacity(50);
-                    let start = row_offset as usize;
-                    let end = (row_offset + 16).min(data_len) as usize;
-                    
-                    if start < binary.data.len() {
-                        let bytes = &binary.data[start..end.min(binary.data.len())];
-                        for (i, byte) in bytes.iter().enumerate() {
-                            use std::fmt::Write;
-                            write!(hex_str, "{:02X} ", byte).unwrap();
-                            if i == 7 { hex_str.push(' '); }
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    for (i, &(byte, live_color)) in bytes.iter().enumerate() {
+                        let offset = row_offset + i as u64;
+                        let gap = if i == 7 { "  " } else { " " };
+                        let mut text = egui::RichText::new(format!("{:02X}", byte))
+                            .color(live_color.unwrap_or(code::hex_byte())).monospace();
+                        if in_match(&state.hex_search_matches, state.hex_search_len, offset) {
+                            text = text.color(catppuccin::crust()).background_color(catppuccin::peach());
                         }
-                        // Pad remaining
-                        for i in bytes.len()..16 {
-                            hex_str.push_str("   ");
-                            if i == 7 { hex_str.push(' '); }
+                        if (sel_start..=sel_end).contains(&offset) && sel_end > sel_start {
+                            text = text.background_color(catppuccin::surface2());
                         }
+                        if offset == state.hex_cursor {
+                            text = text.color(catppuccin::crust()).background_color(catppuccin::yellow());
+                        }
+                        let response = ui.add(egui::Label::new(text).sense(egui::Sense::click()));
+                        select_on_click(&mut state.hex_cursor, &mut state.hex_anchor, &response, offset);
+                        response.context_menu(|ui| {
+                            copy_selection_menu(ui, &binary.data, sel_start, sel_end);
+                            ui.separator();
+                            if ui.button("Follow as pointer (qword)").clicked() {
+                                follow = Some((offset, 8));
+                                ui.close_menu();
+                            }
+                            if !is_64bit && ui.button("Follow as dword").clicked() {
+                                follow = Some((offset, 4));
+                                ui.close_menu();
+                            }
+                        });
+                        ui.label(egui::RichText::new(gap).monospace());
                     }
-                    ui.label(egui::RichText::new(&hex_str)
-                        .color(code::HEX_BYTE).monospace());
                 });
                 
                 // ASCII column
                 row.col(|ui| {
-                    let mut ascii_str = String::with_capacity(16);
-                    let start = row_offset as usize;
-                    let end = (row_offset + 16).min(data_len) as usize;
-                    
-                    if start < binary.data.len() {
-                        let bytes = &binary.data[start..end.min(binary.data.len())];
-                        for byte in bytes {
-                            ascii_str.push(if *byte >= 0x20 && *byte <= 0x7E { 
-                                *byte as char 
-                            } else {
-                                '.' 
-                            });
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    for (i, &(byte, live_color)) in bytes.iter().enumerate() {
+                        let offset = row_offset + i as u64;
+                        let ch = if (0x20..=0x7E).contains(&byte) { byte as char } else { '.' };
+                        let mut text = egui::RichText::new(ch.to_string())
+                            .color(live_color.unwrap_or(code::ascii_printable())).monospace();
+                        if in_match(&state.hex_search_matches, state.hex_search_len, offset) {
+                            text = text.color(catppuccin::crust()).background_color(catppuccin::peach());
+                        }
+                        if (sel_start..=sel_end).contains(&offset) && sel_end > sel_start {
+                            text = text.background_color(catppuccin::surface2());
+                        }
+                        if offset == state.hex_cursor {
+                            text = text.color(catppuccin::crust()).background_color(catppuccin::yellow());
                         }
+                        let response = ui.add(egui::Label::new(text).sense(egui::Sense::click()));
+                        select_on_click(&mut state.hex_cursor, &mut state.hex_anchor, &response, offset);
+                        response.context_menu(|ui| copy_selection_menu(ui, &binary.data, sel_start, sel_end));
                     }
-                    ui.label(egui::RichText::new(&ascii_str)
-                        .color(code::ASCII_PRINTABLE).monospace());
                 });
             });
         });
+
+    let Some((offset, width)) = follow else {
+        return;
+    };
+    let Some(pointer) = pointer_at(&binary.data, live, offset, width) else {
+        state.warn(format!("No {}-byte value at offset {:08X}", width, offset));
+        return;
+    };
+    // Live values are process addresses; file bytes hold file VAs
+    let va = if live.is_some() { state.to_static(pointer) } else { pointer };
+    match binary.va_to_file_offset(va) {
+        Some(target) => {
+            state.hex_back.push(offset);
+            state.hex_cursor = target;
+            state.hex_anchor = None;
+            state.hex_offset = (target / 16) * 16;
+        }
+        // Outside the file image, e.g. heap or another module
+        None if live.is_some() => {
+            state.mem_addr_input = format!("0x{:x}", pointer);
+            state.pending_mem_read = true;
+            state.bottom_tab = BottomTab::Memory;
+        }
+        None => {
+            state.warn(format!("0x{:x} is not mapped by the file", pointer));
+        }
+    }
+}
+
+/// Little-endian value of the `width` bytes at `offset`, as shown (live
+/// bytes where the process read covered them)
+fn pointer_at(data: &[u8], live: Option<&LiveBytes>, offset: u64, width: usize) -> Option<u64> {
+    let mut value = [0u8; 8];
+    for (i, slot) in value.iter_mut().enumerate().take(width) {
+        let at = offset + i as u64;
+        *slot = live_byte(live, at, *data.get(at as usize)?).0;
+    }
+    Some(u64::from_le_bytes(value))
+}
+
+/// Move the cursor to a clicked byte; Shift-click extends the selection
+fn select_on_click(cursor: &mut u64, anchor: &mut Option<u64>, response: &egui::Response, offset: u64) {
+    if !response.clicked() {
+        return;
+    }
+    let shift = response.ctx.input(|i| i.modifiers.shift);
+    *anchor = if shift { anchor.or(Some(*cursor)) } else { None };
+    *cursor = offset;
+}
+
+/// Copy formats offered for the selected bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ByteFormat {
+    /// `"\x41\x42"`
+    CString,
+    /// `{ 0x41, 0x42 }`
+    CArray,
+    /// `b"\x41\x42"`
+    PythonBytes,
+    /// `41 42`
+    Hex,
 }
 
+impl ByteFormat {
+    const ALL: [ByteFormat; 4] = [Self::CString, Self::CArray, Self::PythonBytes, Self::Hex];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::CString => "Copy as C string",
+            Self::CArray => "Copy as C array",
+            Self::PythonBytes => "Copy as Python bytes",
+            Self::Hex => "Copy as hex",
+        }
+    }
+
+    fn format(self, bytes: &[u8]) -> String {
+        let escaped = || bytes.iter().map(|b| format!("\\x{:02x}", b)).collect::<String>();
+        match self {
+            Self::CString => format!("\"{}\"", escaped()),
+            Self::CArray => format!(
+                "{{ {} }}",
+                bytes.iter().map(|b| format!("0x{:02x}", b)).collect::<Vec<_>>().join(", ")
+            ),
+            Self::PythonBytes => format!("b\"{}\"", escaped()),
+            Self::Hex => bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" "),
+        }
+    }
+}
+
+/// Context menu copying the selected file bytes in each `ByteFormat`
+fn copy_selection_menu(ui: &mut egui::Ui, data: &[u8], start: u64, end: u64) {
+    let end = (end as usize).min(data.len().saturating_sub(1));
+    let Some(bytes) = data.get(start as usize..=end) else {
+        return;
+    };
+    ui.label(egui::RichText::new(format!("{} bytes at {:08X}", bytes.len(), start))
+        .color(catppuccin::subtext0()).small());
+    for format in ByteFormat::ALL {
+        copy_menu_button(ui, format.label(), || format.format(bytes));
+    }
+}
+
+/// Byte to display at `offset` and a color override for live mode.
+///
+/// Live bytes that differ from the file are highlighted; offsets the process
+/// read could not cover fall back to the (dimmed) file byte.
+fn live_byte(live: Option<&LiveBytes>, offset: u64, file_byte: u8) -> (u8, Option<egui::Color32>) {
+    let Some(live) = live else {
+        return (file_byte, None);
+    };
+    let index = offset.checked_sub(live.offset).map(|i| i as usize);
+    match index.and_then(|i| live.bytes.get(i).copied().flatten()) {
+        Some(byte) if byte != file_byte => (byte, Some(catppuccin::red())),
+        Some(byte) => (byte, None),
+        None => (file_byte, Some(catppuccin::overlay0())),
+    }
+}
+
+/// Whether `offset` lies inside any match of length `len`
+fn in_match(matches: &[u64], len: usize, offset: u64) -> bool {
+    // Last match starting at or before offset
+    let idx = matches.partition_point(|&m| m <= offset);
+    idx > 0 && offset < matches[idx - 1] + len as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_formats() {
+        let bytes = [0x41, 0x00, 0xff];
+        assert_eq!(ByteFormat::CString.format(&bytes), "\"\\x41\\x00\\xff\"");
+        assert_eq!(ByteFormat::CArray.format(&bytes), "{ 0x41, 0x00, 0xff }");
+        assert_eq!(ByteFormat::PythonBytes.format(&bytes), "b\"\\x41\\x00\\xff\"");
+        assert_eq!(ByteFormat::Hex.format(&bytes), "41 00 FF");
+    }
+
+    #[test]
+    fn test_pointer_at() {
+        let data = [0x00, 0x10, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff];
+        assert_eq!(pointer_at(&data, None, 0, 8), Some(0x401000));
+        assert_eq!(pointer_at(&data, None, 1, 4), Some(0x4010));
+        assert_eq!(pointer_at(&data, None, 2, 8), None);
+
+        let live = LiveBytes { offset: 0, bytes: vec![Some(0x00), Some(0x20), None], read_at: std::time::Instant::now() };
+        assert_eq!(pointer_at(&data, Some(&live), 0, 4), Some(0x402000));
+    }
+
+    #[test]
+    fn test_in_match() {
+        let data = [0x48, 0x8B, 0x05, 0x00, 0x48, 0x8B, 0xC0];
+        let pattern = parse_search_pattern("48 8B ??").unwrap();
+        let matches = find_pattern(&data, &pattern);
+        assert_eq!(matches, vec![0, 4]);
+        assert!(in_match(&matches, pattern.len(), 2));
+        assert!(!in_match(&matches, pattern.len(), 3));
+    }
+}
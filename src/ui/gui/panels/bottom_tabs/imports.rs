@@ -2,6 +2,10 @@
 
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
+use crate::analysis::loader::FunctionInfo;
+use crate::analysis::signatures::{classify, summarize, ImportCategory};
+use crate::debug::iat::{IatTarget, ResolvedImport};
+use crate::ui::gui::panels::copy_menu_button;
 use crate::ui::gui::state::AppState;
 use crate::ui::gui::theme::{catppuccin, code};
 
@@ -11,7 +15,7 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
         ui.vertical_centered(|ui| {
             ui.add_space(20.0);
             ui.label(egui::RichText::new("Load a binary to view imports")
-                .color(catppuccin::OVERLAY0));
+                .color(catppuccin::overlay0()));
         });
         return;
     };
@@ -22,13 +26,30 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
     let exports: Vec<_> = binary.functions.iter()
         .filter(|f| f.is_export)
         .collect();
+    // Runtime IAT column while attached to a PE process
+    let live_iat = state.is_debugging && binary.format == "PE";
+    let mut resolve_iat = false;
 
     ui.horizontal(|ui| {
         ui.label(egui::RichText::new(format!("Imports: {}", imports.len()))
-            .color(catppuccin::PEACH));
+            .color(catppuccin::peach()));
+        for (category, count) in summarize(imports.iter().map(|f| f.name.as_str())) {
+            category_badge(ui, category, Some(count));
+        }
         ui.separator();
         ui.label(egui::RichText::new(format!("Exports: {}", exports.len()))
-            .color(catppuccin::GREEN));
+            .color(catppuccin::green()));
+        if live_iat {
+            ui.separator();
+            resolve_iat = ui.button("⟳ Resolve IAT")
+                .on_hover_text("Read the imported addresses from the attached process")
+                .clicked();
+            let suspicious = state.iat_resolved.values().filter(|r| r.is_suspicious()).count();
+            if suspicious > 0 {
+                ui.label(egui::RichText::new(format!("⚠ {} outside any module", suspicious))
+                    .color(catppuccin::red()));
+            }
+        }
     });
 
     ui.separator();
@@ -37,15 +58,17 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
     
     ui.columns(2, |cols| {
         // Imports column
-        cols[0].label(egui::RichText::new("Imports").color(catppuccin::PEACH).strong());
+        cols[0].label(egui::RichText::new("Imports").color(catppuccin::peach()).strong());
         
         let import_height = (available_height - 30.0).max(50.0);
         cols[0].push_id("imports_table", |ui| {
             TableBuilder::new(ui)
+                .sense(egui::Sense::click())
                 .striped(true)
                 .resizable(true)
                 .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
                 .column(Column::exact(75.0))
+                .columns(Column::exact(130.0), usize::from(live_iat))
                 .column(Column::remainder())
                 .min_scrolled_height(0.0)
                 .max_scroll_height(import_height)
@@ -54,25 +77,38 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                         let func = &imports[row.index()];
                         row.col(|ui| {
                             ui.label(egui::RichText::new(format!("{:08X}", func.address))
-                                .monospace().color(code::ADDRESS));
+                                .monospace().color(code::address()));
                         });
+                        if live_iat {
+                            row.col(|ui| {
+                                if let Some(resolved) = state.iat_resolved.get(&func.address) {
+                                    resolved_label(ui, resolved);
+                                }
+                            });
+                        }
                         row.col(|ui| {
                             ui.label(egui::RichText::new(&func.name)
-                                .color(catppuccin::PEACH));
+                                .color(catppuccin::peach()));
+                            if let Some(category) = classify(&func.name) {
+                                category_badge(ui, category, None);
+                            }
                         });
+                        row.response().context_menu(|ui| function_copy_menu(ui, func));
                     });
                 });
         });
 
         // Exports column
-        cols[1].label(egui::RichText::new("Exports").color(catppuccin::GREEN).strong());
+        cols[1].label(egui::RichText::new("Exports").color(catppuccin::green()).strong());
         
         cols[1].push_id("exports_table", |ui| {
             TableBuilder::new(ui)
+                .sense(egui::Sense::click())
                 .striped(true)
                 .resizable(true)
                 .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
                 .column(Column::exact(75.0))
+                .column(Column::exact(45.0))
                 .column(Column::remainder())
                 .min_scrolled_height(0.0)
                 .max_scroll_height(import_height)
@@ -81,15 +117,75 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                         let func = &exports[row.index()];
                         row.col(|ui| {
                             ui.label(egui::RichText::new(format!("{:08X}", func.address))
-                                .monospace().color(code::ADDRESS));
+                                .monospace().color(code::address()));
+                        });
+                        row.col(|ui| {
+                            if let Some(ordinal) = func.ordinal {
+                                ui.label(egui::RichText::new(format!("#{}", ordinal))
+                                    .monospace().color(catppuccin::subtext0()));
+                            }
                         });
                         row.col(|ui| {
                             ui.label(egui::RichText::new(&func.name)
-                                .color(catppuccin::GREEN));
+                                .color(catppuccin::green()));
+                            if let Some(target) = &func.forwarded_to {
+                                ui.label(egui::RichText::new(format!("→ {}", target))
+                                    .color(catppuccin::overlay0()));
+                            }
                         });
+                        row.response().context_menu(|ui| function_copy_menu(ui, func));
                     });
                 });
         });
     });
+
+    state.pending_iat_read |= resolve_iat;
+}
+
+/// Runtime address of an IAT slot, colored by where it points
+fn resolved_label(ui: &mut egui::Ui, resolved: &ResolvedImport) {
+    let text = format!("{:016X}", resolved.address);
+    let (text, color, hover) = match &resolved.target {
+        IatTarget::Expected => (text, code::address(), None),
+        IatTarget::OtherModule(module) => (text, catppuccin::yellow(), Some(format!("Resolved into {}", module))),
+        IatTarget::Unmapped => (
+            format!("⚠ {}", text),
+            catppuccin::red(),
+            Some("Outside every loaded module (possible IAT hook)".to_string()),
+        ),
+    };
+    let label = ui.label(egui::RichText::new(text).monospace().color(color));
+    if let Some(hover) = hover {
+        label.on_hover_text(hover);
+    }
 }
 
+/// Colored `category` (or `category: count`) tag
+fn category_badge(ui: &mut egui::Ui, category: ImportCategory, count: Option<usize>) {
+    let color = match category {
+        ImportCategory::AntiDebug => catppuccin::red(),
+        ImportCategory::Injection => catppuccin::maroon(),
+        ImportCategory::Crypto => catppuccin::mauve(),
+        ImportCategory::Network => catppuccin::blue(),
+    };
+    let text = match count {
+        Some(count) => format!("{}: {}", category.label(), count),
+        None => category.label().to_string(),
+    };
+    egui::Frame::none()
+        .fill(color.linear_multiply(0.2))
+        .inner_margin(egui::Margin::symmetric(4.0, 0.0))
+        .rounding(3.0)
+        .show(ui, |ui| {
+            ui.label(egui::RichText::new(text).color(color).small());
+        });
+}
+
+/// Copy actions for an import/export row
+fn function_copy_menu(ui: &mut egui::Ui, func: &FunctionInfo) {
+    copy_menu_button(ui, "Copy name", || func.name.clone());
+    copy_menu_button(ui, "Copy address", || format!("0x{:x}", func.address));
+    if let Some(target) = &func.forwarded_to {
+        copy_menu_button(ui, "Copy forwarder", || target.clone());
+    }
+}
@@ -0,0 +1,60 @@
+//! Memory tab panel - Hex dump of the attached process's memory.
+
+use eframe::egui;
+use crate::ui::gui::state::AppState;
+use crate::ui::gui::theme::catppuccin;
+
+/// Render memory tab content
+pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Address:").color(catppuccin::subtext0()));
+        let addr_response = ui.add(
+            egui::TextEdit::singleline(&mut state.mem_addr_input)
+                .desired_width(180.0)
+                .font(egui::TextStyle::Monospace)
+                .hint_text("0x1000, rsp, [rbp+8]")
+        );
+
+        ui.label(egui::RichText::new("Bytes:").color(catppuccin::subtext0()));
+        let len_response = ui.add(
+            egui::TextEdit::singleline(&mut state.mem_len_input)
+                .desired_width(50.0)
+                .font(egui::TextStyle::Monospace)
+        );
+
+        let enter = (addr_response.lost_focus() || len_response.lost_focus())
+            && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if ui.button("Read").clicked() || enter {
+            state.pending_mem_read = true;
+        }
+        if ui.button("💾 Dump...")
+            .on_hover_text("Save the range to a file; unreadable pages are zero-filled")
+            .clicked()
+        {
+            state.pending_mem_dump = true;
+        }
+
+        ui.label(egui::RichText::new("Refreshes when the process suspends")
+            .color(catppuccin::overlay0()).small().italics());
+    });
+
+    ui.separator();
+
+    if state.mem_dump.is_empty() {
+        ui.vertical_centered(|ui| {
+            ui.add_space(20.0);
+            ui.label(egui::RichText::new("Enter an address or register to dump memory")
+                .color(catppuccin::overlay0()));
+        });
+        return;
+    }
+
+    egui::ScrollArea::vertical()
+        .id_source("mem_dump_scroll")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            ui.label(egui::RichText::new(&state.mem_dump)
+                .monospace()
+                .color(catppuccin::text()));
+        });
+}
@@ -1,12 +1,17 @@
-//! Bottom tabbed panel - Console, Hex View, Strings, Imports, Debug tabs.
+//! Bottom tabbed panel - Console, Hex View, Strings, Imports, Sections, File Info, Bookmarks, Debug, Memory, YARA tabs.
 //!
 //! This module organizes the bottom panel into separate sub-modules for each tab.
 
+mod bookmarks;
 mod console;
 mod debug;
+mod file_info;
 mod hexview;
 mod imports;
+mod memory;
+mod sections;
 mod strings;
+mod yara;
 
 use eframe::egui;
 use crate::ui::gui::state::{AppState, BottomTab};
@@ -31,11 +36,16 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> ConsoleAction {
             // Tab bar with styled tabs
             ui.horizontal(|ui| {
                 let tabs = [
-                    (BottomTab::Console, "Console", catppuccin::BLUE),
-                    (BottomTab::HexView, "Hex View", catppuccin::PEACH),
-                    (BottomTab::Strings, "Strings", catppuccin::GREEN),
-                    (BottomTab::Imports, "Imports", catppuccin::MAUVE),
-                    (BottomTab::Debug, "Debug", catppuccin::RED),
+                    (BottomTab::Console, "Console", catppuccin::blue()),
+                    (BottomTab::HexView, "Hex View", catppuccin::peach()),
+                    (BottomTab::Strings, "Strings", catppuccin::green()),
+                    (BottomTab::Imports, "Imports", catppuccin::mauve()),
+                    (BottomTab::Sections, "Sections", catppuccin::teal()),
+                    (BottomTab::FileInfo, "File Info", catppuccin::lavender()),
+                    (BottomTab::Bookmarks, "Bookmarks", catppuccin::yellow()),
+                    (BottomTab::Debug, "Debug", catppuccin::red()),
+                    (BottomTab::Memory, "Memory", catppuccin::sapphire()),
+                    (BottomTab::Yara, "YARA", catppuccin::flamingo()),
                 ];
                 
                 for (tab, label, accent) in tabs {
@@ -43,7 +53,7 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> ConsoleAction {
                     let text = if is_selected {
                         egui::RichText::new(label).color(accent).strong()
                     } else {
-                        egui::RichText::new(label).color(catppuccin::SUBTEXT0)
+                        egui::RichText::new(label).color(catppuccin::subtext0())
                     };
                     if ui.selectable_label(is_selected, text).clicked() {
                         state.bottom_tab = tab;
@@ -63,14 +73,48 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> ConsoleAction {
                         hexview::render(ui, state);
                     }
                     BottomTab::Strings => {
-                        strings::render(ui, state);
+                        if let Some(offset) = strings::render(ui, state) {
+                            state.hex_offset = (offset / 16) * 16;
+                            state.bottom_tab = BottomTab::HexView;
+                        }
                     }
                     BottomTab::Imports => {
                         imports::render(ui, state);
                     }
+                    BottomTab::Sections => {
+                        match sections::render(ui, state) {
+                            sections::SectionsAction::ShowInHex(offset) => {
+                                state.hex_offset = (offset / 16) * 16;
+                                state.bottom_tab = BottomTab::HexView;
+                            }
+                            sections::SectionsAction::ExtractOverlay => {
+                                state.pending_overlay_export = true;
+                            }
+                            sections::SectionsAction::None => {}
+                        }
+                    }
+                    BottomTab::FileInfo => {
+                        file_info::render(ui, state);
+                    }
+                    BottomTab::Bookmarks => {
+                        if let Some(addr) = bookmarks::render(ui, state) {
+                            state.pending_goto = Some(addr);
+                        }
+                    }
                     BottomTab::Debug => {
                         debug::render(ui, state);
                     }
+                    BottomTab::Memory => {
+                        memory::render(ui, state);
+                    }
+                    BottomTab::Yara => {
+                        if let Some(offset) = yara::render(ui, state) {
+                            state.hex_offset = (offset / 16) * 16;
+                            state.hex_cursor = offset;
+                            state.hex_anchor = None;
+                            state.bottom_tab = BottomTab::HexView;
+                        }
+                    }
                 }
             });
         });
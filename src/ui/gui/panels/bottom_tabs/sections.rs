@@ -0,0 +1,131 @@
+//! Sections tab panel - Section headers with permissions and entropy.
+
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+use crate::analysis::loader::{SectionInfo, PACKED_ENTROPY_THRESHOLD};
+use crate::ui::gui::state::AppState;
+use crate::ui::gui::theme::{catppuccin, code};
+
+/// Action requested from the sections tab
+pub enum SectionsAction {
+    /// Show a file offset in the hex view
+    ShowInHex(u64),
+    /// Save the overlay bytes to a file
+    ExtractOverlay,
+    None,
+}
+
+/// Render sections tab content.
+pub fn render(ui: &mut egui::Ui, state: &AppState) -> SectionsAction {
+    let Some(binary) = state.loaded_binary.as_ref() else {
+        ui.vertical_centered(|ui| {
+            ui.add_space(20.0);
+            ui.label(egui::RichText::new("No binary loaded").color(catppuccin::overlay0()));
+        });
+        return SectionsAction::None;
+    };
+
+    let mut action = SectionsAction::None;
+
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(format!("{} sections", binary.sections.len()))
+            .color(catppuccin::subtext0()));
+
+        // Data appended past the last section (installers, droppers, signatures)
+        if let Some(overlay) = &binary.overlay {
+            ui.separator();
+            ui.label(egui::RichText::new(format!(
+                "Overlay: {:X} bytes at file offset {:X}",
+                overlay.end - overlay.start, overlay.start
+            )).color(catppuccin::yellow()));
+            if ui.small_button("Show in Hex View").clicked() {
+                action = SectionsAction::ShowInHex(overlay.start);
+            }
+            if ui.small_button("Extract...").clicked() {
+                action = SectionsAction::ExtractOverlay;
+            }
+        }
+    });
+    ui.separator();
+
+    let available_height = ui.available_height();
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::initial(110.0).at_least(60.0))
+        .column(Column::exact(130.0))
+        .column(Column::exact(80.0))
+        .column(Column::exact(80.0))
+        .column(Column::exact(80.0))
+        .column(Column::exact(40.0))
+        .column(Column::remainder())
+        .min_scrolled_height(0.0)
+        .max_scroll_height(available_height)
+        .header(20.0, |mut header| {
+            header.col(|ui| { ui.strong("Name"); });
+            header.col(|ui| { ui.strong("Virtual Address"); });
+            header.col(|ui| { ui.strong("VSize"); });
+            header.col(|ui| { ui.strong("File Offset"); });
+            header.col(|ui| { ui.strong("File Size"); });
+            header.col(|ui| { ui.strong("Perm"); });
+            header.col(|ui| { ui.strong("Entropy"); });
+        })
+        .body(|body| {
+            body.rows(18.0, binary.sections.len(), |mut row| {
+                let section = &binary.sections[row.index()];
+                row.col(|ui| {
+                    ui.label(egui::RichText::new(&section.name).color(catppuccin::text()));
+                });
+                row.col(|ui| {
+                    let label = ui.add(egui::Label::new(
+                        egui::RichText::new(format!("{:016X}", section.virtual_address))
+                            .monospace()
+                            .color(code::address())
+                    ).sense(egui::Sense::click()));
+                    if label.on_hover_text("Show in Hex View").clicked() {
+                        action = SectionsAction::ShowInHex(section.file_offset);
+                    }
+                });
+                row.col(|ui| {
+                    ui.label(egui::RichText::new(format!("{:X}", section.virtual_size))
+                        .monospace().color(catppuccin::subtext0()));
+                });
+                row.col(|ui| {
+                    ui.label(egui::RichText::new(format!("{:X}", section.file_offset))
+                        .monospace().color(catppuccin::subtext0()));
+                });
+                row.col(|ui| {
+                    ui.label(egui::RichText::new(format!("{:X}", section.file_size))
+                        .monospace().color(catppuccin::subtext0()));
+                });
+                row.col(|ui| {
+                    ui.label(egui::RichText::new(permissions(section))
+                        .monospace().color(catppuccin::peach()));
+                });
+                row.col(|ui| {
+                    let color = if section.entropy > PACKED_ENTROPY_THRESHOLD {
+                        catppuccin::red()
+                    } else {
+                        catppuccin::green()
+                    };
+                    ui.add(egui::ProgressBar::new((section.entropy / 8.0) as f32)
+                        .desired_width(120.0)
+                        .fill(color)
+                        .text(format!("{:.2}", section.entropy)));
+                });
+            });
+        });
+
+    action
+}
+
+/// `rwx`-style permission string for a section
+fn permissions(section: &SectionInfo) -> String {
+    // Every mapped section is readable in practice; only w/x vary
+    format!(
+        "r{}{}",
+        if section.is_writable { 'w' } else { '-' },
+        if section.is_executable { 'x' } else { '-' },
+    )
+}
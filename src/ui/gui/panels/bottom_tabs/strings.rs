@@ -2,14 +2,21 @@
 
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
-use crate::ui::gui::state::{AppState, ExtractedString, StringEncoding};
+use crate::analysis::strings::extract_strings_with;
+use crate::ui::gui::panels::copy_menu_button;
+use crate::ui::gui::state::{AppState, EncodingFilter, ExtractedString, StringEncoding};
 use crate::ui::gui::theme::{catppuccin, code};
 
-/// Render strings tab content with virtual scrolling
-pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
+/// Bounds of the minimum string length control
+const MIN_LEN_RANGE: (usize, usize) = (2, 16);
+
+/// Render strings tab content with virtual scrolling.
+///
+/// Returns the file offset of a string whose offset label was clicked.
+pub fn render(ui: &mut egui::Ui, state: &mut AppState) -> Option<u64> {
     // Controls
     ui.horizontal(|ui| {
-        ui.label(egui::RichText::new("Filter:").color(catppuccin::SUBTEXT0));
+        ui.label(egui::RichText::new("Filter:").color(catppuccin::subtext0()));
         let response = ui.add(
             egui::TextEdit::singleline(&mut state.strings_filter)
                 .desired_width(200.0)
@@ -17,27 +24,77 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
         );
         
         let enter_pressed = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
-        if ui.button(egui::RichText::new("Extract").color(catppuccin::GREEN)).clicked() || enter_pressed {
+        if ui.button(egui::RichText::new("Extract").color(catppuccin::green())).clicked() || enter_pressed {
             extract_strings_from_binary(state);
         }
         
+        let can_export = !state.extracted_strings.is_empty();
+        if ui.add_enabled(can_export, egui::Button::new(
+            egui::RichText::new("Export").color(catppuccin::blue())
+        )).clicked() {
+            let filter = state.strings_filter.to_lowercase();
+            let lines = state.extracted_strings.iter()
+                .filter(|s| filter.is_empty() || s.value.to_lowercase().contains(&filter))
+                .map(format_export_line)
+                .collect();
+            state.pending_strings_export = Some(lines);
+        }
+        
         ui.separator();
         ui.label(egui::RichText::new(format!("{} strings", state.extracted_strings.len()))
-            .color(catppuccin::SUBTEXT0).small());
+            .color(catppuccin::subtext0()).small());
     });
 
+    // Extraction options; changing one re-extracts
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Min length:").color(catppuccin::subtext0()));
+        let response = ui.add(egui::DragValue::new(&mut state.strings_min_len)
+            .clamp_range(MIN_LEN_RANGE.0..=MIN_LEN_RANGE.1));
+        // Wait for the drag to end before re-extracting
+        changed |= response.drag_stopped() || (response.changed() && !response.dragged());
+
+        ui.separator();
+        for encoding in EncodingFilter::ALL {
+            if ui.radio(state.strings_encoding == encoding, encoding.label()).clicked()
+                && state.strings_encoding != encoding
+            {
+                state.strings_encoding = encoding;
+                changed = true;
+            }
+        }
+
+        ui.separator();
+        ui.label(egui::RichText::new("Scope:").color(catppuccin::subtext0()));
+        let sections: Vec<String> = state.loaded_binary.as_ref()
+            .map(|b| b.sections.iter().filter(|s| s.file_size > 0).map(|s| s.name.clone()).collect())
+            .unwrap_or_default();
+        let selected = state.strings_section.clone().unwrap_or_else(|| "Whole file".into());
+        egui::ComboBox::from_id_source("strings_scope")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                changed |= ui.selectable_value(&mut state.strings_section, None, "Whole file").changed();
+                for name in sections {
+                    changed |= ui.selectable_value(&mut state.strings_section, Some(name.clone()), name).changed();
+                }
+            });
+    });
+    if changed {
+        extract_strings_from_binary(state);
+    }
+
     if state.extracted_strings.is_empty() {
         ui.vertical_centered(|ui| {
             ui.add_space(20.0);
             if state.loaded_binary.is_some() {
                 ui.label(egui::RichText::new("Click 'Extract' to find strings")
-                    .color(catppuccin::OVERLAY0));
+                    .color(catppuccin::overlay0()));
             } else {
                 ui.label(egui::RichText::new("Load a binary first")
-                    .color(catppuccin::OVERLAY0));
+                    .color(catppuccin::overlay0()));
             }
         });
-        return;
+        return None;
     }
 
     // Filter strings
@@ -49,10 +106,13 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
     let available_height = ui.available_height();
     let row_height = 20.0;
     let total_rows = filtered_strings.len();
+    let mut clicked_offset = None;
+    let binary = state.loaded_binary.as_ref();
 
     // Virtual scrolling table for strings
     ui.push_id("strings_table", |ui| {
     TableBuilder::new(ui)
+        .sense(egui::Sense::click())
         .striped(true)
         .resizable(true)
         .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
@@ -63,13 +123,13 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
         .max_scroll_height(available_height)
         .header(20.0, |mut header| {
             header.col(|ui| {
-                ui.label(egui::RichText::new("Offset").strong().color(catppuccin::TEXT));
+                ui.label(egui::RichText::new("Offset").strong().color(catppuccin::text()));
             });
             header.col(|ui| {
-                ui.label(egui::RichText::new("Type").strong().color(catppuccin::TEXT));
+                ui.label(egui::RichText::new("Type").strong().color(catppuccin::text()));
             });
             header.col(|ui| {
-                ui.label(egui::RichText::new("String").strong().color(catppuccin::TEXT));
+                ui.label(egui::RichText::new("String").strong().color(catppuccin::text()));
             });
         })
         .body(|body| {
@@ -77,16 +137,19 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                 let s = &filtered_strings[row.index()];
                 
                 row.col(|ui| {
-                    let _ = ui.selectable_label(false, 
+                    let response = ui.selectable_label(false, 
                         egui::RichText::new(format!("{:08X}", s.offset))
-                            .monospace().color(code::ADDRESS)
+                            .monospace().color(code::address())
                     );
+                    if response.on_hover_text("Show in Hex View").clicked() {
+                        clicked_offset = Some(s.offset);
+                    }
                 });
                 
                 row.col(|ui| {
                     let (type_str, color) = match s.encoding {
-                        StringEncoding::Ascii => ("ASCII", catppuccin::BLUE),
-                        StringEncoding::Utf16Le => ("UTF16", catppuccin::MAUVE),
+                        StringEncoding::Ascii => ("ASCII", catppuccin::blue()),
+                        StringEncoding::Utf16Le => ("UTF16", catppuccin::mauve()),
                     };
                     ui.label(egui::RichText::new(type_str).color(color).small());
                 });
@@ -98,43 +161,58 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                         s.value.clone()
                     };
                     ui.label(egui::RichText::new(display_str)
-                        .color(catppuccin::GREEN).monospace());
+                        .color(catppuccin::green()).monospace());
+                });
+
+                let va = binary.and_then(|b| b.file_offset_to_va(s.offset));
+                row.response().context_menu(|ui| {
+                    copy_menu_button(ui, "Copy value", || s.value.clone());
+                    match va {
+                        Some(va) => copy_menu_button(ui, "Copy address", || format!("0x{:x}", va)),
+                        None => copy_menu_button(ui, "Copy offset", || format!("0x{:x}", s.offset)),
+                    }
                 });
             });
         });
     });
+
+    clicked_offset
+}
+
+/// Format a string for text export: `<offset> [<encoding>] <value>`
+fn format_export_line(s: &ExtractedString) -> String {
+    let tag = match s.encoding {
+        StringEncoding::Ascii => "ASCII",
+        StringEncoding::Utf16Le => "UTF16",
+    };
+    format!("{:08X} [{}] {}", s.offset, tag, s.value)
 }
 
-/// Extract strings from binary
+/// Extract strings from binary (or the section in scope) with the tab's options
 pub fn extract_strings_from_binary(state: &mut AppState) {
     state.extracted_strings.clear();
     
     let Some(ref binary) = state.loaded_binary else { return; };
-    
-    let min_len = 4;
-    let data = &binary.data;
-    
-    // ASCII strings
-    let mut current_string = String::new();
-    let mut start_offset: u64 = 0;
-    
-    for (i, &byte) in data.iter().enumerate() {
-        if byte >= 0x20 && byte <= 0x7E {
-            if current_string.is_empty() { start_offset = i as u64; }
-            current_string.push(byte as char);
-        } else {
-            if current_string.len() >= min_len {
-                state.extracted_strings.push(ExtractedString {
-                    offset: start_offset,
-                    value: current_string.clone(),
-                    encoding: StringEncoding::Ascii,
-                });
+
+    // File range to scan; offsets stay relative to the whole file
+    let (start, end) = match &state.strings_section {
+        Some(name) => match binary.sections.iter().find(|s| &s.name == name) {
+            Some(section) => {
+                let start = (section.file_offset as usize).min(binary.data.len());
+                (start, start.saturating_add(section.file_size as usize).min(binary.data.len()))
             }
-            current_string.clear();
-        }
-    }
+            None => {
+                state.warn(format!("No section named {}", name));
+                return;
+            }
+        },
+        None => (0, binary.data.len()),
+    };
     
-    state.extracted_strings.sort_by_key(|s| s.offset);
-    state.log_buffer.push(format!("[✓] Extracted {} strings", state.extracted_strings.len()));
+    let mut strings = extract_strings_with(&binary.data[start..end], state.strings_min_len, state.strings_encoding);
+    for s in &mut strings {
+        s.offset += start as u64;
+    }
+    state.extracted_strings = strings;
+    state.success(format!("Extracted {} strings", state.extracted_strings.len()));
 }
-
@@ -0,0 +1,105 @@
+//! YARA tab panel - Rules and string hits from the last YARA scan.
+
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+use crate::ui::gui::state::AppState;
+use crate::ui::gui::theme::{catppuccin, code};
+
+/// Render YARA tab content.
+///
+/// Returns the file offset of a string hit whose offset label was clicked.
+pub fn render(ui: &mut egui::Ui, state: &mut AppState) -> Option<u64> {
+    ui.horizontal(|ui| {
+        match &state.yara_rules_path {
+            Some(path) => ui.label(egui::RichText::new(path).color(catppuccin::text())),
+            None => ui.label(egui::RichText::new("No rules loaded").color(catppuccin::overlay0())),
+        };
+        if !state.yara_status.is_empty() {
+            ui.separator();
+            ui.label(egui::RichText::new(&state.yara_status)
+                .color(catppuccin::subtext0()).small());
+        }
+    });
+    ui.separator();
+
+    if state.yara_results.is_empty() {
+        ui.vertical_centered(|ui| {
+            ui.add_space(20.0);
+            let hint = if state.yara_running {
+                "Scanning..."
+            } else if state.yara_rules_path.is_some() {
+                "No rules matched"
+            } else {
+                "Use Tools > YARA Scan... to scan the binary with a rules file"
+            };
+            ui.label(egui::RichText::new(hint).color(catppuccin::overlay0()));
+        });
+        return None;
+    }
+
+    // One row per string hit; rules that matched on their condition alone get
+    // a single row without an offset
+    let rows: Vec<(usize, Option<usize>)> = state.yara_results.iter()
+        .enumerate()
+        .flat_map(|(rule, m)| {
+            let count = m.strings.len();
+            (0..count.max(1)).map(move |hit| (rule, (hit < count).then_some(hit)))
+        })
+        .collect();
+
+    let mut clicked = None;
+    let available_height = ui.available_height();
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::initial(200.0).at_least(80.0))
+        .column(Column::initial(120.0).at_least(60.0))
+        .column(Column::exact(90.0))
+        .column(Column::remainder())
+        .min_scrolled_height(0.0)
+        .max_scroll_height(available_height)
+        .header(20.0, |mut header| {
+            header.col(|ui| { ui.strong("Rule"); });
+            header.col(|ui| { ui.strong("String"); });
+            header.col(|ui| { ui.strong("Offset"); });
+            header.col(|ui| { ui.strong("Length"); });
+        })
+        .body(|body| {
+            body.rows(18.0, rows.len(), |mut row| {
+                let (rule, hit) = rows[row.index()];
+                let m = &state.yara_results[rule];
+                let hit = hit.map(|hit| &m.strings[hit]);
+                row.col(|ui| {
+                    ui.label(egui::RichText::new(&m.rule).color(catppuccin::flamingo()));
+                });
+                row.col(|ui| {
+                    if let Some(hit) = hit {
+                        ui.label(egui::RichText::new(&hit.identifier)
+                            .monospace()
+                            .color(catppuccin::text()));
+                    }
+                });
+                row.col(|ui| {
+                    if let Some(hit) = hit {
+                        let label = ui.add(egui::Label::new(
+                            egui::RichText::new(format!("{:08X}", hit.offset))
+                                .monospace()
+                                .color(code::address())
+                        ).sense(egui::Sense::click()));
+                        if label.on_hover_text("Show in Hex View").clicked() {
+                            clicked = Some(hit.offset);
+                        }
+                    }
+                });
+                row.col(|ui| {
+                    if let Some(hit) = hit {
+                        ui.label(egui::RichText::new(hit.length.to_string())
+                            .color(catppuccin::subtext0()));
+                    }
+                });
+            });
+        });
+
+    clicked
+}
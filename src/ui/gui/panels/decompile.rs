@@ -1,7 +1,12 @@
 //! Decompiled code panel - displays C-like decompiled output with syntax highlighting.
 
 use eframe::egui;
-use super::super::state::AppState;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use crate::analysis::loader::LoadedBinary;
+use crate::analysis::xrefs;
+use super::super::app::decompiler::cancel_decompile;
+use super::super::state::{AppState, DecompileView};
 use super::super::theme::{catppuccin, code};
 
 /// Render the decompiled code as a fixed right panel.
@@ -12,111 +17,261 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) {
         .min_width(250.0)
         .show(ctx, |ui| {
             ui.horizontal(|ui| {
-                ui.heading(egui::RichText::new("Decompiled").color(catppuccin::LAVENDER));
+                ui.heading(egui::RichText::new("Decompiled").color(catppuccin::lavender()));
                 
                 if state.decompiling {
                     ui.spinner();
                     ui.label(egui::RichText::new("Processing...")
-                        .color(catppuccin::YELLOW).small());
+                        .color(catppuccin::yellow()).small());
+                    if ui.small_button(egui::RichText::new("Cancel").color(catppuccin::red())).clicked() {
+                        cancel_decompile(state);
+                        state.decompiled_code = "// Decompilation cancelled".into();
+                    }
                 } else if let Some(ref func) = state.selected_function {
                     ui.separator();
-                    ui.label(egui::RichText::new(&func.name)
-                        .color(catppuccin::BLUE).small());
+                    ui.label(egui::RichText::new(state.function_name(func))
+                        .color(catppuccin::blue()).small());
                 }
+                
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.selectable_value(&mut state.decompile_view, DecompileView::Pcode, "P-code");
+                    ui.selectable_value(&mut state.decompile_view, DecompileView::C, "C");
+                });
             });
             ui.separator();
 
+            if state.decompile_view == DecompileView::Pcode {
+                render_pcode(ui, state);
+                return;
+            }
+
             if state.decompiled_code.is_empty() && !state.decompiling {
                 ui.vertical_centered(|ui| {
                     ui.add_space(60.0);
                     ui.label(egui::RichText::new("No decompilation available")
-                        .color(catppuccin::OVERLAY0)
+                        .color(catppuccin::overlay0())
                         .size(14.0));
                     ui.add_space(8.0);
                     ui.label(egui::RichText::new("Select a function to decompile")
-                        .color(catppuccin::OVERLAY0)
+                        .color(catppuccin::overlay0())
                         .small());
                 });
                 return;
             }
 
-            // Code view with syntax highlighting
-            egui::ScrollArea::vertical()
+            // Code view with syntax highlighting, data symbols annotated inline.
+            // Annotating looks up every symbol, so it is only redone on change
+            let key = annotation_key(state, state.loaded_binary.as_ref());
+            if state.annotated_code.key != Some(key) {
+                state.annotated_code.text = match state.loaded_binary.as_ref() {
+                    Some(binary) => xrefs::annotate_decompiled(&state.decompiled_code, binary, &state.extracted_strings),
+                    None => state.decompiled_code.clone(),
+                };
+                state.annotated_code.key = Some(key);
+            }
+            let code_text = &state.annotated_code.text;
+            let clicked_line = egui::ScrollArea::both()
                 .auto_shrink([false, false])
-                .show(ui, |ui| {
-                    // Render code with basic syntax highlighting
-                    render_highlighted_code(ui, &state.decompiled_code);
-                });
+                .show(ui, |ui| render_highlighted_code(ui, code_text, state.c_line_selected))
+                .inner;
+            if let Some(line) = clicked_line {
+                link_line(state, line);
+            }
         });
 }
 
-/// Render code with basic C syntax highlighting
-fn render_highlighted_code(ui: &mut egui::Ui, code_text: &str) {
-    let lines: Vec<&str> = code_text.lines().collect();
-    
-    for (line_num, line) in lines.iter().enumerate() {
-        ui.horizontal(|ui| {
-            // Line number
-            ui.label(egui::RichText::new(format!("{:4}", line_num + 1))
-                .color(catppuccin::OVERLAY0)
-                .monospace());
-            
-            ui.separator();
-            
-            // Highlighted code line
-            let highlighted = highlight_c_line(line);
-            ui.label(highlighted);
-        });
-    }
+/// Hash of what the annotated code depends on: the decompiled text, the
+/// strings index and the binary's functions and base
+fn annotation_key(state: &AppState, binary: Option<&LoadedBinary>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.decompiled_code.hash(&mut hasher);
+    let strings = &state.extracted_strings;
+    (strings.len(), strings.first().map(|s| s.offset), strings.last().map(|s| s.offset)).hash(&mut hasher);
+    binary.map(|b| (&b.path, b.image_base, b.functions.len())).hash(&mut hasher);
+    hasher.finish()
 }
 
-/// Apply C syntax highlighting to a single line
-fn highlight_c_line(line: &str) -> egui::RichText {
-    let trimmed = line.trim();
-    
-    // Comments
-    if trimmed.starts_with("//") || trimmed.starts_with("/*") {
-        return egui::RichText::new(line).color(code::COMMENT).monospace();
+/// Highlight the instructions behind decompiled `line` and scroll to the first
+fn link_line(state: &mut AppState, line: usize) {
+    match state.c_line_map.get(&line).and_then(|addrs| addrs.iter().min()) {
+        Some(&first) => {
+            state.c_line_selected = Some(line);
+            state.asm_scroll_to = Some(first);
+        }
+        None => state.c_line_selected = None,
     }
-    
-    // Preprocessor directives
-    if trimmed.starts_with("#") {
-        return egui::RichText::new(line).color(catppuccin::MAUVE).monospace();
+}
+
+/// Render the raw p-code listing
+fn render_pcode(ui: &mut egui::Ui, state: &AppState) {
+    if state.decompiled_pcode.is_empty() {
+        ui.vertical_centered(|ui| {
+            ui.add_space(60.0);
+            ui.label(egui::RichText::new("No p-code available")
+                .color(catppuccin::overlay0())
+                .size(14.0));
+        });
+        return;
     }
-    
-    // Simple keyword detection
-    let keywords = ["if", "else", "while", "for", "return", "break", "continue", 
-                   "switch", "case", "default", "do", "goto", "sizeof"];
-    let types = ["void", "int", "char", "short", "long", "unsigned", "signed",
-                "float", "double", "struct", "union", "enum", "typedef",
-                "uint8_t", "uint16_t", "uint32_t", "uint64_t",
-                "int8_t", "int16_t", "int32_t", "int64_t", "size_t", "bool"];
-    
-    // Check if line starts with a type (function definition or declaration)
-    for typ in types {
-        if trimmed.starts_with(typ) {
-            return egui::RichText::new(line).color(code::TYPE).monospace();
+
+    egui::ScrollArea::both()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for line in state.decompiled_pcode.lines() {
+                let color = if line.starts_with("0x") { code::address() } else { catppuccin::text() };
+                ui.label(egui::RichText::new(line).color(color).monospace());
+            }
+        });
+}
+
+/// C keywords (control flow and storage)
+const KEYWORDS: &[&str] = &[
+    "if", "else", "while", "for", "return", "break", "continue", "switch", "case",
+    "default", "do", "goto", "sizeof", "static", "extern", "const", "volatile",
+    "register", "inline",
+];
+
+/// C and Ghidra built-in type names
+const TYPES: &[&str] = &[
+    "void", "int", "char", "short", "long", "unsigned", "signed", "float", "double",
+    "struct", "union", "enum", "typedef", "bool",
+    "uint8_t", "uint16_t", "uint32_t", "uint64_t",
+    "int8_t", "int16_t", "int32_t", "int64_t", "size_t",
+    "undefined", "undefined1", "undefined2", "undefined4", "undefined8",
+    "byte", "word", "dword", "qword", "uint", "ulong", "ushort", "uchar",
+    "longlong", "ulonglong", "code",
+];
+
+/// Render code as a read-only, selectable text area with line numbers.
+///
+/// Returns the 0-based line that was clicked, if any; `selected` is drawn
+/// with a highlighted line number.
+fn render_highlighted_code(ui: &mut egui::Ui, code_text: &str, selected: Option<usize>) -> Option<usize> {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let line_count = code_text.lines().count().max(1);
+
+    ui.horizontal_top(|ui| {
+        // Line numbers
+        let mut numbers = egui::text::LayoutJob::default();
+        for line in 0..line_count {
+            let color = if selected == Some(line) { catppuccin::yellow() } else { catppuccin::overlay0() };
+            let sep = if line + 1 < line_count { "\n" } else { "" };
+            numbers.append(&format!("{:4}{}", line + 1, sep), 0.0,
+                egui::TextFormat::simple(font_id.clone(), color));
         }
-    }
-    
-    // Check for keywords
-    for kw in keywords {
-        if trimmed.starts_with(kw) && (trimmed.len() == kw.len() || 
-            !trimmed.chars().nth(kw.len()).unwrap_or(' ').is_alphanumeric()) {
-            return egui::RichText::new(line).color(code::KEYWORD).monospace();
+        ui.label(numbers);
+        
+        ui.separator();
+
+        let mut layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+            let mut job = highlight_c(text, font_id.clone());
+            job.wrap.max_width = wrap_width;
+            ui.fonts(|f| f.layout_job(job))
+        };
+
+        // Passing `&mut &str` keeps the buffer read-only while still allowing selection
+        let mut text = code_text;
+        let output = egui::TextEdit::multiline(&mut text)
+            .code_editor()
+            .desired_width(f32::INFINITY)
+            .frame(false)
+            .layouter(&mut layouter)
+            .show(ui);
+        if !output.response.clicked() {
+            return None;
+        }
+        let index = output.cursor_range?.primary.ccursor.index;
+        Some(code_text.chars().take(index).filter(|&c| c == '\n').count())
+    }).inner
+}
+
+/// Build a highlighted layout job for C source
+pub(super) fn highlight_c(text: &str, font_id: egui::FontId) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob::default();
+    let append = |job: &mut egui::text::LayoutJob, s: &str, color: egui::Color32| {
+        job.append(s, 0.0, egui::TextFormat::simple(font_id.clone(), color));
+    };
+
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    let mut line_start = true;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+
+        if c == b'\n' {
+            append(&mut job, "\n", catppuccin::text());
+            i += 1;
+            line_start = true;
+            continue;
+        }
+
+        if c.is_ascii_whitespace() {
+            while i < bytes.len() && bytes[i] != b'\n' && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            append(&mut job, &text[start..i], catppuccin::text());
+            continue;
+        }
+
+        let at_line_start = line_start;
+        line_start = false;
+
+        if text[i..].starts_with("//") || (at_line_start && c == b'#') {
+            // Line comment or preprocessor directive
+            let end = text[i..].find('\n').map_or(bytes.len(), |n| i + n);
+            let color = if c == b'#' { catppuccin::mauve() } else { code::comment() };
+            append(&mut job, &text[start..end], color);
+            i = end;
+        } else if text[i..].starts_with("/*") {
+            let end = text[i + 2..].find("*/").map_or(bytes.len(), |n| i + 2 + n + 2);
+            append(&mut job, &text[start..end], code::comment());
+            i = end;
+        } else if c == b'"' || c == b'\'' {
+            // String or char literal with escapes
+            i += 1;
+            while i < bytes.len() && bytes[i] != c && bytes[i] != b'\n' {
+                if bytes[i] == b'\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(bytes.len());
+            append(&mut job, &text[start..i], code::string());
+        } else if c.is_ascii_digit() {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'.') {
+                i += 1;
+            }
+            append(&mut job, &text[start..i], code::number());
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            let word = &text[start..i];
+            let next_non_space = text[i..].trim_start_matches([' ', '\t']).chars().next();
+            let color = if KEYWORDS.contains(&word) {
+                code::keyword()
+            } else if TYPES.contains(&word) {
+                code::type_name()
+            } else if next_non_space == Some('(') {
+                code::function()
+            } else {
+                catppuccin::text()
+            };
+            append(&mut job, word, color);
+        } else {
+            // Operators and punctuation (multi-byte chars copied whole)
+            let len = text[i..].chars().next().map_or(1, |ch| ch.len_utf8());
+            i += len;
+            let color = if b"+-*/%=<>!&|^~?:".contains(&c) {
+                code::operator()
+            } else {
+                catppuccin::subtext1()
+            };
+            append(&mut job, &text[start..i], color);
         }
     }
-    
-    // String literals
-    if trimmed.contains('"') {
-        return egui::RichText::new(line).color(code::STRING).monospace();
-    }
-    
-    // Function calls (contains parentheses but not control flow)
-    if trimmed.contains('(') && !keywords.iter().any(|k| trimmed.starts_with(k)) {
-        return egui::RichText::new(line).color(code::FUNCTION).monospace();
-    }
-    
-    // Default
-    egui::RichText::new(line).color(catppuccin::TEXT).monospace()
+
+    job
 }
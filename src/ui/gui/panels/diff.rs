@@ -0,0 +1,152 @@
+//! Binary Diff window - functions added, removed or changed against a second binary.
+
+use eframe::egui;
+use crate::analysis::diff::{DiffKind, FunctionDiff};
+use super::super::state::AppState;
+use super::super::theme::catppuccin;
+use super::decompile::highlight_c;
+
+/// Action requested from the Binary Diff window
+pub enum DiffAction {
+    /// Decompile both sides of a matched row
+    Compare(usize),
+    /// Show a primary-only function in the assembly view
+    GoTo(u64),
+    None,
+}
+
+fn kind_color(kind: DiffKind) -> egui::Color32 {
+    match kind {
+        DiffKind::Added => catppuccin::green(),
+        DiffKind::Removed => catppuccin::red(),
+        DiffKind::Changed => catppuccin::yellow(),
+        DiffKind::Unchanged => catppuccin::overlay0(),
+    }
+}
+
+fn row_name(row: &FunctionDiff) -> String {
+    if row.name.is_empty() {
+        format!("sub_{:08x}", row.primary.or(row.secondary).unwrap_or_default())
+    } else {
+        row.name.clone()
+    }
+}
+
+/// Render the Binary Diff window when it is open
+pub fn render(ctx: &egui::Context, state: &mut AppState) -> DiffAction {
+    if !state.show_diff {
+        return DiffAction::None;
+    }
+
+    let mut open = true;
+    let mut action = DiffAction::None;
+
+    egui::Window::new("Binary Diff")
+        .open(&mut open)
+        .default_width(900.0)
+        .default_height(480.0)
+        .show(ctx, |ui| {
+            if state.diff_loading {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(egui::RichText::new("Loading and matching functions...")
+                        .color(catppuccin::subtext0()));
+                });
+                return;
+            }
+            let (Some(primary), Some(secondary)) = (&state.loaded_binary, &state.secondary_binary) else {
+                ui.label(egui::RichText::new("Use Tools > Diff Against Binary... to pick a second binary")
+                    .color(catppuccin::overlay0()));
+                return;
+            };
+
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(&primary.path).color(catppuccin::blue()));
+                ui.label(egui::RichText::new("↔").color(catppuccin::subtext0()));
+                ui.label(egui::RichText::new(&secondary.path).color(catppuccin::mauve()));
+            });
+            ui.horizontal(|ui| {
+                for kind in [DiffKind::Added, DiffKind::Removed, DiffKind::Changed, DiffKind::Unchanged] {
+                    let count = state.function_diff.iter().filter(|d| d.kind == kind).count();
+                    ui.label(egui::RichText::new(format!("{} {}", count, kind.label()))
+                        .color(kind_color(kind)).small());
+                }
+                ui.separator();
+                ui.checkbox(&mut state.diff_show_unchanged, "Show unchanged");
+            });
+            ui.separator();
+
+            let rows: Vec<usize> = state.function_diff.iter()
+                .enumerate()
+                .filter(|(_, d)| state.diff_show_unchanged || d.kind != DiffKind::Unchanged)
+                .map(|(i, _)| i)
+                .collect();
+
+            ui.horizontal_top(|ui| {
+                ui.vertical(|ui| {
+                    ui.set_width(300.0);
+                    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                    egui::ScrollArea::vertical()
+                        .id_source("diff_rows")
+                        .auto_shrink([false; 2])
+                        .show_rows(ui, row_height, rows.len(), |ui, range| {
+                            for &i in &rows[range] {
+                                let row = &state.function_diff[i];
+                                let mut job = egui::text::LayoutJob::default();
+                                let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+                                job.append(&format!("{:<9} ", row.kind.label()), 0.0,
+                                    egui::TextFormat::simple(font_id.clone(), kind_color(row.kind)));
+                                job.append(&row_name(row), 0.0,
+                                    egui::TextFormat::simple(font_id, catppuccin::text()));
+                                let selected = state.diff_selected == Some(i);
+                                if ui.selectable_label(selected, job).clicked() {
+                                    action = match (row.primary, row.secondary) {
+                                        (Some(_), Some(_)) => DiffAction::Compare(i),
+                                        (Some(address), None) => DiffAction::GoTo(address),
+                                        _ => DiffAction::None,
+                                    };
+                                }
+                            }
+                        });
+                });
+                ui.separator();
+
+                ui.vertical(|ui| {
+                    let Some(row) = state.diff_selected.and_then(|i| state.function_diff.get(i)) else {
+                        ui.label(egui::RichText::new("Select a matched function to compare its decompiles")
+                            .color(catppuccin::overlay0()));
+                        return;
+                    };
+                    let Some((left, right)) = &state.diff_code else {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(egui::RichText::new(format!("Decompiling {}...", row_name(row)))
+                                .color(catppuccin::subtext0()));
+                        });
+                        return;
+                    };
+                    ui.columns(2, |columns| {
+                        let sides = [
+                            (left, row.primary, catppuccin::blue()),
+                            (right, row.secondary, catppuccin::mauve()),
+                        ];
+                        for (side, (ui, (code_text, address, color))) in columns.iter_mut().zip(sides).enumerate() {
+                            ui.label(egui::RichText::new(format!("{:08X}", address.unwrap_or_default()))
+                                .monospace()
+                                .color(color));
+                            egui::ScrollArea::both()
+                                .id_source(("diff_code", side))
+                                .auto_shrink([false; 2])
+                                .show(ui, |ui| {
+                                    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+                                    ui.label(highlight_c(code_text, font_id));
+                                });
+                        }
+                    });
+                });
+            });
+        });
+
+    state.show_diff = open;
+    action
+}
@@ -2,9 +2,13 @@
 
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
-use crate::analysis::loader::FunctionInfo;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use crate::analysis::loader::{FunctionInfo, LoadedBinary};
+use crate::app::display_name;
 use super::super::state::AppState;
 use super::super::theme::{catppuccin, code};
+use super::copy_menu_button;
 
 /// Render the functions list panel on the left side.
 /// 
@@ -18,27 +22,59 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> Option<FunctionInfo>
         .min_width(150.0)
         .show(ctx, |ui| {
             ui.horizontal(|ui| {
-                ui.heading(egui::RichText::new("Functions").color(catppuccin::LAVENDER));
+                ui.heading(egui::RichText::new("Functions").color(catppuccin::lavender()));
                 if let Some(ref binary) = state.loaded_binary {
                     ui.label(egui::RichText::new(format!("({})", binary.functions.len()))
-                        .color(catppuccin::SUBTEXT0).small());
+                        .color(catppuccin::subtext0()).small());
+                    if state.functions_loading {
+                        ui.spinner();
+                    }
                 }
             });
             ui.separator();
 
             if let Some(ref binary) = state.loaded_binary {
                 // Search filter
+                let mut filter_changed = false;
                 ui.horizontal(|ui| {
-                    ui.label(egui::RichText::new("🔍").color(catppuccin::OVERLAY0));
-                    // Could add a filter input here
+                    ui.label(egui::RichText::new("🔍").color(catppuccin::overlay0()));
+                    filter_changed |= ui.add(
+                        egui::TextEdit::singleline(&mut state.function_filter)
+                            .desired_width(f32::INFINITY)
+                            .hint_text("Name or address...")
+                    ).changed();
                 });
+                ui.horizontal(|ui| {
+                    filter_changed |= ui.checkbox(&mut state.show_imports, 
+                        egui::RichText::new("Imports").color(catppuccin::peach()).small()).changed();
+                    filter_changed |= ui.checkbox(&mut state.show_exports, 
+                        egui::RichText::new("Exports").color(catppuccin::green()).small()).changed();
+                });
+                
+                // Filtering every function each frame is what a large list
+                // would spend its time on, so rows are only rebuilt on change
+                let key = rows_key(state, binary);
+                if state.function_rows.key != Some(key) {
+                    state.function_rows.indices = filter_functions(state, binary);
+                    state.function_rows.key = Some(key);
+                }
+                let visible = &state.function_rows.indices;
                 
                 let available_height = ui.available_height();
                 let row_height = 22.0;
-                let total_rows = binary.functions.len();
+                let total_rows = visible.len();
 
                 // Use TableBuilder for virtual scrolling
-                TableBuilder::new(ui)
+                let mut table = TableBuilder::new(ui);
+                if filter_changed {
+                    // Keep the selected function in view when the filter changes
+                    let selected_row = state.selected_function.as_ref()
+                        .and_then(|sel| visible.iter().position(|&i| binary.functions[i].address == sel.address));
+                    if let Some(row) = selected_row {
+                        table = table.scroll_to_row(row, Some(egui::Align::Center));
+                    }
+                }
+                table
                     .striped(true)
                     .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
                     .column(Column::remainder())
@@ -46,24 +82,52 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> Option<FunctionInfo>
                     .max_scroll_height(available_height)
                     .body(|body| {
                         body.rows(row_height, total_rows, |mut row| {
-                            let func = &binary.functions[row.index()];
+                            let func = &binary.functions[visible[row.index()]];
                             
                             row.col(|ui| {
+                                // Inline rename editor
+                                if let Some((addr, buffer)) = state.renaming.as_mut() {
+                                    if *addr == func.address {
+                                        let response = ui.add(
+                                            egui::TextEdit::singleline(buffer)
+                                                .desired_width(f32::INFINITY)
+                                        );
+                                        if !response.has_focus() && !response.lost_focus() {
+                                            response.request_focus();
+                                        }
+                                        if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                                            state.renaming = None;
+                                        } else if response.lost_focus() {
+                                            let new_name = buffer.trim().to_string();
+                                            if new_name.is_empty() || new_name == func.name {
+                                                state.function_renames.remove(&func.address);
+                                            } else {
+                                                state.function_renames.insert(func.address, new_name);
+                                            }
+                                            state.renaming = None;
+                                        }
+                                        return;
+                                    }
+                                }
+                                
                                 // Determine icon and color based on function type
                                 let (icon, name_color) = if func.is_import {
-                                    ("⬇", catppuccin::PEACH)  // Import
+                                    ("⬇", catppuccin::peach())  // Import
+                                } else if func.is_tls_callback {
+                                    ("⚑", catppuccin::red())    // TLS callback
+                                } else if func.forwarded_to.is_some() {
+                                    ("↪", catppuccin::green())  // Forwarded export
                                 } else if func.is_export {
-                                    ("⬆", catppuccin::GREEN)  // Export
+                                    ("⬆", catppuccin::green())  // Export
                                 } else {
-                                    ("◆", catppuccin::BLUE)   // Regular function
+                                    ("◆", catppuccin::blue())   // Regular function
                                 };
                                 
-                                let label = if func.name.is_empty() {
-                                    format!("{} sub_{:08x}", icon, func.address)
-                                } else if func.name.len() > 25 {
-                                    format!("{} {}...", icon, &func.name[..22])
+                                let name = display_name(&state.function_renames, func);
+                                let label = if name.chars().count() > 25 {
+                                    format!("{} {}...", icon, name.chars().take(22).collect::<String>())
                                 } else {
-                                    format!("{} {}", icon, func.name)
+                                    format!("{} {}", icon, name)
                                 };
                                 
                                 let is_selected = state.selected_function
@@ -72,14 +136,35 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> Option<FunctionInfo>
                                     .unwrap_or(false);
                                 
                                 let text = if is_selected {
-                                    egui::RichText::new(&label).color(catppuccin::TEXT).strong()
+                                    egui::RichText::new(&label).color(catppuccin::text()).strong()
                                 } else {
                                     egui::RichText::new(&label).color(name_color)
                                 };
                                 
-                                if ui.selectable_label(is_selected, text).clicked() {
+                                let mut response = ui.selectable_label(is_selected, text);
+                                if let Some(target) = &func.forwarded_to {
+                                    response = response.on_hover_text(format!("Forwarded to {}", target));
+                                } else if func.is_tls_callback {
+                                    response = response.on_hover_text("TLS callback - runs before the entry point");
+                                }
+                                if response.clicked() {
                                     clicked_func = Some(func.clone());
                                 }
+                                response.context_menu(|ui| {
+                                    copy_menu_button(ui, "Copy name", || name.clone());
+                                    copy_menu_button(ui, "Copy address", || format!("0x{:x}", func.address));
+                                    ui.separator();
+                                    if ui.button("Rename").clicked() {
+                                        state.renaming = Some((func.address, name.clone()));
+                                        ui.close_menu();
+                                    }
+                                    if state.function_renames.contains_key(&func.address)
+                                        && ui.button("Reset name").clicked()
+                                    {
+                                        state.function_renames.remove(&func.address);
+                                        ui.close_menu();
+                                    }
+                                });
                             });
                         });
                     });
@@ -87,11 +172,11 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> Option<FunctionInfo>
                 ui.vertical_centered(|ui| {
                     ui.add_space(40.0);
                     ui.label(egui::RichText::new("No binary loaded")
-                        .color(catppuccin::OVERLAY0)
+                        .color(catppuccin::overlay0())
                         .size(14.0));
                     ui.add_space(8.0);
                     ui.label(egui::RichText::new("File → Open to load")
-                        .color(catppuccin::OVERLAY0)
+                        .color(catppuccin::overlay0())
                         .small());
                 });
             }
@@ -99,3 +184,38 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) -> Option<FunctionInfo>
     
     clicked_func
 }
+
+/// Hash of everything the filtered rows depend on
+fn rows_key(state: &AppState, binary: &LoadedBinary) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (&state.function_filter, state.show_imports, state.show_exports).hash(&mut hasher);
+    (&binary.path, binary.image_base, binary.functions.len()).hash(&mut hasher);
+    // Order-independent, since the renames are a HashMap
+    let renames = state.function_renames.iter()
+        .map(|entry| {
+            let mut hasher = DefaultHasher::new();
+            entry.hash(&mut hasher);
+            hasher.finish()
+        })
+        .fold(0u64, u64::wrapping_add);
+    renames.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Indices of the functions passing the panel's type and name/address filters
+fn filter_functions(state: &AppState, binary: &LoadedBinary) -> Vec<usize> {
+    let filter = state.function_filter.trim().to_lowercase();
+    let filter_hex = filter.trim_start_matches("0x");
+    binary.functions.iter()
+        .enumerate()
+        .filter(|(_, f)| (state.show_imports || !f.is_import) && (state.show_exports || !f.is_export))
+        .filter(|(_, f)| {
+            filter.is_empty()
+                || f.name.to_lowercase().contains(&filter)
+                || state.function_renames.get(&f.address)
+                    .is_some_and(|n| n.to_lowercase().contains(&filter))
+                || (!filter_hex.is_empty() && format!("{:x}", f.address).contains(filter_hex))
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
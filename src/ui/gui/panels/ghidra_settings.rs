@@ -0,0 +1,76 @@
+//! Ghidra Settings window - connection retries, decompile timeout and server restart.
+
+use eframe::egui;
+use super::super::state::AppState;
+use super::super::theme::catppuccin;
+
+/// Action requested from the Ghidra Settings window
+pub enum GhidraSettingsAction {
+    /// Kill the server Fission started and spawn a new one
+    RestartServer,
+    None,
+}
+
+/// Render the Ghidra Settings window when it is open
+pub fn render(ctx: &egui::Context, state: &mut AppState) -> GhidraSettingsAction {
+    if !state.show_ghidra_settings {
+        return GhidraSettingsAction::None;
+    }
+
+    let mut open = true;
+    let mut action = GhidraSettingsAction::None;
+
+    egui::Window::new("Ghidra Settings")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let config = &mut state.client_config;
+            egui::Grid::new("ghidra_settings_grid")
+                .num_columns(2)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label("Port");
+                    ui.label(egui::RichText::new(config.port.to_string()).monospace());
+                    ui.end_row();
+
+                    ui.label("Max retries");
+                    ui.add(egui::DragValue::new(&mut config.max_retries).clamp_range(1..=20));
+                    ui.end_row();
+
+                    ui.label("Initial retry delay");
+                    ui.add(egui::DragValue::new(&mut config.initial_retry_delay_ms)
+                        .clamp_range(50..=10_000)
+                        .suffix(" ms"));
+                    ui.end_row();
+
+                    ui.label("Decompile timeout");
+                    ui.add(egui::DragValue::new(&mut config.decompile_timeout_ms)
+                        .clamp_range(1_000..=600_000)
+                        .speed(100)
+                        .suffix(" ms"));
+                    ui.end_row();
+                });
+            ui.label(egui::RichText::new("Changes apply from the next connection or restart")
+                .color(catppuccin::overlay0())
+                .small());
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                let can_restart = !state.server_restarting && state.batch_progress.is_none();
+                if ui.add_enabled(can_restart, egui::Button::new(
+                    egui::RichText::new("⟳ Start/Restart Server").color(catppuccin::peach())
+                ))
+                    .on_disabled_hover_text("Unavailable while restarting or during Analyze All")
+                    .clicked()
+                {
+                    action = GhidraSettingsAction::RestartServer;
+                }
+                if state.server_restarting {
+                    ui.spinner();
+                }
+            });
+        });
+
+    state.show_ghidra_settings = open;
+    action
+}
@@ -0,0 +1,80 @@
+//! Find Instructions window - mnemonic or regex search over the listing or all code.
+
+use eframe::egui;
+use super::super::state::AppState;
+use super::super::theme::{catppuccin, code};
+
+/// Action requested from the Find Instructions window
+pub enum InsnSearchAction {
+    /// Run the query
+    Search,
+    /// Show a match in the assembly view
+    GoTo(u64),
+    None,
+}
+
+/// Render the Find Instructions window when it is open
+pub fn render(ctx: &egui::Context, state: &mut AppState) -> InsnSearchAction {
+    if !state.show_insn_search {
+        return InsnSearchAction::None;
+    }
+
+    let mut open = true;
+    let mut action = InsnSearchAction::None;
+
+    egui::Window::new("Find Instructions")
+        .open(&mut open)
+        .default_width(420.0)
+        .default_height(360.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let hint = if state.insn_search_regex {
+                    "Regex over \"mnemonic operands\", e.g. ^int 0x80$"
+                } else {
+                    "Mnemonic, e.g. syscall"
+                };
+                let response = ui.add(egui::TextEdit::singleline(&mut state.insn_search_query)
+                    .hint_text(hint)
+                    .desired_width(280.0));
+                let enter = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                let can_search = !state.insn_search_running && !state.insn_search_query.trim().is_empty();
+                if (ui.add_enabled(can_search, egui::Button::new("Search")).clicked() || enter) && can_search {
+                    action = InsnSearchAction::Search;
+                }
+                if state.insn_search_running {
+                    ui.spinner();
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut state.insn_search_regex, "Regex");
+                ui.separator();
+                ui.radio_value(&mut state.insn_search_all, true, "All executable sections");
+                ui.radio_value(&mut state.insn_search_all, false, "Current listing");
+            });
+            if !state.insn_search_status.is_empty() {
+                ui.label(egui::RichText::new(&state.insn_search_status)
+                    .color(catppuccin::subtext0())
+                    .small());
+            }
+            ui.separator();
+
+            let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+            egui::ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show_rows(ui, row_height, state.insn_search_results.len(), |ui, rows| {
+                    for m in &state.insn_search_results[rows] {
+                        let mut job = egui::text::LayoutJob::default();
+                        let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+                        job.append(&format!("{:08X}  ", m.address), 0.0,
+                            egui::TextFormat::simple(font_id.clone(), code::address()));
+                        job.append(&m.text, 0.0, egui::TextFormat::simple(font_id, catppuccin::text()));
+                        if ui.selectable_label(false, job).clicked() {
+                            action = InsnSearchAction::GoTo(m.address);
+                        }
+                    }
+                });
+        });
+
+    state.show_insn_search = open;
+    action
+}
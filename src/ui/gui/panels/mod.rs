@@ -6,5 +6,17 @@ pub mod functions;
 pub mod assembly;
 pub mod decompile;
 pub mod bottom_tabs;
+pub mod diff;
+pub mod ghidra_settings;
+pub mod insn_search;
 
+use eframe::egui;
 
+/// Context-menu entry that copies `text()` to the clipboard
+pub fn copy_menu_button(ui: &mut egui::Ui, label: &str, text: impl FnOnce() -> String) {
+    if ui.button(label).clicked() {
+        let text = text();
+        ui.output_mut(|o| o.copied_text = text);
+        ui.close_menu();
+    }
+}
@@ -2,33 +2,153 @@
 //!
 //! Contains all state that needs to be shared across UI panels.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 use std::time::Instant;
 
-use crate::analysis::loader::{LoadedBinary, FunctionInfo};
-use crate::analysis::disasm::DisassembledInstruction;
+use crate::analysis::decomp::{cache, DiskCache};
+use crate::analysis::decomp::client::{BinaryId, ClientConfig};
+use crate::analysis::loader::{Hashes, LoadedBinary, FunctionInfo};
+use crate::analysis::disasm::{DisasmSyntax, DisassembledInstruction};
+use crate::analysis::disasm::search::InsnMatch;
+use crate::analysis::yara::YaraMatch;
+use crate::analysis::diff::FunctionDiff;
+use crate::analysis::stackvars::StackVariable;
+use crate::debug::iat::ResolvedImport;
+use crate::debug::vector::VectorFormat;
+use crate::script::PythonBridge;
+pub use crate::analysis::strings::{EncodingFilter, ExtractedString, StringEncoding};
+use crate::app::display_name;
+use crate::app::session::{Bookmark, Session};
+use super::theme::Flavor;
 
 /// Cached decompile result for performance optimization
 #[derive(Clone)]
 pub struct CachedDecompile {
     pub c_code: String,
+    pub pcode: String,
     pub asm_instructions: Vec<DisassembledInstruction>,
+    /// Listing returned by Ghidra with the decompile (empty from the disk cache)
+    pub ghidra_asm: Vec<DisassembledInstruction>,
+    /// Stack variables Ghidra assigned
+    pub stack_vars: Vec<StackVariable>,
+    /// Instruction addresses behind each C line
+    pub line_map: HashMap<usize, Vec<u64>>,
     #[allow(dead_code)]
     pub timestamp: Instant,
 }
 
+/// Severity of a console line, in increasing order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// A command echoed from the console input
+    Command,
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    /// Line prefix the level is written with (`[✓] Loaded ...`)
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::Command => ">",
+            Self::Info => "[*]",
+            Self::Success => "[✓]",
+            Self::Warning => "[!]",
+            Self::Error => "[✗]",
+        }
+    }
+}
+
+/// Least severe level the console shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFilter {
+    #[default]
+    All,
+    Warnings,
+    Errors,
+}
+
+impl LogFilter {
+    pub const ALL: [LogFilter; 3] = [Self::All, Self::Warnings, Self::Errors];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "All",
+            Self::Warnings => "Warnings",
+            Self::Errors => "Errors",
+        }
+    }
+
+    pub fn shows(self, level: LogLevel) -> bool {
+        match self {
+            Self::All => true,
+            Self::Warnings => level >= LogLevel::Warning,
+            Self::Errors => level == LogLevel::Error,
+        }
+    }
+}
+
+/// One line of the output console
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub level: LogLevel,
+    /// The line as shown, including its prefix
+    pub text: String,
+    pub timestamp: Instant,
+}
+
+impl LogEntry {
+    /// Info entry shown as written
+    pub fn new(message: impl Into<String>) -> Self {
+        Self { level: LogLevel::Info, text: message.into(), timestamp: Instant::now() }
+    }
+
+    /// Entry at `level`, written with the level's prefix
+    pub fn at(level: LogLevel, message: impl std::fmt::Display) -> Self {
+        Self { level, text: format!("{} {}", level.prefix(), message), timestamp: Instant::now() }
+    }
+}
+
+/// Lines the console starts with, kept when it is cleared
+const BANNER: &[&str] = &[
+    "==============================================================",
+    "  Fission - Next-Gen Dynamic Instrumentation Platform",
+    "  \"Split the Binary, Fuse the Power.\"",
+    "==============================================================",
+    "",
+];
+
+/// Last line of a fresh console
+const READY_MESSAGE: &str = "[*] Ready. Load a binary to begin analysis.";
+
 /// Main application state container
 /// 
 /// This struct holds all shared state that panels need to read/modify.
 pub struct AppState {
     /// Log buffer for the output console
-    pub log_buffer: Vec<String>,
+    pub log_buffer: Vec<LogEntry>,
+    /// When the session started (console timestamps are relative to it)
+    pub log_started: Instant,
+    /// Which levels the console shows
+    pub log_filter: LogFilter,
+    /// Case-insensitive substring the console lines must contain
+    pub log_search: String,
+    /// Show a timestamp column in the console
+    pub log_timestamps: bool,
 
     /// Current command input in the integrated CLI
     pub cli_input: String,
 
     /// Currently loaded binary (if any)
     pub loaded_binary: Option<LoadedBinary>,
+    /// Functions of `loaded_binary` are still arriving in batches
+    pub functions_loading: bool,
+    /// Interpreter for console `.<code>`, initialized on first use
+    pub python: PythonBridge,
 
     /// Debugger running state
     pub is_debugging: bool,
@@ -36,37 +156,159 @@ pub struct AppState {
     /// Selected function (for decompilation view)
     pub selected_function: Option<FunctionInfo>,
 
+    /// Functions panel filter (name or hex address substring)
+    pub function_filter: String,
+    /// Show imported functions in the functions panel
+    pub show_imports: bool,
+    /// Show exported functions in the functions panel
+    pub show_exports: bool,
+    /// Functions panel rows, refiltered only when their inputs change
+    pub function_rows: FunctionRows,
+
+    /// User-assigned function names (address -> name)
+    pub function_renames: HashMap<u64, String>,
+    /// In-progress rename in the functions panel (address, edit buffer)
+    pub renaming: Option<(u64, String)>,
+
+    /// Session waiting for its binary to finish loading
+    pub pending_session: Option<Session>,
+    /// Breakpoints from a loaded session, applied on the next attach
+    pub saved_breakpoints: Vec<u64>,
+
     /// Current decompiled C code
     pub decompiled_code: String,
+    /// Decompiled code with data symbols annotated, redone only when its
+    /// inputs change
+    pub annotated_code: AnnotatedCode,
+
+    /// Raw p-code listing for the current function
+    pub decompiled_pcode: String,
+
+    /// Which output the decompile panel shows
+    pub decompile_view: DecompileView,
 
     /// Current assembly instructions
     pub asm_instructions: Vec<DisassembledInstruction>,
 
+    /// Ghidra's listing of the current function, if it was decompiled
+    pub ghidra_asm: Vec<DisassembledInstruction>,
+
+    /// Stack variables of the current function, if it was decompiled
+    pub stack_vars: Vec<StackVariable>,
+    /// Stack variable named by each instruction of the current function
+    /// (empty when `stack_var_names` is off)
+    pub stack_notes: HashMap<u64, String>,
+
+    /// Instruction addresses behind each 0-based line of the decompiled C
+    pub c_line_map: HashMap<usize, Vec<u64>>,
+    /// Decompiled line last clicked, whose instructions the assembly view highlights
+    pub c_line_selected: Option<usize>,
+
+    /// Which disassembly the assembly panel shows
+    pub asm_source: AsmSource,
+
+    /// Address the assembly view should scroll to on the next frame
+    pub asm_scroll_to: Option<u64>,
+
+    /// Navigation history (addresses to return to with Back)
+    pub nav_history: Vec<u64>,
+
+    /// "Go to" input in the assembly view (address or function name)
+    pub goto_input: String,
+    /// Assembly output syntax
+    pub disasm_syntax: DisasmSyntax,
+    /// Instruction selected in the assembly view
+    pub asm_selected: Option<u64>,
+    /// Address requested by another panel (handled like "Go to")
+    pub pending_goto: Option<u64>,
+
+    // ========== Bookmarks ==========
+    /// User bookmarks
+    pub bookmarks: Vec<Bookmark>,
+    /// Bookmark being created (address, note buffer)
+    pub bookmark_draft: Option<(u64, String)>,
+
+    // ========== Patching ==========
+    /// Patch being edited in the assembly view (address, hex buffer)
+    pub patch_draft: Option<(u64, String)>,
+    /// Patch confirmed in the assembly view, applied by the app
+    pub pending_patch: Option<(u64, Vec<u8>)>,
+    /// New image base requested by File → Rebase or `ob`, applied by the app
+    pub pending_rebase: Option<u64>,
+    /// Base to restore once a recovery reload of a rebased binary finishes
+    pub rebase_after_load: Option<u64>,
+
     /// Is decompilation in progress?
     pub decompiling: bool,
 
+    /// Cancellation flag for the in-flight decompile task
+    pub decompile_cancel: Option<Arc<AtomicBool>>,
+    /// "Analyze All Functions" progress (done, total); None when not running
+    pub batch_progress: Option<(usize, usize)>,
+    /// Cancellation flag for the running "Analyze All Functions"
+    pub batch_cancel: Option<Arc<AtomicBool>>,
+
     /// Server connection status
     pub server_connected: bool,
 
+    /// Ghidra server connection settings
+    pub client_config: ClientConfig,
+
     /// File dialog path (unused currently)
     pub file_dialog_path: String,
 
-    /// Decompile result cache (address -> result)
-    pub decompile_cache: HashMap<u64, CachedDecompile>,
+    /// Decompile result cache ((binary, address) -> result)
+    pub decompile_cache: HashMap<(BinaryId, u64), CachedDecompile>,
+
+    /// Persistent decompile cache (None if no config dir)
+    pub disk_cache: Option<DiskCache>,
+
+    /// SHA-256 of the loaded binary (disk cache key)
+    pub binary_hash: Option<String>,
+    /// Identity of the loaded binary (decompile cache scope)
+    pub binary_id: Option<BinaryId>,
+    /// The loaded binary was moved off its file's image base
+    pub rebased: bool,
+    /// File hashes of the loaded binary (File Info tab)
+    pub file_hashes: Option<Hashes>,
 
     /// Last loaded binary path (for recovery reload)
     pub last_binary_path: Option<String>,
 
+    /// Recently loaded binaries, newest first (persisted)
+    pub recent_files: VecDeque<String>,
+
+    /// Text size multiplier (persisted)
+    pub ui_scale: f32,
+    /// Color theme (persisted)
+    pub theme: Flavor,
+    /// Bytes decoded for a raw listing and per More/Less step (persisted)
+    pub asm_window: usize,
+    /// Name stack operands after decompiled variables (persisted)
+    pub stack_var_names: bool,
+
     /// Server recovery in progress
     pub recovering: bool,
+    /// Ghidra Settings window is open
+    pub show_ghidra_settings: bool,
+    /// Start/Restart Server is running
+    pub server_restarting: bool,
 
     // ========== Debug State ==========
     /// Debugger state
     pub debug_state: crate::debug::types::DebugState,
     /// Show attach dialog
     pub show_attach_dialog: bool,
+    /// "Open Raw" dialog waiting for base address and architecture
+    pub raw_open: Option<RawOpenRequest>,
+    /// Base address input of the open "Rebase" dialog
+    pub rebase_input: Option<String>,
     /// Cached process list for dialog
     pub process_list: Vec<crate::debug::types::ProcessInfo>,
+    /// Attach dialog filter (name or PID, case-insensitive)
+    pub process_filter: String,
+    /// Attach dialog row order
+    pub process_sort: ProcessSort,
 
     // ========== Bottom Panel Tab ==========
     /// Currently selected bottom tab
@@ -75,30 +317,138 @@ pub struct AppState {
     // ========== Hex View State ==========
     /// Current offset in hex view
     pub hex_offset: u64,
+    /// Selected byte in hex view (absolute file offset)
+    pub hex_cursor: u64,
+    /// Other end of a Shift-extended selection (the selection is just the
+    /// cursor when None)
+    pub hex_anchor: Option<u64>,
+    /// Cursor offsets to return to after following pointers (most recent last)
+    pub hex_back: Vec<u64>,
+    /// Search input for hex view (hex pattern or quoted string)
+    pub hex_search_input: String,
+    /// Offsets of search matches (sorted)
+    pub hex_search_matches: Vec<u64>,
+    /// Length in bytes of the current search pattern
+    pub hex_search_len: usize,
+    /// Index of the current match in `hex_search_matches`
+    pub hex_search_index: usize,
+    /// Whether the hex view shows file bytes or live process memory
+    pub hex_source: HexSource,
+    /// Last live-memory read for the hex view
+    pub hex_live: Option<LiveBytes>,
+    /// Hex view requested a live read (file offset, length)
+    pub pending_hex_live_read: Option<(u64, usize)>,
+
+    // ========== Instruction Search ==========
+    /// Find Instructions window is open
+    pub show_insn_search: bool,
+    /// Mnemonic substring, or regex over `mnemonic operands`
+    pub insn_search_query: String,
+    /// Treat the query as a regex
+    pub insn_search_regex: bool,
+    /// Search every executable section instead of the current listing
+    pub insn_search_all: bool,
+    /// Matches of the last search
+    pub insn_search_results: Vec<InsnMatch>,
+    /// A whole-binary search is running in the background
+    pub insn_search_running: bool,
+    /// Result count or error shown in the window
+    pub insn_search_status: String,
 
     // ========== Strings State ==========
     /// Extracted strings from binary
     pub extracted_strings: Vec<ExtractedString>,
     /// Filter for strings view
     pub strings_filter: String,
+    /// Shortest string extracted, in characters
+    pub strings_min_len: usize,
+    /// Encodings extracted
+    pub strings_encoding: EncodingFilter,
+    /// Only extract from the section with this name (whole file when None)
+    pub strings_section: Option<String>,
+    /// Pending export of filtered strings (formatted lines)
+    pub pending_strings_export: Option<Vec<String>>,
+    /// Sections tab asked to save the overlay to a file
+    pub pending_overlay_export: bool,
+
+    // ========== Diff State ==========
+    /// Second version of the binary, compared against `loaded_binary`
+    pub secondary_binary: Option<LoadedBinary>,
+    /// Function matches between the primary and secondary binary
+    pub function_diff: Vec<FunctionDiff>,
+    /// The secondary binary is being loaded and diffed in the background
+    pub diff_loading: bool,
+    /// Binary Diff window is open
+    pub show_diff: bool,
+    /// List unchanged functions too
+    pub diff_show_unchanged: bool,
+    /// Row of `function_diff` shown side by side
+    pub diff_selected: Option<usize>,
+    /// Decompiled (primary, secondary) code of the selected row
+    pub diff_code: Option<(String, String)>,
+
+    // ========== YARA State ==========
+    /// Rules file of the last scan
+    pub yara_rules_path: Option<String>,
+    /// Rules that matched in the last scan
+    pub yara_results: Vec<YaraMatch>,
+    /// A scan is running in the background
+    pub yara_running: bool,
+    /// Result count or error shown in the YARA tab
+    pub yara_status: String,
 
     /// Dynamic mode (on/off)
     pub dynamic_mode: bool,
 
+    /// Show addresses in the assembly and debug views as runtime VAs
+    /// (moved by the ASLR slide) instead of file VAs
+    pub runtime_addresses: bool,
+
+    /// Lane interpretation of the XMM/YMM rows in the Registers column
+    pub vector_format: VectorFormat,
+    /// Register values typed in the Registers column but not yet applied,
+    /// keyed by lowercase 64-bit name
+    pub register_edits: HashMap<&'static str, String>,
+
     /// Pending debug control action from UI
     pub pending_debug_action: Option<DebugAction>,
 
     /// Pending breakpoint action from UI
     pub pending_bp_action: Option<DebugBpAction>,
+    /// Registers and call stack need re-reading (process just suspended)
+    pub pending_stack_refresh: bool,
+    /// Console requested an attach (`attach <pid>`)
+    pub pending_attach: Option<u32>,
+    /// Console requested a detach (`detach`)
+    pub pending_detach: bool,
+    /// Console requested the live memory map (`dm` while attached)
+    pub pending_memory_map: bool,
+    /// Move the assembly view to RIP whenever the process suspends
+    pub follow_ip: bool,
     /// Temporary input for breakpoint address
     pub breakpoint_input: String,
+    /// Create new breakpoints as temporary (one-shot)
+    pub breakpoint_temporary: bool,
+    /// Breakpoint condition being edited (address, edit buffer)
+    pub bp_condition_edit: Option<(u64, String)>,
 
-    /// Memory view address input (hex)
+    /// Memory view address input (hex, register name, or expression like `rsp+8`)
     pub mem_addr_input: String,
-    /// Memory view length input (decimal)
+    /// Memory view length input (decimal or `0x` hex)
     pub mem_len_input: String,
     /// Last memory dump text
     pub mem_dump: String,
+    /// Memory view requested a read from the live process
+    pub pending_mem_read: bool,
+    /// Memory view requested a dump of its range to a file
+    pub pending_mem_dump: bool,
+    /// Console requested a dump (`wtf`): address, size, path
+    pub pending_mem_dump_to: Option<(u64, usize, String)>,
+
+    /// Runtime IAT values read from the attached process, keyed by slot address
+    pub iat_resolved: HashMap<u64, ResolvedImport>,
+    /// Imports tab requested an IAT read from the live process
+    pub pending_iat_read: bool,
 }
 
 /// Debug control actions requested from UI
@@ -106,30 +456,94 @@ pub struct AppState {
 pub enum DebugAction {
     Continue,
     Step,
+    /// Step, running through calls to the instruction after them
+    StepOver,
+    /// Suspend the running process
+    Break,
+    /// Show the registers and call stack of another thread
+    SelectThread(u32),
+    /// Write a register (lowercase 64-bit name) of the shown thread
+    WriteRegister(&'static str, u64),
 }
 
 /// Breakpoint actions requested from UI
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DebugBpAction {
     Add(u64),
+    /// Add a breakpoint that is removed after its first hit
+    AddTemporary(u64),
     Remove(u64),
+    /// Set (Some) or clear (None) a breakpoint condition
+    SetCondition(u64, Option<String>),
+}
+/// Headerless file being opened through File → Open Raw
+pub struct RawOpenRequest {
+    pub path: String,
+    /// Base address input (hex)
+    pub base_input: String,
+    /// Selected entry of `RAW_ARCH_SPECS`
+    pub arch_spec: &'static str,
+}
+
+/// Hex view data source
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum HexSource {
+    #[default]
+    File,
+    Live,
 }
-/// Extracted string from binary
+
+/// Process memory backing a range of file offsets
 #[derive(Clone)]
-pub struct ExtractedString {
-    /// Offset in binary
+pub struct LiveBytes {
+    /// File offset of the first byte
     pub offset: u64,
-    /// String value
-    pub value: String,
-    /// String encoding type
-    pub encoding: StringEncoding,
+    /// One entry per byte; `None` where the read failed
+    pub bytes: Vec<Option<u8>>,
+    /// When the read happened (for rate limiting)
+    pub read_at: Instant,
+}
+
+/// Decompile panel view selection
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum DecompileView {
+    #[default]
+    C,
+    Pcode,
+}
+
+/// Indices into the loaded binary's functions that pass the Functions
+/// panel filter, with a hash of the inputs they were computed from
+#[derive(Default)]
+pub struct FunctionRows {
+    pub key: Option<u64>,
+    pub indices: Vec<usize>,
 }
 
-/// String encoding type
-#[derive(Clone, Copy, PartialEq)]
-pub enum StringEncoding {
-    Ascii,
-    Utf16Le,
+/// Decompiled code with its data symbols annotated, with a hash of the
+/// inputs it was computed from
+#[derive(Default)]
+pub struct AnnotatedCode {
+    pub key: Option<u64>,
+    pub text: String,
+}
+
+/// Attach dialog process order
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum ProcessSort {
+    #[default]
+    Pid,
+    Name,
+}
+
+/// Assembly panel listing selection
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum AsmSource {
+    /// Local capstone disassembly
+    #[default]
+    Local,
+    /// Listing returned by the Ghidra decompiler
+    Ghidra,
 }
 
 /// Bottom panel tab selection
@@ -140,62 +554,352 @@ pub enum BottomTab {
     HexView,
     Strings,
     Imports,
+    Sections,
+    FileInfo,
+    Bookmarks,
     Debug,
+    Memory,
+    Yara,
 }
 
 impl Default for AppState {
     fn default() -> Self {
+        let settings = crate::app::settings::Settings::load();
         Self {
-            log_buffer: vec![
-                "==============================================================".into(),
-                "  Fission - Next-Gen Dynamic Instrumentation Platform".into(),
-                "  \"Split the Binary, Fuse the Power.\"".into(),
-                "==============================================================".into(),
-                "".into(),
-                "[*] Ready. Load a binary to begin analysis.".into(),
-            ],
+            log_buffer: BANNER.iter().chain([&READY_MESSAGE]).copied().map(LogEntry::new).collect(),
+            log_started: Instant::now(),
+            log_filter: LogFilter::default(),
+            log_search: String::new(),
+            log_timestamps: false,
             cli_input: String::new(),
             loaded_binary: None,
+            functions_loading: false,
+            python: PythonBridge::new(),
             is_debugging: false,
             selected_function: None,
+            function_filter: String::new(),
+            show_imports: true,
+            show_exports: true,
+            function_rows: FunctionRows::default(),
+            function_renames: HashMap::new(),
+            renaming: None,
+            pending_session: None,
+            saved_breakpoints: Vec::new(),
             decompiled_code: "// Select a function to decompile".into(),
+            annotated_code: AnnotatedCode::default(),
+            decompiled_pcode: String::new(),
+            decompile_view: DecompileView::C,
             asm_instructions: Vec::new(),
+            ghidra_asm: Vec::new(),
+            stack_vars: Vec::new(),
+            stack_notes: HashMap::new(),
+            c_line_map: HashMap::new(),
+            c_line_selected: None,
+            asm_source: AsmSource::default(),
+            asm_scroll_to: None,
+            nav_history: Vec::new(),
+            goto_input: String::new(),
+            disasm_syntax: DisasmSyntax::default(),
+            asm_selected: None,
+            pending_goto: None,
+            bookmarks: Vec::new(),
+            bookmark_draft: None,
+            patch_draft: None,
+            pending_patch: None,
+            pending_rebase: None,
+            rebase_after_load: None,
             decompiling: false,
+            decompile_cancel: None,
+            batch_progress: None,
+            batch_cancel: None,
             server_connected: false,
+            client_config: ClientConfig::default(),
             file_dialog_path: String::new(),
             decompile_cache: HashMap::new(),
+            disk_cache: DiskCache::open_default(),
+            binary_hash: None,
+            rebased: false,
+            binary_id: None,
+            file_hashes: None,
             last_binary_path: None,
+            recent_files: crate::app::recent::load(),
+            ui_scale: settings.ui_scale,
+            theme: Flavor::parse(&settings.theme).unwrap_or_default(),
+            asm_window: settings.asm_window,
+            stack_var_names: settings.stack_var_names,
             recovering: false,
+            show_ghidra_settings: false,
+            server_restarting: false,
             // Debug state
             debug_state: crate::debug::types::DebugState::default(),
             show_attach_dialog: false,
+            raw_open: None,
+            rebase_input: None,
             process_list: Vec::new(),
+            process_filter: String::new(),
+            process_sort: ProcessSort::default(),
             // Bottom panel tab
             bottom_tab: BottomTab::Console,
             // Hex view state
             hex_offset: 0,
+            hex_cursor: 0,
+            hex_anchor: None,
+            hex_back: Vec::new(),
+            hex_search_input: String::new(),
+            hex_search_matches: Vec::new(),
+            hex_search_len: 0,
+            hex_search_index: 0,
+            hex_source: HexSource::File,
+            hex_live: None,
+            pending_hex_live_read: None,
+            // Instruction search
+            show_insn_search: false,
+            insn_search_query: String::new(),
+            insn_search_regex: false,
+            insn_search_all: true,
+            insn_search_results: Vec::new(),
+            insn_search_running: false,
+            insn_search_status: String::new(),
             // Strings state
             extracted_strings: Vec::new(),
             strings_filter: String::new(),
+            strings_min_len: 4,
+            strings_encoding: EncodingFilter::default(),
+            strings_section: None,
+            pending_strings_export: None,
+            pending_overlay_export: false,
+            secondary_binary: None,
+            function_diff: Vec::new(),
+            diff_loading: false,
+            show_diff: false,
+            diff_show_unchanged: false,
+            diff_selected: None,
+            diff_code: None,
+            yara_rules_path: None,
+            yara_results: Vec::new(),
+            yara_running: false,
+            yara_status: String::new(),
             dynamic_mode: true,
+            runtime_addresses: false,
+            vector_format: VectorFormat::default(),
+            register_edits: HashMap::new(),
+            follow_ip: true,
             pending_debug_action: None,
             pending_bp_action: None,
+            pending_stack_refresh: false,
+            pending_attach: None,
+            pending_detach: false,
+            pending_memory_map: false,
             breakpoint_input: String::new(),
+            breakpoint_temporary: false,
+            bp_condition_edit: None,
             mem_addr_input: String::new(),
             mem_len_input: "64".to_string(),
             mem_dump: String::new(),
+            pending_mem_read: false,
+            pending_mem_dump: false,
+            pending_mem_dump_to: None,
+            iat_resolved: HashMap::new(),
+            pending_iat_read: false,
         }
     }
 }
 
 impl AppState {
-    /// Add a log message to the output buffer
+    /// Add an Info message to the output buffer, shown as written
     pub fn log(&mut self, message: impl Into<String>) {
-        self.log_buffer.push(message.into());
+        self.log_buffer.push(LogEntry::new(message));
     }
 
-    /// Clear the log buffer
+    /// Hand Python scripts a copy of the loaded binary as it is now
+    pub fn publish_binary(&mut self) {
+        self.python.set_binary(self.loaded_binary.clone().map(Arc::new));
+    }
+
+    /// Add a message at `level`, written with the level's prefix
+    pub fn log_at(&mut self, level: LogLevel, message: impl std::fmt::Display) {
+        self.log_buffer.push(LogEntry::at(level, message));
+    }
+
+    pub fn success(&mut self, message: impl std::fmt::Display) {
+        self.log_at(LogLevel::Success, message);
+    }
+
+    pub fn warn(&mut self, message: impl std::fmt::Display) {
+        self.log_at(LogLevel::Warning, message);
+    }
+
+    pub fn error(&mut self, message: impl std::fmt::Display) {
+        self.log_at(LogLevel::Error, message);
+    }
+
+    /// Whether a console line passes the level filter and the search box
+    pub fn log_visible(&self, entry: &LogEntry) -> bool {
+        let needle = self.log_search.trim();
+        self.log_filter.shows(entry.level)
+            && (needle.is_empty() || entry.text.to_lowercase().contains(&needle.to_lowercase()))
+    }
+
+    /// Drop all messages, keeping the banner and a marker line
     pub fn clear_logs(&mut self) {
-        self.log_buffer.clear();
+        self.log_buffer = BANNER.iter().copied().map(LogEntry::new).collect();
+        self.log("[*] Console cleared");
+    }
+
+    /// Return the console to its startup state: banner, ready line, no
+    /// filters, timestamps counted from now
+    pub fn reset_logs(&mut self) {
+        self.log_buffer = BANNER.iter().chain([&READY_MESSAGE]).copied().map(LogEntry::new).collect();
+        self.log_started = Instant::now();
+        self.log_filter = LogFilter::default();
+        self.log_search.clear();
+    }
+
+    /// Decompile cache key of `address` in the loaded binary
+    pub fn cache_key(&self, address: u64) -> Option<(BinaryId, u64)> {
+        self.binary_id.clone().map(|id| (id, address))
+    }
+
+    /// Disk cache directory of the loaded binary: its SHA-256 with the
+    /// architecture and base it is decompiled as
+    pub fn disk_cache_key(&self) -> Option<String> {
+        let hash = self.binary_hash.as_deref()?;
+        let binary = self.loaded_binary.as_ref()?;
+        Some(cache::binary_key(hash, &binary.arch_spec, binary.image_base))
+    }
+
+    /// The assembly panel shows Ghidra's listing: selected, and available
+    pub fn showing_ghidra_asm(&self) -> bool {
+        self.asm_source == AsmSource::Ghidra && !self.ghidra_asm.is_empty()
+    }
+
+    /// Instructions shown in the assembly panel
+    pub fn listing(&self) -> &[DisassembledInstruction] {
+        if self.showing_ghidra_asm() {
+            &self.ghidra_asm
+        } else {
+            &self.asm_instructions
+        }
+    }
+
+    /// Hex View selection as an inclusive file offset range
+    pub fn hex_selection(&self) -> (u64, u64) {
+        let anchor = self.hex_anchor.unwrap_or(self.hex_cursor);
+        (anchor.min(self.hex_cursor), anchor.max(self.hex_cursor))
+    }
+
+    /// Address the user is looking at: selected instruction, else selected function
+    pub fn current_address(&self) -> Option<u64> {
+        self.asm_selected
+            .or_else(|| self.selected_function.as_ref().map(|f| f.address))
+    }
+
+    /// Instructions behind the selected decompiled line
+    pub fn linked_instructions(&self) -> &[u64] {
+        self.c_line_selected
+            .and_then(|line| self.c_line_map.get(&line))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// How far the debuggee's main module was moved from the file's image
+    /// base, once the process reported its load address (None when not moved)
+    pub fn aslr_slide(&self) -> Option<u64> {
+        let runtime = self.debug_state.image_base?;
+        let binary = self.loaded_binary.as_ref()?;
+        Some(runtime.wrapping_sub(binary.image_base)).filter(|&slide| slide != 0)
+    }
+
+    /// Process address of file VA `va`; addresses outside the binary are unchanged
+    pub fn to_runtime(&self, va: u64) -> u64 {
+        match (self.aslr_slide(), &self.loaded_binary) {
+            (Some(slide), Some(binary)) if binary.section_at_va(va).is_some() => va.wrapping_add(slide),
+            _ => va,
+        }
+    }
+
+    /// File VA of process address `address`; addresses outside the main
+    /// module (e.g. in a DLL) are unchanged
+    pub fn to_static(&self, address: u64) -> u64 {
+        match (self.aslr_slide(), &self.loaded_binary) {
+            (Some(slide), Some(binary)) if binary.section_at_va(address.wrapping_sub(slide)).is_some() => {
+                address.wrapping_sub(slide)
+            }
+            _ => address,
+        }
+    }
+
+    /// File VA `va` as the address views show it
+    pub fn shown_address(&self, va: u64) -> u64 {
+        if self.runtime_addresses { self.to_runtime(va) } else { va }
+    }
+
+    /// Whether registers should be shown 64 bits wide: the attached process's
+    /// bitness when known, else the loaded binary's
+    pub fn target_is_64bit(&self) -> bool {
+        self.debug_state.is_64bit
+            .or_else(|| self.loaded_binary.as_ref().map(|b| b.is_64bit))
+            .unwrap_or(true)
+    }
+
+    /// Instruction pointer of the suspended debuggee (the current instruction)
+    pub fn debug_ip(&self) -> Option<u64> {
+        if self.debug_state.status != crate::debug::types::DebugStatus::Suspended {
+            return None;
+        }
+        self.debug_state.registers.as_ref().map(|regs| regs.rip)
+    }
+
+    /// Capture the current analysis state as a session
+    pub fn to_session(&self) -> Session {
+        // Stored as file VAs so they survive a different load base next run
+        let mut breakpoints: Vec<u64> = self.debug_state.breakpoints.keys()
+            .map(|&addr| self.to_static(addr))
+            .chain(self.saved_breakpoints.iter().copied())
+            .collect();
+        breakpoints.sort_unstable();
+        breakpoints.dedup();
+
+        Session {
+            binary_path: self.loaded_binary.as_ref().map(|b| b.path.clone()),
+            binary_hash: self.binary_hash.clone(),
+            breakpoints,
+            function_renames: self.function_renames.clone(),
+            bookmarks: self.bookmarks.clone(),
+            last_seek: self.current_address(),
+        }
+    }
+
+    /// `name+0x2c` label for `address` from the nearest preceding function,
+    /// or plain hex when no function precedes it
+    pub fn address_label(&self, address: u64) -> String {
+        match self.loaded_binary.as_ref().and_then(|b| b.nearest_function(address)) {
+            Some((func, 0)) => self.function_name(func),
+            Some((func, offset)) => format!("{}+0x{:x}", self.function_name(func), offset),
+            None => format!("0x{:x}", address),
+        }
+    }
+
+    /// Display name for a function, preferring the user-assigned one
+    pub fn function_name(&self, func: &FunctionInfo) -> String {
+        display_name(&self.function_renames, func)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_levels() {
+        // Script output that happens to start with a prefix stays Info
+        assert_eq!(LogEntry::new("[!] printed by a script").level, LogLevel::Info);
+        assert_eq!(LogEntry::new("  0x1000  main").level, LogLevel::Info);
+        let mut state = AppState::default();
+        state.warn("No binary loaded");
+        let entry = state.log_buffer.last().unwrap();
+        assert_eq!(entry.level, LogLevel::Warning);
+        assert_eq!(entry.text, "[!] No binary loaded");
+        assert!(LogFilter::Warnings.shows(LogLevel::Error));
+        assert!(!LogFilter::Errors.shows(LogLevel::Warning));
     }
 }
@@ -12,22 +12,25 @@ pub fn render(ctx: &egui::Context, state: &AppState) {
             ui.horizontal(|ui| {
                 // Server status (with recovery indicator)
                 let (server_color, server_icon, server_text) = if state.recovering {
-                    (catppuccin::YELLOW, "◉", "Recovering")
+                    (catppuccin::yellow(), "◉", "Recovering")
                 } else if state.server_connected {
-                    (catppuccin::GREEN, "●", "Connected")
+                    (catppuccin::green(), "●", "Connected")
                 } else {
-                    (catppuccin::OVERLAY0, "○", "Offline")
+                    (catppuccin::red(), "○", "Offline")
                 };
-                ui.label(egui::RichText::new(server_icon).color(server_color).small());
-                ui.label(egui::RichText::new(server_text).color(server_color).small());
+                let port_hint = format!("Ghidra server on port {}", state.client_config.port);
+                ui.label(egui::RichText::new(server_icon).color(server_color).small())
+                    .on_hover_text(&port_hint);
+                ui.label(egui::RichText::new(server_text).color(server_color).small())
+                    .on_hover_text(&port_hint);
                 
                 ui.separator();
 
                 // Debugger status indicator
                 let (debug_color, debug_icon, debug_text) = if state.is_debugging {
-                    (catppuccin::GREEN, "▶", "Debugging")
+                    (catppuccin::green(), "▶", "Debugging")
                 } else {
-                    (catppuccin::OVERLAY0, "■", "Idle")
+                    (catppuccin::overlay0(), "■", "Idle")
                 };
                 ui.label(egui::RichText::new(debug_icon).color(debug_color).small());
                 ui.label(egui::RichText::new(debug_text).color(debug_color).small());
@@ -35,29 +38,53 @@ pub fn render(ctx: &egui::Context, state: &AppState) {
                 // Mode indicator
                 ui.separator();
                 if state.dynamic_mode {
-                    ui.label(egui::RichText::new("● Dynamic").color(catppuccin::TEAL).small());
+                    ui.label(egui::RichText::new("● Dynamic").color(catppuccin::teal()).small());
                 } else {
-                    ui.label(egui::RichText::new("○ Static").color(catppuccin::OVERLAY0).small());
+                    ui.label(egui::RichText::new("○ Static").color(catppuccin::overlay0()).small());
                 }
 
                 ui.separator();
 
                 // Loaded binary info
                 if let Some(ref binary) = state.loaded_binary {
-                    let arch = if binary.is_64bit { "x64" } else { "x86" };
+                    let arch = match (binary.format.as_str(), binary.is_64bit) {
+                        ("WASM", _) => "wasm32",
+                        (_, true) => "x64",
+                        (_, false) => "x86",
+                    };
                     ui.label(egui::RichText::new(format!("{} | {} | {} funcs", 
                         truncate_path(&binary.path, 30), arch, binary.functions.len()))
-                        .color(catppuccin::SUBTEXT0).small());
+                        .color(catppuccin::subtext0()).small());
+
+                    let packed = binary.packed_sections();
+                    if !packed.is_empty() {
+                        let names: Vec<String> = packed.iter()
+                            .map(|s| format!("{} ({:.2})", s.name, s.entropy))
+                            .collect();
+                        ui.label(egui::RichText::new("⚠ Packed?").color(catppuccin::peach()).small())
+                            .on_hover_text(format!("High-entropy code: {}", names.join(", ")));
+                    }
+                    if let Some(addr) = state.current_address() {
+                        ui.separator();
+                        ui.label(egui::RichText::new(format!("0x{:x} {}", addr, state.address_label(addr)))
+                            .color(catppuccin::text()).small());
+                    }
                 } else {
-                    ui.label(egui::RichText::new("No binary").color(catppuccin::OVERLAY0).small());
+                    ui.label(egui::RichText::new("No binary").color(catppuccin::overlay0()).small());
                 }
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     ui.label(egui::RichText::new("Fission v0.1.0")
-                        .color(catppuccin::LAVENDER).small());
+                        .color(catppuccin::lavender()).small());
                     ui.separator();
                     ui.label(egui::RichText::new(format!("Cache: {}", state.decompile_cache.len()))
-                        .color(catppuccin::SUBTEXT0).small());
+                        .color(catppuccin::subtext0()).small());
+                    if let Some((done, total)) = state.batch_progress {
+                        ui.separator();
+                        ui.add(egui::ProgressBar::new(done as f32 / total.max(1) as f32)
+                            .desired_width(140.0)
+                            .text(format!("Analyzing {}/{}", done, total)));
+                    }
                 });
             });
         });
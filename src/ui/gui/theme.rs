@@ -4,66 +4,288 @@
 
 use eframe::egui::{self, Color32, Rounding, Stroke, Vec2, FontFamily, FontId, TextStyle};
 use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Catppuccin flavor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Flavor {
+    /// Dark
+    #[default]
+    Mocha,
+    /// Light
+    Latte,
+}
+
+impl Flavor {
+    pub const ALL: [Flavor; 2] = [Flavor::Mocha, Flavor::Latte];
+
+    /// Lowercase name used in settings
+    pub fn name(self) -> &'static str {
+        match self {
+            Flavor::Mocha => "mocha",
+            Flavor::Latte => "latte",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn is_dark(self) -> bool {
+        self == Flavor::Mocha
+    }
+
+    pub fn palette(self) -> &'static Palette {
+        match self {
+            Flavor::Mocha => &MOCHA,
+            Flavor::Latte => &LATTE,
+        }
+    }
+
+    pub fn code_colors(self) -> &'static CodeColors {
+        match self {
+            Flavor::Mocha => &MOCHA_CODE,
+            Flavor::Latte => &LATTE_CODE,
+        }
+    }
+}
+
+impl std::fmt::Display for Flavor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Flavor::Mocha => write!(f, "Mocha (dark)"),
+            Flavor::Latte => write!(f, "Latte (light)"),
+        }
+    }
+}
+
+/// Catppuccin color palette
+pub struct Palette {
+    pub base: Color32,
+    pub mantle: Color32,
+    pub crust: Color32,
+    pub surface0: Color32,
+    pub surface1: Color32,
+    pub surface2: Color32,
+    pub text: Color32,
+    pub subtext1: Color32,
+    pub subtext0: Color32,
+    pub overlay2: Color32,
+    pub overlay1: Color32,
+    pub overlay0: Color32,
+    pub rosewater: Color32,
+    pub flamingo: Color32,
+    pub pink: Color32,
+    pub mauve: Color32,
+    pub red: Color32,
+    pub maroon: Color32,
+    pub peach: Color32,
+    pub yellow: Color32,
+    pub green: Color32,
+    pub teal: Color32,
+    pub sky: Color32,
+    pub sapphire: Color32,
+    pub blue: Color32,
+    pub lavender: Color32,
+}
 
 /// Catppuccin Mocha palette
-pub mod catppuccin {
-    use super::Color32;
-    
+pub const MOCHA: Palette = Palette {
     // Base colors
-    pub const BASE: Color32 = Color32::from_rgb(30, 30, 46);      // #1e1e2e
-    pub const MANTLE: Color32 = Color32::from_rgb(24, 24, 37);    // #181825
-    pub const CRUST: Color32 = Color32::from_rgb(17, 17, 27);     // #11111b
-    pub const SURFACE0: Color32 = Color32::from_rgb(49, 50, 68);  // #313244
-    pub const SURFACE1: Color32 = Color32::from_rgb(69, 71, 90);  // #45475a
-    pub const SURFACE2: Color32 = Color32::from_rgb(88, 91, 112); // #585b70
-    
+    base: Color32::from_rgb(30, 30, 46),         // #1e1e2e
+    mantle: Color32::from_rgb(24, 24, 37),       // #181825
+    crust: Color32::from_rgb(17, 17, 27),        // #11111b
+    surface0: Color32::from_rgb(49, 50, 68),     // #313244
+    surface1: Color32::from_rgb(69, 71, 90),     // #45475a
+    surface2: Color32::from_rgb(88, 91, 112),    // #585b70
+
     // Text colors
-    pub const TEXT: Color32 = Color32::from_rgb(205, 214, 244);   // #cdd6f4
-    pub const SUBTEXT1: Color32 = Color32::from_rgb(186, 194, 222); // #bac2de
-    pub const SUBTEXT0: Color32 = Color32::from_rgb(166, 173, 200); // #a6adc8
-    pub const OVERLAY2: Color32 = Color32::from_rgb(147, 153, 178); // #9399b2
-    pub const OVERLAY1: Color32 = Color32::from_rgb(127, 132, 156); // #7f849c
-    pub const OVERLAY0: Color32 = Color32::from_rgb(108, 112, 134); // #6c7086
-    
+    text: Color32::from_rgb(205, 214, 244),      // #cdd6f4
+    subtext1: Color32::from_rgb(186, 194, 222),  // #bac2de
+    subtext0: Color32::from_rgb(166, 173, 200),  // #a6adc8
+    overlay2: Color32::from_rgb(147, 153, 178),  // #9399b2
+    overlay1: Color32::from_rgb(127, 132, 156),  // #7f849c
+    overlay0: Color32::from_rgb(108, 112, 134),  // #6c7086
+
     // Accent colors
-    pub const ROSEWATER: Color32 = Color32::from_rgb(245, 224, 220); // #f5e0dc
-    pub const FLAMINGO: Color32 = Color32::from_rgb(242, 205, 205);  // #f2cdcd
-    pub const PINK: Color32 = Color32::from_rgb(245, 194, 231);      // #f5c2e7
-    pub const MAUVE: Color32 = Color32::from_rgb(203, 166, 247);     // #cba6f7
-    pub const RED: Color32 = Color32::from_rgb(243, 139, 168);       // #f38ba8
-    pub const MAROON: Color32 = Color32::from_rgb(235, 160, 172);    // #eba0ac
-    pub const PEACH: Color32 = Color32::from_rgb(250, 179, 135);     // #fab387
-    pub const YELLOW: Color32 = Color32::from_rgb(249, 226, 175);    // #f9e2af
-    pub const GREEN: Color32 = Color32::from_rgb(166, 227, 161);     // #a6e3a1
-    pub const TEAL: Color32 = Color32::from_rgb(148, 226, 213);      // #94e2d5
-    pub const SKY: Color32 = Color32::from_rgb(137, 220, 235);       // #89dceb
-    pub const SAPPHIRE: Color32 = Color32::from_rgb(116, 199, 236);  // #74c7ec
-    pub const BLUE: Color32 = Color32::from_rgb(137, 180, 250);      // #89b4fa
-    pub const LAVENDER: Color32 = Color32::from_rgb(180, 190, 254);  // #b4befe
+    rosewater: Color32::from_rgb(245, 224, 220), // #f5e0dc
+    flamingo: Color32::from_rgb(242, 205, 205),  // #f2cdcd
+    pink: Color32::from_rgb(245, 194, 231),      // #f5c2e7
+    mauve: Color32::from_rgb(203, 166, 247),     // #cba6f7
+    red: Color32::from_rgb(243, 139, 168),       // #f38ba8
+    maroon: Color32::from_rgb(235, 160, 172),    // #eba0ac
+    peach: Color32::from_rgb(250, 179, 135),     // #fab387
+    yellow: Color32::from_rgb(249, 226, 175),    // #f9e2af
+    green: Color32::from_rgb(166, 227, 161),     // #a6e3a1
+    teal: Color32::from_rgb(148, 226, 213),      // #94e2d5
+    sky: Color32::from_rgb(137, 220, 235),       // #89dceb
+    sapphire: Color32::from_rgb(116, 199, 236),  // #74c7ec
+    blue: Color32::from_rgb(137, 180, 250),      // #89b4fa
+    lavender: Color32::from_rgb(180, 190, 254),  // #b4befe
+};
+
+/// Catppuccin Latte palette
+pub const LATTE: Palette = Palette {
+    // Base colors
+    base: Color32::from_rgb(239, 241, 245),      // #eff1f5
+    mantle: Color32::from_rgb(230, 233, 239),    // #e6e9ef
+    crust: Color32::from_rgb(220, 224, 232),     // #dce0e8
+    surface0: Color32::from_rgb(204, 208, 218),  // #ccd0da
+    surface1: Color32::from_rgb(188, 192, 204),  // #bcc0cc
+    surface2: Color32::from_rgb(172, 176, 190),  // #acb0be
+
+    // Text colors
+    text: Color32::from_rgb(76, 79, 105),        // #4c4f69
+    subtext1: Color32::from_rgb(92, 95, 119),    // #5c5f77
+    subtext0: Color32::from_rgb(108, 111, 133),  // #6c6f85
+    overlay2: Color32::from_rgb(124, 127, 147),  // #7c7f93
+    overlay1: Color32::from_rgb(140, 143, 161),  // #8c8fa1
+    overlay0: Color32::from_rgb(156, 160, 176),  // #9ca0b0
+
+    // Accent colors
+    rosewater: Color32::from_rgb(220, 138, 120), // #dc8a78
+    flamingo: Color32::from_rgb(221, 120, 120),  // #dd7878
+    pink: Color32::from_rgb(234, 118, 203),      // #ea76cb
+    mauve: Color32::from_rgb(136, 57, 239),      // #8839ef
+    red: Color32::from_rgb(210, 15, 57),         // #d20f39
+    maroon: Color32::from_rgb(230, 69, 83),      // #e64553
+    peach: Color32::from_rgb(254, 100, 11),      // #fe640b
+    yellow: Color32::from_rgb(223, 142, 29),     // #df8e1d
+    green: Color32::from_rgb(64, 160, 43),       // #40a02b
+    teal: Color32::from_rgb(23, 146, 153),       // #179299
+    sky: Color32::from_rgb(4, 165, 229),         // #04a5e5
+    sapphire: Color32::from_rgb(32, 159, 181),   // #209fb5
+    blue: Color32::from_rgb(30, 102, 245),       // #1e66f5
+    lavender: Color32::from_rgb(114, 135, 253),  // #7287fd
+};
+
+/// Flavor used by `catppuccin::*` and `code::*` (index into `Flavor::ALL`)
+static CURRENT_FLAVOR: AtomicU8 = AtomicU8::new(0);
+
+/// Active flavor
+pub fn flavor() -> Flavor {
+    Flavor::ALL[CURRENT_FLAVOR.load(Ordering::Relaxed) as usize]
+}
+
+/// Palette of the active flavor
+pub fn palette() -> &'static Palette {
+    flavor().palette()
+}
+
+/// Semantic colors of the active flavor
+pub fn code_colors() -> &'static CodeColors {
+    flavor().code_colors()
+}
+
+/// Colors of the active flavor
+pub mod catppuccin {
+    use super::{palette, Color32};
+
+    pub fn base() -> Color32 { palette().base }
+    pub fn mantle() -> Color32 { palette().mantle }
+    pub fn crust() -> Color32 { palette().crust }
+    pub fn surface0() -> Color32 { palette().surface0 }
+    pub fn surface1() -> Color32 { palette().surface1 }
+    pub fn surface2() -> Color32 { palette().surface2 }
+    pub fn text() -> Color32 { palette().text }
+    pub fn subtext1() -> Color32 { palette().subtext1 }
+    pub fn subtext0() -> Color32 { palette().subtext0 }
+    pub fn overlay2() -> Color32 { palette().overlay2 }
+    pub fn overlay1() -> Color32 { palette().overlay1 }
+    pub fn overlay0() -> Color32 { palette().overlay0 }
+    pub fn rosewater() -> Color32 { palette().rosewater }
+    pub fn flamingo() -> Color32 { palette().flamingo }
+    pub fn pink() -> Color32 { palette().pink }
+    pub fn mauve() -> Color32 { palette().mauve }
+    pub fn red() -> Color32 { palette().red }
+    pub fn maroon() -> Color32 { palette().maroon }
+    pub fn peach() -> Color32 { palette().peach }
+    pub fn yellow() -> Color32 { palette().yellow }
+    pub fn green() -> Color32 { palette().green }
+    pub fn teal() -> Color32 { palette().teal }
+    pub fn sky() -> Color32 { palette().sky }
+    pub fn sapphire() -> Color32 { palette().sapphire }
+    pub fn blue() -> Color32 { palette().blue }
+    pub fn lavender() -> Color32 { palette().lavender }
 }
 
 /// Semantic colors for code highlighting
+pub struct CodeColors {
+    pub keyword: Color32,
+    pub function: Color32,
+    pub string: Color32,
+    pub number: Color32,
+    pub comment: Color32,
+    pub operator: Color32,
+    pub type_name: Color32,
+    pub register: Color32,
+    pub address: Color32,
+    pub mnemonic_flow: Color32,
+    pub mnemonic_normal: Color32,
+    pub mnemonic_data: Color32,
+    pub hex_byte: Color32,
+    pub ascii_printable: Color32,
+}
+
+const MOCHA_CODE: CodeColors = CodeColors {
+    keyword: MOCHA.mauve,
+    function: MOCHA.blue,
+    string: MOCHA.green,
+    number: MOCHA.peach,
+    comment: MOCHA.overlay0,
+    operator: MOCHA.sky,
+    type_name: MOCHA.yellow,
+    register: MOCHA.red,
+    address: MOCHA.overlay1,
+    mnemonic_flow: MOCHA.red,           // jmp, call, ret
+    mnemonic_normal: MOCHA.blue,        // mov, add, etc.
+    mnemonic_data: MOCHA.pink,          // db (undecodable bytes)
+    hex_byte: MOCHA.subtext0,
+    ascii_printable: MOCHA.green,
+};
+
+// Latte swaps the faintest shades for darker ones to keep contrast on white
+const LATTE_CODE: CodeColors = CodeColors {
+    keyword: LATTE.mauve,
+    function: LATTE.blue,
+    string: LATTE.green,
+    number: LATTE.peach,
+    comment: LATTE.overlay1,
+    operator: LATTE.sky,
+    type_name: LATTE.maroon,
+    register: LATTE.red,
+    address: LATTE.subtext0,
+    mnemonic_flow: LATTE.red,           // jmp, call, ret
+    mnemonic_normal: LATTE.blue,        // mov, add, etc.
+    mnemonic_data: LATTE.pink,          // db (undecodable bytes)
+    hex_byte: LATTE.subtext1,
+    ascii_printable: LATTE.green,
+};
+
+/// Code highlighting colors of the active flavor
 pub mod code {
-    use super::catppuccin::*;
-    
-    pub const KEYWORD: super::Color32 = MAUVE;
-    pub const FUNCTION: super::Color32 = BLUE;
-    pub const STRING: super::Color32 = GREEN;
-    pub const NUMBER: super::Color32 = PEACH;
-    pub const COMMENT: super::Color32 = OVERLAY0;
-    pub const OPERATOR: super::Color32 = SKY;
-    pub const TYPE: super::Color32 = YELLOW;
-    pub const REGISTER: super::Color32 = RED;
-    pub const ADDRESS: super::Color32 = OVERLAY1;
-    pub const MNEMONIC_FLOW: super::Color32 = RED;      // jmp, call, ret
-    pub const MNEMONIC_NORMAL: super::Color32 = BLUE;   // mov, add, etc.
-    pub const HEX_BYTE: super::Color32 = SUBTEXT0;
-    pub const ASCII_PRINTABLE: super::Color32 = GREEN;
+    use super::{code_colors, Color32};
+
+    pub fn keyword() -> Color32 { code_colors().keyword }
+    pub fn function() -> Color32 { code_colors().function }
+    pub fn string() -> Color32 { code_colors().string }
+    pub fn number() -> Color32 { code_colors().number }
+    pub fn comment() -> Color32 { code_colors().comment }
+    pub fn operator() -> Color32 { code_colors().operator }
+    pub fn type_name() -> Color32 { code_colors().type_name }
+    pub fn register() -> Color32 { code_colors().register }
+    pub fn address() -> Color32 { code_colors().address }
+    pub fn mnemonic_flow() -> Color32 { code_colors().mnemonic_flow }
+    pub fn mnemonic_normal() -> Color32 { code_colors().mnemonic_normal }
+    pub fn mnemonic_data() -> Color32 { code_colors().mnemonic_data }
+    pub fn hex_byte() -> Color32 { code_colors().hex_byte }
+    pub fn ascii_printable() -> Color32 { code_colors().ascii_printable }
 }
 
-/// Apply Catppuccin theme to egui context
-pub fn apply_catppuccin_theme(ctx: &egui::Context) {
-    use catppuccin::*;
+/// Apply a Catppuccin flavor to egui context
+pub fn apply_catppuccin_theme(ctx: &egui::Context, flavor: Flavor) {
+    CURRENT_FLAVOR.store(Flavor::ALL.iter().position(|f| *f == flavor).unwrap_or(0) as u8, Ordering::Relaxed);
+    let p = flavor.palette();
     
     let mut style = (*ctx.style()).clone();
     
@@ -79,71 +301,74 @@ pub fn apply_catppuccin_theme(ctx: &egui::Context) {
         ..Default::default()
     };
     
+    // Light backgrounds need softer shadows
+    let shadow = |alpha: u8| if flavor.is_dark() { alpha } else { alpha / 3 };
+    
     // Rounding - modern rounded corners
     let rounding = Rounding::same(6.0);
     let small_rounding = Rounding::same(4.0);
     
     // Visuals
-    let mut visuals = egui::Visuals::dark();
+    let mut visuals = if flavor.is_dark() { egui::Visuals::dark() } else { egui::Visuals::light() };
     
     // Window
-    visuals.window_fill = BASE;
-    visuals.window_stroke = Stroke::new(1.0, SURFACE0);
+    visuals.window_fill = p.base;
+    visuals.window_stroke = Stroke::new(1.0, p.surface0);
     visuals.window_rounding = rounding;
     visuals.window_shadow = egui::epaint::Shadow {
         offset: Vec2::new(0.0, 4.0),
         blur: 8.0,
         spread: 0.0,
-        color: Color32::from_black_alpha(60),
+        color: Color32::from_black_alpha(shadow(60)),
     };
     
     // Panel
-    visuals.panel_fill = BASE;
+    visuals.panel_fill = p.base;
     
     // Widgets
-    visuals.widgets.noninteractive.bg_fill = SURFACE0;
-    visuals.widgets.noninteractive.fg_stroke = Stroke::new(1.0, TEXT);
+    visuals.widgets.noninteractive.bg_fill = p.surface0;
+    visuals.widgets.noninteractive.fg_stroke = Stroke::new(1.0, p.text);
     visuals.widgets.noninteractive.rounding = small_rounding;
     visuals.widgets.noninteractive.bg_stroke = Stroke::NONE;
     
-    visuals.widgets.inactive.bg_fill = SURFACE0;
-    visuals.widgets.inactive.fg_stroke = Stroke::new(1.0, SUBTEXT1);
+    visuals.widgets.inactive.bg_fill = p.surface0;
+    visuals.widgets.inactive.fg_stroke = Stroke::new(1.0, p.subtext1);
     visuals.widgets.inactive.rounding = small_rounding;
     visuals.widgets.inactive.bg_stroke = Stroke::NONE;
     
-    visuals.widgets.hovered.bg_fill = SURFACE1;
-    visuals.widgets.hovered.fg_stroke = Stroke::new(1.0, TEXT);
+    visuals.widgets.hovered.bg_fill = p.surface1;
+    visuals.widgets.hovered.fg_stroke = Stroke::new(1.0, p.text);
     visuals.widgets.hovered.rounding = small_rounding;
-    visuals.widgets.hovered.bg_stroke = Stroke::new(1.0, BLUE);
+    visuals.widgets.hovered.bg_stroke = Stroke::new(1.0, p.blue);
     
-    visuals.widgets.active.bg_fill = SURFACE2;
-    visuals.widgets.active.fg_stroke = Stroke::new(1.0, TEXT);
+    visuals.widgets.active.bg_fill = p.surface2;
+    visuals.widgets.active.fg_stroke = Stroke::new(1.0, p.text);
     visuals.widgets.active.rounding = small_rounding;
-    visuals.widgets.active.bg_stroke = Stroke::new(2.0, BLUE);
+    visuals.widgets.active.bg_stroke = Stroke::new(2.0, p.blue);
     
-    visuals.widgets.open.bg_fill = SURFACE1;
-    visuals.widgets.open.fg_stroke = Stroke::new(1.0, TEXT);
+    visuals.widgets.open.bg_fill = p.surface1;
+    visuals.widgets.open.fg_stroke = Stroke::new(1.0, p.text);
     visuals.widgets.open.rounding = small_rounding;
     
     // Selection
-    visuals.selection.bg_fill = BLUE.linear_multiply(0.3);
-    visuals.selection.stroke = Stroke::new(1.0, BLUE);
+    visuals.selection.bg_fill = p.blue.linear_multiply(0.3);
+    visuals.selection.stroke = Stroke::new(1.0, p.blue);
     
     // Hyperlink
-    visuals.hyperlink_color = SAPPHIRE;
+    visuals.hyperlink_color = p.sapphire;
     
     // Faint background for striped tables
-    visuals.faint_bg_color = SURFACE0.linear_multiply(0.5);
+    visuals.faint_bg_color = p.surface0.linear_multiply(0.5);
     
     // Extreme background
-    visuals.extreme_bg_color = CRUST;
+    visuals.extreme_bg_color = p.crust;
     
     // Code background
-    visuals.code_bg_color = MANTLE;
+    visuals.code_bg_color = p.mantle;
     
     // Warn/error foreground
-    visuals.warn_fg_color = YELLOW;
-    visuals.error_fg_color = RED;
+    visuals.warn_fg_color = p.yellow;
+    visuals.error_fg_color = p.red;
     
     // Text cursor
     visuals.text_cursor.width = 2.0;
@@ -153,7 +378,7 @@ pub fn apply_catppuccin_theme(ctx: &egui::Context) {
         offset: Vec2::new(0.0, 4.0),
         blur: 12.0,
         spread: 0.0,
-        color: Color32::from_black_alpha(80),
+        color: Color32::from_black_alpha(shadow(80)),
     };
     
     // Resize corner
@@ -182,17 +407,17 @@ pub fn apply_catppuccin_theme(ctx: &egui::Context) {
     ctx.set_style(style);
 }
 
-/// Configure custom fonts and text sizes
-pub fn configure_fonts(ctx: &egui::Context) {
+/// Configure custom fonts and text sizes, scaled by `scale`
+pub fn configure_fonts(ctx: &egui::Context, scale: f32) {
     // Configure font sizes for different text styles
     let mut style = (*ctx.style()).clone();
     
     style.text_styles = [
-        (TextStyle::Small, FontId::new(11.0, FontFamily::Proportional)),
-        (TextStyle::Body, FontId::new(13.0, FontFamily::Proportional)),
-        (TextStyle::Button, FontId::new(13.0, FontFamily::Proportional)),
-        (TextStyle::Heading, FontId::new(18.0, FontFamily::Proportional)),
-        (TextStyle::Monospace, FontId::new(13.0, FontFamily::Monospace)),
+        (TextStyle::Small, FontId::new(11.0 * scale, FontFamily::Proportional)),
+        (TextStyle::Body, FontId::new(13.0 * scale, FontFamily::Proportional)),
+        (TextStyle::Button, FontId::new(13.0 * scale, FontFamily::Proportional)),
+        (TextStyle::Heading, FontId::new(18.0 * scale, FontFamily::Proportional)),
+        (TextStyle::Monospace, FontId::new(13.0 * scale, FontFamily::Monospace)),
     ].into();
     
     ctx.set_style(style);
@@ -241,9 +466,11 @@ pub fn load_jetbrains_mono(ctx: &egui::Context) {
 }
 
 /// Initialize theme and fonts
-pub fn init(ctx: &egui::Context) {
-    apply_catppuccin_theme(ctx);
-    configure_fonts(ctx);
+pub fn init(ctx: &egui::Context, flavor: Flavor, scale: f32) {
+    apply_catppuccin_theme(ctx, flavor);
+    configure_fonts(ctx, scale);
     load_jetbrains_mono(ctx);
+    // Ctrl+/- rescale text instead of zooming the whole UI
+    ctx.options_mut(|o| o.zoom_with_keyboard = false);
 }
 